@@ -3,9 +3,11 @@
 use std::sync::Arc;
 
 use ovo_tools::{SharedTool, ToolRegistry};
-use ovo_types::{ErrorCode, OvoError};
+use ovo_types::{ErrorCode, OvoError, SamplingParams};
 
-use crate::definition::{AgentDefinition, CompletionRequirement, Instructions};
+use crate::definition::{
+    AgentDefinition, CompletionRequirement, Instructions, StructuredOutputMode, ToolChoicePolicy,
+};
 use crate::instance::Agent;
 
 /// Builds a validated [`Agent`].
@@ -85,6 +87,24 @@ pub fn max_steps(mut self, max_steps: usize) -> Self {
         self
     }
 
+    /// Set sampling parameters.
+    #[must_use]
+    pub fn sampling(mut self, sampling: SamplingParams) -> Self {
+        if let Some(def) = &mut self.definition {
+            def.sampling = sampling;
+        }
+        self
+    }
+
+    /// Set the per-step tool-choice policy.
+    #[must_use]
+    pub fn tool_choice(mut self, policy: ToolChoicePolicy) -> Self {
+        if let Some(def) = &mut self.definition {
+            def.tool_choice = policy;
+        }
+        self
+    }
+
     /// Attach tools available to the agent (filtered by definition policy).
     #[must_use]
     pub fn tools(mut self, tools: Vec<SharedTool>) -> Self {
@@ -110,6 +130,15 @@ pub fn output_schema(mut self, schema: serde_json::Value) -> Self {
         self
     }
 
+    /// Choose how the output schema is requested (default: by model capability).
+    #[must_use]
+    pub fn structured_output(mut self, mode: StructuredOutputMode) -> Self {
+        if let Some(def) = &mut self.definition {
+            def.structured_output = mode;
+        }
+        self
+    }
+
     /// Build the agent instance.
     ///
     /// # Errors
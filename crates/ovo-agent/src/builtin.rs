@@ -1,8 +1,11 @@
 //! Builtin agent types.
 
 use ovo_tools::CapabilityMode;
+use ovo_types::SamplingParams;
 
-use crate::definition::{AgentDefinition, AgentSource, Instructions, ToolPolicy};
+use crate::definition::{
+    AgentDefinition, AgentSource, Instructions, StructuredOutputMode, ToolChoicePolicy, ToolPolicy,
+};
 
 /// Builtin agent type names.
 pub const GENERAL_PURPOSE: &str = "general-purpose";
@@ -42,8 +45,11 @@ pub fn builtin_definitions() -> Vec<AgentDefinition> {
             model: "default".into(),
             tools: ToolPolicy::InheritAll,
             output_schema: None,
+            structured_output: StructuredOutputMode::Auto,
             completion: None,
             max_steps: 32,
+            sampling: SamplingParams::default(),
+            tool_choice: ToolChoicePolicy::default(),
             enabled: true,
             capability: Some(CapabilityMode::Full),
             source: Some(AgentSource::Builtin),
@@ -55,8 +61,11 @@ pub fn builtin_definitions() -> Vec<AgentDefinition> {
             model: "default".into(),
             tools: ToolPolicy::InheritAll,
             output_schema: None,
+            structured_output: StructuredOutputMode::Auto,
             completion: None,
             max_steps: 24,
+            sampling: SamplingParams::default(),
+            tool_choice: ToolChoicePolicy::default(),
             enabled: true,
             capability: Some(CapabilityMode::ReadOnly),
             source: Some(AgentSource::Builtin),
@@ -68,8 +77,11 @@ pub fn builtin_definitions() -> Vec<AgentDefinition> {
             model: "default".into(),
             tools: ToolPolicy::InheritAll,
             output_schema: None,
+            structured_output: StructuredOutputMode::Auto,
             completion: None,
             max_steps: 16,
+            sampling: SamplingParams::default(),
+            tool_choice: ToolChoicePolicy::default(),
             enabled: true,
             capability: Some(CapabilityMode::Plan),
             source: Some(AgentSource::Builtin),
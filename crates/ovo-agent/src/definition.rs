@@ -1,6 +1,7 @@
 //! Portable agent configuration.
 
 use ovo_tools::CapabilityMode;
+use ovo_types::{SamplingParams, ToolChoice};
 use serde::{Deserialize, Serialize};
 use serde_json::Value;
 
@@ -75,6 +76,102 @@ pub struct CompletionRequirement {
     pub max_retries: u32,
 }
 
+/// Per-step tool-choice rules applied by the turn loop.
+///
+/// The default sends [`ToolChoice::Auto`] on every step. Rules are checked in
+/// field order: `no_tools_after`, then `require_tool_steps`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolChoicePolicy {
+    /// Require a tool call on steps `1..=require_tool_steps`.
+    #[serde(default)]
+    pub require_tool_steps: u32,
+    /// Disallow tools on every step after this one.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub no_tools_after: Option<u32>,
+    /// Once the [`CompletionRequirement`] reminders are exhausted, re-sample
+    /// once with its tool forced instead of failing the turn.
+    #[serde(default)]
+    pub force_completion_tool: bool,
+}
+
+impl ToolChoicePolicy {
+    /// Require a tool call on the first `steps` steps.
+    #[must_use]
+    pub const fn require_tool_steps(mut self, steps: u32) -> Self {
+        self.require_tool_steps = steps;
+        self
+    }
+
+    /// Disallow tools after `step`.
+    #[must_use]
+    pub const fn no_tools_after(mut self, step: u32) -> Self {
+        self.no_tools_after = Some(step);
+        self
+    }
+
+    /// Force the completion tool once its reminders are exhausted.
+    #[must_use]
+    pub const fn force_completion_tool(mut self, force: bool) -> Self {
+        self.force_completion_tool = force;
+        self
+    }
+
+    /// Choice for one-based `step` with a short reason, or `None` for `Auto`.
+    #[must_use]
+    pub fn choice_for_step(&self, step: u32) -> Option<(ToolChoice, &'static str)> {
+        if self.no_tools_after.is_some_and(|n| step > n) {
+            return Some((ToolChoice::None, "no_tools_after"));
+        }
+        if step <= self.require_tool_steps {
+            return Some((ToolChoice::Required, "require_tool_steps"));
+        }
+        None
+    }
+
+    /// True when every step uses `Auto`.
+    #[must_use]
+    pub fn is_default(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// How [`AgentDefinition::output_schema`] is requested from the model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StructuredOutputMode {
+    /// `ResponseFormat` when the sampler reports JSON Schema support for the
+    /// model, otherwise `ToolCall`.
+    #[default]
+    Auto,
+    /// Send the schema as the provider's `response_format`.
+    ResponseFormat,
+    /// Offer a synthetic `final_answer` tool whose parameters are the schema,
+    /// force it, and take its arguments as the output.
+    ToolCall,
+}
+
+impl StructuredOutputMode {
+    /// Concrete mode for a model; `Auto` resolves by `json_schema_supported`.
+    #[must_use]
+    pub const fn resolve(self, json_schema_supported: bool) -> Self {
+        match self {
+            Self::Auto if json_schema_supported => Self::ResponseFormat,
+            Self::Auto => Self::ToolCall,
+            other => other,
+        }
+    }
+
+    /// True for [`Self::Auto`].
+    #[must_use]
+    #[allow(
+        clippy::trivially_copy_pass_by_ref,
+        reason = "serde skip_serializing_if passes a reference"
+    )]
+    pub const fn is_auto(&self) -> bool {
+        matches!(self, Self::Auto)
+    }
+}
+
 /// Where a definition was loaded from (for discovery precedence).
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "snake_case")]
@@ -106,12 +203,21 @@ pub struct AgentDefinition {
     /// Optional structured output schema (JSON Schema object).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub output_schema: Option<Value>,
+    /// How `output_schema` is requested (ignored without a schema).
+    #[serde(default, skip_serializing_if = "StructuredOutputMode::is_auto")]
+    pub structured_output: StructuredOutputMode,
     /// Optional completion gate.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub completion: Option<CompletionRequirement>,
     /// Default max steps for turns using this agent.
     #[serde(default = "default_max_steps")]
     pub max_steps: usize,
+    /// Sampling parameters (turn / spawn options override field-wise).
+    #[serde(default, skip_serializing_if = "SamplingParams::is_empty")]
+    pub sampling: SamplingParams,
+    /// Per-step tool-choice policy (turn options may replace it).
+    #[serde(default, skip_serializing_if = "ToolChoicePolicy::is_default")]
+    pub tool_choice: ToolChoicePolicy,
     /// When false, definition is invisible and not callable.
     #[serde(default = "default_enabled")]
     pub enabled: bool,
@@ -142,8 +248,11 @@ pub fn new(name: impl Into<String>) -> Self {
             model: "default".into(),
             tools: ToolPolicy::InheritAll,
             output_schema: None,
+            structured_output: StructuredOutputMode::Auto,
             completion: None,
             max_steps: default_max_steps(),
+            sampling: SamplingParams::default(),
+            tool_choice: ToolChoicePolicy::default(),
             enabled: true,
             capability: None,
             source: None,
@@ -154,7 +263,8 @@ pub fn new(name: impl Into<String>) -> Self {
     ///
     /// # Errors
     ///
-    /// Returns [`ovo_types::OvoError`] when name/model empty or `max_steps` is zero.
+    /// Returns [`ovo_types::OvoError`] when name/model empty, `max_steps` is zero
+    /// or a sampling parameter is out of range.
     pub fn validate(&self) -> Result<(), ovo_types::OvoError> {
         use ovo_types::{ErrorCode, OvoError};
         if self.name.trim().is_empty() {
@@ -175,6 +285,8 @@ pub fn validate(&self) -> Result<(), ovo_types::OvoError> {
                 "max_steps must be >= 1",
             ));
         }
-        Ok(())
+        self.sampling
+            .validate()
+            .map_err(|e| OvoError::new(ErrorCode::AgentInvalidDefinition, e.message()))
     }
 }
@@ -12,10 +12,12 @@
 use std::path::{Path, PathBuf};
 
 use ovo_tools::CapabilityMode;
-use ovo_types::{ErrorCode, OvoError};
+use ovo_types::{ErrorCode, OvoError, ReasoningEffort, SamplingParams};
 
 use crate::builtin::builtin_definitions;
-use crate::definition::{AgentDefinition, AgentSource, Instructions, ToolPolicy};
+use crate::definition::{
+    AgentDefinition, AgentSource, Instructions, StructuredOutputMode, ToolChoicePolicy, ToolPolicy,
+};
 
 /// Default relative directory under a project root.
 pub const PROJECT_AGENTS_DIR: &str = ".ovo/agents";
@@ -26,7 +28,10 @@
 /// Parse a definition file: YAML frontmatter between `---` fences, body = instructions.
 ///
 /// Frontmatter keys: `name`, `description`, `model`, `max_steps`, `enabled`,
-/// `capability`, `allowed_tools` / `tools` (comma-separated), `denied_tools`.
+/// `capability`, `allowed_tools` / `tools` (comma-separated), `denied_tools`,
+/// and sampling keys `temperature`, `top_p`, `seed`, `stop` (one sequence or a
+/// JSON string array), `presence_penalty`, `frequency_penalty`,
+/// `reasoning_effort`.
 ///
 /// # Errors
 ///
@@ -76,6 +81,7 @@ pub fn parse_definition_markdown(raw: &str) -> Result<AgentDefinition, OvoError>
         })?),
     };
     let tools = parse_tool_policy(&meta);
+    let sampling = parse_sampling(&meta)?;
     let def = AgentDefinition {
         name,
         description,
@@ -83,8 +89,11 @@ pub fn parse_definition_markdown(raw: &str) -> Result<AgentDefinition, OvoError>
         model,
         tools,
         output_schema: None,
+        structured_output: StructuredOutputMode::Auto,
         completion: None,
         max_steps,
+        sampling,
+        tool_choice: ToolChoicePolicy::default(),
         enabled,
         capability,
         source: None,
@@ -109,6 +118,52 @@ fn parse_tool_policy(meta: &BTreeMap<String, String>) -> ToolPolicy {
     ToolPolicy::InheritAll
 }
 
+fn parse_sampling(meta: &BTreeMap<String, String>) -> Result<SamplingParams, OvoError> {
+    fn num<T: std::str::FromStr>(
+        meta: &BTreeMap<String, String>,
+        key: &str,
+    ) -> Result<Option<T>, OvoError> {
+        meta.get(key)
+            .map(|raw| {
+                raw.parse::<T>().map_err(|_| {
+                    OvoError::new(
+                        ErrorCode::AgentInvalidDefinition,
+                        format!("invalid {key} '{raw}'"),
+                    )
+                })
+            })
+            .transpose()
+    }
+    let stop = match meta.get("stop") {
+        None => Vec::new(),
+        Some(raw) if raw.starts_with('[') => serde_json::from_str(raw).map_err(|e| {
+            OvoError::new(
+                ErrorCode::AgentInvalidDefinition,
+                format!("invalid stop list: {e}"),
+            )
+        })?,
+        Some(raw) => vec![raw.clone()],
+    };
+    let reasoning_effort = match meta.get("reasoning_effort") {
+        None => None,
+        Some(raw) => Some(ReasoningEffort::parse(raw).ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::AgentInvalidDefinition,
+                format!("unknown reasoning_effort '{raw}' (expected minimal|low|medium|high)"),
+            )
+        })?),
+    };
+    Ok(SamplingParams {
+        temperature: num(meta, "temperature")?,
+        top_p: num(meta, "top_p")?,
+        seed: num(meta, "seed")?,
+        stop,
+        presence_penalty: num(meta, "presence_penalty")?,
+        frequency_penalty: num(meta, "frequency_penalty")?,
+        reasoning_effort,
+    })
+}
+
 fn split_csv(raw: &str) -> Vec<String> {
     raw.split([',', ' '])
         .map(str::trim)
@@ -395,6 +450,37 @@ fn parses_frontmatter() {
         assert_eq!(def.capability, Some(CapabilityMode::ReadOnly));
     }
 
+    #[test]
+    fn parses_sampling_keys() {
+        let raw = "---\n\
+name: eval\n\
+model: m\n\
+temperature: 0\n\
+seed: 42\n\
+stop: [\"###\", \"END\"]\n\
+reasoning_effort: high\n\
+---\n\
+\n\
+Answer.\n";
+        let def = parse_definition_markdown(raw).expect("parse");
+        assert_eq!(
+            def.sampling,
+            SamplingParams::new()
+                .with_temperature(0.0)
+                .with_seed(42)
+                .with_stop(["###", "END"])
+                .with_reasoning_effort(ReasoningEffort::High)
+        );
+
+        let bad = "---\nname: x\nmodel: m\ntemperature: hot\n---\n\nHi.\n";
+        assert_eq!(
+            parse_definition_markdown(bad).expect_err("bad").code(),
+            ErrorCode::AgentInvalidDefinition
+        );
+        let out_of_range = "---\nname: x\nmodel: m\ntop_p: 3\n---\n\nHi.\n";
+        assert!(parse_definition_markdown(out_of_range).is_err());
+    }
+
     #[test]
     fn parses_disabled() {
         let raw = "---\nname: x\nmodel: m\nenabled: false\n---\n\nHi.\n";
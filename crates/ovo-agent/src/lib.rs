@@ -19,7 +19,8 @@
     builtin_names,
 };
 pub use definition::{
-    AgentDefinition, AgentSource, CompletionRequirement, Instructions, ToolPolicy,
+    AgentDefinition, AgentSource, CompletionRequirement, Instructions, StructuredOutputMode,
+    ToolChoicePolicy, ToolPolicy,
 };
 pub use discovery::{
     PROJECT_AGENTS_DIR, USER_AGENTS_DIR, by_name_in_dir, by_name_resolved, discover_in_dir,
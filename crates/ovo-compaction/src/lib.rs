@@ -12,7 +12,10 @@
 pub mod token_threshold;
 
 pub use max_messages::MaxMessages;
-pub use prune::{DropPrefix, PruneToolResults, StripImages, SummarizingCompaction};
+pub use prune::{
+    DropPrefix, PruneToolResults, StripImages, StripReasoning, SummarizingCompaction,
+    strip_settled_reasoning,
+};
 pub use select::{
     CompactionRange, apply_range, is_safe_split, select_compaction_range, snap_split_forward,
     tool_pair_invariant_holds,
@@ -1,4 +1,4 @@
-//! Light compaction: prune tool results and strip images.
+//! Light compaction: prune tool results, strip images and settled reasoning.
 
 use ovo_types::{ContentPart, Message, OvoError, Role};
 
@@ -99,6 +99,55 @@ fn compact(&self, messages: Vec<Message>) -> Result<CompactionOutcome, OvoError>
     }
 }
 
+/// Drop reasoning parts (text and provider-opaque state) from every message
+/// before the last user message.
+///
+/// Reasoning of a finished exchange is the cheapest context to lose; the
+/// in-flight tool loop after the last user message keeps its reasoning because
+/// some providers (Anthropic thinking) require it until the turn completes.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct StripReasoning;
+
+impl CompactionStrategy for StripReasoning {
+    fn name(&self) -> &'static str {
+        "strip_reasoning"
+    }
+
+    fn should_compact(&self, messages: &[Message], _token_estimate: u64) -> bool {
+        messages
+            .get(..settled_len(messages))
+            .unwrap_or(&[])
+            .iter()
+            .any(Message::has_reasoning)
+    }
+
+    fn compact(&self, mut messages: Vec<Message>) -> Result<CompactionOutcome, OvoError> {
+        let changed = strip_settled_reasoning(&mut messages);
+        Ok(CompactionOutcome {
+            messages,
+            changed,
+            strategy: self.name(),
+        })
+    }
+}
+
+/// Remove reasoning parts before the last user message; true when any was removed.
+pub fn strip_settled_reasoning(messages: &mut [Message]) -> bool {
+    let settled = settled_len(messages);
+    let mut changed = false;
+    for m in messages.get_mut(..settled).unwrap_or(&mut []) {
+        changed |= m.strip_reasoning();
+    }
+    changed
+}
+
+fn settled_len(messages: &[Message]) -> usize {
+    messages
+        .iter()
+        .rposition(|m| m.role == Role::User)
+        .unwrap_or(0)
+}
+
 /// Drop oldest messages via [`select_compaction_range`] (tool-safe).
 #[derive(Debug, Clone, Copy)]
 pub struct DropPrefix {
@@ -243,6 +292,43 @@ fn prune_shortens_tool_bodies() {
         );
     }
 
+    #[test]
+    fn strip_reasoning_keeps_the_open_turn() {
+        let thought = |text: &str| {
+            let mut m = Message::assistant(text);
+            m.parts.push(ContentPart::Reasoning {
+                text: format!("why {text}"),
+                signature: None,
+            });
+            m
+        };
+        let msgs = vec![
+            Message::user("1"),
+            thought("a"),
+            Message::user("2"),
+            thought("b"),
+        ];
+        let s = StripReasoning;
+        assert!(s.should_compact(&msgs, 0));
+        let out = s.compact(msgs).expect("ok");
+        assert!(out.changed);
+        let reasoning: Vec<Option<String>> = out.messages.iter().map(Message::reasoning).collect();
+        assert_eq!(reasoning, vec![None, None, None, Some("why b".into())]);
+        assert_eq!(out.messages.get(1).map(Message::text).as_deref(), Some("a"));
+        assert!(!s.should_compact(&out.messages, 0));
+
+        let mut redacted = Message::assistant("c");
+        redacted.parts.push(ContentPart::Opaque {
+            provider: "anthropic".into(),
+            data: r#"{"type":"redacted_thinking","data":"x"}"#.into(),
+        });
+        let msgs = vec![Message::user("1"), redacted, Message::user("2")];
+        assert!(s.should_compact(&msgs, 0));
+        let out = s.compact(msgs).expect("ok");
+        assert!(out.changed);
+        assert!(!out.messages.iter().any(Message::has_reasoning));
+    }
+
     #[test]
     fn summarizing_inserts_summary() {
         let s = SummarizingCompaction {
@@ -1,20 +1,30 @@
 //! Compact when estimated tokens exceed a threshold.
 
+use ovo_protocol::SharedTokenCounter;
 use ovo_types::{ErrorCode, Message, OvoError};
 
 use crate::max_messages::compact_max_messages;
+use crate::prune::strip_settled_reasoning;
 use crate::strategy::{CompactionOutcome, CompactionStrategy};
 
 /// Drop oldest non-system messages when `token_estimate` exceeds `max_tokens`.
 ///
 /// Uses the same tail-preserving algorithm as [`crate::MaxMessages`], keeping
 /// at most `keep_messages` after the optional leading system message.
-#[derive(Debug, Clone, Copy)]
+/// With [`TokenThreshold::with_counter`] the trigger recounts the messages
+/// under that tokenizer instead of trusting the caller's estimate.
+///
+/// Reasoning before the last user message is dropped first (see
+/// [`crate::StripReasoning`]); with a counter, no messages are dropped when
+/// that alone brings the conversation under `max_tokens`.
+#[derive(Debug, Clone)]
 pub struct TokenThreshold {
     /// Token estimate that triggers compaction (must be >= 1).
     pub max_tokens: u64,
     /// Message count retained after compaction (must be >= 1).
     pub keep_messages: usize,
+    /// Tokenizer used for the trigger (default: caller-supplied estimate).
+    pub counter: Option<SharedTokenCounter>,
 }
 
 impl TokenThreshold {
@@ -39,8 +49,16 @@ pub fn new(max_tokens: u64, keep_messages: usize) -> Result<Self, OvoError> {
         Ok(Self {
             max_tokens,
             keep_messages,
+            counter: None,
         })
     }
+
+    /// Count tokens with `counter` when deciding whether to compact.
+    #[must_use]
+    pub fn with_counter(mut self, counter: SharedTokenCounter) -> Self {
+        self.counter = Some(counter);
+        self
+    }
 }
 
 impl CompactionStrategy for TokenThreshold {
@@ -49,14 +67,24 @@ fn name(&self) -> &'static str {
     }
 
     fn should_compact(&self, messages: &[Message], token_estimate: u64) -> bool {
-        token_estimate > self.max_tokens && messages.len() > self.keep_messages
+        let tokens = self
+            .counter
+            .as_ref()
+            .map_or(token_estimate, |c| u64::from(c.count_messages(messages)));
+        tokens > self.max_tokens && messages.len() > self.keep_messages
     }
 
-    fn compact(&self, messages: Vec<Message>) -> Result<CompactionOutcome, OvoError> {
-        if messages.len() <= self.keep_messages {
+    fn compact(&self, mut messages: Vec<Message>) -> Result<CompactionOutcome, OvoError> {
+        let stripped = strip_settled_reasoning(&mut messages);
+        let fits = stripped
+            && self
+                .counter
+                .as_ref()
+                .is_some_and(|c| u64::from(c.count_messages(&messages)) <= self.max_tokens);
+        if fits || messages.len() <= self.keep_messages {
             return Ok(CompactionOutcome {
                 messages,
-                changed: false,
+                changed: stripped,
                 strategy: self.name(),
             });
         }
@@ -96,6 +124,52 @@ fn triggers_on_token_estimate() {
         );
     }
 
+    #[derive(Debug)]
+    struct PerChar;
+
+    impl ovo_protocol::TokenCounter for PerChar {
+        fn name(&self) -> &'static str {
+            "per_char"
+        }
+
+        fn count_text(&self, text: &str) -> u32 {
+            u32::try_from(text.chars().count()).unwrap_or(u32::MAX)
+        }
+
+        fn message_overhead(&self) -> u32 {
+            0
+        }
+    }
+
+    #[test]
+    fn custom_counter_overrides_estimate() {
+        let s = TokenThreshold::new(10, 1)
+            .expect("new")
+            .with_counter(std::sync::Arc::new(PerChar));
+        let short = vec![Message::user("abc"), Message::user("def")];
+        assert!(!s.should_compact(&short, 1_000));
+        let long = vec![Message::user("abcdef"), Message::user("ghijkl")];
+        assert!(s.should_compact(&long, 0));
+    }
+
+    #[test]
+    fn drops_settled_reasoning_before_messages() {
+        let s = TokenThreshold::new(10, 1)
+            .expect("new")
+            .with_counter(std::sync::Arc::new(PerChar));
+        let mut answer = Message::assistant("ok");
+        answer.parts.push(ovo_types::ContentPart::Reasoning {
+            text: "a long chain of thought".into(),
+            signature: None,
+        });
+        let msgs = vec![Message::user("hi"), answer, Message::user("more")];
+        assert!(s.should_compact(&msgs, 0));
+        let out = s.compact(msgs).expect("compact");
+        assert!(out.changed);
+        assert_eq!(out.messages.len(), 3, "stripping reasoning was enough");
+        assert!(out.messages.iter().all(|m| m.reasoning().is_none()));
+    }
+
     #[test]
     fn rejects_zero() {
         assert!(TokenThreshold::new(0, 3).is_err());
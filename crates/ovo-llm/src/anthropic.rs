@@ -0,0 +1,1091 @@
+//! Anthropic Messages API wire format (build + parse + stream).
+//!
+//! Pure helpers are always available so unit tests need no HTTP client.
+//! The live [`AnthropicSampler`] is behind the `anthropic` feature.
+//!
+//! `Usage::input_tokens` is the full prompt size (uncached + cache read + cache
+//! write) so it lines up with Chat Completions `prompt_tokens`; the cache split
+//! is kept in `cache_read_tokens` / `cache_creation_tokens`.
+//!
+//! Thinking blocks become [`ContentPart::Reasoning`] parts carrying their
+//! signature, and `redacted_thinking` blocks [`ContentPart::Opaque`] parts
+//! tagged [`ANTHROPIC_PROVIDER`]. Both are replayed ahead of the assistant
+//! text, as the API requires during tool use; unsigned reasoning from other
+//! providers is dropped. Extended thinking is requested when
+//! `SamplingParams::reasoning_effort` is set (see [`anthropic_thinking_budget`]).
+
+use ovo_tools::ToolDefinition;
+use ovo_types::{
+    ContentPart, ErrorCode, Message, OvoError, ReasoningEffort, Role, ToolCall, ToolCallId, Usage,
+};
+use serde_json::{Value, json};
+
+use crate::sample::{SampleRequest, SampleResponse, SamplingDialect, ToolChoice, sampling_fields};
+use crate::stream::SampleEvent;
+
+/// [`ContentPart::Opaque`] provider tag for `redacted_thinking` blocks.
+pub const ANTHROPIC_PROVIDER: &str = "anthropic";
+
+/// `anthropic-version` header value.
+pub const ANTHROPIC_VERSION: &str = "2023-06-01";
+
+/// `max_tokens` sent when the request leaves it unset (the API requires one).
+pub const ANTHROPIC_DEFAULT_MAX_TOKENS: u32 = 4096;
+
+/// Extended thinking `budget_tokens` for a reasoning effort (the API minimum
+/// is 1024).
+#[must_use]
+pub const fn anthropic_thinking_budget(effort: ReasoningEffort) -> u32 {
+    match effort {
+        ReasoningEffort::Minimal => 1024,
+        ReasoningEffort::Low => 2048,
+        ReasoningEffort::Medium => 8192,
+        ReasoningEffort::High => 16384,
+    }
+}
+
+/// Configuration for the Anthropic Messages endpoint.
+#[derive(Debug, Clone)]
+pub struct AnthropicConfig {
+    /// Base URL without trailing slash (default `https://api.anthropic.com`).
+    pub base_url: String,
+    /// API key sent as `x-api-key` when non-empty.
+    pub api_key: String,
+    /// Path under base URL (default `/v1/messages`).
+    pub messages_path: String,
+    /// `anthropic-version` header (default [`ANTHROPIC_VERSION`]).
+    pub version: String,
+}
+
+impl AnthropicConfig {
+    /// Messages API at `{base_url}/v1/messages`.
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self {
+            base_url,
+            api_key: api_key.into(),
+            messages_path: "/v1/messages".into(),
+            version: ANTHROPIC_VERSION.into(),
+        }
+    }
+
+    /// Full request URL.
+    #[must_use]
+    pub fn messages_url(&self) -> String {
+        format!("{}{}", self.base_url, self.messages_path)
+    }
+}
+
+impl Default for AnthropicConfig {
+    fn default() -> Self {
+        Self::new("https://api.anthropic.com", "")
+    }
+}
+
+/// Build the JSON body for `POST /v1/messages` from a kernel [`SampleRequest`].
+///
+/// `System` / `Developer` messages are joined into the top-level `system` field;
+/// tool results become `tool_result` blocks on a `user` turn. `response_format`
+/// has no Messages API equivalent and is not sent.
+///
+/// A `reasoning_effort` enables extended thinking: the budget is added on top
+/// of `max_tokens` (which counts it) and `temperature` / `top_p` are omitted.
+/// Thinking only allows `auto` / `none` tool choice, so steps that force a
+/// tool (e.g. the `final_answer` tool) run without it.
+#[must_use]
+pub fn build_anthropic_messages_body(req: &SampleRequest) -> Value {
+    let mut system: Vec<String> = Vec::new();
+    let mut messages: Vec<Value> = Vec::new();
+    for msg in &req.messages {
+        if matches!(msg.role, Role::System | Role::Developer) {
+            let text = msg.text();
+            if !text.is_empty() {
+                system.push(text);
+            }
+            continue;
+        }
+        let (role, blocks) = message_to_anthropic(msg);
+        if blocks.is_empty() {
+            continue;
+        }
+        // The API requires alternating roles: fold consecutive same-role turns
+        // (e.g. parallel tool results) into one message.
+        let same_role = messages
+            .last()
+            .and_then(|m| m.get("role"))
+            .and_then(Value::as_str)
+            == Some(role);
+        if same_role
+            && let Some(content) = messages
+                .last_mut()
+                .and_then(|m| m.get_mut("content"))
+                .and_then(Value::as_array_mut)
+        {
+            content.extend(blocks);
+        } else {
+            messages.push(json!({ "role": role, "content": blocks }));
+        }
+    }
+
+    let forced = !req.tools.is_empty()
+        && matches!(req.tool_choice, ToolChoice::Required | ToolChoice::Named(_));
+    let thinking = req
+        .sampling
+        .reasoning_effort
+        .filter(|_| !forced)
+        .map(anthropic_thinking_budget);
+    let max_tokens = req
+        .max_output_tokens
+        .unwrap_or(ANTHROPIC_DEFAULT_MAX_TOKENS)
+        .saturating_add(thinking.unwrap_or(0));
+
+    let mut map = serde_json::Map::new();
+    map.insert("model".into(), json!(req.model));
+    map.insert("max_tokens".into(), json!(max_tokens));
+    if let Some(budget) = thinking {
+        map.insert(
+            "thinking".into(),
+            json!({ "type": "enabled", "budget_tokens": budget }),
+        );
+    }
+    if !system.is_empty() {
+        map.insert("system".into(), json!(system.join("\n\n")));
+    }
+    map.insert("messages".into(), Value::Array(messages));
+    if !req.tools.is_empty() {
+        map.insert(
+            "tools".into(),
+            Value::Array(req.tools.iter().map(tool_to_anthropic).collect()),
+        );
+        map.insert(
+            "tool_choice".into(),
+            tool_choice_to_anthropic(&req.tool_choice),
+        );
+    }
+    // No seed or penalties on this API; thinking rejects custom temperature / top_p.
+    map.extend(
+        sampling_fields(&req.sampling, SamplingDialect::Anthropic)
+            .filter(|(k, _)| thinking.is_none() || !matches!(*k, "temperature" | "top_p"))
+            .map(|(k, v)| (k.to_owned(), v)),
+    );
+    Value::Object(map)
+}
+
+/// Build the streaming Messages body (`stream: true`).
+#[must_use]
+pub fn build_anthropic_messages_stream_body(req: &SampleRequest) -> Value {
+    let mut body = build_anthropic_messages_body(req);
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("stream".into(), json!(true));
+    }
+    body
+}
+
+fn message_to_anthropic(msg: &Message) -> (&'static str, Vec<Value>) {
+    match msg.role {
+        Role::Tool => {
+            let mut block = serde_json::Map::new();
+            block.insert("type".into(), json!("tool_result"));
+            if let Some(id) = &msg.tool_call_id {
+                block.insert("tool_use_id".into(), json!(id.as_str()));
+            }
+            block.insert("content".into(), json!(msg.text()));
+            ("user", vec![Value::Object(block)])
+        }
+        Role::Assistant => {
+            let mut blocks = Vec::with_capacity(msg.tool_calls.len().saturating_add(1));
+            blocks.extend(msg.parts.iter().filter_map(thinking_block));
+            let text = msg.text();
+            if !text.is_empty() {
+                blocks.push(json!({ "type": "text", "text": text }));
+            }
+            for tc in &msg.tool_calls {
+                let input = match &tc.arguments {
+                    Value::String(s) => serde_json::from_str(s).unwrap_or_else(|_| json!({})),
+                    other => other.clone(),
+                };
+                blocks.push(json!({
+                    "type": "tool_use",
+                    "id": tc.id.as_str(),
+                    "name": tc.name,
+                    "input": input,
+                }));
+            }
+            ("assistant", blocks)
+        }
+        _ => {
+            let text = msg.text();
+            if text.is_empty() {
+                ("user", Vec::new())
+            } else {
+                ("user", vec![json!({ "type": "text", "text": text })])
+            }
+        }
+    }
+}
+
+/// Wire block for a signed reasoning part or a redacted thinking item.
+fn thinking_block(part: &ContentPart) -> Option<Value> {
+    match part {
+        ContentPart::Reasoning {
+            text,
+            signature: Some(signature),
+        } => Some(json!({ "type": "thinking", "thinking": text, "signature": signature })),
+        ContentPart::Opaque { provider, data } if provider == ANTHROPIC_PROVIDER => {
+            serde_json::from_str(data).ok()
+        }
+        _ => None,
+    }
+}
+
+fn tool_to_anthropic(tool: &ToolDefinition) -> Value {
+    json!({
+        "name": tool.name,
+        "description": tool.description,
+        "input_schema": tool.parameters,
+    })
+}
+
+fn tool_choice_to_anthropic(choice: &ToolChoice) -> Value {
+    match choice {
+        ToolChoice::Auto => json!({ "type": "auto" }),
+        ToolChoice::None => json!({ "type": "none" }),
+        ToolChoice::Required => json!({ "type": "any" }),
+        ToolChoice::Named(name) => json!({ "type": "tool", "name": name }),
+    }
+}
+
+/// Parse a Messages API JSON response into a kernel [`SampleResponse`].
+///
+/// # Errors
+///
+/// Returns [`OvoError`] when `content` is missing or a `tool_use` block lacks id/name.
+pub fn parse_anthropic_messages_response(body: &Value) -> Result<SampleResponse, OvoError> {
+    let blocks = body
+        .get("content")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "anthropic response missing content",
+            )
+        })?;
+    let mut text = String::new();
+    let mut tool_calls = Vec::new();
+    let mut parts = Vec::new();
+    for block in blocks {
+        match block.get("type").and_then(Value::as_str) {
+            Some("text") => {
+                text.push_str(block.get("text").and_then(Value::as_str).unwrap_or(""));
+            }
+            Some("thinking") => parts.push(ContentPart::Reasoning {
+                text: block
+                    .get("thinking")
+                    .and_then(Value::as_str)
+                    .unwrap_or("")
+                    .to_owned(),
+                signature: block
+                    .get("signature")
+                    .and_then(Value::as_str)
+                    .map(str::to_owned),
+            }),
+            Some("redacted_thinking") => parts.push(ContentPart::Opaque {
+                provider: ANTHROPIC_PROVIDER.to_owned(),
+                data: block.to_string(),
+            }),
+            Some("tool_use") => {
+                let id = block.get("id").and_then(Value::as_str).ok_or_else(|| {
+                    OvoError::new(ErrorCode::LlmInvalidResponse, "tool_use missing id")
+                })?;
+                let name = block.get("name").and_then(Value::as_str).ok_or_else(|| {
+                    OvoError::new(ErrorCode::LlmInvalidResponse, "tool_use missing name")
+                })?;
+                tool_calls.push(ToolCall {
+                    id: ToolCallId::new(id)?,
+                    name: name.to_owned(),
+                    arguments: block.get("input").cloned().unwrap_or_else(|| json!({})),
+                });
+            }
+            _ => {}
+        }
+    }
+    let mut usage = RawUsage::default();
+    if let Some(u) = body.get("usage") {
+        usage.merge(u);
+    }
+    Ok(SampleResponse {
+        message: assistant_message(text, tool_calls, parts),
+        usage: usage.into_usage(),
+        stop_reason: body
+            .get("stop_reason")
+            .and_then(Value::as_str)
+            .map(str::to_owned),
+        served_by: None,
+        rate_limit: None,
+    })
+}
+
+fn assistant_message(text: String, tool_calls: Vec<ToolCall>, parts: Vec<ContentPart>) -> Message {
+    let mut m = if tool_calls.is_empty() {
+        Message::assistant(text)
+    } else {
+        let mut m = Message::assistant_tools(tool_calls);
+        if !text.is_empty() {
+            m.content = Some(text);
+        }
+        m
+    };
+    m.parts = parts;
+    m
+}
+
+/// Raw Messages API usage counters (later frames overwrite earlier ones).
+#[derive(Debug, Clone, Copy, Default)]
+struct RawUsage {
+    input: u32,
+    output: u32,
+    cache_read: u32,
+    cache_creation: u32,
+}
+
+impl RawUsage {
+    fn merge(&mut self, raw: &Value) {
+        let field = |name: &str| {
+            raw.get(name)
+                .and_then(Value::as_u64)
+                .and_then(|n| u32::try_from(n).ok())
+        };
+        if let Some(n) = field("input_tokens") {
+            self.input = n;
+        }
+        if let Some(n) = field("output_tokens") {
+            self.output = n;
+        }
+        if let Some(n) = field("cache_read_input_tokens") {
+            self.cache_read = n;
+        }
+        if let Some(n) = field("cache_creation_input_tokens") {
+            self.cache_creation = n;
+        }
+    }
+
+    fn into_usage(self) -> Usage {
+        let prompt = self
+            .input
+            .saturating_add(self.cache_read)
+            .saturating_add(self.cache_creation);
+        let mut usage = Usage::new(prompt, self.output);
+        usage.cache_read_tokens = self.cache_read;
+        usage.cache_creation_tokens = self.cache_creation;
+        usage.prompt_details.cached_tokens = self.cache_read;
+        usage
+    }
+}
+
+/// Accumulates Messages API stream events into [`SampleEvent`]s.
+///
+/// Feed each SSE `data:` payload to [`Self::push_data`]; `message_stop` marks the
+/// end, after which [`Self::finish`] yields the coalesced message.
+#[derive(Debug, Default)]
+pub struct AnthropicMessagesStream {
+    blocks: Vec<StreamBlock>,
+    tool_count: u32,
+    usage: RawUsage,
+    stop_reason: Option<String>,
+    done: bool,
+}
+
+#[derive(Debug, Default)]
+enum StreamBlock {
+    Text(String),
+    Thinking {
+        text: String,
+        signature: Option<String>,
+    },
+    Redacted(Value),
+    ToolUse {
+        id: String,
+        name: String,
+        json: String,
+        ordinal: u32,
+    },
+    #[default]
+    Other,
+}
+
+impl AnthropicMessagesStream {
+    /// Empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True once `message_stop` has been seen.
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Decode one `data:` payload and return the incremental events it carries.
+    ///
+    /// # Errors
+    ///
+    /// Invalid JSON yields [`ErrorCode::LlmInvalidResponse`]; an `error` event
+    /// yields [`ErrorCode::LlmProvider`] (or [`ErrorCode::LlmRateLimit`] for
+    /// `rate_limit_error`).
+    pub fn push_data(&mut self, data: &str) -> Result<Vec<SampleEvent>, OvoError> {
+        let event: Value = serde_json::from_str(data).map_err(|e| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                format!("invalid anthropic stream event: {e}"),
+            )
+        })?;
+        self.push_event(&event)
+    }
+
+    /// Apply one parsed stream event.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::push_data`].
+    pub fn push_event(&mut self, event: &Value) -> Result<Vec<SampleEvent>, OvoError> {
+        let mut out = Vec::new();
+        match event.get("type").and_then(Value::as_str) {
+            Some("message_start") => {
+                if let Some(u) = event.pointer("/message/usage") {
+                    self.usage.merge(u);
+                }
+            }
+            Some("content_block_start") => {
+                let slot = block_index(event)?;
+                let block = event.get("content_block").unwrap_or(&Value::Null);
+                let state = match block.get("type").and_then(Value::as_str) {
+                    Some("text") => {
+                        let text = block.get("text").and_then(Value::as_str).unwrap_or("");
+                        if !text.is_empty() {
+                            out.push(SampleEvent::TextDelta {
+                                text: text.to_owned(),
+                            });
+                        }
+                        StreamBlock::Text(text.to_owned())
+                    }
+                    Some("thinking") => StreamBlock::Thinking {
+                        text: block
+                            .get("thinking")
+                            .and_then(Value::as_str)
+                            .unwrap_or("")
+                            .to_owned(),
+                        signature: None,
+                    },
+                    Some("redacted_thinking") => StreamBlock::Redacted(block.clone()),
+                    Some("tool_use") => {
+                        let ordinal = self.tool_count;
+                        self.tool_count = self.tool_count.saturating_add(1);
+                        StreamBlock::ToolUse {
+                            id: block
+                                .get("id")
+                                .and_then(Value::as_str)
+                                .unwrap_or("")
+                                .to_owned(),
+                            name: block
+                                .get("name")
+                                .and_then(Value::as_str)
+                                .unwrap_or("")
+                                .to_owned(),
+                            json: String::new(),
+                            ordinal,
+                        }
+                    }
+                    _ => StreamBlock::Other,
+                };
+                if self.blocks.len() <= slot {
+                    self.blocks.resize_with(slot + 1, StreamBlock::default);
+                }
+                if let Some(entry) = self.blocks.get_mut(slot) {
+                    *entry = state;
+                }
+            }
+            Some("content_block_delta") => {
+                let slot = block_index(event)?;
+                let delta = event.get("delta").unwrap_or(&Value::Null);
+                if let Some(ev) = self.apply_delta(slot, delta) {
+                    out.push(ev);
+                }
+            }
+            Some("message_delta") => {
+                if let Some(reason) = event.pointer("/delta/stop_reason").and_then(Value::as_str) {
+                    self.stop_reason = Some(reason.to_owned());
+                }
+                if let Some(u) = event.get("usage") {
+                    self.usage.merge(u);
+                }
+            }
+            Some("message_stop") => self.done = true,
+            Some("error") => return Err(stream_error(event)),
+            _ => {}
+        }
+        Ok(out)
+    }
+
+    fn apply_delta(&mut self, slot: usize, delta: &Value) -> Option<SampleEvent> {
+        let block = self.blocks.get_mut(slot)?;
+        match (delta.get("type").and_then(Value::as_str), block) {
+            (Some("text_delta"), StreamBlock::Text(buf)) => {
+                let text = delta.get("text").and_then(Value::as_str)?;
+                buf.push_str(text);
+                Some(SampleEvent::TextDelta {
+                    text: text.to_owned(),
+                })
+            }
+            (Some("input_json_delta"), StreamBlock::ToolUse { json, ordinal, .. }) => {
+                let fragment = delta
+                    .get("partial_json")
+                    .and_then(Value::as_str)
+                    .filter(|f| !f.is_empty())?;
+                json.push_str(fragment);
+                Some(SampleEvent::ToolCallDelta {
+                    index: *ordinal,
+                    arguments_delta: fragment.to_owned(),
+                })
+            }
+            (Some("thinking_delta"), block) => {
+                let text = delta.get("thinking").and_then(Value::as_str)?;
+                if let StreamBlock::Thinking { text: buf, .. } = block {
+                    buf.push_str(text);
+                }
+                Some(SampleEvent::ReasoningDelta {
+                    text: text.to_owned(),
+                })
+            }
+            (Some("signature_delta"), StreamBlock::Thinking { signature, .. }) => {
+                let fragment = delta.get("signature").and_then(Value::as_str)?;
+                signature.get_or_insert_default().push_str(fragment);
+                None
+            }
+            _ => None,
+        }
+    }
+
+    /// Terminal events: `ToolCalls` (when any), `Usage`, `Completed`.
+    ///
+    /// # Errors
+    ///
+    /// A stream that ended before `message_stop`, or a `tool_use` block without
+    /// id/name, yields [`ErrorCode::LlmInvalidResponse`].
+    pub fn finish(self) -> Result<Vec<SampleEvent>, OvoError> {
+        if !self.done {
+            return Err(OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "anthropic stream ended before message_stop",
+            ));
+        }
+        let mut text = String::new();
+        let mut tool_calls = Vec::new();
+        let mut parts = Vec::new();
+        for block in self.blocks {
+            match block {
+                StreamBlock::Text(t) => text.push_str(&t),
+                StreamBlock::Thinking { text, signature } => {
+                    parts.push(ContentPart::Reasoning { text, signature });
+                }
+                StreamBlock::Redacted(block) => parts.push(ContentPart::Opaque {
+                    provider: ANTHROPIC_PROVIDER.to_owned(),
+                    data: block.to_string(),
+                }),
+                StreamBlock::ToolUse { id, name, json, .. } => {
+                    if id.is_empty() || name.is_empty() {
+                        return Err(OvoError::new(
+                            ErrorCode::LlmInvalidResponse,
+                            "streamed tool_use missing id or name",
+                        ));
+                    }
+                    let arguments = if json.trim().is_empty() {
+                        json!({})
+                    } else {
+                        serde_json::from_str(&json).unwrap_or(Value::String(json))
+                    };
+                    tool_calls.push(ToolCall {
+                        id: ToolCallId::new(id)?,
+                        name,
+                        arguments,
+                    });
+                }
+                StreamBlock::Other => {}
+            }
+        }
+        let has_tools = !tool_calls.is_empty();
+        let message = assistant_message(text, tool_calls, parts);
+        let mut out = Vec::with_capacity(3);
+        if has_tools {
+            out.push(SampleEvent::ToolCalls {
+                message: message.clone(),
+            });
+        }
+        out.push(SampleEvent::Usage(self.usage.into_usage()));
+        out.push(SampleEvent::Completed {
+            message,
+            stop_reason: self.stop_reason,
+            served_by: None,
+        });
+        Ok(out)
+    }
+}
+
+fn block_index(event: &Value) -> Result<usize, OvoError> {
+    event
+        .get("index")
+        .and_then(Value::as_u64)
+        .and_then(|n| usize::try_from(n).ok())
+        .ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "anthropic stream block missing index",
+            )
+        })
+}
+
+fn stream_error(event: &Value) -> OvoError {
+    let kind = event
+        .pointer("/error/type")
+        .and_then(Value::as_str)
+        .unwrap_or("error");
+    let msg = event
+        .pointer("/error/message")
+        .and_then(Value::as_str)
+        .unwrap_or("");
+    let code = if kind == "rate_limit_error" {
+        ErrorCode::LlmRateLimit
+    } else {
+        ErrorCode::LlmProvider
+    };
+    OvoError::new(code, format!("anthropic stream {kind}: {msg}"))
+}
+
+#[cfg(feature = "anthropic")]
+mod client {
+    use async_trait::async_trait;
+    use ovo_types::{ErrorCode, OvoError};
+    use tracing::{Instrument, info_span};
+
+    use super::{
+        AnthropicConfig, AnthropicMessagesStream, build_anthropic_messages_body,
+        build_anthropic_messages_stream_body, parse_anthropic_messages_response,
+    };
+    use crate::http::{
+        ChunkDecoder, body_stream, precheck, rate_limit_from_headers, read_text, send_checked,
+    };
+    use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
+    use crate::sampler::LlmSampler;
+    use crate::sse::SseDecoder;
+    use crate::stream::{SampleEvent, SampleStream};
+
+    /// HTTP client for the Anthropic Messages API.
+    #[derive(Debug, Clone)]
+    pub struct AnthropicSampler {
+        config: AnthropicConfig,
+        client: reqwest::Client,
+    }
+
+    impl AnthropicSampler {
+        /// Create a sampler with a default `reqwest` client.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error when the HTTP client cannot be built.
+        pub fn new(config: AnthropicConfig) -> Result<Self, OvoError> {
+            let client = reqwest::Client::builder().build().map_err(|e| {
+                OvoError::new(ErrorCode::LlmProvider, format!("http client build: {e}"))
+            })?;
+            Ok(Self { config, client })
+        }
+
+        /// Create with an existing client.
+        #[must_use]
+        pub fn with_client(config: AnthropicConfig, client: reqwest::Client) -> Self {
+            Self { config, client }
+        }
+
+        fn post(&self, body: &serde_json::Value) -> reqwest::RequestBuilder {
+            let mut req = self
+                .client
+                .post(self.config.messages_url())
+                .header("anthropic-version", &self.config.version)
+                .json(body);
+            if !self.config.api_key.is_empty() {
+                req = req.header("x-api-key", &self.config.api_key);
+            }
+            req
+        }
+    }
+
+    #[async_trait]
+    impl LlmSampler for AnthropicSampler {
+        async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+            precheck(&request)?;
+            let body = build_anthropic_messages_body(&request);
+            let span = info_span!(
+                "ovo.sample.http",
+                ovo.model = %request.model,
+                ovo.provider = "anthropic",
+            );
+            async move {
+                let response = send_checked("anthropic", self.post(&body), &request.cancel).await?;
+                let rate_limit = rate_limit_from_headers(&response);
+                let text = read_text(response, &request.cancel).await?;
+                let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
+                    OvoError::new(
+                        ErrorCode::LlmInvalidResponse,
+                        format!("invalid JSON body: {e}"),
+                    )
+                })?;
+                let mut response = parse_anthropic_messages_response(&value)?;
+                response.rate_limit = rate_limit;
+                Ok(response)
+            }
+            .instrument(span)
+            .await
+        }
+
+        async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+            precheck(&request)?;
+            let body = build_anthropic_messages_stream_body(&request);
+            let span = info_span!(
+                "ovo.sample.http",
+                ovo.model = %request.model,
+                ovo.provider = "anthropic",
+                ovo.stream = true,
+            );
+            let response = send_checked("anthropic", self.post(&body), &request.cancel)
+                .instrument(span)
+                .await?;
+            Ok(body_stream(
+                response,
+                request.cancel,
+                SseMessagesDecoder::default(),
+            ))
+        }
+
+        /// Messages API bodies carry no `response_format`, so structured
+        /// output uses the forced `final_answer` tool.
+        fn capabilities(&self, _model: &str) -> ModelCapabilities {
+            ModelCapabilities::new().with_json_schema(false)
+        }
+    }
+
+    /// SSE framing + [`AnthropicMessagesStream`] mapping.
+    #[derive(Debug, Default)]
+    struct SseMessagesDecoder {
+        sse: SseDecoder,
+        acc: AnthropicMessagesStream,
+    }
+
+    impl ChunkDecoder for SseMessagesDecoder {
+        fn push(&mut self, chunk: &[u8]) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            for ev in self.sse.push(chunk) {
+                if self.acc.is_done() {
+                    break;
+                }
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            Ok(out)
+        }
+
+        fn is_done(&self) -> bool {
+            self.acc.is_done()
+        }
+
+        fn finish(&mut self) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            if !self.acc.is_done()
+                && let Some(ev) = self.sse.finish()
+            {
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            out.extend(std::mem::take(&mut self.acc).finish()?);
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(feature = "anthropic")]
+pub use client::AnthropicSampler;
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use tokio_util::sync::CancellationToken;
+
+    use ovo_types::SamplingParams;
+
+    use super::*;
+
+    fn req(messages: Vec<Message>) -> SampleRequest {
+        SampleRequest {
+            model: "claude-test".into(),
+            messages,
+            tools: vec![ToolDefinition {
+                name: "calc".into(),
+                description: "calc".into(),
+                parameters: json!({"type": "object"}),
+            }],
+            tool_choice: ToolChoice::Required,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::new().with_temperature(0.5),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[test]
+    fn build_body_maps_system_tools_and_results() {
+        let id_a = ToolCallId::new("toolu_a").expect("id");
+        let id_b = ToolCallId::new("toolu_b").expect("id");
+        let mut assistant = Message::assistant_tools(vec![
+            ToolCall {
+                id: id_a.clone(),
+                name: "calc".into(),
+                arguments: json!({"expr": "1+1"}),
+            },
+            ToolCall {
+                id: id_b.clone(),
+                name: "calc".into(),
+                arguments: json!("{\"expr\":\"2+2\"}"),
+            },
+        ]);
+        assistant.content = Some("checking".into());
+        let body = build_anthropic_messages_body(&req(vec![
+            Message::system("be terse"),
+            Message::user("add"),
+            assistant,
+            Message::tool_result(id_a, "calc", "2"),
+            Message::tool_result(id_b, "calc", "4"),
+        ]));
+        assert_eq!(body.get("system"), Some(&json!("be terse")));
+        assert_eq!(
+            body.get("max_tokens"),
+            Some(&json!(ANTHROPIC_DEFAULT_MAX_TOKENS))
+        );
+        assert_eq!(body.get("tool_choice"), Some(&json!({"type": "any"})));
+        assert_eq!(
+            body.pointer("/tools/0/input_schema"),
+            Some(&json!({"type": "object"}))
+        );
+        let messages = body
+            .get("messages")
+            .and_then(Value::as_array)
+            .expect("messages");
+        assert_eq!(messages.len(), 3, "tool results fold into one user turn");
+        assert_eq!(
+            body.pointer("/messages/1/content/1/type"),
+            Some(&json!("tool_use"))
+        );
+        assert_eq!(
+            body.pointer("/messages/1/content/2/input/expr"),
+            Some(&json!("2+2"))
+        );
+        assert_eq!(body.pointer("/messages/2/role"), Some(&json!("user")));
+        assert_eq!(
+            body.pointer("/messages/2/content/1/tool_use_id"),
+            Some(&json!("toolu_b"))
+        );
+        assert_eq!(
+            body.pointer("/messages/2/content/1/type"),
+            Some(&json!("tool_result"))
+        );
+    }
+
+    #[test]
+    fn sampling_keeps_supported_fields_only() {
+        let mut request = req(vec![Message::user("hi")]);
+        request.sampling = request
+            .sampling
+            .with_top_p(0.9)
+            .with_seed(42)
+            .with_stop(["END"]);
+        let body = build_anthropic_messages_body(&request);
+        assert_eq!(body.get("temperature"), Some(&json!(0.5)));
+        assert_eq!(body.get("stop_sequences"), Some(&json!(["END"])));
+        assert!(body.get("top_p").is_some());
+        assert!(body.get("seed").is_none(), "Messages API has no seed");
+    }
+
+    #[test]
+    fn reasoning_effort_enables_thinking_unless_a_tool_is_forced() {
+        let mut request = req(vec![Message::user("think")]);
+        request.tool_choice = ToolChoice::Auto;
+        request.max_output_tokens = Some(1000);
+        request.sampling = request
+            .sampling
+            .with_reasoning_effort(ReasoningEffort::Medium);
+        let body = build_anthropic_messages_body(&request);
+        assert_eq!(
+            body.get("thinking"),
+            Some(&json!({"type": "enabled", "budget_tokens": 8192}))
+        );
+        assert_eq!(body.get("max_tokens"), Some(&json!(9192)));
+        assert!(body.get("temperature").is_none());
+
+        request.tool_choice = ToolChoice::Named("calc".into());
+        let body = build_anthropic_messages_body(&request);
+        assert!(body.get("thinking").is_none());
+        assert_eq!(body.get("max_tokens"), Some(&json!(1000)));
+        assert_eq!(body.get("temperature"), Some(&json!(0.5)));
+    }
+
+    #[test]
+    fn parse_response_maps_tool_use_and_cache_usage() {
+        let body = json!({
+            "content": [
+                {"type": "text", "text": "let me compute"},
+                {"type": "tool_use", "id": "toolu_1", "name": "calc", "input": {"expr": "3*3"}}
+            ],
+            "stop_reason": "tool_use",
+            "usage": {
+                "input_tokens": 10,
+                "output_tokens": 7,
+                "cache_read_input_tokens": 100,
+                "cache_creation_input_tokens": 20
+            }
+        });
+        let resp = parse_anthropic_messages_response(&body).expect("parse");
+        assert_eq!(resp.message.content.as_deref(), Some("let me compute"));
+        let tc = resp.message.tool_calls.first().expect("tc");
+        assert_eq!(tc.id.as_str(), "toolu_1");
+        assert_eq!(tc.arguments.get("expr"), Some(&json!("3*3")));
+        assert_eq!(resp.stop_reason.as_deref(), Some("tool_use"));
+        assert_eq!(resp.usage.input_tokens, 130);
+        assert_eq!(resp.usage.output_tokens, 7);
+        assert_eq!(resp.usage.cache_read_tokens, 100);
+        assert_eq!(resp.usage.cache_creation_tokens, 20);
+        assert_eq!(resp.usage.prompt_details.cached_tokens, 100);
+    }
+
+    #[test]
+    fn signed_thinking_round_trips_and_unsigned_reasoning_is_dropped() {
+        let body = json!({
+            "content": [
+                {"type": "thinking", "thinking": "square it", "signature": "sig_1"},
+                {"type": "redacted_thinking", "data": "enc"},
+                {"type": "tool_use", "id": "toolu_1", "name": "calc", "input": {"expr": "3*3"}}
+            ],
+            "stop_reason": "tool_use"
+        });
+        let resp = parse_anthropic_messages_response(&body).expect("parse");
+        assert_eq!(resp.message.reasoning().as_deref(), Some("square it"));
+        let mut foreign = Message::assistant("earlier");
+        foreign.parts.push(ContentPart::Reasoning {
+            text: "unsigned".into(),
+            signature: None,
+        });
+        let body = build_anthropic_messages_body(&req(vec![
+            Message::user("q"),
+            foreign,
+            Message::user("again"),
+            resp.message,
+        ]));
+        assert_eq!(
+            body.pointer("/messages/1/content"),
+            Some(&json!([{"type": "text", "text": "earlier"}]))
+        );
+        assert_eq!(
+            body.pointer("/messages/3/content/0"),
+            Some(&json!({"type": "thinking", "thinking": "square it", "signature": "sig_1"}))
+        );
+        assert_eq!(
+            body.pointer("/messages/3/content/1"),
+            Some(&json!({"type": "redacted_thinking", "data": "enc"}))
+        );
+        assert_eq!(
+            body.pointer("/messages/3/content/2/type"),
+            Some(&json!("tool_use"))
+        );
+    }
+
+    #[test]
+    fn stream_maps_thinking_text_and_tool_json() {
+        let events = [
+            json!({"type":"message_start","message":{"usage":{"input_tokens":5,"output_tokens":1,"cache_read_input_tokens":3}}}),
+            json!({"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}),
+            json!({"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"ponder"}}),
+            json!({"type":"content_block_delta","index":0,"delta":{"type":"signature_delta","signature":"sig"}}),
+            json!({"type":"content_block_stop","index":0}),
+            json!({"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}),
+            json!({"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"ok"}}),
+            json!({"type":"content_block_start","index":2,"content_block":{"type":"tool_use","id":"toolu_s","name":"calc","input":{}}}),
+            json!({"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"{\"expr\":"}}),
+            json!({"type":"content_block_delta","index":2,"delta":{"type":"input_json_delta","partial_json":"\"4/2\"}"}}),
+            json!({"type":"ping"}),
+            json!({"type":"message_delta","delta":{"stop_reason":"tool_use"},"usage":{"output_tokens":9}}),
+            json!({"type":"message_stop"}),
+        ];
+        let mut acc = AnthropicMessagesStream::new();
+        let mut out = Vec::new();
+        for ev in &events {
+            out.extend(acc.push_event(ev).expect("event"));
+        }
+        assert!(acc.is_done());
+        assert!(matches!(
+            out.first(),
+            Some(SampleEvent::ReasoningDelta { text }) if text == "ponder"
+        ));
+        assert!(matches!(
+            out.get(1),
+            Some(SampleEvent::TextDelta { text }) if text == "ok"
+        ));
+        assert!(matches!(
+            out.get(2),
+            Some(SampleEvent::ToolCallDelta { index: 0, .. })
+        ));
+        let tail = acc.finish().expect("finish");
+        assert!(matches!(
+            tail.get(1),
+            Some(SampleEvent::Usage(u))
+                if u.input_tokens == 8 && u.output_tokens == 9 && u.cache_read_tokens == 3
+        ));
+        match tail.last() {
+            Some(SampleEvent::Completed {
+                message,
+                stop_reason,
+                ..
+            }) => {
+                assert_eq!(stop_reason.as_deref(), Some("tool_use"));
+                assert_eq!(message.content.as_deref(), Some("ok"));
+                assert_eq!(
+                    message.parts,
+                    vec![ContentPart::Reasoning {
+                        text: "ponder".into(),
+                        signature: Some("sig".into()),
+                    }]
+                );
+                let tc = message.tool_calls.first().expect("tc");
+                assert_eq!(tc.arguments.get("expr"), Some(&json!("4/2")));
+            }
+            other => unreachable!("expected Completed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn stream_error_event_and_truncation() {
+        let mut acc = AnthropicMessagesStream::new();
+        let err = acc
+            .push_data(
+                r#"{"type":"error","error":{"type":"overloaded_error","message":"Overloaded"}}"#,
+            )
+            .expect_err("error event");
+        assert_eq!(err.code(), ErrorCode::LlmProvider);
+        assert!(err.message().contains("Overloaded"), "{err}");
+
+        let mut acc = AnthropicMessagesStream::new();
+        acc.push_data(r#"{"type":"message_start","message":{"usage":{"input_tokens":1}}}"#)
+            .expect("start");
+        let err = acc.finish().expect_err("truncated");
+        assert_eq!(err.code(), ErrorCode::LlmInvalidResponse);
+    }
+}
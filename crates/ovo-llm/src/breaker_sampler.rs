@@ -10,7 +10,7 @@
 use ovo_types::{ErrorCode, OvoError};
 
 use crate::breaker::{Admission, BreakerOutcome, CircuitBreaker};
-use crate::sample::{SampleRequest, SampleResponse};
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
 use crate::sampler::LlmSampler;
 use crate::stream::{SampleEvent, SampleStream};
 
@@ -80,22 +80,30 @@ async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, Ov
             }
         }
     }
+
+    fn capabilities(&self, model: &str) -> ModelCapabilities {
+        self.inner.capabilities(model)
+    }
 }
 
 impl<S> BreakerSampler<S> {
     fn admit(&self) -> Result<(), OvoError> {
-        match self.breaker.check() {
-            Admission::Allow => Ok(()),
-            Admission::Reject { retry_after } => Err(OvoError::new(
-                ErrorCode::LlmProvider,
-                format!(
-                    "circuit breaker open for endpoint '{}'; retry after {}ms",
-                    self.endpoint,
-                    retry_after.as_millis()
-                ),
-            )
-            .with_retry(ovo_types::RetryClass::Backoff)),
-        }
+        admit(&self.breaker, &self.endpoint)
+    }
+}
+
+/// Breaker admission check shared with [`crate::BreakerEmbedder`].
+pub(crate) fn admit(breaker: &CircuitBreaker, endpoint: &str) -> Result<(), OvoError> {
+    match breaker.check() {
+        Admission::Allow => Ok(()),
+        Admission::Reject { retry_after } => Err(OvoError::new(
+            ErrorCode::LlmProvider,
+            format!(
+                "circuit breaker open for endpoint '{endpoint}'; retry after {}ms",
+                retry_after.as_millis()
+            ),
+        )
+        .with_retry(ovo_types::RetryClass::Backoff)),
     }
 }
 
@@ -0,0 +1,523 @@
+//! [`CachingSampler`]: serve repeated identical requests from a response cache.
+//!
+//! Keys are [`request_fingerprint`]s (model, messages, tools, tool choice,
+//! response format, sampling knobs). Caching is opt-in: a request is eligible
+//! when [`SampleRequest::cacheable`] is set or its model was enabled with
+//! [`CachingSampler::with_model`]. Hits come back with zeroed token counts and
+//! [`Usage::response_cache_hits`] set, so metrics, ledgers and token budgets do
+//! not count them as fresh spend. Backends are best effort: storage errors
+//! degrade to a miss.
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+use async_trait::async_trait;
+use futures::Stream;
+use ovo_types::{OvoError, Usage};
+use serde::{Deserialize, Serialize};
+
+use crate::fingerprint::{RequestMask, request_fingerprint};
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
+use crate::sampler::{LlmSampler, response_to_stream};
+use crate::stream::{SampleEvent, SampleStream, response_from_events};
+
+/// Storage for cached responses keyed by request fingerprint.
+pub trait ResponseCache: Send + Sync {
+    /// Fresh entry for `key`, if any.
+    fn get(&self, key: &str) -> Option<SampleResponse>;
+    /// Store `response` under `key`.
+    fn put(&self, key: &str, response: &SampleResponse);
+}
+
+#[derive(Debug)]
+struct MemoryEntry {
+    response: SampleResponse,
+    stored_at: Instant,
+    tick: u64,
+}
+
+#[derive(Debug, Default)]
+struct MemoryState {
+    entries: HashMap<String, MemoryEntry>,
+    /// Recency order: tick → key (oldest first).
+    order: BTreeMap<u64, String>,
+    next_tick: u64,
+}
+
+impl MemoryState {
+    fn touch(&mut self, key: &str) {
+        let tick = self.next_tick;
+        self.next_tick = self.next_tick.saturating_add(1);
+        if let Some(entry) = self.entries.get_mut(key) {
+            self.order.remove(&entry.tick);
+            entry.tick = tick;
+            self.order.insert(tick, key.to_owned());
+        }
+    }
+
+    fn remove(&mut self, key: &str) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.order.remove(&entry.tick);
+        }
+    }
+}
+
+/// In-process LRU cache with an entry cap and optional TTL.
+#[derive(Debug)]
+pub struct MemoryCache {
+    max_entries: usize,
+    ttl: Option<Duration>,
+    state: Mutex<MemoryState>,
+}
+
+impl MemoryCache {
+    /// LRU holding at most `max_entries` responses (minimum 1).
+    #[must_use]
+    pub fn new(max_entries: usize) -> Self {
+        Self {
+            max_entries: max_entries.max(1),
+            ttl: None,
+            state: Mutex::new(MemoryState::default()),
+        }
+    }
+
+    /// Expire entries older than `ttl`.
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Entries currently held (including not-yet-evicted expired ones).
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// True when nothing is cached.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, MemoryState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl ResponseCache for MemoryCache {
+    fn get(&self, key: &str) -> Option<SampleResponse> {
+        let mut state = self.lock();
+        let expired = state
+            .entries
+            .get(key)
+            .map(|e| self.ttl.is_some_and(|ttl| e.stored_at.elapsed() > ttl))?;
+        if expired {
+            state.remove(key);
+            return None;
+        }
+        state.touch(key);
+        state.entries.get(key).map(|e| e.response.clone())
+    }
+
+    fn put(&self, key: &str, response: &SampleResponse) {
+        let mut state = self.lock();
+        state.remove(key);
+        state.entries.insert(
+            key.to_owned(),
+            MemoryEntry {
+                response: response.clone(),
+                stored_at: Instant::now(),
+                tick: 0,
+            },
+        );
+        state.touch(key);
+        while state.entries.len() > self.max_entries {
+            let Some((_, oldest)) = state.order.pop_first() else {
+                break;
+            };
+            state.entries.remove(&oldest);
+        }
+        drop(state);
+    }
+}
+
+#[derive(Serialize, Deserialize)]
+struct DiskEntry {
+    stored_at_ms: u64,
+    response: SampleResponse,
+}
+
+fn unix_ms(at: SystemTime) -> u64 {
+    at.duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+}
+
+/// One JSON file per entry under a directory, with TTL and a total-bytes cap
+/// (least recently written files are evicted first).
+#[derive(Debug)]
+pub struct DiskCache {
+    dir: PathBuf,
+    ttl: Option<Duration>,
+    max_bytes: Option<u64>,
+    /// Serializes eviction scans.
+    evict: Mutex<()>,
+}
+
+impl DiskCache {
+    /// Cache rooted at `dir` (created on first write).
+    #[must_use]
+    pub fn new(dir: impl Into<PathBuf>) -> Self {
+        Self {
+            dir: dir.into(),
+            ttl: None,
+            max_bytes: None,
+            evict: Mutex::new(()),
+        }
+    }
+
+    /// Expire entries older than `ttl`.
+    #[must_use]
+    pub const fn with_ttl(mut self, ttl: Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    /// Keep the directory under `max_bytes` of entry files.
+    #[must_use]
+    pub const fn with_max_bytes(mut self, max_bytes: u64) -> Self {
+        self.max_bytes = Some(max_bytes);
+        self
+    }
+
+    /// Cache directory.
+    #[must_use]
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    fn entry_path(&self, key: &str) -> PathBuf {
+        self.dir.join(format!("{key}.json"))
+    }
+
+    fn is_expired(&self, stored_at_ms: u64) -> bool {
+        self.ttl.is_some_and(|ttl| {
+            let age = unix_ms(SystemTime::now()).saturating_sub(stored_at_ms);
+            u128::from(age) > ttl.as_millis()
+        })
+    }
+
+    fn enforce_max_bytes(&self) {
+        let Some(max_bytes) = self.max_bytes else {
+            return;
+        };
+        let _scan = self.evict.lock().unwrap_or_else(PoisonError::into_inner);
+        let Ok(read_dir) = std::fs::read_dir(&self.dir) else {
+            return;
+        };
+        let mut files: Vec<(SystemTime, u64, PathBuf)> = read_dir
+            .filter_map(Result::ok)
+            .filter(|e| e.path().extension().is_some_and(|x| x == "json"))
+            .filter_map(|e| {
+                let meta = e.metadata().ok()?;
+                Some((meta.modified().ok()?, meta.len(), e.path()))
+            })
+            .collect();
+        let mut total: u64 = files.iter().map(|(_, len, _)| *len).sum();
+        files.sort_unstable_by_key(|(modified, _, _)| *modified);
+        for (_, len, path) in files {
+            if total <= max_bytes {
+                break;
+            }
+            if std::fs::remove_file(&path).is_ok() {
+                total = total.saturating_sub(len);
+            }
+        }
+    }
+}
+
+impl ResponseCache for DiskCache {
+    fn get(&self, key: &str) -> Option<SampleResponse> {
+        let path = self.entry_path(key);
+        let raw = std::fs::read_to_string(&path).ok()?;
+        let entry: DiskEntry = serde_json::from_str(&raw).ok()?;
+        if self.is_expired(entry.stored_at_ms) {
+            let _ = std::fs::remove_file(&path);
+            return None;
+        }
+        Some(entry.response)
+    }
+
+    fn put(&self, key: &str, response: &SampleResponse) {
+        let entry = DiskEntry {
+            stored_at_ms: unix_ms(SystemTime::now()),
+            response: response.clone(),
+        };
+        let Ok(raw) = serde_json::to_vec(&entry) else {
+            return;
+        };
+        if std::fs::create_dir_all(&self.dir).is_err() {
+            return;
+        }
+        // Write-then-rename so concurrent readers never see a torn file.
+        let path = self.entry_path(key);
+        let tmp = path.with_extension("json.tmp");
+        if std::fs::write(&tmp, raw).is_ok() && std::fs::rename(&tmp, &path).is_err() {
+            let _ = std::fs::remove_file(&tmp);
+        }
+        self.enforce_max_bytes();
+    }
+}
+
+/// Decorator answering eligible requests from a [`ResponseCache`].
+#[derive(Clone)]
+pub struct CachingSampler<S> {
+    inner: Arc<S>,
+    cache: Arc<dyn ResponseCache>,
+    models: BTreeSet<String>,
+    mask: RequestMask,
+}
+
+impl<S> fmt::Debug for CachingSampler<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CachingSampler")
+            .field("models", &self.models)
+            .field("mask", &self.mask)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> CachingSampler<S> {
+    /// Wrap `inner`; only [`SampleRequest::cacheable`] requests are cached
+    /// until models are enabled with [`Self::with_model`].
+    #[must_use]
+    pub fn new(inner: Arc<S>, cache: Arc<dyn ResponseCache>) -> Self {
+        Self {
+            inner,
+            cache,
+            models: BTreeSet::new(),
+            mask: RequestMask::new(),
+        }
+    }
+
+    /// Cache every request for `model` (for deterministic configurations).
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>) -> Self {
+        self.models.insert(model.into());
+        self
+    }
+
+    /// Mask volatile request fields before hashing.
+    #[must_use]
+    pub fn with_mask(mut self, mask: RequestMask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Cache key for `request`, or `None` when it has not opted in.
+    #[must_use]
+    pub fn cache_key(&self, request: &SampleRequest) -> Option<String> {
+        (request.cacheable || self.models.contains(&request.model))
+            .then(|| request_fingerprint(request, &self.mask))
+    }
+
+    fn hit(&self, key: &str) -> Option<SampleResponse> {
+        self.cache.get(key).map(|mut response| {
+            let mut usage = Usage::zero();
+            usage.response_cache_hits = 1;
+            response.usage = usage;
+            response.rate_limit = None;
+            response
+        })
+    }
+}
+
+#[async_trait]
+impl<S: LlmSampler + 'static> LlmSampler for CachingSampler<S> {
+    async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+        let Some(key) = self.cache_key(&request) else {
+            return self.inner.sample(request).await;
+        };
+        if let Some(hit) = self.hit(&key) {
+            return Ok(hit);
+        }
+        let response = self.inner.sample(request).await?;
+        self.cache.put(&key, &response);
+        Ok(response)
+    }
+
+    async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+        let Some(key) = self.cache_key(&request) else {
+            return self.inner.sample_stream(request).await;
+        };
+        if let Some(hit) = self.hit(&key) {
+            return Ok(response_to_stream(hit));
+        }
+        let inner = self.inner.sample_stream(request).await?;
+        Ok(Box::pin(FillStream {
+            inner,
+            cache: Arc::clone(&self.cache),
+            key: Some(key),
+            events: Vec::new(),
+        }))
+    }
+
+    fn capabilities(&self, model: &str) -> ModelCapabilities {
+        self.inner.capabilities(model)
+    }
+}
+
+/// Passes events through and stores the response once the stream completes.
+struct FillStream {
+    inner: SampleStream,
+    cache: Arc<dyn ResponseCache>,
+    key: Option<String>,
+    events: Vec<SampleEvent>,
+}
+
+impl Stream for FillStream {
+    type Item = SampleEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let polled = Pin::new(&mut self.inner).poll_next(cx);
+        match &polled {
+            Poll::Ready(Some(ev)) => {
+                if self.key.is_some() {
+                    self.events.push(ev.clone());
+                }
+            }
+            Poll::Ready(None) => {
+                let events = std::mem::take(&mut self.events);
+                if let Some(key) = self.key.take()
+                    && matches!(events.last(), Some(SampleEvent::Completed { .. }))
+                    && let Ok(response) = response_from_events(events)
+                {
+                    self.cache.put(&key, &response);
+                }
+            }
+            Poll::Pending => {}
+        }
+        polled
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use futures::StreamExt;
+    use ovo_types::{Message, SamplingParams};
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::mock::MockSampler;
+    use crate::sample::ToolChoice;
+
+    fn req(model: &str, cacheable: bool) -> SampleRequest {
+        SampleRequest {
+            model: model.into(),
+            messages: vec![Message::user("same prompt")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::new().with_temperature(0.0),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable,
+        }
+    }
+
+    #[tokio::test]
+    async fn caches_only_opted_in_requests_and_flags_hits() {
+        let mock = Arc::new(MockSampler::new());
+        mock.push_text("live-1");
+        mock.push_text("live-2");
+        mock.push_text("live-3");
+        let cached = CachingSampler::new(mock, Arc::new(MemoryCache::new(8))).with_model("det");
+
+        let first = cached.sample(req("m", true)).await.expect("first");
+        assert_eq!(first.usage.response_cache_hits, 0);
+        let again = cached.sample(req("m", true)).await.expect("hit");
+        assert_eq!(again.message.text(), "live-1");
+        assert_eq!(again.usage.response_cache_hits, 1);
+
+        let uncached = cached.sample(req("m", false)).await.expect("miss");
+        assert_eq!(uncached.message.text(), "live-2");
+
+        let events: Vec<SampleEvent> = cached
+            .sample_stream(req("det", false))
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        assert!(matches!(events.last(), Some(SampleEvent::Completed { .. })));
+        let hit = cached.sample(req("det", false)).await.expect("model hit");
+        assert_eq!(hit.message.text(), "live-3");
+        assert_eq!(hit.usage.response_cache_hits, 1);
+        assert_eq!(hit.usage.input_tokens + hit.usage.output_tokens, 0);
+
+        let events: Vec<SampleEvent> = cached
+            .sample_stream(req("det", false))
+            .await
+            .expect("stream hit")
+            .collect()
+            .await;
+        assert!(
+            events.iter().any(|e| matches!(
+                e,
+                SampleEvent::Usage(u) if u.response_cache_hits == 1 && u.total_tokens == 0
+            )),
+            "{events:?}"
+        );
+    }
+
+    #[tokio::test]
+    async fn memory_lru_evicts_least_recent_and_expires() {
+        let response = |t: &str| SampleResponse {
+            message: Message::assistant(t),
+            usage: Usage::zero(),
+            stop_reason: None,
+            served_by: None,
+            rate_limit: None,
+        };
+        let lru = MemoryCache::new(2);
+        lru.put("a", &response("a"));
+        lru.put("b", &response("b"));
+        assert!(lru.get("a").is_some());
+        lru.put("c", &response("c"));
+        assert!(lru.get("b").is_none(), "b was least recently used");
+        assert!(lru.get("a").is_some() && lru.get("c").is_some());
+
+        let expiring = MemoryCache::new(2).with_ttl(Duration::ZERO);
+        expiring.put("a", &response("a"));
+        tokio::time::sleep(Duration::from_millis(2)).await;
+        assert!(expiring.get("a").is_none());
+        assert!(expiring.is_empty());
+    }
+
+    #[test]
+    fn disk_cache_round_trips_and_caps_size() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let response = SampleResponse {
+            message: Message::assistant("x".repeat(200)),
+            usage: Usage::new(3, 4),
+            stop_reason: Some("stop".into()),
+            served_by: None,
+            rate_limit: None,
+        };
+        let disk = DiskCache::new(dir.path().join("cache")).with_max_bytes(600);
+        disk.put("k1", &response);
+        assert_eq!(disk.get("k1"), Some(response.clone()));
+        for key in ["k2", "k3", "k4"] {
+            disk.put(key, &response);
+        }
+        let files = std::fs::read_dir(disk.dir()).expect("dir").count();
+        assert!(files < 4, "size cap evicted old entries ({files} left)");
+        assert!(disk.get("k4").is_some(), "newest entry survives");
+    }
+}
@@ -0,0 +1,445 @@
+//! Record/replay cassettes for deterministic integration tests.
+//!
+//! [`RecordingSampler`] wraps a live sampler and appends one JSONL line per
+//! sample: request fingerprint → [`SampleResponse`] (or the full
+//! [`SampleEvent`] list for streams). Failed samples and streams ending in
+//! [`SampleEvent::Failed`] are not recorded. [`ReplaySampler`] serves a cassette
+//! offline and fails loudly on any request it has no recording for.
+//!
+//! Format v1:
+//! - optional first line: `# ovo-cassette/1`
+//! - one [`CassetteEntry`] per line; repeated fingerprints replay in order,
+//!   the last recording is reused once the queue drains
+
+use std::collections::{HashMap, VecDeque};
+use std::fs::{File, OpenOptions};
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+
+use async_trait::async_trait;
+use futures::{Stream, stream};
+use ovo_types::{ErrorCode, OvoError, RetryClass};
+use serde::{Deserialize, Serialize};
+
+use crate::fingerprint::{RequestMask, request_fingerprint};
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
+use crate::sampler::{LlmSampler, response_to_stream};
+use crate::stream::{SampleEvent, SampleStream, response_from_events};
+
+/// First-line format marker for cassettes.
+pub const CASSETTE_VERSION_HEADER: &str = "# ovo-cassette/1";
+
+/// What was recorded for one request.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+#[non_exhaustive]
+pub enum Recording {
+    /// Non-streaming [`LlmSampler::sample`] result.
+    Sample {
+        /// Recorded response.
+        response: Box<SampleResponse>,
+    },
+    /// Every event of a [`LlmSampler::sample_stream`] run.
+    Stream {
+        /// Recorded events in order.
+        events: Vec<SampleEvent>,
+    },
+}
+
+/// One cassette line.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CassetteEntry {
+    /// [`request_fingerprint`] of the masked request.
+    pub hash: String,
+    /// Requested model (diagnostics only; not matched).
+    pub model: String,
+    /// Recorded result.
+    #[serde(flatten)]
+    pub recording: Recording,
+}
+
+fn cassette_error(path: &Path, action: &str, e: impl std::fmt::Display) -> OvoError {
+    OvoError::new(
+        ErrorCode::Internal,
+        format!("cassette {action} {}: {e}", path.display()),
+    )
+}
+
+#[derive(Debug)]
+struct CassetteWriter {
+    path: PathBuf,
+    file: Mutex<File>,
+}
+
+impl CassetteWriter {
+    fn append(&self, entry: &CassetteEntry) -> Result<(), OvoError> {
+        let mut line =
+            serde_json::to_string(entry).map_err(|e| cassette_error(&self.path, "encode", e))?;
+        line.push('\n');
+        let mut file = self.file.lock().unwrap_or_else(PoisonError::into_inner);
+        file.write_all(line.as_bytes())
+            .and_then(|()| file.flush())
+            .map_err(|e| cassette_error(&self.path, "write", e))
+    }
+}
+
+/// Decorator that records every successful sample or stream to a JSONL cassette.
+#[derive(Debug, Clone)]
+pub struct RecordingSampler<S> {
+    inner: Arc<S>,
+    writer: Arc<CassetteWriter>,
+    mask: RequestMask,
+}
+
+impl<S> RecordingSampler<S> {
+    /// Record into `path` (created with a version header, or appended to).
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::Internal`] when the file cannot be opened or written.
+    pub fn create(inner: Arc<S>, path: impl Into<PathBuf>) -> Result<Self, OvoError> {
+        let path = path.into();
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .map_err(|e| cassette_error(&path, "open", e))?;
+        let empty = file
+            .metadata()
+            .map_err(|e| cassette_error(&path, "stat", e))?
+            .len()
+            == 0;
+        if empty {
+            writeln!(file, "{CASSETTE_VERSION_HEADER}")
+                .map_err(|e| cassette_error(&path, "write", e))?;
+        }
+        Ok(Self {
+            inner,
+            writer: Arc::new(CassetteWriter {
+                path,
+                file: Mutex::new(file),
+            }),
+            mask: RequestMask::new(),
+        })
+    }
+
+    /// Mask volatile request fields before hashing (must match the replayer).
+    #[must_use]
+    pub fn with_mask(mut self, mask: RequestMask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Cassette path.
+    #[must_use]
+    pub fn path(&self) -> &Path {
+        &self.writer.path
+    }
+}
+
+#[async_trait]
+impl<S: LlmSampler + 'static> LlmSampler for RecordingSampler<S> {
+    async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+        let hash = request_fingerprint(&request, &self.mask);
+        let model = request.model.clone();
+        let response = self.inner.sample(request).await?;
+        self.writer.append(&CassetteEntry {
+            hash,
+            model,
+            recording: Recording::Sample {
+                response: Box::new(response.clone()),
+            },
+        })?;
+        Ok(response)
+    }
+
+    async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+        let hash = request_fingerprint(&request, &self.mask);
+        let model = request.model.clone();
+        let inner = self.inner.sample_stream(request).await?;
+        Ok(Box::pin(RecordStream {
+            inner,
+            writer: Arc::clone(&self.writer),
+            pending: Some((hash, model)),
+            events: Vec::new(),
+        }))
+    }
+
+    fn capabilities(&self, model: &str) -> ModelCapabilities {
+        self.inner.capabilities(model)
+    }
+}
+
+/// Tees stream events and appends the recording when the stream ends
+/// (unless it ended in [`SampleEvent::Failed`]).
+struct RecordStream {
+    inner: SampleStream,
+    writer: Arc<CassetteWriter>,
+    pending: Option<(String, String)>,
+    events: Vec<SampleEvent>,
+}
+
+impl Stream for RecordStream {
+    type Item = SampleEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        match Pin::new(&mut self.inner).poll_next(cx) {
+            Poll::Ready(Some(ev)) => {
+                self.events.push(ev.clone());
+                Poll::Ready(Some(ev))
+            }
+            Poll::Ready(None) => {
+                let failed = matches!(self.events.last(), Some(SampleEvent::Failed { .. }));
+                if let Some((hash, model)) = self.pending.take().filter(|_| !failed) {
+                    let events = std::mem::take(&mut self.events);
+                    let entry = CassetteEntry {
+                        hash,
+                        model,
+                        recording: Recording::Stream { events },
+                    };
+                    if let Err(e) = self.writer.append(&entry) {
+                        return Poll::Ready(Some(SampleEvent::Failed {
+                            message: e.to_string(),
+                        }));
+                    }
+                }
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Offline sampler serving a cassette written by [`RecordingSampler`].
+#[derive(Debug, Default)]
+pub struct ReplaySampler {
+    entries: Mutex<HashMap<String, VecDeque<Recording>>>,
+    mask: RequestMask,
+    capabilities: ModelCapabilities,
+}
+
+impl ReplaySampler {
+    /// Load a cassette file.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::Internal`] when the file cannot be read or a line is not a
+    /// valid [`CassetteEntry`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, OvoError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| cassette_error(path, "read", e))?;
+        let mut entries = Vec::new();
+        for (n, line) in text.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let entry: CassetteEntry = serde_json::from_str(line).map_err(|e| {
+                cassette_error(path, "parse", format!("line {}: {e}", n.saturating_add(1)))
+            })?;
+            entries.push(entry);
+        }
+        Ok(Self::from_entries(entries))
+    }
+
+    /// Build from in-memory entries.
+    #[must_use]
+    pub fn from_entries(entries: impl IntoIterator<Item = CassetteEntry>) -> Self {
+        let mut map: HashMap<String, VecDeque<Recording>> = HashMap::new();
+        for entry in entries {
+            map.entry(entry.hash)
+                .or_default()
+                .push_back(entry.recording);
+        }
+        Self {
+            entries: Mutex::new(map),
+            mask: RequestMask::new(),
+            capabilities: ModelCapabilities::new(),
+        }
+    }
+
+    /// Mask volatile request fields before hashing (must match the recorder).
+    #[must_use]
+    pub fn with_mask(mut self, mask: RequestMask) -> Self {
+        self.mask = mask;
+        self
+    }
+
+    /// Report `capabilities` for every model (must match the recorded backend,
+    /// or structured output picks a different mode and every step misses).
+    #[must_use]
+    pub const fn with_capabilities(mut self, capabilities: ModelCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
+    fn next(&self, request: &SampleRequest) -> Result<Recording, OvoError> {
+        if request.cancel.is_cancelled() {
+            return Err(OvoError::llm_cancelled("sample cancelled"));
+        }
+        let hash = request_fingerprint(request, &self.mask);
+        let recording = {
+            let mut entries = self.entries.lock().unwrap_or_else(PoisonError::into_inner);
+            match entries.get_mut(&hash) {
+                Some(q) if q.len() > 1 => q.pop_front(),
+                Some(q) => q.front().cloned(),
+                None => None,
+            }
+        };
+        recording.ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                format!(
+                    "cassette miss: no recording for request {hash} (model '{}')",
+                    request.model
+                ),
+            )
+            .with_retry(RetryClass::Never)
+        })
+    }
+}
+
+#[async_trait]
+impl LlmSampler for ReplaySampler {
+    async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+        match self.next(&request)? {
+            Recording::Sample { response } => Ok(*response),
+            Recording::Stream { events } => response_from_events(events),
+        }
+    }
+
+    async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+        Ok(match self.next(&request)? {
+            Recording::Sample { response } => response_to_stream(*response),
+            Recording::Stream { events } => Box::pin(stream::iter(events)),
+        })
+    }
+
+    fn capabilities(&self, _model: &str) -> ModelCapabilities {
+        self.capabilities
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use futures::StreamExt;
+    use ovo_types::{Message, SamplingParams};
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::mock::MockSampler;
+    use crate::sample::ToolChoice;
+
+    fn req(prompt: &str) -> SampleRequest {
+        SampleRequest {
+            model: "m".into(),
+            messages: vec![Message::user(prompt)],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    /// Streams one delta, then fails mid-stream.
+    struct TornStream;
+
+    #[async_trait]
+    impl LlmSampler for TornStream {
+        async fn sample(&self, _request: SampleRequest) -> Result<SampleResponse, OvoError> {
+            Err(OvoError::new(ErrorCode::LlmProvider, "unused"))
+        }
+
+        async fn sample_stream(&self, _request: SampleRequest) -> Result<SampleStream, OvoError> {
+            Ok(Box::pin(stream::iter([
+                SampleEvent::TextDelta { text: "par".into() },
+                SampleEvent::Failed {
+                    message: "connection reset".into(),
+                },
+            ])))
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_streams_are_not_recorded() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("torn.cassette.jsonl");
+        let recorder = RecordingSampler::create(Arc::new(TornStream), &path).expect("create");
+        let events: Vec<SampleEvent> = recorder
+            .sample_stream(req("a"))
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        assert!(matches!(events.last(), Some(SampleEvent::Failed { .. })));
+
+        let raw = std::fs::read_to_string(&path).expect("read");
+        assert_eq!(raw.trim(), CASSETTE_VERSION_HEADER);
+        let replay = ReplaySampler::load(&path).expect("load");
+        assert!(replay.sample_stream(req("a")).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn record_then_replay_sample_and_stream() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("run.cassette.jsonl");
+        let live = Arc::new(MockSampler::new());
+        live.push_text("first");
+        live.push_text("streamed");
+        let recorder = RecordingSampler::create(live, &path).expect("create");
+        assert_eq!(
+            recorder.sample(req("a")).await.expect("a").message.text(),
+            "first"
+        );
+        let recorded: Vec<SampleEvent> = recorder
+            .sample_stream(req("b"))
+            .await
+            .expect("b")
+            .collect()
+            .await;
+        assert!(matches!(
+            recorded.last(),
+            Some(SampleEvent::Completed { .. })
+        ));
+
+        let raw = std::fs::read_to_string(&path).expect("read");
+        assert!(raw.starts_with(CASSETTE_VERSION_HEADER), "{raw}");
+
+        let replay = ReplaySampler::load(&path).expect("load");
+        assert_eq!(
+            replay.sample(req("a")).await.expect("a").message.text(),
+            "first"
+        );
+        let replayed: Vec<SampleEvent> = replay
+            .sample_stream(req("b"))
+            .await
+            .expect("b")
+            .collect()
+            .await;
+        assert_eq!(replayed.len(), recorded.len());
+        assert_eq!(
+            replay
+                .sample(req("b"))
+                .await
+                .expect("folded")
+                .message
+                .text(),
+            "streamed"
+        );
+
+        let err = replay
+            .sample(req("never recorded"))
+            .await
+            .expect_err("miss");
+        assert_eq!(err.code(), ErrorCode::LlmInvalidResponse);
+        assert!(err.message().contains("cassette miss"), "{err}");
+    }
+}
@@ -0,0 +1,257 @@
+//! [`Embedder`] trait, [`MockEmbedder`] and resilience decorators.
+//!
+//! HTTP embedders live next to their samplers ([`crate::openai_compat`],
+//! `ollama`) and share the same transport, so status codes map to the same
+//! typed errors that [`RetryingEmbedder`] and [`BreakerEmbedder`] classify
+//! exactly like [`crate::RetryingSampler`] and [`crate::BreakerSampler`].
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use ovo_types::{ErrorCode, OvoError};
+use tokio::time::sleep;
+
+use crate::breaker::{BreakerOutcome, CircuitBreaker};
+use crate::breaker_sampler::admit;
+use crate::retry::{RetryDecision, RetryPolicy, decide_retry};
+use crate::retrying::retry_ctx;
+
+/// Batch text embedding.
+#[async_trait]
+pub trait Embedder: Send + Sync {
+    /// One vector per input text, in input order.
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, OvoError>;
+}
+
+/// Check a provider batch has one vector per input.
+#[cfg(any(feature = "openai", feature = "ollama"))]
+pub(crate) fn check_batch(
+    vectors: Vec<Vec<f32>>,
+    expected: usize,
+) -> Result<Vec<Vec<f32>>, OvoError> {
+    if vectors.len() == expected {
+        Ok(vectors)
+    } else {
+        Err(OvoError::new(
+            ErrorCode::LlmInvalidResponse,
+            format!("expected {expected} embeddings, got {}", vectors.len()),
+        ))
+    }
+}
+
+/// Deterministic offline embedder: unit vectors seeded by a hash of the text.
+///
+/// Equal texts embed identically; different texts are near-orthogonal in
+/// practice. Scripted errors ([`Self::push_error`]) are returned first.
+#[derive(Debug)]
+pub struct MockEmbedder {
+    dimensions: usize,
+    errors: Mutex<Vec<OvoError>>,
+    calls: Mutex<u32>,
+}
+
+impl Default for MockEmbedder {
+    fn default() -> Self {
+        Self::new(8)
+    }
+}
+
+impl MockEmbedder {
+    /// Mock producing `dimensions`-long vectors.
+    #[must_use]
+    pub const fn new(dimensions: usize) -> Self {
+        Self {
+            dimensions,
+            errors: Mutex::new(Vec::new()),
+            calls: Mutex::new(0),
+        }
+    }
+
+    /// Fail the next call with `error` (FIFO).
+    pub fn push_error(&self, error: OvoError) {
+        self.errors
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .push(error);
+    }
+
+    /// Number of `embed` calls so far.
+    #[must_use]
+    pub fn calls(&self) -> u32 {
+        *self.calls.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    /// The vector this mock returns for `text`.
+    #[must_use]
+    pub fn vector(&self, text: &str) -> Vec<f32> {
+        // FNV-1a seed, splitmix64 stream.
+        let mut state = text.bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| {
+            (h ^ u64::from(b)).wrapping_mul(0x0100_0000_01b3)
+        });
+        let mut v: Vec<f32> = (0..self.dimensions)
+            .map(|_| {
+                state = state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+                let mut z = state;
+                z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+                z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+                z ^= z >> 31;
+                let top = u16::try_from(z >> 48).unwrap_or(u16::MAX);
+                f32::from(top) / 32_767.5 - 1.0
+            })
+            .collect();
+        let norm = v.iter().fold(0.0f32, |acc, x| x.mul_add(*x, acc)).sqrt();
+        if norm > 0.0 {
+            for x in &mut v {
+                *x /= norm;
+            }
+        }
+        v
+    }
+}
+
+#[async_trait]
+impl Embedder for MockEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, OvoError> {
+        {
+            let mut calls = self.calls.lock().unwrap_or_else(PoisonError::into_inner);
+            *calls = calls.saturating_add(1);
+        }
+        let scripted = {
+            let mut errors = self.errors.lock().unwrap_or_else(PoisonError::into_inner);
+            (!errors.is_empty()).then(|| errors.remove(0))
+        };
+        if let Some(err) = scripted {
+            return Err(err);
+        }
+        Ok(texts.iter().map(|t| self.vector(t)).collect())
+    }
+}
+
+/// Wraps an [`Embedder`] with the sampler [`RetryPolicy`].
+#[derive(Debug, Clone)]
+pub struct RetryingEmbedder<E> {
+    inner: Arc<E>,
+    policy: RetryPolicy,
+}
+
+impl<E> RetryingEmbedder<E> {
+    /// Wrap `inner` with the default policy.
+    #[must_use]
+    pub fn new(inner: Arc<E>) -> Self {
+        Self {
+            inner,
+            policy: RetryPolicy::default(),
+        }
+    }
+
+    /// Custom policy.
+    #[must_use]
+    pub fn with_policy(mut self, policy: RetryPolicy) -> Self {
+        self.policy = policy;
+        self
+    }
+}
+
+#[async_trait]
+impl<E: Embedder + 'static> Embedder for RetryingEmbedder<E> {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, OvoError> {
+        let mut attempt = 0u32;
+        let mut rate_limit_retries = 0u32;
+        loop {
+            match self.inner.embed(texts).await {
+                Ok(vectors) => return Ok(vectors),
+                Err(err) => {
+                    let ctx = retry_ctx(attempt, rate_limit_retries, &err);
+                    match decide_retry(&self.policy, &err, &ctx) {
+                        RetryDecision::Fatal => return Err(err),
+                        RetryDecision::Retry { backoff, .. } => {
+                            if err.code() == ErrorCode::LlmRateLimit {
+                                rate_limit_retries = rate_limit_retries.saturating_add(1);
+                            }
+                            attempt = attempt.saturating_add(1);
+                            sleep(backoff).await;
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// [`Embedder`] wrapper that refuses traffic while the breaker is open.
+#[derive(Debug, Clone)]
+pub struct BreakerEmbedder<E> {
+    inner: Arc<E>,
+    breaker: Arc<CircuitBreaker>,
+    endpoint: String,
+}
+
+impl<E> BreakerEmbedder<E> {
+    /// Wrap `inner` with a shared breaker.
+    #[must_use]
+    pub fn new(inner: Arc<E>, breaker: Arc<CircuitBreaker>, endpoint: impl Into<String>) -> Self {
+        Self {
+            inner,
+            breaker,
+            endpoint: endpoint.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl<E: Embedder + 'static> Embedder for BreakerEmbedder<E> {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, OvoError> {
+        admit(&self.breaker, &self.endpoint)?;
+        let result = self.inner.embed(texts).await;
+        self.breaker.record(if result.is_ok() {
+            BreakerOutcome::Success
+        } else {
+            BreakerOutcome::Failure
+        });
+        result
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use super::*;
+    use crate::openai_compat::http_status_error;
+
+    fn texts(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| (*s).to_owned()).collect()
+    }
+
+    #[tokio::test]
+    async fn mock_is_deterministic_and_normalized() {
+        let mock = MockEmbedder::new(16);
+        let out = mock
+            .embed(&texts(&["alpha", "beta", "alpha"]))
+            .await
+            .expect("embed");
+        assert_eq!(out.len(), 3);
+        assert_eq!(out.first(), out.get(2));
+        assert_ne!(out.first(), out.get(1));
+        for v in &out {
+            assert_eq!(v.len(), 16);
+            let norm = v.iter().map(|x| x * x).sum::<f32>().sqrt();
+            assert!((norm - 1.0).abs() < 1e-4, "{norm}");
+        }
+    }
+
+    #[tokio::test]
+    async fn retrying_embedder_retries_transient_errors() {
+        let mock = Arc::new(MockEmbedder::new(4));
+        mock.push_error(http_status_error(503, "busy"));
+        let retrying =
+            RetryingEmbedder::new(Arc::clone(&mock)).with_policy(RetryPolicy::for_tests());
+        let out = retrying.embed(&texts(&["x"])).await.expect("embed");
+        assert_eq!(out.len(), 1);
+        assert_eq!(mock.calls(), 2);
+
+        mock.push_error(http_status_error(401, "bad key"));
+        let err = retrying.embed(&texts(&["x"])).await.expect_err("fatal");
+        assert_eq!(err.code(), ErrorCode::LlmAuth);
+        assert_eq!(mock.calls(), 3);
+    }
+}
@@ -0,0 +1,255 @@
+//! [`FallbackSampler`]: walk an ordered chain of `(sampler, model)` targets.
+//!
+//! Unlike [`crate::RetryingSampler`] (same backend, backoff), a fallback moves
+//! to the next target immediately when the current one is rate limited, down
+//! (including an open [`crate::BreakerSampler`]), or rejects the prompt as too
+//! long for its context window. Streams fall back only while opening; once
+//! events flow the stream belongs to that target.
+
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use futures::{StreamExt, stream};
+use ovo_types::{ErrorCode, OvoError};
+
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
+use crate::sampler::LlmSampler;
+use crate::stream::{SampleEvent, SampleStream, with_served_by};
+
+/// Provider phrasings for "prompt exceeds the context window".
+const OVERFLOW_MARKERS: &[&str] = &[
+    "context_length_exceeded",
+    "maximum context length",
+    "context window",
+    "prompt is too long",
+    "too many tokens",
+];
+
+/// True when `err` reports a context-window overflow.
+#[must_use]
+pub fn is_context_overflow(err: &OvoError) -> bool {
+    if err.code() == ErrorCode::CompactionOverflow {
+        return true;
+    }
+    let msg = err.message().to_ascii_lowercase();
+    OVERFLOW_MARKERS.iter().any(|m| msg.contains(m))
+}
+
+/// True when a fallback chain should move past `err` to the next target.
+#[must_use]
+pub fn should_fall_back(err: &OvoError) -> bool {
+    matches!(err.code(), ErrorCode::LlmRateLimit | ErrorCode::LlmProvider)
+        || is_context_overflow(err)
+}
+
+/// One link in a [`FallbackSampler`] chain.
+#[derive(Clone)]
+pub struct FallbackTarget {
+    /// Backend.
+    pub sampler: Arc<dyn LlmSampler>,
+    /// Model id sent to `sampler` (replaces [`SampleRequest::model`]).
+    pub model: String,
+}
+
+impl fmt::Debug for FallbackTarget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FallbackTarget")
+            .field("model", &self.model)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Sampler that tries each target in order until one answers.
+///
+/// Responses carry the serving model in [`SampleResponse::served_by`]; streams
+/// carry it on [`SampleEvent::Completed`].
+#[derive(Debug, Clone, Default)]
+pub struct FallbackSampler {
+    targets: Vec<FallbackTarget>,
+}
+
+impl FallbackSampler {
+    /// Empty chain.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append a target.
+    #[must_use]
+    pub fn with_target(mut self, sampler: Arc<dyn LlmSampler>, model: impl Into<String>) -> Self {
+        self.targets.push(FallbackTarget {
+            sampler,
+            model: model.into(),
+        });
+        self
+    }
+
+    /// Targets in fallback order.
+    #[must_use]
+    pub fn targets(&self) -> &[FallbackTarget] {
+        &self.targets
+    }
+
+    fn empty_chain() -> OvoError {
+        OvoError::new(ErrorCode::LlmProvider, "fallback chain has no targets")
+    }
+}
+
+#[async_trait]
+impl LlmSampler for FallbackSampler {
+    async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+        let mut last = Self::empty_chain();
+        for target in &self.targets {
+            if request.cancel.is_cancelled() {
+                return Err(OvoError::llm_cancelled("sample cancelled"));
+            }
+            let mut req = request.clone();
+            req.model.clone_from(&target.model);
+            match target.sampler.sample(req).await {
+                Ok(mut response) => {
+                    response
+                        .served_by
+                        .get_or_insert_with(|| target.model.clone());
+                    return Ok(response);
+                }
+                Err(e) if should_fall_back(&e) => last = e,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last)
+    }
+
+    async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+        let mut last = Self::empty_chain();
+        let mut notices: Vec<SampleEvent> = Vec::new();
+        for (i, target) in self.targets.iter().enumerate() {
+            if request.cancel.is_cancelled() {
+                return Err(OvoError::llm_cancelled("sample cancelled"));
+            }
+            if i > 0 {
+                notices.push(SampleEvent::Retrying {
+                    attempt: u32::try_from(i.saturating_add(1)).unwrap_or(u32::MAX),
+                    reason: format!("fallback:{}", target.model),
+                });
+            }
+            let mut req = request.clone();
+            req.model.clone_from(&target.model);
+            match target.sampler.sample_stream(req).await {
+                Ok(inner) => {
+                    let inner = with_served_by(inner, target.model.clone());
+                    return Ok(Box::pin(stream::iter(notices).chain(inner)));
+                }
+                Err(e) if should_fall_back(&e) => last = e,
+                Err(e) => return Err(e),
+            }
+        }
+        Err(last)
+    }
+
+    /// Any target may serve, so a capability holds only when every target has it.
+    fn capabilities(&self, _model: &str) -> ModelCapabilities {
+        ModelCapabilities::new().with_json_schema(
+            self.targets
+                .iter()
+                .all(|t| t.sampler.capabilities(&t.model).json_schema),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use ovo_types::{Message, SamplingParams};
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::mock::MockSampler;
+    use crate::sample::ToolChoice;
+
+    fn req() -> SampleRequest {
+        SampleRequest {
+            model: "primary".into(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn falls_back_on_rate_limit_and_overflow() {
+        let a = Arc::new(MockSampler::new());
+        a.push_error(OvoError::new(ErrorCode::LlmRateLimit, "429"));
+        let b = Arc::new(MockSampler::new());
+        b.push_error(OvoError::new(
+            ErrorCode::LlmInvalidResponse,
+            "This model's maximum context length is 8192 tokens",
+        ));
+        let c = Arc::new(MockSampler::new());
+        c.push_text("from c");
+        let chain = FallbackSampler::new()
+            .with_target(a, "model-a")
+            .with_target(b, "model-b")
+            .with_target(c, "model-c");
+        let response = chain.sample(req()).await.expect("fallback");
+        assert_eq!(response.message.text(), "from c");
+        assert_eq!(response.served_by.as_deref(), Some("model-c"));
+    }
+
+    #[tokio::test]
+    async fn fatal_errors_stop_the_chain() {
+        let a = Arc::new(MockSampler::new());
+        a.push_error(OvoError::new(ErrorCode::LlmAuth, "bad key"));
+        let b = Arc::new(MockSampler::new());
+        b.push_text("unreached");
+        let chain = FallbackSampler::new()
+            .with_target(a, "model-a")
+            .with_target(b, "model-b");
+        let err = chain.sample(req()).await.expect_err("auth is fatal");
+        assert_eq!(err.code(), ErrorCode::LlmAuth);
+    }
+
+    #[tokio::test]
+    async fn stream_emits_retrying_with_next_target() {
+        let a = Arc::new(MockSampler::new());
+        a.push_error(OvoError::new(ErrorCode::LlmProvider, "breaker open"));
+        let b = Arc::new(MockSampler::new());
+        b.push_text("ok");
+        let chain = FallbackSampler::new()
+            .with_target(a, "model-a")
+            .with_target(b, "model-b");
+        let events: Vec<SampleEvent> = chain
+            .sample_stream(req())
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        assert!(matches!(
+            events.first(),
+            Some(SampleEvent::Retrying { attempt: 2, reason }) if reason == "fallback:model-b"
+        ));
+        assert!(matches!(
+            events.last(),
+            Some(SampleEvent::Completed { served_by: Some(m), .. }) if m == "model-b"
+        ));
+    }
+
+    #[test]
+    fn capabilities_require_every_target() {
+        let full: Arc<dyn LlmSampler> = Arc::new(MockSampler::new());
+        let plain: Arc<dyn LlmSampler> = Arc::new(
+            MockSampler::new().with_capabilities(ModelCapabilities::new().with_json_schema(false)),
+        );
+        let one = FallbackSampler::new().with_target(Arc::clone(&full), "a");
+        assert!(one.capabilities("a").json_schema);
+        let both = one.with_target(plain, "b");
+        assert!(!both.capabilities("a").json_schema);
+    }
+}
@@ -0,0 +1,193 @@
+//! Stable request fingerprints for cassettes and response caches.
+//!
+//! Same approach as the workflow journal: canonical JSON (sorted keys) hashed
+//! with SHA-256, first 16 digest bytes as 32 hex chars. A [`RequestMask`]
+//! blanks volatile fields (ids, timestamps) before hashing.
+
+use std::collections::BTreeSet;
+
+use serde_json::{Value, json};
+use sha2::{Digest, Sha256};
+
+use crate::sample::SampleRequest;
+
+/// Placeholder written over masked values.
+pub const MASKED: &str = "<masked>";
+
+/// Volatile-field masking applied before hashing.
+#[derive(Debug, Clone, Default)]
+pub struct RequestMask {
+    keys: BTreeSet<String>,
+    text_filter: Option<fn(&str) -> String>,
+}
+
+impl RequestMask {
+    /// No masking.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Mask tool-call ids (`id`, `tool_call_id`), which providers mint per run.
+    #[must_use]
+    pub fn tool_call_ids() -> Self {
+        Self::new().with_key("id").with_key("tool_call_id")
+    }
+
+    /// Mask every object value stored under `key` (at any depth).
+    #[must_use]
+    pub fn with_key(mut self, key: impl Into<String>) -> Self {
+        self.keys.insert(key.into());
+        self
+    }
+
+    /// Rewrite every string value (e.g. strip timestamps from prompts).
+    #[must_use]
+    pub fn with_text_filter(mut self, filter: fn(&str) -> String) -> Self {
+        self.text_filter = Some(filter);
+        self
+    }
+
+    /// Apply the mask to `value` in place.
+    pub fn apply(&self, value: &mut Value) {
+        match value {
+            Value::Object(map) => {
+                for (k, v) in map.iter_mut() {
+                    if self.keys.contains(k) {
+                        *v = json!(MASKED);
+                    } else {
+                        self.apply(v);
+                    }
+                }
+            }
+            Value::Array(items) => items.iter_mut().for_each(|v| self.apply(v)),
+            Value::String(s) => {
+                if let Some(filter) = self.text_filter {
+                    *s = filter(s);
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Recursively sort object keys for stable hashing.
+#[must_use]
+pub fn canonical_json(value: &Value) -> Value {
+    match value {
+        Value::Object(map) => {
+            let mut entries: Vec<(&String, &Value)> = map.iter().collect();
+            entries.sort_unstable_by(|a, b| a.0.cmp(b.0));
+            Value::Object(
+                entries
+                    .into_iter()
+                    .map(|(k, v)| (k.clone(), canonical_json(v)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => Value::Array(items.iter().map(canonical_json).collect()),
+        other => other.clone(),
+    }
+}
+
+/// Provider-neutral JSON view of the fields that determine a response.
+///
+/// Cancellation and deadlines are excluded.
+#[must_use]
+pub fn sample_request_json(req: &SampleRequest) -> Value {
+    let mut value = json!({
+        "model": req.model,
+        "messages": req.messages,
+        "tools": req.tools,
+        "tool_choice": req.tool_choice,
+        "response_format": req.response_format,
+        "max_output_tokens": req.max_output_tokens,
+        "temperature": req.sampling.temperature,
+    });
+    // Other sampling knobs only when set, so fingerprints recorded before
+    // they existed stay stable.
+    if let Ok(Value::Object(extra)) = serde_json::to_value(&req.sampling)
+        && let Some(obj) = value.as_object_mut()
+    {
+        for (key, v) in extra {
+            obj.entry(key).or_insert(v);
+        }
+    }
+    value
+}
+
+/// Fingerprint `req` after masking (16 digest bytes → 32 hex chars).
+#[must_use]
+pub fn request_fingerprint(req: &SampleRequest, mask: &RequestMask) -> String {
+    let mut value = sample_request_json(req);
+    mask.apply(&mut value);
+    let mut hasher = Sha256::new();
+    hasher.update(canonical_json(&value).to_string().as_bytes());
+    let digest = hasher.finalize();
+    encode_hex(digest.iter().take(16).copied())
+}
+
+fn encode_hex(bytes: impl IntoIterator<Item = u8>) -> String {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    let mut s = String::with_capacity(32);
+    for b in bytes {
+        let hi = usize::from(b >> 4);
+        let lo = usize::from(b & 0x0f);
+        if let (Some(&h), Some(&l)) = (HEX.get(hi), HEX.get(lo)) {
+            s.push(char::from(h));
+            s.push(char::from(l));
+        }
+    }
+    s
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use ovo_types::{Message, SamplingParams, ToolCall, ToolCallId};
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::sample::ToolChoice;
+
+    fn req(call_id: &str, prompt: &str) -> SampleRequest {
+        SampleRequest {
+            model: "m".into(),
+            messages: vec![
+                Message::user(prompt),
+                Message::assistant_tools(vec![ToolCall {
+                    id: ToolCallId::new(call_id).expect("id"),
+                    name: "t".into(),
+                    arguments: json!({"b": 1, "a": 2}),
+                }]),
+            ],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[test]
+    fn mask_makes_ids_and_filtered_text_irrelevant() {
+        let plain = RequestMask::new();
+        assert_ne!(
+            request_fingerprint(&req("call_1", "hi"), &plain),
+            request_fingerprint(&req("call_2", "hi"), &plain)
+        );
+        let ids = RequestMask::tool_call_ids();
+        assert_eq!(
+            request_fingerprint(&req("call_1", "hi"), &ids),
+            request_fingerprint(&req("call_2", "hi"), &ids)
+        );
+        let strip_digits = ids.with_text_filter(|s| s.replace(|c: char| c.is_ascii_digit(), "#"));
+        let a = request_fingerprint(&req("call_1", "now 12:00"), &strip_digits);
+        let b = request_fingerprint(&req("call_2", "now 13:45"), &strip_digits);
+        assert_eq!(a, b);
+        assert_eq!(a.len(), 32);
+    }
+}
@@ -0,0 +1,934 @@
+//! Google Gemini `generateContent` wire format (build + parse + stream).
+//!
+//! Pure helpers are always available so unit tests need no HTTP client.
+//! The live [`GeminiSampler`] is behind the `gemini` feature.
+//!
+//! Gemini function calls carry no ids, so parsed calls get a synthetic
+//! [`ToolCallId`] derived from the request's `contents`, position, name and
+//! arguments: the same exchange always yields the same ids (journals and
+//! replays stay stable), while a repeated call in a later step still gets a
+//! fresh one (fallback backends reject duplicate ids in one conversation).
+//! Tool results are sent back as `functionResponse` parts keyed by tool name.
+//! Thought summaries are kept as reasoning parts but never sent back;
+//! `thoughtSignature`s are kept as [`ContentPart::Opaque`] parts tagged
+//! [`GEMINI_PROVIDER`] and replayed on the matching `functionCall` (or text)
+//! part of the `model` turn, which thinking models require during multi-step
+//! tool use.
+//!
+//! Schemas go out as `responseJsonSchema` / `parametersJsonSchema`, which take
+//! full JSON Schema; the older `responseSchema` / `parameters` fields only
+//! accept an OpenAPI subset and reject `additionalProperties`, `$defs`, etc.
+
+use std::collections::HashMap;
+
+use ovo_tools::ToolDefinition;
+use ovo_types::{ContentPart, ErrorCode, Message, OvoError, Role, ToolCall, ToolCallId, Usage};
+use serde_json::{Value, json};
+
+use crate::reasoning::attach_reasoning;
+use crate::sample::{SampleRequest, SampleResponse, SamplingDialect, ToolChoice, sampling_fields};
+use crate::stream::SampleEvent;
+
+/// [`ContentPart::Opaque`] provider tag for `thoughtSignature`s.
+pub const GEMINI_PROVIDER: &str = "gemini";
+
+/// Configuration for the Gemini API (`generativelanguage.googleapis.com` or a proxy).
+#[derive(Debug, Clone)]
+pub struct GeminiConfig {
+    /// Base URL without trailing slash.
+    pub base_url: String,
+    /// API key sent as `x-goog-api-key` when non-empty.
+    pub api_key: String,
+    /// API version path segment (default `v1beta`).
+    pub api_version: String,
+}
+
+impl GeminiConfig {
+    /// Gemini API at `{base_url}/{api_version}/models/…`.
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self {
+            base_url,
+            api_key: api_key.into(),
+            api_version: "v1beta".into(),
+        }
+    }
+
+    /// `generateContent` URL for `model`.
+    #[must_use]
+    pub fn generate_url(&self, model: &str) -> String {
+        format!(
+            "{}/{}/models/{model}:generateContent",
+            self.base_url, self.api_version
+        )
+    }
+
+    /// `streamGenerateContent` URL for `model` (SSE framing).
+    #[must_use]
+    pub fn stream_url(&self, model: &str) -> String {
+        format!(
+            "{}/{}/models/{model}:streamGenerateContent?alt=sse",
+            self.base_url, self.api_version
+        )
+    }
+}
+
+impl Default for GeminiConfig {
+    fn default() -> Self {
+        Self::new("https://generativelanguage.googleapis.com", "")
+    }
+}
+
+/// Build the `generateContent` JSON body from a kernel [`SampleRequest`].
+///
+/// The model id travels in the URL, not the body. `System` / `Developer`
+/// messages become `systemInstruction`.
+#[must_use]
+pub fn build_gemini_generate_body(req: &SampleRequest) -> Value {
+    let mut system: Vec<Value> = Vec::new();
+    let mut contents: Vec<Value> = Vec::new();
+    // Tool results may omit `name`; recover it from the assistant call that issued the id.
+    let mut call_names: HashMap<&str, &str> = HashMap::new();
+    for msg in &req.messages {
+        for tc in &msg.tool_calls {
+            call_names.insert(tc.id.as_str(), tc.name.as_str());
+        }
+        if matches!(msg.role, Role::System | Role::Developer) {
+            let text = msg.text();
+            if !text.is_empty() {
+                system.push(json!({ "text": text }));
+            }
+            continue;
+        }
+        let (role, parts) = message_to_gemini(msg, &call_names);
+        if parts.is_empty() {
+            continue;
+        }
+        // Parallel function responses must share one turn; fold same-role runs.
+        let same_role = contents
+            .last()
+            .and_then(|c| c.get("role"))
+            .and_then(Value::as_str)
+            == Some(role);
+        if same_role
+            && let Some(existing) = contents
+                .last_mut()
+                .and_then(|c| c.get_mut("parts"))
+                .and_then(Value::as_array_mut)
+        {
+            existing.extend(parts);
+        } else {
+            contents.push(json!({ "role": role, "parts": parts }));
+        }
+    }
+
+    let mut map = serde_json::Map::new();
+    map.insert("contents".into(), Value::Array(contents));
+    if !system.is_empty() {
+        map.insert("systemInstruction".into(), json!({ "parts": system }));
+    }
+    if !req.tools.is_empty() {
+        let decls: Vec<Value> = req.tools.iter().map(tool_to_gemini).collect();
+        map.insert("tools".into(), json!([{ "functionDeclarations": decls }]));
+        map.insert(
+            "toolConfig".into(),
+            json!({ "functionCallingConfig": tool_choice_to_gemini(&req.tool_choice) }),
+        );
+    }
+    let mut generation = serde_json::Map::new();
+    if let Some(max) = req.max_output_tokens {
+        generation.insert("maxOutputTokens".into(), json!(max));
+    }
+    generation.extend(
+        sampling_fields(&req.sampling, SamplingDialect::Gemini).map(|(k, v)| (k.to_owned(), v)),
+    );
+    if let Some(fmt) = &req.response_format {
+        generation.insert("responseMimeType".into(), json!("application/json"));
+        if let Some(schema) = response_schema(fmt) {
+            generation.insert("responseJsonSchema".into(), schema);
+        }
+    }
+    if !generation.is_empty() {
+        map.insert("generationConfig".into(), Value::Object(generation));
+    }
+    Value::Object(map)
+}
+
+/// Raw JSON Schema, a Chat Completions `json_schema` envelope, or `json_object` (no schema).
+fn response_schema(fmt: &Value) -> Option<Value> {
+    match fmt.get("type").and_then(Value::as_str) {
+        Some("json_schema") => fmt.pointer("/json_schema/schema").cloned(),
+        Some("json_object") => None,
+        _ => Some(fmt.clone()),
+    }
+}
+
+fn message_to_gemini(
+    msg: &Message,
+    call_names: &HashMap<&str, &str>,
+) -> (&'static str, Vec<Value>) {
+    match msg.role {
+        Role::Tool => {
+            let name = msg
+                .name
+                .as_deref()
+                .or_else(|| {
+                    msg.tool_call_id
+                        .as_ref()
+                        .and_then(|id| call_names.get(id.as_str()).copied())
+                })
+                .unwrap_or("unknown");
+            let text = msg.text();
+            // `response` must be an object; wrap non-object results.
+            let response = match serde_json::from_str::<Value>(&text) {
+                Ok(v @ Value::Object(_)) => v,
+                _ => json!({ "content": text }),
+            };
+            (
+                "user",
+                vec![json!({ "functionResponse": { "name": name, "response": response } })],
+            )
+        }
+        Role::Assistant => {
+            let signatures: Vec<Value> = msg
+                .parts
+                .iter()
+                .filter_map(|p| match p {
+                    ContentPart::Opaque { provider, data } if provider == GEMINI_PROVIDER => {
+                        serde_json::from_str(data).ok()
+                    }
+                    _ => None,
+                })
+                .collect();
+            // Signature recorded for the call with `id`, or for the text when `None`.
+            let signature_for = |id: Option<&str>| {
+                signatures
+                    .iter()
+                    .find(|s| s.get("toolCallId").and_then(Value::as_str) == id)
+                    .and_then(|s| s.get("thoughtSignature"))
+                    .cloned()
+            };
+            let mut parts = Vec::with_capacity(msg.tool_calls.len().saturating_add(1));
+            let text = msg.text();
+            if !text.is_empty() {
+                let mut part = json!({ "text": text });
+                if let (Some(sig), Some(obj)) = (signature_for(None), part.as_object_mut()) {
+                    obj.insert("thoughtSignature".into(), sig);
+                }
+                parts.push(part);
+            }
+            for tc in &msg.tool_calls {
+                let args = match &tc.arguments {
+                    Value::String(s) => serde_json::from_str(s).unwrap_or_else(|_| json!({})),
+                    other => other.clone(),
+                };
+                let mut part = json!({ "functionCall": { "name": tc.name, "args": args } });
+                if let (Some(sig), Some(obj)) =
+                    (signature_for(Some(tc.id.as_str())), part.as_object_mut())
+                {
+                    obj.insert("thoughtSignature".into(), sig);
+                }
+                parts.push(part);
+            }
+            ("model", parts)
+        }
+        _ => {
+            let text = msg.text();
+            if text.is_empty() {
+                ("user", Vec::new())
+            } else {
+                ("user", vec![json!({ "text": text })])
+            }
+        }
+    }
+}
+
+fn tool_to_gemini(tool: &ToolDefinition) -> Value {
+    json!({
+        "name": tool.name,
+        "description": tool.description,
+        "parametersJsonSchema": tool.parameters,
+    })
+}
+
+fn tool_choice_to_gemini(choice: &ToolChoice) -> Value {
+    match choice {
+        ToolChoice::Auto => json!({ "mode": "AUTO" }),
+        ToolChoice::None => json!({ "mode": "NONE" }),
+        ToolChoice::Required => json!({ "mode": "ANY" }),
+        ToolChoice::Named(name) => json!({ "mode": "ANY", "allowedFunctionNames": [name] }),
+    }
+}
+
+const FNV_OFFSET: u64 = 0xcbf2_9ce4_8422_2325;
+
+/// FNV-1a 64: stable across processes (unlike `DefaultHasher`).
+fn fnv1a(mut hash: u64, bytes: impl IntoIterator<Item = u8>) -> u64 {
+    for byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+    hash
+}
+
+/// Hash of a request body's `contents`, used as the `history` of
+/// [`gemini_tool_call_id`].
+#[must_use]
+pub fn gemini_history_hash(request: &Value) -> u64 {
+    request.get("contents").map_or(FNV_OFFSET, |c| {
+        fnv1a(FNV_OFFSET, c.to_string().into_bytes())
+    })
+}
+
+/// Deterministic id for the `ordinal`-th function call of a response to a
+/// request whose [`gemini_history_hash`] is `history`.
+#[must_use]
+pub fn gemini_tool_call_id(history: u64, ordinal: usize, name: &str, args: &Value) -> String {
+    let hash = fnv1a(
+        history,
+        name.bytes()
+            .chain([0u8])
+            .chain(args.to_string().into_bytes()),
+    );
+    format!("gemini_call_{ordinal}_{:08x}", hash >> 32)
+}
+
+/// Parse a `generateContent` JSON response to `request` (the body from
+/// [`build_gemini_generate_body`]) into a kernel [`SampleResponse`].
+///
+/// # Errors
+///
+/// Returns [`OvoError`] when there are no candidates (including prompt blocks).
+pub fn parse_gemini_generate_response(
+    request: &Value,
+    body: &Value,
+) -> Result<SampleResponse, OvoError> {
+    let mut acc = GeminiStream::for_request(request);
+    let candidates = body.get("candidates").and_then(Value::as_array);
+    if candidates.is_none_or(Vec::is_empty) {
+        let reason = body
+            .pointer("/promptFeedback/blockReason")
+            .and_then(Value::as_str)
+            .unwrap_or("no candidates");
+        return Err(OvoError::new(
+            ErrorCode::LlmInvalidResponse,
+            format!("gemini response missing candidates[0]: {reason}"),
+        ));
+    }
+    acc.push_chunk(body)?;
+    Ok(acc.into_response())
+}
+
+fn parse_usage(raw: &Value) -> Usage {
+    let field = |name: &str| {
+        raw.get(name)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+            .unwrap_or(0)
+    };
+    let thoughts = field("thoughtsTokenCount");
+    let cached = field("cachedContentTokenCount");
+    let mut usage = Usage::new(
+        field("promptTokenCount"),
+        field("candidatesTokenCount").saturating_add(thoughts),
+    );
+    usage.cache_read_tokens = cached;
+    usage.prompt_details.cached_tokens = cached;
+    usage.reasoning_tokens = thoughts;
+    usage.completion_details.reasoning_tokens = thoughts;
+    usage
+}
+
+/// Accumulates `streamGenerateContent` chunks into [`SampleEvent`]s.
+///
+/// Each SSE `data:` payload is a partial `GenerateContentResponse`; the body
+/// simply ends after the last one (no sentinel).
+#[derive(Debug, Default)]
+pub struct GeminiStream {
+    text: String,
+    reasoning: String,
+    tool_calls: Vec<ToolCall>,
+    /// First `thoughtSignature` seen outside a function call.
+    text_signature: Option<String>,
+    /// `(tool call id, thoughtSignature)` pairs.
+    call_signatures: Vec<(String, String)>,
+    /// [`gemini_history_hash`] of the request, seeding synthetic call ids.
+    history: u64,
+    usage: Option<Usage>,
+    stop_reason: Option<String>,
+}
+
+impl GeminiStream {
+    /// Empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Empty accumulator for a response to `request` (the body from
+    /// [`build_gemini_generate_body`]), so synthetic call ids differ per step.
+    #[must_use]
+    pub fn for_request(request: &Value) -> Self {
+        Self {
+            history: gemini_history_hash(request),
+            ..Self::default()
+        }
+    }
+
+    /// Decode one `data:` payload and return the incremental events it carries.
+    ///
+    /// # Errors
+    ///
+    /// Invalid JSON yields [`ErrorCode::LlmInvalidResponse`]; an in-band `error`
+    /// object yields [`ErrorCode::LlmProvider`].
+    pub fn push_data(&mut self, data: &str) -> Result<Vec<SampleEvent>, OvoError> {
+        let chunk: Value = serde_json::from_str(data).map_err(|e| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                format!("invalid gemini stream chunk: {e}"),
+            )
+        })?;
+        self.push_chunk(&chunk)
+    }
+
+    /// Apply one parsed chunk.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::push_data`].
+    pub fn push_chunk(&mut self, chunk: &Value) -> Result<Vec<SampleEvent>, OvoError> {
+        if let Some(err) = chunk.get("error") {
+            let msg = err
+                .get("message")
+                .and_then(Value::as_str)
+                .map_or_else(|| err.to_string(), str::to_owned);
+            return Err(OvoError::new(
+                ErrorCode::LlmProvider,
+                format!("gemini stream error: {msg}"),
+            ));
+        }
+        if let Some(u) = chunk.get("usageMetadata") {
+            self.usage = Some(parse_usage(u));
+        }
+        let Some(candidate) = chunk
+            .get("candidates")
+            .and_then(Value::as_array)
+            .and_then(|a| a.first())
+        else {
+            return Ok(Vec::new());
+        };
+        if let Some(reason) = candidate.get("finishReason").and_then(Value::as_str) {
+            self.stop_reason = Some(reason.to_owned());
+        }
+        let mut out = Vec::new();
+        let parts = candidate
+            .pointer("/content/parts")
+            .and_then(Value::as_array)
+            .map_or(&[][..], Vec::as_slice);
+        for part in parts {
+            let signature = part.get("thoughtSignature").and_then(Value::as_str);
+            if let Some(call) = part.get("functionCall") {
+                let id = self.push_call(call)?;
+                if let Some(signature) = signature {
+                    self.call_signatures.push((id, signature.to_owned()));
+                }
+                continue;
+            }
+            // Text signatures may ride on an otherwise empty closing part.
+            if let Some(signature) = signature
+                && self.text_signature.is_none()
+            {
+                self.text_signature = Some(signature.to_owned());
+            }
+            let Some(text) = part
+                .get("text")
+                .and_then(Value::as_str)
+                .filter(|t| !t.is_empty())
+            else {
+                continue;
+            };
+            if part.get("thought").and_then(Value::as_bool) == Some(true) {
+                self.reasoning.push_str(text);
+                out.push(SampleEvent::ReasoningDelta {
+                    text: text.to_owned(),
+                });
+            } else {
+                self.text.push_str(text);
+                out.push(SampleEvent::TextDelta {
+                    text: text.to_owned(),
+                });
+            }
+        }
+        Ok(out)
+    }
+
+    fn push_call(&mut self, call: &Value) -> Result<String, OvoError> {
+        let name = call.get("name").and_then(Value::as_str).ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "gemini functionCall missing name",
+            )
+        })?;
+        let args = call.get("args").cloned().unwrap_or_else(|| json!({}));
+        let id = match call.get("id").and_then(Value::as_str) {
+            Some(id) if !id.is_empty() => id.to_owned(),
+            _ => gemini_tool_call_id(self.history, self.tool_calls.len(), name, &args),
+        };
+        self.tool_calls.push(ToolCall {
+            id: ToolCallId::new(id.clone())?,
+            name: name.to_owned(),
+            arguments: args,
+        });
+        Ok(id)
+    }
+
+    fn into_response(self) -> SampleResponse {
+        let mut message = if self.tool_calls.is_empty() {
+            Message::assistant(self.text)
+        } else {
+            let mut m = Message::assistant_tools(self.tool_calls);
+            if !self.text.is_empty() {
+                m.content = Some(self.text);
+            }
+            m
+        };
+        attach_reasoning(&mut message, self.reasoning);
+        let signatures = self
+            .text_signature
+            .map(|sig| json!({ "thoughtSignature": sig }))
+            .into_iter()
+            .chain(
+                self.call_signatures
+                    .into_iter()
+                    .map(|(id, sig)| json!({ "thoughtSignature": sig, "toolCallId": id })),
+            );
+        message
+            .parts
+            .extend(signatures.map(|data| ContentPart::Opaque {
+                provider: GEMINI_PROVIDER.to_owned(),
+                data: data.to_string(),
+            }));
+        SampleResponse {
+            message,
+            usage: self.usage.unwrap_or_else(Usage::zero),
+            stop_reason: self.stop_reason,
+            served_by: None,
+            rate_limit: None,
+        }
+    }
+
+    /// Terminal events: `ToolCalls` (when any), `Usage` (when reported), `Completed`.
+    #[must_use]
+    pub fn finish(self) -> Vec<SampleEvent> {
+        let reported = self.usage.is_some();
+        let response = self.into_response();
+        let mut out = Vec::with_capacity(3);
+        if !response.message.tool_calls.is_empty() {
+            out.push(SampleEvent::ToolCalls {
+                message: response.message.clone(),
+            });
+        }
+        if reported {
+            out.push(SampleEvent::Usage(response.usage));
+        }
+        out.push(SampleEvent::Completed {
+            message: response.message,
+            stop_reason: response.stop_reason,
+            served_by: None,
+        });
+        out
+    }
+}
+
+#[cfg(feature = "gemini")]
+mod client {
+    use async_trait::async_trait;
+    use ovo_types::{ErrorCode, OvoError};
+    use tracing::{Instrument, info_span};
+
+    use super::{
+        GeminiConfig, GeminiStream, build_gemini_generate_body, parse_gemini_generate_response,
+    };
+    use crate::http::{ChunkDecoder, body_stream, precheck, read_text, send_checked};
+    use crate::sample::{SampleRequest, SampleResponse};
+    use crate::sampler::LlmSampler;
+    use crate::sse::SseDecoder;
+    use crate::stream::{SampleEvent, SampleStream};
+
+    /// HTTP client for Gemini `generateContent` / `streamGenerateContent`.
+    #[derive(Debug, Clone)]
+    pub struct GeminiSampler {
+        config: GeminiConfig,
+        client: reqwest::Client,
+    }
+
+    impl GeminiSampler {
+        /// Create a sampler with a default `reqwest` client.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error when the HTTP client cannot be built.
+        pub fn new(config: GeminiConfig) -> Result<Self, OvoError> {
+            let client = reqwest::Client::builder().build().map_err(|e| {
+                OvoError::new(ErrorCode::LlmProvider, format!("http client build: {e}"))
+            })?;
+            Ok(Self { config, client })
+        }
+
+        /// Create with an existing client.
+        #[must_use]
+        pub fn with_client(config: GeminiConfig, client: reqwest::Client) -> Self {
+            Self { config, client }
+        }
+
+        fn post(&self, url: String, body: &serde_json::Value) -> reqwest::RequestBuilder {
+            let mut req = self.client.post(url).json(body);
+            if !self.config.api_key.is_empty() {
+                req = req.header("x-goog-api-key", &self.config.api_key);
+            }
+            req
+        }
+    }
+
+    #[async_trait]
+    impl LlmSampler for GeminiSampler {
+        async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+            precheck(&request)?;
+            let body = build_gemini_generate_body(&request);
+            let url = self.config.generate_url(&request.model);
+            let span = info_span!(
+                "ovo.sample.http",
+                ovo.model = %request.model,
+                ovo.provider = "gemini",
+            );
+            async move {
+                let response =
+                    send_checked("gemini", self.post(url, &body), &request.cancel).await?;
+                let text = read_text(response, &request.cancel).await?;
+                let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
+                    OvoError::new(
+                        ErrorCode::LlmInvalidResponse,
+                        format!("invalid JSON body: {e}"),
+                    )
+                })?;
+                parse_gemini_generate_response(&body, &value)
+            }
+            .instrument(span)
+            .await
+        }
+
+        async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+            precheck(&request)?;
+            let body = build_gemini_generate_body(&request);
+            let url = self.config.stream_url(&request.model);
+            let span = info_span!(
+                "ovo.sample.http",
+                ovo.model = %request.model,
+                ovo.provider = "gemini",
+                ovo.stream = true,
+            );
+            let response = send_checked("gemini", self.post(url, &body), &request.cancel)
+                .instrument(span)
+                .await?;
+            let decoder = SseGeminiDecoder {
+                sse: SseDecoder::new(),
+                acc: GeminiStream::for_request(&body),
+            };
+            Ok(body_stream(response, request.cancel, decoder))
+        }
+    }
+
+    /// SSE framing + [`GeminiStream`] mapping; the stream ends with the body.
+    #[derive(Debug)]
+    struct SseGeminiDecoder {
+        sse: SseDecoder,
+        acc: GeminiStream,
+    }
+
+    impl ChunkDecoder for SseGeminiDecoder {
+        fn push(&mut self, chunk: &[u8]) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            for ev in self.sse.push(chunk) {
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            Ok(out)
+        }
+
+        fn is_done(&self) -> bool {
+            false
+        }
+
+        fn finish(&mut self) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            if let Some(ev) = self.sse.finish() {
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            out.extend(std::mem::take(&mut self.acc).finish());
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(feature = "gemini")]
+pub use client::GeminiSampler;
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use tokio_util::sync::CancellationToken;
+
+    use ovo_types::SamplingParams;
+
+    use super::*;
+
+    fn req(messages: Vec<Message>, tool_choice: ToolChoice) -> SampleRequest {
+        SampleRequest {
+            model: "gemini-test".into(),
+            messages,
+            tools: vec![ToolDefinition {
+                name: "lookup".into(),
+                description: "look things up".into(),
+                parameters: json!({
+                    "type": "object",
+                    "properties": {"q": {"type": "string"}},
+                    "additionalProperties": false
+                }),
+            }],
+            tool_choice,
+            response_format: None,
+            max_output_tokens: Some(256),
+            sampling: SamplingParams::new().with_temperature(0.0),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[test]
+    fn build_body_maps_tools_choice_and_function_responses() {
+        let id = ToolCallId::new("gemini_call_0_deadbeef").expect("id");
+        let assistant = Message::assistant_tools(vec![ToolCall {
+            id: id.clone(),
+            name: "lookup".into(),
+            arguments: json!({"q": "rust"}),
+        }]);
+        let mut result = Message::tool_result(id, "lookup", "{\"hits\":3}");
+        result.name = None;
+        let body = build_gemini_generate_body(&req(
+            vec![
+                Message::system("be brief"),
+                Message::user("find"),
+                assistant,
+                result,
+            ],
+            ToolChoice::Named("lookup".into()),
+        ));
+        assert_eq!(
+            body.pointer("/systemInstruction/parts/0/text"),
+            Some(&json!("be brief"))
+        );
+        assert_eq!(
+            body.pointer(
+                "/tools/0/functionDeclarations/0/parametersJsonSchema/additionalProperties"
+            ),
+            Some(&json!(false))
+        );
+        assert_eq!(
+            body.pointer("/tools/0/functionDeclarations/0/name"),
+            Some(&json!("lookup"))
+        );
+        assert_eq!(
+            body.pointer("/toolConfig/functionCallingConfig"),
+            Some(&json!({"mode": "ANY", "allowedFunctionNames": ["lookup"]}))
+        );
+        assert_eq!(body.pointer("/contents/1/role"), Some(&json!("model")));
+        assert_eq!(
+            body.pointer("/contents/1/parts/0/functionCall/args/q"),
+            Some(&json!("rust"))
+        );
+        assert_eq!(
+            body.pointer("/contents/2/parts/0/functionResponse"),
+            Some(&json!({"name": "lookup", "response": {"hits": 3}}))
+        );
+        assert_eq!(
+            body.pointer("/generationConfig/maxOutputTokens"),
+            Some(&json!(256))
+        );
+    }
+
+    #[test]
+    fn response_format_becomes_response_json_schema() {
+        let schema = json!({
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "type": "object",
+            "properties": {"ok": {"type": "boolean"}},
+            "additionalProperties": false
+        });
+        let mut request = req(vec![Message::user("hi")], ToolChoice::Auto);
+        request.response_format = Some(json!({
+            "type": "json_schema",
+            "json_schema": {"name": "x", "schema": schema.clone()}
+        }));
+        let body = build_gemini_generate_body(&request);
+        assert_eq!(
+            body.pointer("/generationConfig/responseMimeType"),
+            Some(&json!("application/json"))
+        );
+        assert_eq!(
+            body.pointer("/generationConfig/responseJsonSchema"),
+            Some(&schema)
+        );
+        assert!(body.pointer("/generationConfig/responseSchema").is_none());
+        assert_eq!(
+            body.pointer("/toolConfig/functionCallingConfig/mode"),
+            Some(&json!("AUTO"))
+        );
+    }
+
+    #[test]
+    fn parse_synthesises_stable_tool_call_ids() {
+        let body = json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "calling"},
+                    {"functionCall": {"name": "lookup", "args": {"q": "a"}}},
+                    {"functionCall": {"name": "lookup", "args": {"q": "b"}}}
+                ]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {
+                "promptTokenCount": 10,
+                "candidatesTokenCount": 4,
+                "thoughtsTokenCount": 6,
+                "cachedContentTokenCount": 2
+            }
+        });
+        let first = build_gemini_generate_body(&req(vec![Message::user("go")], ToolChoice::Auto));
+        let a = parse_gemini_generate_response(&first, &body).expect("parse");
+        let b = parse_gemini_generate_response(&first, &body).expect("parse again");
+        assert_eq!(a.message.tool_calls.len(), 2);
+        assert_eq!(a.message.tool_calls, b.message.tool_calls);
+        let ids: Vec<&str> = a.message.tool_calls.iter().map(|c| c.id.as_str()).collect();
+        assert_ne!(ids.first(), ids.get(1));
+        assert!(ids.iter().all(|id| id.starts_with("gemini_call_")));
+
+        // The same calls one step later (history now holds the first step)
+        // must not reuse the first step's ids.
+        let results = a
+            .message
+            .tool_calls
+            .iter()
+            .map(|c| Message::tool_result(c.id.clone(), &c.name, "done"));
+        let history = [Message::user("go"), a.message.clone()]
+            .into_iter()
+            .chain(results)
+            .collect();
+        let second = build_gemini_generate_body(&req(history, ToolChoice::Auto));
+        let c = parse_gemini_generate_response(&second, &body).expect("parse later step");
+        assert!(
+            c.message
+                .tool_calls
+                .iter()
+                .all(|call| !ids.contains(&call.id.as_str()))
+        );
+        assert_eq!(a.message.content.as_deref(), Some("calling"));
+        assert_eq!(a.usage.input_tokens, 10);
+        assert_eq!(a.usage.output_tokens, 10);
+        assert_eq!(a.usage.reasoning_tokens, 6);
+        assert_eq!(a.usage.cache_read_tokens, 2);
+        assert_eq!(a.stop_reason.as_deref(), Some("STOP"));
+    }
+
+    #[test]
+    fn thought_signatures_round_trip_onto_their_parts() {
+        let body = json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [
+                    {"text": "checking", "thoughtSignature": "sig-text"},
+                    {"functionCall": {"name": "lookup", "args": {"q": "a"}}, "thoughtSignature": "sig-a"},
+                    {"functionCall": {"name": "lookup", "args": {"q": "b"}}}
+                ]},
+                "finishReason": "STOP"
+            }]
+        });
+        let parsed = parse_gemini_generate_response(&json!({}), &body).expect("parse");
+        let opaque = parsed
+            .message
+            .parts
+            .iter()
+            .filter(|p| matches!(p, ContentPart::Opaque { provider, .. } if provider == GEMINI_PROVIDER))
+            .count();
+        assert_eq!(opaque, 2);
+
+        let request = req(
+            vec![Message::user("find"), parsed.message],
+            ToolChoice::Auto,
+        );
+        let built = build_gemini_generate_body(&request);
+        assert_eq!(
+            built.pointer("/contents/1/parts/0"),
+            Some(&json!({"text": "checking", "thoughtSignature": "sig-text"}))
+        );
+        assert_eq!(
+            built.pointer("/contents/1/parts/1/thoughtSignature"),
+            Some(&json!("sig-a"))
+        );
+        assert_eq!(
+            built.pointer("/contents/1/parts/1/functionCall/args/q"),
+            Some(&json!("a"))
+        );
+        assert!(
+            built
+                .pointer("/contents/1/parts/2/thoughtSignature")
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn parse_blocked_prompt_is_error() {
+        let body = json!({"promptFeedback": {"blockReason": "SAFETY"}});
+        let err = parse_gemini_generate_response(&json!({}), &body).expect_err("blocked");
+        assert_eq!(err.code(), ErrorCode::LlmInvalidResponse);
+        assert!(err.message().contains("SAFETY"), "{err}");
+    }
+
+    #[test]
+    fn stream_emits_text_thoughts_and_calls() {
+        let mut acc = GeminiStream::new();
+        let mut out = Vec::new();
+        for chunk in [
+            json!({"candidates":[{"content":{"parts":[{"text":"plan","thought":true}]}}]}),
+            json!({"candidates":[{"content":{"parts":[{"text":"Hi"}]}}]}),
+            json!({"candidates":[{"content":{"parts":[{"functionCall":{"name":"lookup","args":{"q":"z"}}}]},"finishReason":"STOP"}],"usageMetadata":{"promptTokenCount":3,"candidatesTokenCount":2}}),
+        ] {
+            out.extend(acc.push_chunk(&chunk).expect("chunk"));
+        }
+        assert!(matches!(
+            out.first(),
+            Some(SampleEvent::ReasoningDelta { text }) if text == "plan"
+        ));
+        assert!(matches!(
+            out.get(1),
+            Some(SampleEvent::TextDelta { text }) if text == "Hi"
+        ));
+        let tail = acc.finish();
+        assert!(matches!(tail.first(), Some(SampleEvent::ToolCalls { .. })));
+        assert!(matches!(
+            tail.get(1),
+            Some(SampleEvent::Usage(u)) if u.input_tokens == 3 && u.output_tokens == 2
+        ));
+        let Some(SampleEvent::Completed { message, .. }) = tail.last() else {
+            unreachable!("expected Completed, got {tail:?}");
+        };
+        assert_eq!(message.reasoning().as_deref(), Some("plan"));
+        let body = build_gemini_generate_body(&req(vec![message.clone()], ToolChoice::Auto));
+        assert_eq!(
+            body.pointer("/contents/0/parts/0"),
+            Some(&json!({"text": "Hi"}))
+        );
+    }
+}
@@ -0,0 +1,494 @@
+//! [`HedgedSampler`]: fire a backup request when the first one is slow.
+//!
+//! The primary request starts immediately. If it has not started responding
+//! (a stream has not produced [`SampleEvent::ResponseStarted`] or any content;
+//! a plain sample has not returned) within the hedge delay, an identical
+//! request goes to the hedge backend. The first leg to answer wins, the loser
+//! is cancelled through a child of the request's [`CancellationToken`], and its
+//! cost is reported in [`Usage::wasted_tokens`] instead of input/output.
+//!
+//! The delay is fixed ([`HedgedSampler::with_delay`]) or derived from a
+//! quantile (p95 by default) of recently observed time-to-first-response.
+
+use std::collections::VecDeque;
+use std::fmt;
+use std::future::Future;
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::{StreamExt, stream};
+use ovo_protocol::{HeuristicCounter, TokenCounter};
+use ovo_types::{OvoError, Usage};
+use tokio::time::sleep;
+use tokio_util::sync::CancellationToken;
+
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
+use crate::sampler::LlmSampler;
+use crate::stream::{SampleEvent, SampleStream, with_served_by};
+
+/// Delay used before enough latencies have been observed.
+pub const DEFAULT_HEDGE_DELAY: Duration = Duration::from_secs(2);
+/// Latencies kept for the adaptive delay.
+const WINDOW: usize = 128;
+/// Observations required before the quantile replaces the initial delay.
+const MIN_OBSERVATIONS: usize = 16;
+
+/// How long to wait before firing the hedge.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum HedgeDelay {
+    /// Always wait this long.
+    Fixed(Duration),
+    /// Use `quantile` (0..=1) of recent latencies; `initial` until warmed up.
+    Adaptive {
+        /// Latency quantile, e.g. `0.95`.
+        quantile: f64,
+        /// Delay while fewer than 16 latencies have been observed.
+        initial: Duration,
+    },
+}
+
+impl Default for HedgeDelay {
+    fn default() -> Self {
+        Self::Adaptive {
+            quantile: 0.95,
+            initial: DEFAULT_HEDGE_DELAY,
+        }
+    }
+}
+
+/// Sliding window of observed first-response latencies.
+#[derive(Debug, Default)]
+struct LatencyWindow {
+    samples: Mutex<VecDeque<Duration>>,
+}
+
+impl LatencyWindow {
+    fn observe(&self, latency: Duration) {
+        let mut samples = self.samples.lock().unwrap_or_else(PoisonError::into_inner);
+        if samples.len() >= WINDOW {
+            samples.pop_front();
+        }
+        samples.push_back(latency);
+    }
+
+    fn quantile(&self, q: f64) -> Option<Duration> {
+        let mut sorted: Vec<Duration> = {
+            let samples = self.samples.lock().unwrap_or_else(PoisonError::into_inner);
+            if samples.len() < MIN_OBSERVATIONS {
+                return None;
+            }
+            samples.iter().copied().collect()
+        };
+        sorted.sort_unstable();
+        #[allow(
+            clippy::cast_possible_truncation,
+            clippy::cast_sign_loss,
+            reason = "q is clamped to 0..=1 and the window is small"
+        )]
+        let rank = (q.clamp(0.0, 1.0) * (sorted.len().saturating_sub(1)) as f64).ceil() as usize;
+        sorted.get(rank).copied()
+    }
+}
+
+#[derive(Clone)]
+struct HedgeTarget {
+    sampler: Arc<dyn LlmSampler>,
+    model: Option<String>,
+}
+
+/// Which leg answered first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Leg {
+    Primary,
+    Hedge,
+}
+
+/// Decorator that hedges slow requests with a second identical request.
+pub struct HedgedSampler<S> {
+    inner: Arc<S>,
+    hedge: Option<HedgeTarget>,
+    delay: HedgeDelay,
+    latencies: Arc<LatencyWindow>,
+}
+
+impl<S> fmt::Debug for HedgedSampler<S> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("HedgedSampler")
+            .field("delay", &self.delay)
+            .field(
+                "hedge_model",
+                &self.hedge.as_ref().map(|h| h.model.as_deref()),
+            )
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> Clone for HedgedSampler<S> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: Arc::clone(&self.inner),
+            hedge: self.hedge.clone(),
+            delay: self.delay,
+            latencies: Arc::clone(&self.latencies),
+        }
+    }
+}
+
+impl<S: LlmSampler + 'static> HedgedSampler<S> {
+    /// Hedge `inner` against itself with the default p95-derived delay.
+    #[must_use]
+    pub fn new(inner: Arc<S>) -> Self {
+        Self {
+            inner,
+            hedge: None,
+            delay: HedgeDelay::default(),
+            latencies: Arc::new(LatencyWindow::default()),
+        }
+    }
+
+    /// Fixed hedge delay.
+    #[must_use]
+    pub const fn with_delay(mut self, delay: Duration) -> Self {
+        self.delay = HedgeDelay::Fixed(delay);
+        self
+    }
+
+    /// Derive the delay from `quantile` of recent latencies (`initial` until warm).
+    #[must_use]
+    pub const fn with_adaptive_delay(mut self, quantile: f64, initial: Duration) -> Self {
+        self.delay = HedgeDelay::Adaptive { quantile, initial };
+        self
+    }
+
+    /// Send the hedge to another backend; `model` replaces the request model.
+    #[must_use]
+    pub fn with_hedge_target(
+        mut self,
+        sampler: Arc<dyn LlmSampler>,
+        model: Option<String>,
+    ) -> Self {
+        self.hedge = Some(HedgeTarget { sampler, model });
+        self
+    }
+
+    /// Delay the next request will wait before hedging.
+    #[must_use]
+    pub fn current_delay(&self) -> Duration {
+        match self.delay {
+            HedgeDelay::Fixed(d) => d,
+            HedgeDelay::Adaptive { quantile, initial } => {
+                self.latencies.quantile(quantile).unwrap_or(initial)
+            }
+        }
+    }
+
+    fn hedge_sampler(&self) -> Arc<dyn LlmSampler> {
+        match &self.hedge {
+            Some(h) => Arc::clone(&h.sampler),
+            None => self.inner.clone(),
+        }
+    }
+
+    fn hedge_request(&self, request: &SampleRequest, cancel: CancellationToken) -> SampleRequest {
+        let mut req = request.clone();
+        req.cancel = cancel;
+        if let Some(model) = self.hedge.as_ref().and_then(|h| h.model.as_ref()) {
+            req.model.clone_from(model);
+        }
+        req
+    }
+
+    fn hedge_model(&self) -> Option<String> {
+        self.hedge.as_ref().and_then(|h| h.model.clone())
+    }
+}
+
+/// Prompt-side tokens billed to a leg cancelled mid-flight.
+fn wasted_estimate(request: &SampleRequest) -> u32 {
+    HeuristicCounter.count_messages(&request.messages)
+}
+
+fn wasted_usage(tokens: u32) -> Usage {
+    let mut usage = Usage::zero();
+    usage.wasted_tokens = tokens;
+    usage
+}
+
+/// Open a stream and buffer events until the response has visibly started.
+async fn until_started(
+    open: impl Future<Output = Result<SampleStream, OvoError>>,
+) -> Result<(SampleStream, Vec<SampleEvent>), OvoError> {
+    let mut stream = open.await?;
+    let mut head = Vec::new();
+    while let Some(ev) = stream.next().await {
+        let started = !matches!(ev, SampleEvent::RateLimit(_) | SampleEvent::Retrying { .. });
+        head.push(ev);
+        if started {
+            break;
+        }
+    }
+    Ok((stream, head))
+}
+
+/// Race two legs: the first success wins; a first failure defers to the other.
+async fn race<T>(
+    primary: impl Future<Output = Result<T, OvoError>>,
+    hedge: impl Future<Output = Result<T, OvoError>>,
+) -> (Leg, Result<T, OvoError>) {
+    tokio::pin!(primary);
+    tokio::pin!(hedge);
+    tokio::select! {
+        r = &mut primary => match r {
+            Ok(v) => (Leg::Primary, Ok(v)),
+            Err(_) => (Leg::Hedge, hedge.await),
+        },
+        r = &mut hedge => match r {
+            Ok(v) => (Leg::Hedge, Ok(v)),
+            Err(_) => (Leg::Primary, primary.await),
+        },
+    }
+}
+
+#[async_trait]
+impl<S: LlmSampler + 'static> LlmSampler for HedgedSampler<S> {
+    async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+        let delay = self.current_delay();
+        let primary_cancel = request.cancel.child_token();
+        let mut primary_req = request.clone();
+        primary_req.cancel = primary_cancel.clone();
+        let started = Instant::now();
+        let primary = self.inner.sample(primary_req);
+        tokio::pin!(primary);
+        tokio::select! {
+            r = &mut primary => {
+                if r.is_ok() {
+                    self.latencies.observe(started.elapsed());
+                }
+                return r;
+            }
+            () = sleep(delay) => {}
+            () = request.cancel.cancelled() => {
+                return Err(OvoError::llm_cancelled("sample cancelled"));
+            }
+        }
+
+        let hedge_cancel = request.cancel.child_token();
+        let hedge_started = Instant::now();
+        let hedge_sampler = self.hedge_sampler();
+        let hedge = hedge_sampler.sample(self.hedge_request(&request, hedge_cancel.clone()));
+        let (leg, result) = race(primary, hedge).await;
+        let mut response = result?;
+        let loser = match leg {
+            Leg::Primary => {
+                self.latencies.observe(started.elapsed());
+                hedge_cancel
+            }
+            Leg::Hedge => {
+                self.latencies.observe(hedge_started.elapsed());
+                if response.served_by.is_none() {
+                    response.served_by = self.hedge_model();
+                }
+                primary_cancel
+            }
+        };
+        loser.cancel();
+        response.usage += wasted_usage(wasted_estimate(&request));
+        Ok(response)
+    }
+
+    async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+        let delay = self.current_delay();
+        let primary_cancel = request.cancel.child_token();
+        let mut primary_req = request.clone();
+        primary_req.cancel = primary_cancel.clone();
+        let started = Instant::now();
+        let primary = until_started(self.inner.sample_stream(primary_req));
+        tokio::pin!(primary);
+        tokio::select! {
+            r = &mut primary => {
+                let (rest, head) = r?;
+                self.latencies.observe(started.elapsed());
+                return Ok(Box::pin(stream::iter(head).chain(rest)));
+            }
+            () = sleep(delay) => {}
+            () = request.cancel.cancelled() => {
+                return Err(OvoError::llm_cancelled("sample cancelled"));
+            }
+        }
+
+        let hedge_cancel = request.cancel.child_token();
+        let hedge_started = Instant::now();
+        let hedge_sampler = self.hedge_sampler();
+        let hedge = until_started(
+            hedge_sampler.sample_stream(self.hedge_request(&request, hedge_cancel.clone())),
+        );
+        let (leg, result) = race(primary, hedge).await;
+        let (rest, head) = result?;
+        let loser = match leg {
+            Leg::Primary => {
+                self.latencies.observe(started.elapsed());
+                hedge_cancel
+            }
+            Leg::Hedge => {
+                self.latencies.observe(hedge_started.elapsed());
+                primary_cancel
+            }
+        };
+        loser.cancel();
+        let reason = match leg {
+            Leg::Primary => "hedge_lost",
+            Leg::Hedge => "hedge_won",
+        };
+        let notices = vec![
+            SampleEvent::Retrying {
+                attempt: 2,
+                reason: reason.into(),
+            },
+            SampleEvent::Usage(wasted_usage(wasted_estimate(&request))),
+        ];
+        let out: SampleStream = Box::pin(stream::iter(head).chain(rest));
+        let out = match (leg, self.hedge_model()) {
+            (Leg::Hedge, Some(model)) => with_served_by(out, model),
+            _ => out,
+        };
+        Ok(Box::pin(stream::iter(notices).chain(out)))
+    }
+
+    fn capabilities(&self, model: &str) -> ModelCapabilities {
+        self.inner.capabilities(model)
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use ovo_types::{Message, SamplingParams};
+
+    use super::*;
+    use crate::sample::ToolChoice;
+    use crate::sampler::response_to_stream;
+
+    /// Answers `text` after `latency`, keeping each call's cancel token.
+    #[derive(Debug)]
+    struct Slow {
+        text: &'static str,
+        latency: Duration,
+        tokens: Mutex<Vec<CancellationToken>>,
+    }
+
+    impl Slow {
+        fn new(text: &'static str, latency_ms: u64) -> Arc<Self> {
+            Arc::new(Self {
+                text,
+                latency: Duration::from_millis(latency_ms),
+                tokens: Mutex::new(Vec::new()),
+            })
+        }
+
+        async fn wait(&self, cancel: &CancellationToken) -> Result<(), OvoError> {
+            self.tokens.lock().expect("lock").push(cancel.clone());
+            tokio::select! {
+                () = sleep(self.latency) => Ok(()),
+                () = cancel.cancelled() => Err(OvoError::llm_cancelled("cancelled")),
+            }
+        }
+
+        fn all_cancelled(&self) -> bool {
+            let tokens = self.tokens.lock().expect("lock");
+            !tokens.is_empty() && tokens.iter().all(CancellationToken::is_cancelled)
+        }
+
+        fn response(&self) -> SampleResponse {
+            SampleResponse {
+                message: Message::assistant(self.text),
+                usage: Usage::new(10, 2),
+                stop_reason: Some("stop".into()),
+                served_by: None,
+                rate_limit: None,
+            }
+        }
+    }
+
+    #[async_trait]
+    impl LlmSampler for Slow {
+        async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+            self.wait(&request.cancel).await?;
+            Ok(self.response())
+        }
+
+        async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+            self.wait(&request.cancel).await?;
+            Ok(response_to_stream(self.response()))
+        }
+    }
+
+    fn req() -> SampleRequest {
+        SampleRequest {
+            model: "m".into(),
+            messages: vec![Message::user("hello there")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn fast_primary_is_not_hedged() {
+        let primary = Slow::new("primary", 0);
+        let backup = Slow::new("backup", 0);
+        let sampler = HedgedSampler::new(Arc::clone(&primary))
+            .with_delay(Duration::from_millis(200))
+            .with_hedge_target(backup.clone(), None);
+        let out = sampler.sample(req()).await.expect("sample");
+        assert_eq!(out.message.text(), "primary");
+        assert_eq!(out.usage.wasted_tokens, 0);
+    }
+
+    #[tokio::test]
+    async fn slow_primary_loses_to_hedge_and_is_cancelled() {
+        let primary = Slow::new("primary", 5_000);
+        let backup = Slow::new("backup", 0);
+        let sampler = HedgedSampler::new(Arc::clone(&primary))
+            .with_delay(Duration::from_millis(20))
+            .with_hedge_target(backup.clone(), Some("backup-model".into()));
+
+        let request = req();
+        let parent = request.cancel.clone();
+        let out = sampler.sample(request).await.expect("sample");
+        assert_eq!(out.message.text(), "backup");
+        assert_eq!(out.served_by.as_deref(), Some("backup-model"));
+        assert_eq!(out.usage.input_tokens, 10, "only the winner is billed");
+        assert!(out.usage.wasted_tokens > 0);
+
+        let events: Vec<SampleEvent> = sampler
+            .sample_stream(req())
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        let folded = crate::stream::response_from_events(events).expect("fold");
+        assert_eq!(folded.message.text(), "backup");
+        assert_eq!(folded.usage.output_tokens, 2);
+        assert!(folded.usage.wasted_tokens > 0);
+        assert!(primary.all_cancelled(), "losing primaries are cancelled");
+        assert!(!backup.all_cancelled());
+        assert!(!parent.is_cancelled(), "caller token untouched");
+    }
+
+    #[test]
+    fn adaptive_delay_tracks_quantile() {
+        let sampler = HedgedSampler::new(Slow::new("x", 0))
+            .with_adaptive_delay(0.95, Duration::from_millis(700));
+        assert_eq!(sampler.current_delay(), Duration::from_millis(700));
+        for ms in 1..=100 {
+            sampler.latencies.observe(Duration::from_millis(ms));
+        }
+        assert_eq!(sampler.current_delay(), Duration::from_millis(96));
+    }
+}
@@ -0,0 +1,200 @@
+//! Shared HTTP transport for provider clients (request send + streaming bodies).
+//!
+//! Providers own wire framing and mapping via [`ChunkDecoder`]; this module owns
+//! cancellation, status → [`OvoError`] mapping, body reads and the
+//! single-terminal-event stream contract.
+
+use std::collections::VecDeque;
+
+use futures::stream;
+use ovo_types::{ErrorCode, OvoError, RetryClass};
+use tokio_util::sync::CancellationToken;
+
+use crate::openai_compat::{parse_retry_after_header, provider_http_status_error};
+use crate::ratelimit::parse_rate_limit_headers;
+use crate::sample::{RateLimitSnapshot, SampleRequest};
+use crate::stream::{SampleEvent, SampleStream};
+
+/// Fail fast when `request` is already cancelled or past its deadline.
+pub(crate) fn precheck(request: &SampleRequest) -> Result<(), OvoError> {
+    if request.cancel.is_cancelled() {
+        return Err(OvoError::llm_cancelled("sample cancelled"));
+    }
+    if request.deadline.is_some_and(|d| d.is_expired()) {
+        return Err(OvoError::llm_cancelled("sample deadline expired"));
+    }
+    Ok(())
+}
+
+/// Send `request`; non-2xx statuses become typed errors carrying retry metadata,
+/// labelled with `provider` (e.g. `anthropic`).
+pub(crate) async fn send_checked(
+    provider: &str,
+    request: reqwest::RequestBuilder,
+    cancel: &CancellationToken,
+) -> Result<reqwest::Response, OvoError> {
+    let response = tokio::select! {
+        biased;
+        () = cancel.cancelled() => {
+            return Err(OvoError::llm_cancelled("sample cancelled during http"));
+        }
+        res = request.send() => res.map_err(|e| {
+            OvoError::new(ErrorCode::LlmProvider, format!("{provider} http request failed: {e}"))
+                .with_retry(RetryClass::Backoff)
+        })?,
+    };
+    let status = response.status().as_u16();
+    if (200..300).contains(&status) {
+        return Ok(response);
+    }
+    let retry_after = response
+        .headers()
+        .get("retry-after")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_retry_after_header);
+    let x_should_retry = response
+        .headers()
+        .get("x-should-retry")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|s| match s.trim().to_ascii_lowercase().as_str() {
+            "true" | "1" | "yes" => Some(true),
+            "false" | "0" | "no" => Some(false),
+            _ => None,
+        });
+    let text = read_text(response, cancel).await?;
+    Err(provider_http_status_error(
+        provider,
+        status,
+        &text,
+        retry_after,
+        x_should_retry,
+    ))
+}
+
+/// Rate-limit budget advertised in `response` headers.
+pub(crate) fn rate_limit_from_headers(response: &reqwest::Response) -> Option<RateLimitSnapshot> {
+    let headers = response.headers();
+    parse_rate_limit_headers(|name| headers.get(name).and_then(|v| v.to_str().ok()))
+}
+
+/// Read a full body as text, honoring cancellation.
+pub(crate) async fn read_text(
+    response: reqwest::Response,
+    cancel: &CancellationToken,
+) -> Result<String, OvoError> {
+    tokio::select! {
+        biased;
+        () = cancel.cancelled() => {
+            Err(OvoError::llm_cancelled("sample cancelled during body read"))
+        }
+        text = response.text() => text.map_err(|e| {
+            OvoError::new(
+                ErrorCode::LlmProvider,
+                format!("http body read failed: {e}"),
+            )
+        }),
+    }
+}
+
+/// Provider-specific decoding of raw response bytes into [`SampleEvent`]s.
+pub(crate) trait ChunkDecoder: Send + 'static {
+    /// Feed one raw body chunk (frames may be torn across calls).
+    fn push(&mut self, chunk: &[u8]) -> Result<Vec<SampleEvent>, OvoError>;
+
+    /// True once the provider signalled end-of-stream in-band (`[DONE]`, `done: true`, …).
+    fn is_done(&self) -> bool;
+
+    /// Terminal events (coalesced message, usage, `Completed`) after the body ends.
+    fn finish(&mut self) -> Result<Vec<SampleEvent>, OvoError>;
+}
+
+struct BodyState<D> {
+    /// `None` once the body is exhausted or abandoned.
+    response: Option<reqwest::Response>,
+    /// `None` once a terminal event has been queued.
+    decoder: Option<D>,
+    pending: VecDeque<SampleEvent>,
+    cancel: CancellationToken,
+}
+
+impl<D: ChunkDecoder> BodyState<D> {
+    fn extend(&mut self, events: Result<Vec<SampleEvent>, OvoError>) {
+        match events {
+            Ok(events) => self.pending.extend(events),
+            Err(e) => self.fail(e.to_string()),
+        }
+    }
+
+    fn finish(&mut self) {
+        self.response = None;
+        if let Some(mut decoder) = self.decoder.take() {
+            let events = decoder.finish();
+            match events {
+                Ok(events) => self.pending.extend(events),
+                Err(e) => self.pending.push_back(SampleEvent::Failed {
+                    message: e.to_string(),
+                }),
+            }
+        }
+    }
+
+    fn fail(&mut self, message: String) {
+        self.response = None;
+        if self.decoder.take().is_some() {
+            self.pending.push_back(SampleEvent::Failed { message });
+        }
+    }
+}
+
+/// Drive a 2xx streaming `response` through `decoder`.
+///
+/// Emits [`SampleEvent::ResponseStarted`] first and exactly one terminal
+/// `Completed` / `Failed` (cancellation and transport errors become `Failed`).
+pub(crate) fn body_stream<D: ChunkDecoder>(
+    response: reqwest::Response,
+    cancel: CancellationToken,
+    decoder: D,
+) -> SampleStream {
+    let mut pending = VecDeque::new();
+    pending.push_back(SampleEvent::ResponseStarted {
+        cache_read: None,
+        cache_creation: None,
+    });
+    if let Some(snapshot) = rate_limit_from_headers(&response) {
+        pending.push_back(SampleEvent::RateLimit(snapshot));
+    }
+    let state = BodyState {
+        response: Some(response),
+        decoder: Some(decoder),
+        pending,
+        cancel,
+    };
+    Box::pin(stream::unfold(state, |mut st| async move {
+        loop {
+            if let Some(ev) = st.pending.pop_front() {
+                return Some((ev, st));
+            }
+            let response = st.response.as_mut()?;
+            let chunk = tokio::select! {
+                biased;
+                () = st.cancel.cancelled() => None,
+                chunk = response.chunk() => Some(chunk),
+            };
+            match chunk {
+                None => st.fail("sample stream cancelled".into()),
+                Some(Ok(Some(bytes))) => {
+                    if let Some(decoder) = st.decoder.as_mut() {
+                        let events = decoder.push(&bytes);
+                        let done = decoder.is_done();
+                        st.extend(events);
+                        if done {
+                            st.finish();
+                        }
+                    }
+                }
+                Some(Ok(None)) => st.finish(),
+                Some(Err(e)) => st.fail(format!("stream body read failed: {e}")),
+            }
+        }
+    }))
+}
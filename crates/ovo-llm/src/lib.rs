@@ -1,34 +1,110 @@
 //! LLM sampling contracts for the Ovo kernel.
 //!
-//! - Always: [`LlmSampler`], [`MockSampler`], wire helpers in [`openai_compat`].
-//! - Decorators: [`RetryingSampler`], [`BreakerSampler`].
-//! - Feature `openai`: [`OpenAiCompatSampler`] HTTP client.
-//! - Feature `ollama`: [`OllamaSampler`] HTTP client.
+//! - Always: [`LlmSampler`], [`MockSampler`], [`RecordingSampler`] /
+//!   [`ReplaySampler`] cassettes, wire helpers in [`openai_compat`]
+//!   [`responses`], [`anthropic`] and [`gemini`].
+//! - Decorators: [`RetryingSampler`], [`BreakerSampler`], [`RoutingSampler`],
+//!   [`FallbackSampler`], [`RateLimitedSampler`], [`CachingSampler`],
+//!   [`HedgedSampler`].
+//! - Embeddings: [`Embedder`], [`MockEmbedder`], [`RetryingEmbedder`],
+//!   [`BreakerEmbedder`].
+//! - Feature `openai`: [`OpenAiCompatSampler`] and [`ResponsesSampler`] HTTP
+//!   clients (SSE streaming), [`OpenAiCompatEmbedder`].
+//! - Feature `ollama`: [`OllamaSampler`] HTTP client (NDJSON streaming),
+//!   [`OllamaEmbedder`].
+//! - Feature `anthropic`: [`AnthropicSampler`] HTTP client (SSE streaming).
+//! - Feature `gemini`: [`GeminiSampler`] HTTP client (SSE streaming).
 
 #![forbid(unsafe_code)]
 
+pub mod anthropic;
 pub mod breaker;
 pub mod breaker_sampler;
+pub mod cache;
+pub mod cassette;
+pub mod embed;
+pub mod fallback;
+pub mod fingerprint;
+pub mod gemini;
+pub mod hedge;
 pub mod mock;
 pub mod openai_compat;
+pub mod ratelimit;
+pub mod reasoning;
+pub mod repair;
+pub mod responses;
 pub mod retry;
 pub mod retrying;
+pub mod routing;
 pub mod sample;
 pub mod sampler;
+pub mod sse;
 pub mod stream;
 
+#[cfg(any(
+    feature = "openai",
+    feature = "ollama",
+    feature = "anthropic",
+    feature = "gemini"
+))]
+mod http;
+
 #[cfg(feature = "ollama")]
 pub mod ollama;
 
+#[cfg(feature = "anthropic")]
+pub use anthropic::AnthropicSampler;
+pub use anthropic::{
+    ANTHROPIC_DEFAULT_MAX_TOKENS, ANTHROPIC_PROVIDER, ANTHROPIC_VERSION, AnthropicConfig,
+    AnthropicMessagesStream, anthropic_thinking_budget, build_anthropic_messages_body,
+    build_anthropic_messages_stream_body, parse_anthropic_messages_response,
+};
 pub use breaker::{Admission, BreakerConfig, BreakerOutcome, BreakerState, CircuitBreaker};
 pub use breaker_sampler::BreakerSampler;
+pub use cache::{CachingSampler, DiskCache, MemoryCache, ResponseCache};
+pub use cassette::{
+    CASSETTE_VERSION_HEADER, CassetteEntry, Recording, RecordingSampler, ReplaySampler,
+};
+pub use embed::{BreakerEmbedder, Embedder, MockEmbedder, RetryingEmbedder};
+pub use fallback::{FallbackSampler, FallbackTarget, is_context_overflow, should_fall_back};
+pub use fingerprint::{
+    MASKED, RequestMask, canonical_json, request_fingerprint, sample_request_json,
+};
+#[cfg(feature = "gemini")]
+pub use gemini::GeminiSampler;
+pub use gemini::{
+    GEMINI_PROVIDER, GeminiConfig, GeminiStream, build_gemini_generate_body, gemini_history_hash,
+    gemini_tool_call_id, parse_gemini_generate_response,
+};
+pub use hedge::{DEFAULT_HEDGE_DELAY, HedgeDelay, HedgedSampler};
 pub use mock::MockSampler;
 #[cfg(feature = "ollama")]
-pub use ollama::{OllamaConfig, OllamaSampler, build_ollama_chat_body, parse_ollama_chat_response};
-#[cfg(feature = "openai")]
-pub use openai_compat::OpenAiCompatSampler;
+pub use ollama::{
+    OllamaChatStream, OllamaConfig, OllamaEmbedder, OllamaSampler, build_ollama_chat_body,
+    build_ollama_chat_body_with, build_ollama_chat_stream_body, build_ollama_chat_stream_body_with,
+    build_ollama_embed_body, parse_ollama_chat_response, parse_ollama_chat_response_with,
+    parse_ollama_embed_response,
+};
 pub use openai_compat::{
-    OpenAiCompatConfig, build_chat_completions_body, parse_chat_completions_response,
+    ChatCompletionsStream, OpenAiCompatConfig, build_chat_completions_body,
+    build_chat_completions_body_with, build_chat_completions_stream_body,
+    build_chat_completions_stream_body_with, build_embeddings_body,
+    parse_chat_completions_response, parse_chat_completions_response_with,
+    parse_embeddings_response,
+};
+#[cfg(feature = "openai")]
+pub use openai_compat::{OpenAiCompatEmbedder, OpenAiCompatSampler};
+pub use ratelimit::{
+    RateLimit, RateLimitedSampler, estimate_request_tokens, parse_rate_limit_headers,
+    parse_reset_duration,
+};
+pub use reasoning::ReasoningReplay;
+pub use repair::{ArgumentRepair, ToolArguments, parse_tool_arguments, repair_json};
+#[cfg(feature = "openai")]
+pub use responses::ResponsesSampler;
+pub use responses::{
+    RESPONSES_PROVIDER, ResponsesConfig, ResponsesStream, build_responses_body,
+    build_responses_stream_body, parse_responses_response,
 };
 pub use retry::{
     DEFAULT_MAX_ATTEMPTS, HttpRetryClass, MAX_RETRY_AFTER, MAX_RETRY_BACKOFF,
@@ -36,6 +112,8 @@
     classify_http_status, decide_retry, error_code_for_http, is_empty_response,
 };
 pub use retrying::{DEFAULT_IDLE_TIMEOUT, RetryingSampler};
-pub use sample::{SampleRequest, SampleResponse, ToolChoice};
+pub use routing::{ModelPattern, RoutingSampler};
+pub use sample::{ModelCapabilities, RateLimitSnapshot, SampleRequest, SampleResponse, ToolChoice};
 pub use sampler::{LlmSampler, response_to_stream};
+pub use sse::{SseDecoder, SseEvent};
 pub use stream::{SampleEvent, SampleStream};
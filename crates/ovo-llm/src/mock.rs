@@ -6,7 +6,7 @@
 use async_trait::async_trait;
 use ovo_types::{ErrorCode, Message, OvoError, Role, Usage};
 
-use crate::sample::{SampleRequest, SampleResponse};
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
 use crate::sampler::LlmSampler;
 
 /// Queue of scripted responses (FIFO) plus optional prompt-keyed responses.
@@ -15,6 +15,7 @@ pub struct MockSampler {
     responses: Mutex<Vec<Result<SampleResponse, OvoError>>>,
     /// Exact match on the last user message text → response text.
     by_user_text: Mutex<HashMap<String, String>>,
+    capabilities: ModelCapabilities,
 }
 
 impl MockSampler {
@@ -24,6 +25,13 @@ pub fn new() -> Self {
         Self::default()
     }
 
+    /// Report `capabilities` for every model.
+    #[must_use]
+    pub const fn with_capabilities(mut self, capabilities: ModelCapabilities) -> Self {
+        self.capabilities = capabilities;
+        self
+    }
+
     /// Push a FIFO response.
     pub fn push(&self, response: SampleResponse) {
         self.responses
@@ -46,6 +54,8 @@ pub fn push_text(&self, text: impl Into<String>) {
             message: Message::assistant(text),
             usage: Usage::new(1, 1),
             stop_reason: Some("stop".into()),
+            served_by: None,
+            rate_limit: None,
         });
     }
 
@@ -63,6 +73,8 @@ pub fn push_tools(&self, message: Message) {
             message,
             usage: Usage::new(1, 1),
             stop_reason: Some("tool_calls".into()),
+            served_by: None,
+            rate_limit: None,
         });
     }
 
@@ -100,6 +112,8 @@ async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoErro
                     message: Message::assistant(text),
                     usage: Usage::new(1, 1),
                     stop_reason: Some("stop".into()),
+                    served_by: None,
+                    rate_limit: None,
                 });
             }
         }
@@ -116,12 +130,18 @@ async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoErro
         }
         guard.remove(0)
     }
+
+    fn capabilities(&self, _model: &str) -> ModelCapabilities {
+        self.capabilities
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use tokio_util::sync::CancellationToken;
 
+    use ovo_types::SamplingParams;
+
     use super::*;
     use crate::sample::ToolChoice;
 
@@ -137,9 +157,10 @@ async fn pops_in_order() {
             tool_choice: ToolChoice::default(),
             response_format: None,
             max_output_tokens: None,
-            temperature: None,
+            sampling: SamplingParams::default(),
             cancel: CancellationToken::new(),
             deadline: None,
+            cacheable: false,
         };
         let a = mock.sample(req.clone()).await.expect("a");
         let b = mock.sample(req).await.expect("b");
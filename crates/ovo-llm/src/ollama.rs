@@ -1,25 +1,44 @@
 //! Ollama `/api/chat` client (feature `ollama`).
+//!
+//! Streaming samples read newline-delimited JSON chunks via [`OllamaChatStream`].
+//! Image parts are sent as the `images` base64 array; only `data:` URLs can be
+//! inlined (remote URLs are skipped with a warning).
 
 use async_trait::async_trait;
-use ovo_types::{ErrorCode, Message, OvoError, Role, Usage};
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD as BASE64;
+use ovo_types::{ContentPart, ErrorCode, Message, OvoError, Role, ToolCall, ToolCallId, Usage};
+use serde::Deserialize;
 use serde_json::{Value, json};
 use tracing::{Instrument, info_span};
 
-use crate::openai_compat::{http_status_error_with_meta, parse_retry_after_header};
-use crate::sample::{SampleRequest, SampleResponse, ToolChoice};
+use crate::embed::{Embedder, check_batch};
+use crate::http::{ChunkDecoder, body_stream, precheck, read_text, send_checked};
+use crate::reasoning::{ReasoningReplay, attach_reasoning, echo_reasoning};
+use crate::repair::{ArgumentRepair, parse_tool_arguments};
+use crate::sample::{
+    ModelCapabilities, SampleRequest, SampleResponse, SamplingDialect, ToolChoice, sampling_fields,
+};
 use crate::sampler::LlmSampler;
+use crate::stream::{SampleEvent, SampleStream};
 
 /// Ollama HTTP configuration.
 #[derive(Debug, Clone)]
 pub struct OllamaConfig {
     /// Base URL without trailing slash (default `http://127.0.0.1:11434`).
     pub base_url: String,
+    /// How malformed tool-call argument JSON is handled (default lenient).
+    pub argument_repair: ArgumentRepair,
+    /// Whether assistant reasoning is echoed as `thinking` (default strip).
+    pub reasoning_replay: ReasoningReplay,
 }
 
 impl Default for OllamaConfig {
     fn default() -> Self {
         Self {
             base_url: "http://127.0.0.1:11434".into(),
+            argument_repair: ArgumentRepair::default(),
+            reasoning_replay: ReasoningReplay::default(),
         }
     }
 }
@@ -32,7 +51,25 @@ pub fn new(base_url: impl Into<String>) -> Self {
         while base_url.ends_with('/') {
             base_url.pop();
         }
-        Self { base_url }
+        Self {
+            base_url,
+            argument_repair: ArgumentRepair::default(),
+            reasoning_replay: ReasoningReplay::default(),
+        }
+    }
+
+    /// Set the tool-call argument repair policy.
+    #[must_use]
+    pub const fn with_argument_repair(mut self, mode: ArgumentRepair) -> Self {
+        self.argument_repair = mode;
+        self
+    }
+
+    /// Set whether stored reasoning is sent back as `thinking`.
+    #[must_use]
+    pub const fn with_reasoning_replay(mut self, replay: ReasoningReplay) -> Self {
+        self.reasoning_replay = replay;
+        self
     }
 
     /// Chat endpoint URL.
@@ -40,12 +77,32 @@ pub fn new(base_url: impl Into<String>) -> Self {
     pub fn chat_url(&self) -> String {
         format!("{}/api/chat", self.base_url)
     }
+
+    /// Embeddings endpoint URL.
+    #[must_use]
+    pub fn embed_url(&self) -> String {
+        format!("{}/api/embed", self.base_url)
+    }
 }
 
-/// Build Ollama `/api/chat` JSON body.
+/// Build Ollama `/api/chat` JSON body (stored reasoning is stripped).
 #[must_use]
 pub fn build_ollama_chat_body(req: &SampleRequest) -> Value {
-    let messages: Vec<Value> = req.messages.iter().map(message_to_ollama).collect();
+    build_ollama_chat_body_with(req, ReasoningReplay::Strip)
+}
+
+/// [`build_ollama_chat_body`] with an explicit reasoning replay policy.
+#[must_use]
+pub fn build_ollama_chat_body_with(req: &SampleRequest, replay: ReasoningReplay) -> Value {
+    let messages: Vec<Value> = req
+        .messages
+        .iter()
+        .map(|m| {
+            let mut wire = message_to_ollama(m);
+            echo_reasoning(&mut wire, m, replay, "thinking");
+            wire
+        })
+        .collect();
     let tools = if req.tools.is_empty() || matches!(req.tool_choice, ToolChoice::None) {
         None
     } else {
@@ -70,10 +127,34 @@ pub fn build_ollama_chat_body(req: &SampleRequest) -> Value {
         "messages": messages,
         "stream": false,
     });
-    if let Some(tools) = tools
-        && let Some(obj) = body.as_object_mut()
-    {
-        obj.insert("tools".into(), tools);
+    if let Some(obj) = body.as_object_mut() {
+        if let Some(tools) = tools {
+            obj.insert("tools".into(), tools);
+        }
+        // Reasoning effort has no Ollama equivalent and is not sent.
+        let options: serde_json::Map<String, Value> =
+            sampling_fields(&req.sampling, SamplingDialect::Ollama)
+                .map(|(k, v)| (k.to_owned(), v))
+                .collect();
+        if !options.is_empty() {
+            obj.insert("options".into(), Value::Object(options));
+        }
+    }
+    body
+}
+
+/// Build the streaming `/api/chat` body (`stream: true`, NDJSON response).
+#[must_use]
+pub fn build_ollama_chat_stream_body(req: &SampleRequest) -> Value {
+    build_ollama_chat_stream_body_with(req, ReasoningReplay::Strip)
+}
+
+/// [`build_ollama_chat_stream_body`] with an explicit reasoning replay policy.
+#[must_use]
+pub fn build_ollama_chat_stream_body_with(req: &SampleRequest, replay: ReasoningReplay) -> Value {
+    let mut body = build_ollama_chat_body_with(req, replay);
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("stream".into(), json!(true));
     }
     body
 }
@@ -112,19 +193,89 @@ fn message_to_ollama(m: &Message) -> Value {
             map.insert("tool_calls".into(), Value::Array(tool_calls));
             Value::Object(map)
         }
-        _ => json!({
-            "role": m.role.as_str(),
-            "content": m.text(),
-        }),
+        _ => {
+            let mut map = serde_json::Map::new();
+            map.insert("role".into(), json!(m.role.as_str()));
+            map.insert("content".into(), json!(m.text()));
+            let images: Vec<Value> = m
+                .parts
+                .iter()
+                .filter_map(|p| match p {
+                    ContentPart::Image { url, .. } => data_url_base64(url),
+                    _ => None,
+                })
+                .map(Value::String)
+                .collect();
+            if !images.is_empty() {
+                map.insert("images".into(), Value::Array(images));
+            }
+            Value::Object(map)
+        }
     }
 }
 
+/// Base64 payload of a `data:` URL (percent-encoded payloads are re-encoded).
+fn data_url_base64(url: &str) -> Option<String> {
+    let Some((meta, payload)) = url
+        .strip_prefix("data:")
+        .and_then(|rest| rest.split_once(','))
+    else {
+        tracing::warn!(
+            target: "ovo.llm.ollama",
+            "skipping non-data image url (ollama needs inline bytes)"
+        );
+        return None;
+    };
+    if meta.ends_with(";base64") {
+        let cleaned: String = payload.chars().filter(|c| !c.is_whitespace()).collect();
+        return match BASE64.decode(cleaned.as_bytes()) {
+            Ok(_) => Some(cleaned),
+            Err(e) => {
+                tracing::warn!(target: "ovo.llm.ollama", error = %e, "skipping invalid base64 image");
+                None
+            }
+        };
+    }
+    Some(BASE64.encode(percent_decode(payload)))
+}
+
+fn percent_decode(s: &str) -> Vec<u8> {
+    let bytes = s.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while let Some(&b) = bytes.get(i) {
+        let hex = bytes
+            .get(i.saturating_add(1)..i.saturating_add(3))
+            .and_then(|h| std::str::from_utf8(h).ok())
+            .and_then(|h| u8::from_str_radix(h, 16).ok())
+            .filter(|_| b == b'%');
+        out.push(hex.unwrap_or(b));
+        i = i.saturating_add(if hex.is_some() { 3 } else { 1 });
+    }
+    out
+}
+
 /// Parse Ollama `/api/chat` non-streaming response.
 ///
 /// # Errors
 ///
 /// Invalid JSON shape yields [`ErrorCode::LlmInvalidResponse`].
 pub fn parse_ollama_chat_response(body: &Value) -> Result<SampleResponse, OvoError> {
+    parse_ollama_chat_response_with(body, ArgumentRepair::Lenient)
+}
+
+/// [`parse_ollama_chat_response`] with an explicit argument repair policy.
+///
+/// Repaired calls are counted in `usage.repaired_tool_args`; `thinking`
+/// becomes a [`ContentPart::Reasoning`] part.
+///
+/// # Errors
+///
+/// Invalid JSON shape yields [`ErrorCode::LlmInvalidResponse`].
+pub fn parse_ollama_chat_response_with(
+    body: &Value,
+    repair: ArgumentRepair,
+) -> Result<SampleResponse, OvoError> {
     let message_v = body.get("message").ok_or_else(|| {
         OvoError::new(
             ErrorCode::LlmInvalidResponse,
@@ -137,25 +288,8 @@ pub fn parse_ollama_chat_response(body: &Value) -> Result<SampleResponse, OvoErr
         .unwrap_or("")
         .to_owned();
     let mut tool_calls = Vec::new();
-    if let Some(arr) = message_v.get("tool_calls").and_then(Value::as_array) {
-        for (i, item) in arr.iter().enumerate() {
-            let name = item
-                .pointer("/function/name")
-                .and_then(Value::as_str)
-                .unwrap_or("unknown");
-            let args = item
-                .pointer("/function/arguments")
-                .cloned()
-                .unwrap_or(json!({}));
-            let id = format!("ollama_call_{i}");
-            tool_calls.push(ovo_types::ToolCall {
-                id: ovo_types::ToolCallId::new(id)?,
-                name: name.to_owned(),
-                arguments: args,
-            });
-        }
-    }
-    let message = if tool_calls.is_empty() {
+    let repaired = parse_ollama_tool_calls(message_v, repair, &mut tool_calls)?;
+    let mut message = if tool_calls.is_empty() {
         Message::assistant(content)
     } else {
         let mut m = Message::assistant_tools(tool_calls);
@@ -164,6 +298,65 @@ pub fn parse_ollama_chat_response(body: &Value) -> Result<SampleResponse, OvoErr
         }
         m
     };
+    if let Some(thinking) = message_v.get("thinking").and_then(Value::as_str) {
+        attach_reasoning(&mut message, thinking.to_owned());
+    }
+    let mut usage = parse_ollama_usage(body);
+    usage.repaired_tool_args = repaired;
+    let stop_reason = body
+        .get("done_reason")
+        .and_then(Value::as_str)
+        .map(str::to_owned);
+    Ok(SampleResponse {
+        message,
+        usage,
+        stop_reason,
+        served_by: None,
+        rate_limit: None,
+    })
+}
+
+/// Append `message.tool_calls` to `out`, numbering synthetic ids by position.
+///
+/// Ollama usually sends `arguments` as an object; string arguments (some
+/// models and proxies) go through [`parse_tool_arguments`]. Returns the number
+/// of repaired calls.
+fn parse_ollama_tool_calls(
+    message_v: &Value,
+    repair: ArgumentRepair,
+    out: &mut Vec<ToolCall>,
+) -> Result<u32, OvoError> {
+    let Some(arr) = message_v.get("tool_calls").and_then(Value::as_array) else {
+        return Ok(0);
+    };
+    let mut repaired = 0;
+    for item in arr {
+        let name = item
+            .pointer("/function/name")
+            .and_then(Value::as_str)
+            .unwrap_or("unknown");
+        let args = match item.pointer("/function/arguments") {
+            None | Some(Value::Null) => json!({}),
+            Some(Value::String(raw)) => {
+                let parsed = parse_tool_arguments(raw, repair);
+                if parsed.was_repaired() {
+                    repaired += 1;
+                }
+                parsed.into_value()
+            }
+            Some(other) => other.clone(),
+        };
+        let id = format!("ollama_call_{}", out.len());
+        out.push(ToolCall {
+            id: ToolCallId::new(id)?,
+            name: name.to_owned(),
+            arguments: args,
+        });
+    }
+    Ok(repaired)
+}
+
+fn parse_ollama_usage(body: &Value) -> Usage {
     let prompt_eval = body
         .get("prompt_eval_count")
         .and_then(Value::as_u64)
@@ -174,16 +367,180 @@ pub fn parse_ollama_chat_response(body: &Value) -> Result<SampleResponse, OvoErr
         .and_then(Value::as_u64)
         .and_then(|n| u32::try_from(n).ok())
         .unwrap_or(0);
-    let usage = Usage::new(prompt_eval, eval);
-    let stop_reason = body
-        .get("done_reason")
-        .and_then(Value::as_str)
-        .map(str::to_owned);
-    Ok(SampleResponse {
-        message,
-        usage,
-        stop_reason,
-    })
+    Usage::new(prompt_eval, eval)
+}
+
+/// Accumulates Ollama NDJSON `/api/chat` stream chunks into [`SampleEvent`]s.
+///
+/// Feed raw body bytes to [`Self::push`] (lines may be torn across chunks);
+/// the `done: true` line carries usage and `done_reason`.
+#[derive(Debug, Default)]
+pub struct OllamaChatStream {
+    line: Vec<u8>,
+    text: String,
+    thinking: String,
+    tool_calls: Vec<ToolCall>,
+    usage: Option<Usage>,
+    stop_reason: Option<String>,
+    done: bool,
+    argument_repair: ArgumentRepair,
+    repaired: u32,
+}
+
+impl OllamaChatStream {
+    /// Empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tool-call argument repair policy.
+    #[must_use]
+    pub const fn with_argument_repair(mut self, mode: ArgumentRepair) -> Self {
+        self.argument_repair = mode;
+        self
+    }
+
+    /// True once the `done: true` chunk has been seen.
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.done
+    }
+
+    /// Feed raw body bytes; returns the incremental events of every completed line.
+    ///
+    /// # Errors
+    ///
+    /// Invalid JSON lines yield [`ErrorCode::LlmInvalidResponse`]; an in-band
+    /// `error` line yields [`ErrorCode::LlmProvider`].
+    pub fn push(&mut self, chunk: &[u8]) -> Result<Vec<SampleEvent>, OvoError> {
+        self.line.extend_from_slice(chunk);
+        let mut out = Vec::new();
+        while !self.done
+            && let Some(pos) = self.line.iter().position(|b| *b == b'\n')
+        {
+            let line: Vec<u8> = self.line.drain(..=pos).collect();
+            out.extend(self.push_line(&String::from_utf8_lossy(&line))?);
+        }
+        Ok(out)
+    }
+
+    /// Apply one complete NDJSON line (blank lines are ignored).
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::push`].
+    pub fn push_line(&mut self, line: &str) -> Result<Vec<SampleEvent>, OvoError> {
+        let line = line.trim();
+        if line.is_empty() || self.done {
+            return Ok(Vec::new());
+        }
+        let chunk: Value = serde_json::from_str(line).map_err(|e| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                format!("invalid ollama stream line: {e}"),
+            )
+        })?;
+        if let Some(err) = chunk.get("error") {
+            let msg = err.as_str().map_or_else(|| err.to_string(), str::to_owned);
+            return Err(OvoError::new(
+                ErrorCode::LlmProvider,
+                format!("ollama stream error: {msg}"),
+            ));
+        }
+        let mut out = Vec::new();
+        if let Some(message_v) = chunk.get("message") {
+            if let Some(text) = message_v
+                .get("thinking")
+                .and_then(Value::as_str)
+                .filter(|t| !t.is_empty())
+            {
+                self.thinking.push_str(text);
+                out.push(SampleEvent::ReasoningDelta {
+                    text: text.to_owned(),
+                });
+            }
+            if let Some(text) = message_v
+                .get("content")
+                .and_then(Value::as_str)
+                .filter(|t| !t.is_empty())
+            {
+                self.text.push_str(text);
+                out.push(SampleEvent::TextDelta {
+                    text: text.to_owned(),
+                });
+            }
+            let repaired =
+                parse_ollama_tool_calls(message_v, self.argument_repair, &mut self.tool_calls)?;
+            self.repaired += repaired;
+        }
+        if chunk.get("done").and_then(Value::as_bool) == Some(true) {
+            self.done = true;
+            self.usage = Some(parse_ollama_usage(&chunk));
+            self.stop_reason = chunk
+                .get("done_reason")
+                .and_then(Value::as_str)
+                .map(str::to_owned);
+        }
+        Ok(out)
+    }
+
+    /// Terminal events: `ToolCalls` (when any), `Usage`, `Completed`.
+    ///
+    /// # Errors
+    ///
+    /// A trailing unterminated line that is not valid JSON, or a body that ended
+    /// before `done: true`, yields [`ErrorCode::LlmInvalidResponse`].
+    pub fn finish(mut self) -> Result<Vec<SampleEvent>, OvoError> {
+        let mut out = Vec::with_capacity(3);
+        if !self.done && !self.line.is_empty() {
+            let rest = std::mem::take(&mut self.line);
+            out.extend(self.push_line(&String::from_utf8_lossy(&rest))?);
+        }
+        if !self.done {
+            return Err(OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "ollama stream ended before done",
+            ));
+        }
+        let message = if self.tool_calls.is_empty() {
+            let mut m = Message::assistant(self.text);
+            attach_reasoning(&mut m, self.thinking);
+            m
+        } else {
+            let mut m = Message::assistant_tools(self.tool_calls);
+            if !self.text.is_empty() {
+                m.content = Some(self.text);
+            }
+            attach_reasoning(&mut m, self.thinking);
+            out.push(SampleEvent::ToolCalls { message: m.clone() });
+            m
+        };
+        if let Some(mut usage) = self.usage {
+            usage.repaired_tool_args = self.repaired;
+            out.push(SampleEvent::Usage(usage));
+        }
+        out.push(SampleEvent::Completed {
+            message,
+            stop_reason: self.stop_reason,
+            served_by: None,
+        });
+        Ok(out)
+    }
+}
+
+impl ChunkDecoder for OllamaChatStream {
+    fn push(&mut self, chunk: &[u8]) -> Result<Vec<SampleEvent>, OvoError> {
+        Self::push(self, chunk)
+    }
+
+    fn is_done(&self) -> bool {
+        self.done
+    }
+
+    fn finish(&mut self) -> Result<Vec<SampleEvent>, OvoError> {
+        std::mem::take(self).finish()
+    }
 }
 
 /// HTTP client for Ollama chat.
@@ -216,72 +573,159 @@ pub fn with_client(config: OllamaConfig, client: reqwest::Client) -> Self {
 #[async_trait]
 impl LlmSampler for OllamaSampler {
     async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
-        if request.cancel.is_cancelled() {
-            return Err(OvoError::llm_cancelled("sample cancelled"));
-        }
-        if request.deadline.is_some_and(|d| d.is_expired()) {
-            return Err(OvoError::llm_cancelled("sample deadline expired"));
+        precheck(&request)?;
+        let body = build_ollama_chat_body_with(&request, self.config.reasoning_replay);
+        let url = self.config.chat_url();
+        let span = info_span!(
+            "ovo.sample.http",
+            ovo.model = %request.model,
+            ovo.provider = "ollama",
+        );
+        async move {
+            let response = send_checked(
+                "ollama",
+                self.client.post(&url).json(&body),
+                &request.cancel,
+            )
+            .await?;
+            let text = read_text(response, &request.cancel).await?;
+            let value: Value = serde_json::from_str(&text).map_err(|e| {
+                OvoError::new(
+                    ErrorCode::LlmInvalidResponse,
+                    format!("invalid JSON body: {e}"),
+                )
+            })?;
+            parse_ollama_chat_response_with(&value, self.config.argument_repair)
         }
-        let body = build_ollama_chat_body(&request);
+        .instrument(span)
+        .await
+    }
+
+    async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+        precheck(&request)?;
+        let body = build_ollama_chat_stream_body_with(&request, self.config.reasoning_replay);
         let url = self.config.chat_url();
         let span = info_span!(
             "ovo.sample.http",
             ovo.model = %request.model,
             ovo.provider = "ollama",
+            ovo.stream = true,
+        );
+        let response = send_checked(
+            "ollama",
+            self.client.post(&url).json(&body),
+            &request.cancel,
+        )
+        .instrument(span)
+        .await?;
+        Ok(body_stream(
+            response,
+            request.cancel,
+            OllamaChatStream::new().with_argument_repair(self.config.argument_repair),
+        ))
+    }
+
+    /// `/api/chat` gets no `format` schema, so structured output uses a
+    /// forced tool call.
+    fn capabilities(&self, _model: &str) -> ModelCapabilities {
+        ModelCapabilities::new().with_json_schema(false)
+    }
+}
+
+/// Build an Ollama `/api/embed` body.
+#[must_use]
+pub fn build_ollama_embed_body(model: &str, texts: &[String]) -> Value {
+    json!({
+        "model": model,
+        "input": texts,
+    })
+}
+
+/// Parse an Ollama `/api/embed` response (`embeddings` array).
+///
+/// # Errors
+///
+/// [`ErrorCode::LlmInvalidResponse`] when `embeddings` is missing or not numeric.
+pub fn parse_ollama_embed_response(body: &Value) -> Result<Vec<Vec<f32>>, OvoError> {
+    body.get("embeddings")
+        .map(Vec::<Vec<f32>>::deserialize)
+        .transpose()
+        .map_err(|e| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                format!("invalid embed response: {e}"),
+            )
+        })?
+        .ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "embed response missing 'embeddings'",
+            )
+        })
+}
+
+/// HTTP client for Ollama `/api/embed`.
+#[derive(Debug, Clone)]
+pub struct OllamaEmbedder {
+    config: OllamaConfig,
+    client: reqwest::Client,
+    model: String,
+}
+
+impl OllamaEmbedder {
+    /// Embedder for `model` with a default client.
+    ///
+    /// # Errors
+    ///
+    /// Client build failures.
+    pub fn new(config: OllamaConfig, model: impl Into<String>) -> Result<Self, OvoError> {
+        let client = reqwest::Client::builder().build().map_err(|e| {
+            OvoError::new(ErrorCode::LlmProvider, format!("http client build: {e}"))
+        })?;
+        Ok(Self::with_client(config, client, model))
+    }
+
+    /// Inject client (tests).
+    #[must_use]
+    pub fn with_client(
+        config: OllamaConfig,
+        client: reqwest::Client,
+        model: impl Into<String>,
+    ) -> Self {
+        Self {
+            config,
+            client,
+            model: model.into(),
+        }
+    }
+}
+
+#[async_trait]
+impl Embedder for OllamaEmbedder {
+    async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, OvoError> {
+        if texts.is_empty() {
+            return Ok(Vec::new());
+        }
+        let body = build_ollama_embed_body(&self.model, texts);
+        let url = self.config.embed_url();
+        let span = info_span!(
+            "ovo.embed.http",
+            ovo.model = %self.model,
+            ovo.provider = "ollama",
         );
         async move {
-            let response = tokio::select! {
-                biased;
-                () = request.cancel.cancelled() => {
-                    return Err(OvoError::llm_cancelled("sample cancelled during http"));
-                }
-                res = self.client.post(&url).json(&body).send() => res.map_err(|e| {
-                    OvoError::new(ErrorCode::LlmProvider, format!("http request failed: {e}"))
-                        .with_retry(ovo_types::RetryClass::Backoff)
-                })?,
-            };
-            let status = response.status().as_u16();
-            let retry_after = response
-                .headers()
-                .get("retry-after")
-                .and_then(|v| v.to_str().ok())
-                .and_then(parse_retry_after_header);
-            let x_should_retry = response
-                .headers()
-                .get("x-should-retry")
-                .and_then(|v| v.to_str().ok())
-                .and_then(|s| match s.trim().to_ascii_lowercase().as_str() {
-                    "true" | "1" | "yes" => Some(true),
-                    "false" | "0" | "no" => Some(false),
-                    _ => None,
-                });
-            let text = tokio::select! {
-                biased;
-                () = request.cancel.cancelled() => {
-                    return Err(OvoError::llm_cancelled("sample cancelled during body read"));
-                }
-                text = response.text() => text.map_err(|e| {
-                    OvoError::new(
-                        ErrorCode::LlmProvider,
-                        format!("http body read failed: {e}"),
-                    )
-                })?,
-            };
-            if !(200..300).contains(&status) {
-                return Err(http_status_error_with_meta(
-                    status,
-                    &text,
-                    retry_after,
-                    x_should_retry,
-                ));
-            }
+            // Embeddings have no cancel token; dropping the future cancels.
+            let cancel = tokio_util::sync::CancellationToken::new();
+            let response =
+                send_checked("ollama", self.client.post(&url).json(&body), &cancel).await?;
+            let text = read_text(response, &cancel).await?;
             let value: Value = serde_json::from_str(&text).map_err(|e| {
                 OvoError::new(
                     ErrorCode::LlmInvalidResponse,
                     format!("invalid JSON body: {e}"),
                 )
             })?;
-            parse_ollama_chat_response(&value)
+            check_batch(parse_ollama_embed_response(&value)?, texts.len())
         }
         .instrument(span)
         .await
@@ -289,9 +733,101 @@ async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoErro
 }
 
 #[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
 mod tests {
+    use ovo_types::SamplingParams;
+
     use super::*;
 
+    #[test]
+    fn stream_handles_torn_lines_thinking_and_tools() {
+        let lines = [
+            json!({"message":{"role":"assistant","content":"","thinking":"hmm"},"done":false}),
+            json!({"message":{"role":"assistant","content":"Hi "},"done":false}),
+            json!({"message":{"role":"assistant","content":"","tool_calls":[{"function":{"name":"calc","arguments":{"expr":"1+1"}}}]},"done":false}),
+            json!({"message":{"role":"assistant","content":""},"done":true,"done_reason":"stop","prompt_eval_count":12,"eval_count":3}),
+        ];
+        let mut body = String::new();
+        for line in &lines {
+            body.push_str(&line.to_string());
+            body.push('\n');
+        }
+        let mut acc = OllamaChatStream::new();
+        let mut events = Vec::new();
+        for piece in body.as_bytes().chunks(7) {
+            events.extend(acc.push(piece).expect("push"));
+        }
+        assert!(acc.is_done());
+        events.extend(acc.finish().expect("finish"));
+
+        assert!(matches!(
+            events.first(),
+            Some(SampleEvent::ReasoningDelta { text }) if text == "hmm"
+        ));
+        assert!(matches!(
+            events.get(1),
+            Some(SampleEvent::TextDelta { text }) if text == "Hi "
+        ));
+        assert!(matches!(events.get(2), Some(SampleEvent::ToolCalls { .. })));
+        assert!(matches!(
+            events.get(3),
+            Some(SampleEvent::Usage(u)) if u.input_tokens == 12 && u.output_tokens == 3
+        ));
+        match events.get(4) {
+            Some(SampleEvent::Completed {
+                message,
+                stop_reason,
+                ..
+            }) => {
+                assert_eq!(stop_reason.as_deref(), Some("stop"));
+                assert_eq!(message.content.as_deref(), Some("Hi "));
+                let tc = message.tool_calls.first().expect("tc");
+                assert_eq!(tc.id.as_str(), "ollama_call_0");
+                assert_eq!(tc.arguments.get("expr"), Some(&json!("1+1")));
+            }
+            other => unreachable!("expected Completed, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn string_arguments_are_repaired() {
+        let body = json!({
+            "message": {"role": "assistant", "content": "", "tool_calls": [
+                {"function": {"name": "ls", "arguments": "{path: '.', all: True"}}
+            ]},
+            "done": true, "prompt_eval_count": 4, "eval_count": 2
+        });
+        let resp = parse_ollama_chat_response(&body).expect("parse");
+        assert_eq!(resp.usage.repaired_tool_args, 1);
+        let tc = resp.message.tool_calls.first().expect("tc");
+        assert_eq!(tc.arguments, json!({"path": ".", "all": true}));
+
+        let strict = parse_ollama_chat_response_with(&body, ArgumentRepair::Off).expect("strict");
+        let tc = strict.message.tool_calls.first().expect("tc");
+        assert!(tc.arguments.is_string());
+    }
+
+    #[test]
+    fn stream_mid_error_and_truncation_fail() {
+        let mut acc = OllamaChatStream::new();
+        let first = acc
+            .push(b"{\"message\":{\"content\":\"par\"},\"done\":false}\n")
+            .expect("first");
+        assert_eq!(first.len(), 1);
+        let err = acc
+            .push(b"{\"error\":\"model crashed\"}\n")
+            .expect_err("error line");
+        assert_eq!(err.code(), ErrorCode::LlmProvider);
+        assert!(err.message().contains("model crashed"), "{err}");
+
+        let mut truncated = OllamaChatStream::new();
+        truncated
+            .push(b"{\"message\":{\"content\":\"x\"},\"done\":false}\n{\"mess")
+            .expect("push");
+        let err = truncated.finish().expect_err("truncated");
+        assert_eq!(err.code(), ErrorCode::LlmInvalidResponse);
+    }
+
     #[test]
     fn parse_text() {
         let body = json!({
@@ -302,4 +838,143 @@ fn parse_text() {
         let resp = parse_ollama_chat_response(&body).expect("parse");
         assert_eq!(resp.message.text(), "hi from ollama");
     }
+
+    #[test]
+    fn thinking_is_kept_and_echoed_on_request() {
+        let body = json!({
+            "message": { "role": "assistant", "content": "4", "thinking": "2 + 2" },
+            "done": true
+        });
+        let resp = parse_ollama_chat_response(&body).expect("parse");
+        assert_eq!(resp.message.text(), "4");
+        assert_eq!(resp.message.reasoning().as_deref(), Some("2 + 2"));
+        let mut acc = OllamaChatStream::new();
+        acc.push_line(r#"{"message":{"content":"","thinking":"2 + "}}"#)
+            .expect("line");
+        acc.push_line(r#"{"message":{"content":"4","thinking":"2"},"done":true}"#)
+            .expect("line");
+        let streamed = match acc.finish().expect("finish").pop() {
+            Some(SampleEvent::Completed { message, .. }) => message,
+            other => unreachable!("expected Completed, got {other:?}"),
+        };
+        assert_eq!(streamed, resp.message);
+
+        let req = SampleRequest {
+            model: "qwen3".into(),
+            messages: vec![Message::user("2 + 2?"), resp.message],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: tokio_util::sync::CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        };
+        let stripped = build_ollama_chat_body(&req);
+        assert!(stripped.pointer("/messages/1/thinking").is_none());
+        let echoed = build_ollama_chat_stream_body_with(&req, ReasoningReplay::Echo);
+        assert_eq!(
+            echoed.pointer("/messages/1/thinking"),
+            Some(&json!("2 + 2"))
+        );
+    }
+
+    #[test]
+    fn image_parts_round_trip_as_base64_images() {
+        let png: &[u8] = &[0x89, b'P', b'N', b'G', 0, 255];
+        let mut user = Message::user("describe");
+        user.content = None;
+        user.parts = vec![
+            ContentPart::Text {
+                text: "describe".into(),
+            },
+            ContentPart::Image {
+                mime: ovo_types::ImageMime::Png,
+                url: format!("data:image/png;base64,{}", BASE64.encode(png)),
+            },
+            ContentPart::Image {
+                mime: ovo_types::ImageMime::Png,
+                url: "data:image/svg+xml,%3Csvg%2F%3E".into(),
+            },
+            ContentPart::Image {
+                mime: ovo_types::ImageMime::Jpeg,
+                url: "https://example.com/cat.jpg".into(),
+            },
+        ];
+        let req = SampleRequest {
+            model: "llava".into(),
+            messages: vec![user],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: tokio_util::sync::CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        };
+        let body = build_ollama_chat_body(&req);
+        assert_eq!(
+            body.pointer("/messages/0/content"),
+            Some(&json!("describe"))
+        );
+        let images = body
+            .pointer("/messages/0/images")
+            .and_then(Value::as_array)
+            .expect("images");
+        let decoded: Vec<Vec<u8>> = images
+            .iter()
+            .map(|v| BASE64.decode(v.as_str().expect("str")).expect("base64"))
+            .collect();
+        assert_eq!(decoded, vec![png.to_vec(), b"<svg/>".to_vec()]);
+        let plain = build_ollama_chat_body(&SampleRequest {
+            messages: vec![Message::user("hi")],
+            ..req
+        });
+        assert!(plain.pointer("/messages/0/images").is_none());
+        assert!(plain.get("options").is_none(), "no options when unset");
+    }
+
+    #[test]
+    fn sampling_params_go_to_options() {
+        let req = SampleRequest {
+            model: "llama3".into(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::new()
+                .with_temperature(0.0)
+                .with_seed(42)
+                .with_stop(["###"])
+                .with_reasoning_effort(ovo_types::ReasoningEffort::High),
+            cancel: tokio_util::sync::CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        };
+        let body = build_ollama_chat_body(&req);
+        assert_eq!(
+            body.get("options"),
+            Some(&json!({"temperature": 0.0, "seed": 42, "stop": ["###"]}))
+        );
+    }
+
+    #[test]
+    fn embed_body_and_response() {
+        let texts = vec!["hello".to_owned()];
+        let body = build_ollama_embed_body("nomic-embed-text", &texts);
+        assert_eq!(
+            body,
+            json!({"model": "nomic-embed-text", "input": ["hello"]})
+        );
+        let vectors = parse_ollama_embed_response(&json!({
+            "model": "nomic-embed-text",
+            "embeddings": [[0.25, 0.75]]
+        }))
+        .expect("embeddings");
+        assert_eq!(vectors, vec![vec![0.25, 0.75]]);
+        assert!(parse_ollama_embed_response(&json!({"model": "x"})).is_err());
+    }
 }
@@ -1,13 +1,18 @@
 //! OpenAI-compatible Chat Completions wire format (build + parse).
 //!
 //! Pure helpers are always available so unit tests need no HTTP client.
-//! The live [`OpenAiCompatSampler`] is behind the `openai` feature.
+//! The live [`OpenAiCompatSampler`] is behind the `openai` feature; streaming
+//! chunks are coalesced by [`ChatCompletionsStream`].
 
 use ovo_tools::ToolDefinition;
-use ovo_types::{ErrorCode, Message, OvoError, Role, ToolCall, ToolCallId, Usage};
+use ovo_types::{ContentPart, ErrorCode, Message, OvoError, Role, ToolCall, ToolCallId, Usage};
+use serde::Deserialize;
 use serde_json::{Value, json};
 
-use crate::sample::{SampleRequest, SampleResponse, ToolChoice};
+use crate::reasoning::{ReasoningReplay, attach_reasoning, echo_reasoning};
+use crate::repair::{ArgumentRepair, parse_tool_arguments};
+use crate::sample::{SampleRequest, SampleResponse, SamplingDialect, ToolChoice, sampling_fields};
+use crate::stream::SampleEvent;
 
 /// Configuration for an OpenAI-compatible endpoint.
 #[derive(Debug, Clone)]
@@ -18,6 +23,18 @@ pub struct OpenAiCompatConfig {
     pub api_key: String,
     /// Path under base URL (default `/v1/chat/completions`).
     pub chat_path: String,
+    /// Embeddings path under base URL (default `/v1/embeddings`).
+    pub embeddings_path: String,
+    /// How malformed tool-call argument JSON is handled (default lenient).
+    pub argument_repair: ArgumentRepair,
+    /// Whether the server honors `response_format` JSON Schema (default true).
+    /// Turn off for servers that ignore it so structured output falls back to
+    /// a forced tool call.
+    pub json_schema: bool,
+    /// Whether assistant reasoning is echoed as `reasoning_content` (default
+    /// strip; DeepSeek-style servers that want it back need
+    /// [`ReasoningReplay::Echo`]).
+    pub reasoning_replay: ReasoningReplay,
 }
 
 impl OpenAiCompatConfig {
@@ -28,14 +45,45 @@ pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
             base_url: trim_slash(base_url.into()),
             api_key: api_key.into(),
             chat_path: "/v1/chat/completions".into(),
+            embeddings_path: "/v1/embeddings".into(),
+            argument_repair: ArgumentRepair::default(),
+            json_schema: true,
+            reasoning_replay: ReasoningReplay::default(),
         }
     }
 
+    /// Set the tool-call argument repair policy.
+    #[must_use]
+    pub const fn with_argument_repair(mut self, mode: ArgumentRepair) -> Self {
+        self.argument_repair = mode;
+        self
+    }
+
+    /// Declare whether the server honors `response_format` JSON Schema.
+    #[must_use]
+    pub const fn with_json_schema(mut self, supported: bool) -> Self {
+        self.json_schema = supported;
+        self
+    }
+
+    /// Set whether stored reasoning is sent back to the server.
+    #[must_use]
+    pub const fn with_reasoning_replay(mut self, replay: ReasoningReplay) -> Self {
+        self.reasoning_replay = replay;
+        self
+    }
+
     /// Full request URL.
     #[must_use]
     pub fn chat_url(&self) -> String {
         format!("{}{}", self.base_url, self.chat_path)
     }
+
+    /// Full embeddings URL.
+    #[must_use]
+    pub fn embeddings_url(&self) -> String {
+        format!("{}{}", self.base_url, self.embeddings_path)
+    }
 }
 
 fn trim_slash(mut s: String) -> String {
@@ -46,9 +94,25 @@ fn trim_slash(mut s: String) -> String {
 }
 
 /// Build the JSON body for Chat Completions from a kernel [`SampleRequest`].
+///
+/// Stored reasoning is stripped; see [`build_chat_completions_body_with`].
 #[must_use]
 pub fn build_chat_completions_body(req: &SampleRequest) -> Value {
-    let messages: Vec<Value> = req.messages.iter().map(message_to_openai).collect();
+    build_chat_completions_body_with(req, ReasoningReplay::Strip)
+}
+
+/// [`build_chat_completions_body`] with an explicit reasoning replay policy.
+#[must_use]
+pub fn build_chat_completions_body_with(req: &SampleRequest, replay: ReasoningReplay) -> Value {
+    let messages: Vec<Value> = req
+        .messages
+        .iter()
+        .map(|m| {
+            let mut wire = message_to_openai(m);
+            echo_reasoning(&mut wire, m, replay, "reasoning_content");
+            wire
+        })
+        .collect();
     let mut map = serde_json::Map::new();
     map.insert("model".into(), json!(req.model));
     map.insert("messages".into(), Value::Array(messages));
@@ -65,9 +129,9 @@ pub fn build_chat_completions_body(req: &SampleRequest) -> Value {
     if let Some(max) = req.max_output_tokens {
         map.insert("max_tokens".into(), json!(max));
     }
-    if let Some(temp) = req.temperature {
-        map.insert("temperature".into(), json!(temp));
-    }
+    map.extend(
+        sampling_fields(&req.sampling, SamplingDialect::OpenAi).map(|(k, v)| (k.to_owned(), v)),
+    );
     if let Some(fmt) = &req.response_format {
         // Accept either a raw JSON Schema object or an already-wrapped OpenAI envelope.
         let wire = if fmt.get("type").and_then(Value::as_str) == Some("json_schema")
@@ -82,6 +146,26 @@ pub fn build_chat_completions_body(req: &SampleRequest) -> Value {
     Value::Object(map)
 }
 
+/// Build the streaming Chat Completions body (`stream: true`, usage chunk requested).
+#[must_use]
+pub fn build_chat_completions_stream_body(req: &SampleRequest) -> Value {
+    build_chat_completions_stream_body_with(req, ReasoningReplay::Strip)
+}
+
+/// [`build_chat_completions_stream_body`] with an explicit reasoning replay policy.
+#[must_use]
+pub fn build_chat_completions_stream_body_with(
+    req: &SampleRequest,
+    replay: ReasoningReplay,
+) -> Value {
+    let mut body = build_chat_completions_body_with(req, replay);
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("stream".into(), json!(true));
+        obj.insert("stream_options".into(), json!({ "include_usage": true }));
+    }
+    body
+}
+
 /// Parse `Retry-After` as seconds (integer). HTTP-date forms are not supported (returns `None`).
 #[must_use]
 pub fn parse_retry_after_header(raw: &str) -> Option<std::time::Duration> {
@@ -148,6 +232,17 @@ fn message_to_openai(msg: &Message) -> Value {
             );
             Value::Object(map)
         }
+        Role::User
+            if msg
+                .parts
+                .iter()
+                .any(|p| matches!(p, ContentPart::Image { .. })) =>
+        {
+            json!({
+                "role": "user",
+                "content": openai_content_parts(msg),
+            })
+        }
         role => {
             json!({
                 "role": role.as_str(),
@@ -157,6 +252,29 @@ fn message_to_openai(msg: &Message) -> Value {
     }
 }
 
+/// Multimodal user content as a Chat Completions content array (text + `image_url`).
+fn openai_content_parts(msg: &Message) -> Value {
+    let mut out = Vec::with_capacity(msg.parts.len().saturating_add(1));
+    if let Some(text) = msg.content.as_ref().filter(|t| !t.is_empty())
+        && !msg
+            .parts
+            .iter()
+            .any(|p| matches!(p, ContentPart::Text { .. }))
+    {
+        out.push(json!({ "type": "text", "text": text }));
+    }
+    for part in &msg.parts {
+        match part {
+            ContentPart::Text { text } => out.push(json!({ "type": "text", "text": text })),
+            ContentPart::Image { url, .. } => {
+                out.push(json!({ "type": "image_url", "image_url": { "url": url } }));
+            }
+            _ => {}
+        }
+    }
+    Value::Array(out)
+}
+
 fn tool_to_openai(tool: &ToolDefinition) -> Value {
     json!({
         "type": "function",
@@ -182,10 +300,30 @@ fn tool_choice_to_openai(choice: &ToolChoice) -> Value {
 
 /// Parse a Chat Completions JSON response into a kernel [`SampleResponse`].
 ///
+/// Malformed tool-call arguments are repaired leniently; see
+/// [`parse_chat_completions_response_with`].
+///
 /// # Errors
 ///
 /// Returns [`OvoError`] when the payload is missing choices/message or ids.
 pub fn parse_chat_completions_response(body: &Value) -> Result<SampleResponse, OvoError> {
+    parse_chat_completions_response_with(body, ArgumentRepair::Lenient)
+}
+
+/// [`parse_chat_completions_response`] with an explicit argument repair policy.
+///
+/// Repaired calls are counted in `usage.repaired_tool_args`; arguments that
+/// cannot be parsed stay a raw JSON string for the tool layer to reject.
+/// `reasoning_content` (DeepSeek-style servers) or `reasoning` becomes a
+/// [`ContentPart::Reasoning`] part.
+///
+/// # Errors
+///
+/// Returns [`OvoError`] when the payload is missing choices/message or ids.
+pub fn parse_chat_completions_response_with(
+    body: &Value,
+    repair: ArgumentRepair,
+) -> Result<SampleResponse, OvoError> {
     let choice = body
         .get("choices")
         .and_then(Value::as_array)
@@ -209,15 +347,20 @@ pub fn parse_chat_completions_response(body: &Value) -> Result<SampleResponse, O
             c.as_str().map(str::to_owned)
         }
     });
-    let tool_calls = parse_tool_calls(message_v.get("tool_calls"))?;
-    let message = if tool_calls.is_empty() {
+    let mut repaired = 0;
+    let tool_calls = parse_tool_calls(message_v.get("tool_calls"), repair, &mut repaired)?;
+    let mut message = if tool_calls.is_empty() {
         Message::assistant(content.unwrap_or_default())
     } else {
         let mut m = Message::assistant_tools(tool_calls);
         m.content = content;
         m
     };
-    let usage = parse_usage(body.get("usage"));
+    if let Some(reasoning) = reasoning_field(message_v) {
+        attach_reasoning(&mut message, reasoning.to_owned());
+    }
+    let mut usage = parse_usage(body.get("usage"));
+    usage.repaired_tool_args = repaired;
     let stop_reason = choice
         .get("finish_reason")
         .and_then(Value::as_str)
@@ -226,10 +369,25 @@ pub fn parse_chat_completions_response(body: &Value) -> Result<SampleResponse, O
         message,
         usage,
         stop_reason,
+        served_by: None,
+        rate_limit: None,
     })
 }
 
-fn parse_tool_calls(raw: Option<&Value>) -> Result<Vec<ToolCall>, OvoError> {
+/// Reasoning text from `reasoning_content` or `reasoning`, whichever the server uses.
+fn reasoning_field(message: &Value) -> Option<&str> {
+    message
+        .get("reasoning_content")
+        .or_else(|| message.get("reasoning"))
+        .and_then(Value::as_str)
+        .filter(|t| !t.is_empty())
+}
+
+fn parse_tool_calls(
+    raw: Option<&Value>,
+    repair: ArgumentRepair,
+    repaired: &mut u32,
+) -> Result<Vec<ToolCall>, OvoError> {
     let Some(arr) = raw.and_then(Value::as_array) else {
         return Ok(Vec::new());
     };
@@ -253,7 +411,13 @@ fn parse_tool_calls(raw: Option<&Value>) -> Result<Vec<ToolCall>, OvoError> {
             .cloned()
             .unwrap_or(json!("{}"));
         let arguments = match args_raw {
-            Value::String(s) => serde_json::from_str(&s).unwrap_or(Value::String(s)),
+            Value::String(s) => {
+                let parsed = parse_tool_arguments(&s, repair);
+                if parsed.was_repaired() {
+                    *repaired += 1;
+                }
+                parsed.into_value()
+            }
             other => other,
         };
         out.push(ToolCall {
@@ -265,23 +429,228 @@ fn parse_tool_calls(raw: Option<&Value>) -> Result<Vec<ToolCall>, OvoError> {
     Ok(out)
 }
 
+/// Accumulates Chat Completions stream chunks into [`SampleEvent`]s.
+///
+/// Feed each SSE `data:` payload (except `[DONE]`) to [`Self::push_data`], then
+/// call [`Self::finish`] for the coalesced tool calls, usage and `Completed`.
+#[derive(Debug, Default)]
+pub struct ChatCompletionsStream {
+    text: String,
+    reasoning: String,
+    tool_calls: Vec<PartialToolCall>,
+    usage: Option<Usage>,
+    stop_reason: Option<String>,
+    argument_repair: ArgumentRepair,
+}
+
+#[derive(Debug, Default)]
+struct PartialToolCall {
+    id: String,
+    name: String,
+    arguments: String,
+}
+
+impl ChatCompletionsStream {
+    /// Empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the tool-call argument repair policy applied in [`Self::finish`].
+    #[must_use]
+    pub const fn with_argument_repair(mut self, mode: ArgumentRepair) -> Self {
+        self.argument_repair = mode;
+        self
+    }
+
+    /// Decode one `data:` payload and return the incremental events it carries.
+    ///
+    /// # Errors
+    ///
+    /// Invalid JSON or an in-band `error` object yields [`ErrorCode::LlmInvalidResponse`]
+    /// / [`ErrorCode::LlmProvider`].
+    pub fn push_data(&mut self, data: &str) -> Result<Vec<SampleEvent>, OvoError> {
+        let chunk: Value = serde_json::from_str(data).map_err(|e| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                format!("invalid stream chunk: {e}"),
+            )
+        })?;
+        self.push_chunk(&chunk)
+    }
+
+    /// Apply one parsed chunk.
+    ///
+    /// # Errors
+    ///
+    /// An in-band `error` object yields [`ErrorCode::LlmProvider`].
+    pub fn push_chunk(&mut self, chunk: &Value) -> Result<Vec<SampleEvent>, OvoError> {
+        if let Some(err) = chunk.get("error").filter(|e| !e.is_null()) {
+            let msg = err
+                .get("message")
+                .and_then(Value::as_str)
+                .map_or_else(|| err.to_string(), str::to_owned);
+            return Err(OvoError::new(
+                ErrorCode::LlmProvider,
+                format!("stream error: {msg}"),
+            ));
+        }
+        if let Some(u) = chunk.get("usage").filter(|u| !u.is_null()) {
+            self.usage = Some(parse_usage(Some(u)));
+        }
+        let Some(choice) = chunk
+            .get("choices")
+            .and_then(Value::as_array)
+            .and_then(|a| a.first())
+        else {
+            return Ok(Vec::new());
+        };
+        if let Some(reason) = choice.get("finish_reason").and_then(Value::as_str) {
+            self.stop_reason = Some(reason.to_owned());
+        }
+        let Some(delta) = choice.get("delta") else {
+            return Ok(Vec::new());
+        };
+        let mut out = Vec::new();
+        if let Some(text) = reasoning_field(delta) {
+            self.reasoning.push_str(text);
+            out.push(SampleEvent::ReasoningDelta {
+                text: text.to_owned(),
+            });
+        }
+        if let Some(text) = delta
+            .get("content")
+            .and_then(Value::as_str)
+            .filter(|t| !t.is_empty())
+        {
+            self.text.push_str(text);
+            out.push(SampleEvent::TextDelta {
+                text: text.to_owned(),
+            });
+        }
+        if let Some(calls) = delta.get("tool_calls").and_then(Value::as_array) {
+            for (pos, call) in calls.iter().enumerate() {
+                let index = call
+                    .get("index")
+                    .and_then(Value::as_u64)
+                    .and_then(|n| u32::try_from(n).ok())
+                    .unwrap_or_else(|| u32::try_from(pos).unwrap_or(u32::MAX));
+                if let Some(fragment) = self.apply_tool_delta(index, call) {
+                    out.push(SampleEvent::ToolCallDelta {
+                        index,
+                        arguments_delta: fragment,
+                    });
+                }
+            }
+        }
+        Ok(out)
+    }
+
+    fn apply_tool_delta(&mut self, index: u32, call: &Value) -> Option<String> {
+        let slot = usize::try_from(index).ok()?;
+        if self.tool_calls.len() <= slot {
+            self.tool_calls
+                .resize_with(slot + 1, PartialToolCall::default);
+        }
+        let partial = self.tool_calls.get_mut(slot)?;
+        if let Some(id) = call.get("id").and_then(Value::as_str) {
+            id.clone_into(&mut partial.id);
+        }
+        if let Some(name) = call.pointer("/function/name").and_then(Value::as_str) {
+            partial.name.push_str(name);
+        }
+        let fragment = call
+            .pointer("/function/arguments")
+            .and_then(Value::as_str)
+            .filter(|f| !f.is_empty())?;
+        partial.arguments.push_str(fragment);
+        Some(fragment.to_owned())
+    }
+
+    /// Terminal events: `ToolCalls` (when any), `Usage` (when reported or a
+    /// tool-call argument was repaired), `Completed`.
+    ///
+    /// # Errors
+    ///
+    /// A streamed tool call without id or name yields [`ErrorCode::LlmInvalidResponse`].
+    pub fn finish(self) -> Result<Vec<SampleEvent>, OvoError> {
+        let mut tool_calls = Vec::with_capacity(self.tool_calls.len());
+        let mut repaired = 0;
+        for partial in self.tool_calls {
+            if partial.id.is_empty() || partial.name.is_empty() {
+                return Err(OvoError::new(
+                    ErrorCode::LlmInvalidResponse,
+                    "streamed tool_call missing id or function.name",
+                ));
+            }
+            let parsed = parse_tool_arguments(&partial.arguments, self.argument_repair);
+            if parsed.was_repaired() {
+                repaired += 1;
+            }
+            let arguments = parsed.into_value();
+            tool_calls.push(ToolCall {
+                id: ToolCallId::new(partial.id)?,
+                name: partial.name,
+                arguments,
+            });
+        }
+        let mut out = Vec::with_capacity(3);
+        let message = if tool_calls.is_empty() {
+            let mut m = Message::assistant(self.text);
+            attach_reasoning(&mut m, self.reasoning);
+            m
+        } else {
+            let mut m = Message::assistant_tools(tool_calls);
+            if !self.text.is_empty() {
+                m.content = Some(self.text);
+            }
+            attach_reasoning(&mut m, self.reasoning);
+            out.push(SampleEvent::ToolCalls { message: m.clone() });
+            m
+        };
+        let usage = if repaired > 0 {
+            let mut usage = self.usage.unwrap_or_else(Usage::zero);
+            usage.repaired_tool_args = repaired;
+            Some(usage)
+        } else {
+            self.usage
+        };
+        if let Some(usage) = usage {
+            out.push(SampleEvent::Usage(usage));
+        }
+        out.push(SampleEvent::Completed {
+            message,
+            stop_reason: self.stop_reason,
+            served_by: None,
+        });
+        Ok(out)
+    }
+}
+
 fn parse_usage(raw: Option<&Value>) -> Usage {
     let Some(u) = raw else {
         return Usage::zero();
     };
-    let input = u
-        .get("prompt_tokens")
-        .or_else(|| u.get("input_tokens"))
-        .and_then(Value::as_u64)
-        .and_then(|n| u32::try_from(n).ok())
+    let field = |ptr: &str| {
+        u.pointer(ptr)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+    };
+    let input = field("/prompt_tokens")
+        .or_else(|| field("/input_tokens"))
         .unwrap_or(0);
-    let output = u
-        .get("completion_tokens")
-        .or_else(|| u.get("output_tokens"))
-        .and_then(Value::as_u64)
-        .and_then(|n| u32::try_from(n).ok())
+    let output = field("/completion_tokens")
+        .or_else(|| field("/output_tokens"))
         .unwrap_or(0);
-    Usage::new(input, output)
+    let mut usage = Usage::new(input, output);
+    let cached = field("/prompt_tokens_details/cached_tokens").unwrap_or(0);
+    usage.cache_read_tokens = cached;
+    usage.prompt_details.cached_tokens = cached;
+    let reasoning = field("/completion_tokens_details/reasoning_tokens").unwrap_or(0);
+    usage.reasoning_tokens = reasoning;
+    usage.completion_details.reasoning_tokens = reasoning;
+    usage
 }
 
 /// Map HTTP status (+ optional headers) to a typed LLM error.
@@ -300,6 +669,24 @@ pub fn http_status_error_with_meta(
     body: &str,
     retry_after: Option<std::time::Duration>,
     x_should_retry: Option<bool>,
+) -> OvoError {
+    provider_http_status_error(
+        "openai-compatible",
+        status,
+        body,
+        retry_after,
+        x_should_retry,
+    )
+}
+
+/// Like [`http_status_error_with_meta`], with `provider` labelling the message.
+#[must_use]
+pub fn provider_http_status_error(
+    provider: &str,
+    status: u16,
+    body: &str,
+    retry_after: Option<std::time::Duration>,
+    x_should_retry: Option<bool>,
 ) -> OvoError {
     use ovo_types::RetryClass;
 
@@ -314,7 +701,7 @@ pub fn http_status_error_with_meta(
         (_, HttpRetryClass::RateLimited | HttpRetryClass::Retry) => RetryClass::Backoff,
         (_, HttpRetryClass::Fatal) => RetryClass::Never,
     };
-    let mut err = OvoError::new(code, format!("openai-compatible HTTP {status}: {snippet}"))
+    let mut err = OvoError::new(code, format!("{provider} HTTP {status}: {snippet}"))
         .with_retry(retry)
         .with_http_status(status);
     if let Some(after) = retry_after {
@@ -326,6 +713,49 @@ pub fn http_status_error_with_meta(
     err
 }
 
+/// Build a `/v1/embeddings` body; `dimensions` is sent only when set.
+#[must_use]
+pub fn build_embeddings_body(model: &str, texts: &[String], dimensions: Option<u32>) -> Value {
+    let mut body = json!({
+        "model": model,
+        "input": texts,
+        "encoding_format": "float",
+    });
+    if let (Some(dims), Some(obj)) = (dimensions, body.as_object_mut()) {
+        obj.insert("dimensions".into(), json!(dims));
+    }
+    body
+}
+
+#[derive(Deserialize)]
+struct EmbeddingsBody {
+    data: Vec<EmbeddingItem>,
+}
+
+#[derive(Deserialize)]
+struct EmbeddingItem {
+    #[serde(default)]
+    index: usize,
+    embedding: Vec<f32>,
+}
+
+/// Parse a `/v1/embeddings` response into vectors ordered by `index`.
+///
+/// # Errors
+///
+/// [`ErrorCode::LlmInvalidResponse`] when `data[].embedding` is missing or not
+/// numeric.
+pub fn parse_embeddings_response(body: &Value) -> Result<Vec<Vec<f32>>, OvoError> {
+    let mut parsed = EmbeddingsBody::deserialize(body).map_err(|e| {
+        OvoError::new(
+            ErrorCode::LlmInvalidResponse,
+            format!("invalid embeddings response: {e}"),
+        )
+    })?;
+    parsed.data.sort_by_key(|item| item.index);
+    Ok(parsed.data.into_iter().map(|item| item.embedding).collect())
+}
+
 #[cfg(feature = "openai")]
 mod client {
     use async_trait::async_trait;
@@ -333,11 +763,18 @@ mod client {
     use tracing::{Instrument, info_span};
 
     use super::{
-        OpenAiCompatConfig, build_chat_completions_body, http_status_error_with_meta,
-        parse_chat_completions_response, parse_retry_after_header,
+        ChatCompletionsStream, OpenAiCompatConfig, build_chat_completions_body_with,
+        build_chat_completions_stream_body_with, build_embeddings_body,
+        parse_chat_completions_response_with, parse_embeddings_response,
+    };
+    use crate::embed::{Embedder, check_batch};
+    use crate::http::{
+        ChunkDecoder, body_stream, precheck, rate_limit_from_headers, read_text, send_checked,
     };
-    use crate::sample::{SampleRequest, SampleResponse};
+    use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
     use crate::sampler::LlmSampler;
+    use crate::sse::SseDecoder;
+    use crate::stream::{SampleEvent, SampleStream};
 
     /// HTTP client for OpenAI-compatible Chat Completions.
     #[derive(Debug, Clone)]
@@ -364,88 +801,190 @@ pub fn new(config: OpenAiCompatConfig) -> Result<Self, OvoError> {
         pub fn with_client(config: OpenAiCompatConfig, client: reqwest::Client) -> Self {
             Self { config, client }
         }
+
+        fn post(&self, body: &serde_json::Value) -> reqwest::RequestBuilder {
+            let mut req = self.client.post(self.config.chat_url()).json(body);
+            if !self.config.api_key.is_empty() {
+                req = req.bearer_auth(&self.config.api_key);
+            }
+            req
+        }
     }
 
     #[async_trait]
     impl LlmSampler for OpenAiCompatSampler {
         async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
-            if request.cancel.is_cancelled() {
-                return Err(OvoError::new(ErrorCode::LlmCancelled, "sample cancelled"));
-            }
-            if request.deadline.is_some_and(|d| d.is_expired()) {
-                return Err(OvoError::new(
-                    ErrorCode::LlmCancelled,
-                    "sample deadline expired",
-                ));
+            precheck(&request)?;
+            let body = build_chat_completions_body_with(&request, self.config.reasoning_replay);
+            let span = info_span!(
+                "ovo.sample.http",
+                ovo.model = %request.model,
+                ovo.provider = "openai_compat",
+            );
+
+            async move {
+                let response =
+                    send_checked("openai-compatible", self.post(&body), &request.cancel).await?;
+                let rate_limit = rate_limit_from_headers(&response);
+                let text = read_text(response, &request.cancel).await?;
+                let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
+                    OvoError::new(
+                        ErrorCode::LlmInvalidResponse,
+                        format!("invalid JSON body: {e}"),
+                    )
+                })?;
+                let mut response =
+                    parse_chat_completions_response_with(&value, self.config.argument_repair)?;
+                response.rate_limit = rate_limit;
+                Ok(response)
             }
+            .instrument(span)
+            .await
+        }
 
-            let body = build_chat_completions_body(&request);
-            let url = self.config.chat_url();
+        async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+            precheck(&request)?;
+            let body =
+                build_chat_completions_stream_body_with(&request, self.config.reasoning_replay);
             let span = info_span!(
                 "ovo.sample.http",
                 ovo.model = %request.model,
                 ovo.provider = "openai_compat",
+                ovo.stream = true,
             );
+            let response = send_checked("openai-compatible", self.post(&body), &request.cancel)
+                .instrument(span)
+                .await?;
+            Ok(body_stream(
+                response,
+                request.cancel,
+                SseChatDecoder {
+                    acc: ChatCompletionsStream::new()
+                        .with_argument_repair(self.config.argument_repair),
+                    ..SseChatDecoder::default()
+                },
+            ))
+        }
 
-            async move {
-                let mut req = self.client.post(&url).json(&body);
-                if !self.config.api_key.is_empty() {
-                    req = req.bearer_auth(&self.config.api_key);
-                }
+        fn capabilities(&self, _model: &str) -> ModelCapabilities {
+            ModelCapabilities::new().with_json_schema(self.config.json_schema)
+        }
+    }
 
-                let response = tokio::select! {
-                    biased;
-                    () = request.cancel.cancelled() => {
-                        return Err(OvoError::llm_cancelled("sample cancelled during http"));
-                    }
-                    res = req.send() => res.map_err(|e| {
-                        OvoError::new(ErrorCode::LlmProvider, format!("http request failed: {e}"))
-                            .with_retry(ovo_types::RetryClass::Backoff)
-                    })?,
-                };
-
-                let status = response.status().as_u16();
-                let retry_after = response
-                    .headers()
-                    .get("retry-after")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(parse_retry_after_header);
-                let x_should_retry = response
-                    .headers()
-                    .get("x-should-retry")
-                    .and_then(|v| v.to_str().ok())
-                    .and_then(|s| match s.trim().to_ascii_lowercase().as_str() {
-                        "true" | "1" | "yes" => Some(true),
-                        "false" | "0" | "no" => Some(false),
-                        _ => None,
-                    });
-                let text = tokio::select! {
-                    biased;
-                    () = request.cancel.cancelled() => {
-                        return Err(OvoError::llm_cancelled("sample cancelled during body read"));
-                    }
-                    text = response.text() => text.map_err(|e| {
-                        OvoError::new(
-                            ErrorCode::LlmProvider,
-                            format!("http body read failed: {e}"),
-                        )
-                    })?,
-                };
-                if !(200..300).contains(&status) {
-                    return Err(http_status_error_with_meta(
-                        status,
-                        &text,
-                        retry_after,
-                        x_should_retry,
-                    ));
+    /// SSE framing + [`ChatCompletionsStream`] mapping; `[DONE]` ends the stream.
+    #[derive(Debug, Default)]
+    struct SseChatDecoder {
+        sse: SseDecoder,
+        acc: ChatCompletionsStream,
+        done: bool,
+    }
+
+    impl ChunkDecoder for SseChatDecoder {
+        fn push(&mut self, chunk: &[u8]) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            for ev in self.sse.push(chunk) {
+                if self.done {
+                    break;
                 }
+                if ev.data.trim() == "[DONE]" {
+                    self.done = true;
+                    continue;
+                }
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            Ok(out)
+        }
+
+        fn is_done(&self) -> bool {
+            self.done
+        }
+
+        fn finish(&mut self) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            if !self.done
+                && let Some(ev) = self.sse.finish()
+                && ev.data.trim() != "[DONE]"
+            {
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            out.extend(std::mem::take(&mut self.acc).finish()?);
+            Ok(out)
+        }
+    }
+
+    /// HTTP client for OpenAI-compatible `/v1/embeddings`.
+    #[derive(Debug, Clone)]
+    pub struct OpenAiCompatEmbedder {
+        config: OpenAiCompatConfig,
+        client: reqwest::Client,
+        model: String,
+        dimensions: Option<u32>,
+    }
+
+    impl OpenAiCompatEmbedder {
+        /// Embedder for `model` with a default `reqwest` client.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error when the HTTP client cannot be built.
+        pub fn new(config: OpenAiCompatConfig, model: impl Into<String>) -> Result<Self, OvoError> {
+            let client = reqwest::Client::builder().build().map_err(|e| {
+                OvoError::new(ErrorCode::LlmProvider, format!("http client build: {e}"))
+            })?;
+            Ok(Self::with_client(config, client, model))
+        }
+
+        /// Create with an existing client.
+        #[must_use]
+        pub fn with_client(
+            config: OpenAiCompatConfig,
+            client: reqwest::Client,
+            model: impl Into<String>,
+        ) -> Self {
+            Self {
+                config,
+                client,
+                model: model.into(),
+                dimensions: None,
+            }
+        }
+
+        /// Request `dimensions`-long vectors (models that support shortening).
+        #[must_use]
+        pub const fn with_dimensions(mut self, dimensions: u32) -> Self {
+            self.dimensions = Some(dimensions);
+            self
+        }
+    }
+
+    #[async_trait]
+    impl Embedder for OpenAiCompatEmbedder {
+        async fn embed(&self, texts: &[String]) -> Result<Vec<Vec<f32>>, OvoError> {
+            if texts.is_empty() {
+                return Ok(Vec::new());
+            }
+            let body = build_embeddings_body(&self.model, texts, self.dimensions);
+            let mut req = self.client.post(self.config.embeddings_url()).json(&body);
+            if !self.config.api_key.is_empty() {
+                req = req.bearer_auth(&self.config.api_key);
+            }
+            let span = info_span!(
+                "ovo.embed.http",
+                ovo.model = %self.model,
+                ovo.provider = "openai_compat",
+            );
+            async move {
+                // Embeddings have no cancel token; dropping the future cancels.
+                let cancel = tokio_util::sync::CancellationToken::new();
+                let response = send_checked("openai-compatible", req, &cancel).await?;
+                let text = read_text(response, &cancel).await?;
                 let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
                     OvoError::new(
                         ErrorCode::LlmInvalidResponse,
                         format!("invalid JSON body: {e}"),
                     )
                 })?;
-                parse_chat_completions_response(&value)
+                check_batch(parse_embeddings_response(&value)?, texts.len())
             }
             .instrument(span)
             .await
@@ -454,11 +993,12 @@ async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoErro
 }
 
 #[cfg(feature = "openai")]
-pub use client::OpenAiCompatSampler;
+pub use client::{OpenAiCompatEmbedder, OpenAiCompatSampler};
 
 #[cfg(test)]
+#[allow(clippy::panic, reason = "unit tests")]
 mod tests {
-    use ovo_types::{ErrorCode, Message, ToolCallId};
+    use ovo_types::{ErrorCode, Message, SamplingParams, ToolCallId};
     use serde_json::json;
 
     use super::*;
@@ -476,9 +1016,14 @@ fn build_body_includes_tools() {
             tool_choice: ToolChoice::Auto,
             response_format: None,
             max_output_tokens: Some(64),
-            temperature: Some(0.0),
+            sampling: SamplingParams::new()
+                .with_temperature(0.0)
+                .with_seed(42)
+                .with_stop(["END"])
+                .with_reasoning_effort(ovo_types::ReasoningEffort::Low),
             cancel: tokio_util::sync::CancellationToken::new(),
             deadline: None,
+            cacheable: false,
         };
         let body = build_chat_completions_body(&req);
         assert_eq!(body.get("model"), Some(&json!("gpt-test")));
@@ -489,6 +1034,11 @@ fn build_body_includes_tools() {
                 .is_some_and(|a| !a.is_empty())
         );
         assert_eq!(body.get("tool_choice"), Some(&json!("auto")));
+        assert_eq!(body.get("temperature"), Some(&json!(0.0)));
+        assert_eq!(body.get("seed"), Some(&json!(42)));
+        assert_eq!(body.get("stop"), Some(&json!(["END"])));
+        assert_eq!(body.get("reasoning_effort"), Some(&json!("low")));
+        assert!(body.get("top_p").is_none());
     }
 
     #[test]
@@ -507,6 +1057,47 @@ fn parse_assistant_text() {
         assert_eq!(resp.stop_reason.as_deref(), Some("stop"));
     }
 
+    #[test]
+    fn reasoning_content_is_kept_and_echoed_on_request() {
+        let body = json!({
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "content": "42",
+                    "reasoning_content": "6 * 7"
+                },
+                "finish_reason": "stop"
+            }]
+        });
+        let resp = parse_chat_completions_response(&body).expect("parse");
+        assert_eq!(resp.message.text(), "42");
+        assert_eq!(resp.message.reasoning().as_deref(), Some("6 * 7"));
+
+        let req = SampleRequest {
+            model: "deepseek-reasoner".into(),
+            messages: vec![Message::user("q"), resp.message],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: tokio_util::sync::CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        };
+        let stripped = build_chat_completions_body(&req);
+        assert_eq!(
+            stripped.pointer("/messages/1"),
+            Some(&json!({"role": "assistant", "content": "42"}))
+        );
+        let echoed = build_chat_completions_body_with(&req, ReasoningReplay::Echo);
+        assert_eq!(
+            echoed.pointer("/messages/1/reasoning_content"),
+            Some(&json!("6 * 7"))
+        );
+        assert!(echoed.pointer("/messages/0/reasoning_content").is_none());
+    }
+
     #[test]
     fn response_format_wraps_raw_schema() {
         let schema = json!({"type": "object", "properties": {"ok": {"type": "boolean"}}});
@@ -517,9 +1108,10 @@ fn response_format_wraps_raw_schema() {
             tool_choice: ToolChoice::Auto,
             response_format: Some(schema.clone()),
             max_output_tokens: None,
-            temperature: None,
+            sampling: SamplingParams::default(),
             cancel: tokio_util::sync::CancellationToken::new(),
             deadline: None,
+            cacheable: false,
         };
         let body = build_chat_completions_body(&req);
         let fmt = body.get("response_format").expect("response_format");
@@ -548,6 +1140,39 @@ fn http_meta_sets_transport_fields() {
         assert_eq!(err.http_status(), Some(429));
         assert_eq!(err.retry_after(), Some(std::time::Duration::from_secs(3)));
         assert_eq!(err.x_should_retry(), Some(true));
+
+        let err = provider_http_status_error("anthropic", 529, "overloaded", None, None);
+        assert!(err.message().starts_with("anthropic HTTP 529"), "{err}");
+    }
+
+    #[test]
+    fn usage_details_reach_cached_and_reasoning_rates() {
+        let body = json!({
+            "choices": [{"message": {"role": "assistant", "content": "ok"}, "finish_reason": "stop"}],
+            "usage": {
+                "prompt_tokens": 1000,
+                "completion_tokens": 500,
+                "prompt_tokens_details": {"cached_tokens": 800},
+                "completion_tokens_details": {"reasoning_tokens": 300}
+            }
+        });
+        let usage = parse_chat_completions_response(&body).expect("parse").usage;
+        assert_eq!(usage.cache_read_tokens, 800);
+        assert_eq!(usage.reasoning_tokens, 300);
+        let price = ovo_types::ModelPrice::new(2.0, 8.0)
+            .with_cached_input(0.5)
+            .with_reasoning(10.0);
+        // 200 uncached at 2, 800 cached at 0.5, 200 output at 8, 300 reasoning at 10.
+        assert!((price.cost(&usage) - 5400.0 / 1e6).abs() < 1e-12);
+
+        let mut acc = ChatCompletionsStream::new();
+        acc.push_chunk(&json!({"choices": [], "usage": body.get("usage")}))
+            .expect("usage chunk");
+        let streamed = acc.finish().expect("finish");
+        assert!(streamed.iter().any(|e| matches!(
+            e,
+            SampleEvent::Usage(u) if u.cache_read_tokens == 800 && u.reasoning_tokens == 300
+        )));
     }
 
     #[test]
@@ -579,4 +1204,191 @@ fn parse_tool_calls() {
         assert_eq!(resp.stop_reason.as_deref(), Some("tool_calls"));
         let _ = ToolCallId::new("call_1");
     }
+
+    #[test]
+    fn repairs_malformed_tool_arguments() {
+        let body = json!({
+            "choices": [{
+                "message": {
+                    "role": "assistant",
+                    "tool_calls": [
+                        {"id": "call_1", "function": {"name": "read", "arguments": "{'path': 'a.txt',}"}},
+                        {"id": "call_2", "function": {"name": "read", "arguments": "<garbage>"}}
+                    ]
+                }
+            }]
+        });
+        let resp = parse_chat_completions_response(&body).expect("parse");
+        assert_eq!(resp.usage.repaired_tool_args, 1);
+        let [first, second] = resp.message.tool_calls.as_slice() else {
+            panic!("two calls expected");
+        };
+        assert_eq!(first.arguments, json!({"path": "a.txt"}));
+        assert_eq!(second.arguments, json!("<garbage>"));
+
+        let strict =
+            parse_chat_completions_response_with(&body, ArgumentRepair::Off).expect("parse strict");
+        assert_eq!(strict.usage.repaired_tool_args, 0);
+        let first = strict.message.tool_calls.first().expect("tc");
+        assert_eq!(first.arguments, json!("{'path': 'a.txt',}"));
+
+        let mut stream = ChatCompletionsStream::new();
+        stream
+            .push_chunk(&json!({"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_3","function":{"name":"read","arguments":"{\"path\": \"a.txt"}}]}}]}))
+            .expect("chunk");
+        let events = stream.finish().expect("finish");
+        let usage = events.iter().find_map(|e| match e {
+            SampleEvent::Usage(u) => Some(u.repaired_tool_args),
+            _ => None,
+        });
+        assert_eq!(usage, Some(1), "{events:?}");
+    }
+
+    #[test]
+    fn stream_body_requests_usage_chunk() {
+        let req = SampleRequest {
+            model: "gpt-test".into(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: tokio_util::sync::CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        };
+        let body = build_chat_completions_stream_body(&req);
+        assert_eq!(body.get("stream"), Some(&json!(true)));
+        assert_eq!(
+            body.pointer("/stream_options/include_usage"),
+            Some(&json!(true))
+        );
+    }
+
+    #[test]
+    fn stream_coalesces_text_reasoning_and_tool_calls() {
+        let mut acc = ChatCompletionsStream::new();
+        let chunks = [
+            json!({"choices":[{"index":0,"delta":{"role":"assistant","reasoning_content":"think"}}]}),
+            json!({"choices":[{"index":0,"delta":{"content":"Sum: "}}]}),
+            json!({"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_9","type":"function","function":{"name":"calc","arguments":""}}]}}]}),
+            json!({"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"{\"expr\":"}}]}}]}),
+            json!({"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":"\"1+2\"}"}}]},"finish_reason":"tool_calls"}]}),
+            json!({"choices":[],"usage":{"prompt_tokens":5,"completion_tokens":8}}),
+        ];
+        let mut events = Vec::new();
+        for chunk in &chunks {
+            events.extend(acc.push_chunk(chunk).expect("chunk"));
+        }
+        assert!(matches!(
+            events.first(),
+            Some(SampleEvent::ReasoningDelta { text }) if text == "think"
+        ));
+        assert!(matches!(
+            events.get(1),
+            Some(SampleEvent::TextDelta { text }) if text == "Sum: "
+        ));
+        let deltas: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                SampleEvent::ToolCallDelta {
+                    index: 0,
+                    arguments_delta,
+                } => Some(arguments_delta.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(deltas, vec!["{\"expr\":", "\"1+2\"}"]);
+
+        let tail = acc.finish().expect("finish");
+        assert!(matches!(tail.first(), Some(SampleEvent::ToolCalls { .. })));
+        assert!(matches!(
+            tail.get(1),
+            Some(SampleEvent::Usage(u)) if u.input_tokens == 5 && u.output_tokens == 8
+        ));
+        let Some(SampleEvent::Completed {
+            message,
+            stop_reason,
+            ..
+        }) = tail.last()
+        else {
+            panic!("expected Completed, got {tail:?}");
+        };
+        assert_eq!(stop_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(message.content.as_deref(), Some("Sum: "));
+        assert_eq!(message.reasoning().as_deref(), Some("think"));
+        let tc = message.tool_calls.first().expect("tc");
+        assert_eq!(tc.id.as_str(), "call_9");
+        assert_eq!(tc.arguments.get("expr"), Some(&json!("1+2")));
+    }
+
+    #[test]
+    fn stream_in_band_error_is_provider_error() {
+        let mut acc = ChatCompletionsStream::new();
+        let err = acc
+            .push_data(r#"{"error":{"message":"overloaded"}}"#)
+            .expect_err("error chunk");
+        assert_eq!(err.code(), ErrorCode::LlmProvider);
+        assert!(err.message().contains("overloaded"), "{err}");
+        let err = acc.push_data("{not json").expect_err("bad json");
+        assert_eq!(err.code(), ErrorCode::LlmInvalidResponse);
+    }
+
+    #[test]
+    fn user_image_parts_become_image_url_content_array() {
+        let mut user = Message::user("what is this?");
+        user.parts = vec![ContentPart::Image {
+            mime: ovo_types::ImageMime::Png,
+            url: "data:image/png;base64,iVBORw0KGgo=".into(),
+        }];
+        let req = SampleRequest {
+            model: "gpt-test".into(),
+            messages: vec![Message::system("sys"), user],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: tokio_util::sync::CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        };
+        let body = build_chat_completions_body(&req);
+        assert_eq!(body.pointer("/messages/0/content"), Some(&json!("sys")));
+        assert_eq!(
+            body.pointer("/messages/1/content"),
+            Some(&json!([
+                {"type": "text", "text": "what is this?"},
+                {"type": "image_url", "image_url": {"url": "data:image/png;base64,iVBORw0KGgo="}}
+            ]))
+        );
+    }
+
+    #[test]
+    fn embeddings_body_and_response() {
+        let texts = vec!["a".to_owned(), "b".to_owned()];
+        let body = build_embeddings_body("text-embedding-3-small", &texts, Some(64));
+        assert_eq!(body.get("input"), Some(&json!(["a", "b"])));
+        assert_eq!(body.get("dimensions"), Some(&json!(64)));
+        assert!(
+            build_embeddings_body("m", &texts, None)
+                .get("dimensions")
+                .is_none()
+        );
+
+        let resp = json!({
+            "object": "list",
+            "data": [
+                {"object": "embedding", "index": 1, "embedding": [0.5, -0.5]},
+                {"object": "embedding", "index": 0, "embedding": [1.0, 0.0]}
+            ],
+            "usage": {"prompt_tokens": 2, "total_tokens": 2}
+        });
+        let vectors = parse_embeddings_response(&resp).unwrap_or_else(|e| panic!("{e}"));
+        assert_eq!(vectors, vec![vec![1.0, 0.0], vec![0.5, -0.5]]);
+        let err = parse_embeddings_response(&json!({"data": [{"embedding": "x"}]}))
+            .expect_err("bad embedding");
+        assert_eq!(err.code(), ErrorCode::LlmInvalidResponse);
+    }
 }
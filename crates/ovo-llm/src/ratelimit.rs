@@ -0,0 +1,629 @@
+//! [`RateLimitedSampler`]: client-side RPM/TPM token buckets per model.
+//!
+//! [`crate::RetryingSampler`] reacts after a 429; this decorator keeps wide
+//! `parallel()` fan-outs under the provider budget up front. Callers queue
+//! FIFO per model; the head of the queue waits until both buckets hold its
+//! cost (one request, [`estimate_request_tokens`] tokens). Buckets shrink to
+//! the provider's `x-ratelimit-remaining-*` headers and pause on
+//! `Retry-After`, and actual usage is charged back after each sample.
+
+use std::collections::HashMap;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use async_trait::async_trait;
+use futures::Stream;
+use ovo_obs::{NoopMetrics, SharedMetrics, record_rate_limit_queue, record_rate_limit_wait};
+use ovo_protocol::{HeuristicCounter, TokenCounter, estimate_text_tokens};
+use ovo_types::{ErrorCode, OvoError, Usage};
+use tokio_util::sync::CancellationToken;
+
+use crate::sample::{ModelCapabilities, RateLimitSnapshot, SampleRequest, SampleResponse};
+use crate::sampler::LlmSampler;
+use crate::stream::{SampleEvent, SampleStream};
+
+/// Per-minute budgets for one model (`None` = unlimited).
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RateLimit {
+    /// Requests per minute.
+    pub requests_per_minute: Option<u32>,
+    /// Tokens (input + output) per minute.
+    pub tokens_per_minute: Option<u32>,
+}
+
+impl RateLimit {
+    /// No limits.
+    #[must_use]
+    pub const fn unlimited() -> Self {
+        Self {
+            requests_per_minute: None,
+            tokens_per_minute: None,
+        }
+    }
+
+    /// Set requests per minute.
+    #[must_use]
+    pub const fn with_rpm(mut self, rpm: u32) -> Self {
+        self.requests_per_minute = Some(rpm);
+        self
+    }
+
+    /// Set tokens per minute.
+    #[must_use]
+    pub const fn with_tpm(mut self, tpm: u32) -> Self {
+        self.tokens_per_minute = Some(tpm);
+        self
+    }
+
+    const fn is_unlimited(&self) -> bool {
+        self.requests_per_minute.is_none() && self.tokens_per_minute.is_none()
+    }
+}
+
+/// Admission estimate: message tokens (as preflight counts them), tool
+/// schemas, and the requested output budget.
+#[must_use]
+pub fn estimate_request_tokens(request: &SampleRequest) -> u32 {
+    let mut n = HeuristicCounter.count_messages(&request.messages);
+    for tool in &request.tools {
+        let schema = serde_json::to_string(tool).unwrap_or_default();
+        n = n.saturating_add(estimate_text_tokens(&schema));
+    }
+    n.saturating_add(request.max_output_tokens.unwrap_or(0))
+}
+
+/// Parse provider rate-limit headers (`x-ratelimit-*` and
+/// `anthropic-ratelimit-*-remaining`); `None` when none are present.
+#[must_use]
+pub fn parse_rate_limit_headers<'a>(
+    get: impl Fn(&str) -> Option<&'a str>,
+) -> Option<RateLimitSnapshot> {
+    let count = |names: &[&str]| {
+        names
+            .iter()
+            .find_map(|n| get(n))
+            .and_then(|v| v.trim().parse::<u64>().ok())
+    };
+    let snapshot = RateLimitSnapshot {
+        remaining_requests: count(&[
+            "x-ratelimit-remaining-requests",
+            "anthropic-ratelimit-requests-remaining",
+        ]),
+        remaining_tokens: count(&[
+            "x-ratelimit-remaining-tokens",
+            "anthropic-ratelimit-tokens-remaining",
+        ]),
+        reset_requests: get("x-ratelimit-reset-requests").and_then(parse_reset_duration),
+        reset_tokens: get("x-ratelimit-reset-tokens").and_then(parse_reset_duration),
+    };
+    (snapshot != RateLimitSnapshot::default()).then_some(snapshot)
+}
+
+/// Parse reset hints: plain seconds (`"12"`, `"0.5"`) or Go-style
+/// durations (`"6m0s"`, `"1.5s"`, `"20ms"`).
+#[must_use]
+pub fn parse_reset_duration(raw: &str) -> Option<Duration> {
+    let s = raw.trim();
+    if let Ok(secs) = s.parse::<f64>() {
+        return Duration::try_from_secs_f64(secs).ok();
+    }
+    let mut total = 0.0_f64;
+    let mut rest = s;
+    while !rest.is_empty() {
+        let split = rest
+            .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+            .filter(|i| *i > 0)?;
+        let (num, tail) = rest.split_at(split);
+        let value: f64 = num.parse().ok()?;
+        let (scale, tail) = if let Some(t) = tail.strip_prefix("ms") {
+            (0.001, t)
+        } else if let Some(t) = tail.strip_prefix('h') {
+            (3600.0, t)
+        } else if let Some(t) = tail.strip_prefix('m') {
+            (60.0, t)
+        } else if let Some(t) = tail.strip_prefix('s') {
+            (1.0, t)
+        } else {
+            return None;
+        };
+        total += value * scale;
+        rest = tail;
+    }
+    Duration::try_from_secs_f64(total).ok()
+}
+
+/// Token bucket; `level` may go negative after usage charge-back.
+#[derive(Debug)]
+struct Bucket {
+    capacity: f64,
+    level: f64,
+    per_sec: f64,
+}
+
+impl Bucket {
+    fn new(per_minute: u32) -> Self {
+        let capacity = f64::from(per_minute.max(1));
+        Self {
+            capacity,
+            level: capacity,
+            per_sec: capacity / 60.0,
+        }
+    }
+
+    fn refill(&mut self, secs: f64) {
+        self.level = self.per_sec.mul_add(secs, self.level).min(self.capacity);
+    }
+
+    /// Oversized costs are clamped so a single call can always be admitted.
+    fn clamp(&self, cost: f64) -> f64 {
+        cost.min(self.capacity)
+    }
+
+    fn wait_for(&self, cost: f64) -> Duration {
+        let missing = self.clamp(cost) - self.level;
+        if missing <= 0.0 {
+            Duration::ZERO
+        } else {
+            Duration::from_secs_f64(missing / self.per_sec)
+        }
+    }
+}
+
+#[derive(Debug)]
+struct BucketState {
+    requests: Option<Bucket>,
+    tokens: Option<Bucket>,
+    refilled_at: Instant,
+    paused_until: Option<Instant>,
+}
+
+impl BucketState {
+    fn refill(&mut self, now: Instant) {
+        let secs = now
+            .saturating_duration_since(self.refilled_at)
+            .as_secs_f64();
+        self.refilled_at = now;
+        for bucket in [&mut self.requests, &mut self.tokens].into_iter().flatten() {
+            bucket.refill(secs);
+        }
+    }
+
+    /// Take `tokens` (and one request) when available; otherwise how long to wait.
+    fn try_take(&mut self, now: Instant, tokens: f64) -> Duration {
+        self.refill(now);
+        if let Some(until) = self.paused_until {
+            if until > now {
+                return until.saturating_duration_since(now);
+            }
+            self.paused_until = None;
+        }
+        let wait = [
+            self.requests.as_ref().map(|b| b.wait_for(1.0)),
+            self.tokens.as_ref().map(|b| b.wait_for(tokens)),
+        ]
+        .into_iter()
+        .flatten()
+        .max()
+        .unwrap_or(Duration::ZERO);
+        if wait.is_zero() {
+            if let Some(b) = self.requests.as_mut() {
+                b.level -= 1.0;
+            }
+            if let Some(b) = self.tokens.as_mut() {
+                b.level -= b.clamp(tokens);
+            }
+        }
+        wait
+    }
+
+    fn pause_for(&mut self, now: Instant, after: Duration) {
+        let until = now.checked_add(after).unwrap_or(now);
+        self.paused_until = Some(self.paused_until.map_or(until, |p| p.max(until)));
+    }
+
+    #[allow(
+        clippy::cast_precision_loss,
+        reason = "header budgets are far below f64 precision"
+    )]
+    fn observe(&mut self, now: Instant, snapshot: &RateLimitSnapshot) {
+        self.refill(now);
+        if let (Some(b), Some(left)) = (self.requests.as_mut(), snapshot.remaining_requests) {
+            b.level = b.level.min(left as f64);
+        }
+        if let (Some(b), Some(left)) = (self.tokens.as_mut(), snapshot.remaining_tokens) {
+            b.level = b.level.min(left as f64);
+        }
+        let exhausted = [
+            (snapshot.remaining_requests, snapshot.reset_requests),
+            (snapshot.remaining_tokens, snapshot.reset_tokens),
+        ];
+        for (left, reset) in exhausted {
+            if let (Some(0), Some(reset)) = (left, reset) {
+                self.pause_for(now, reset);
+            }
+        }
+    }
+
+    /// Charge tokens used beyond the admission estimate.
+    fn charge(&mut self, estimated: u32, usage: &Usage) {
+        let actual = usage.input_tokens.saturating_add(usage.output_tokens);
+        if let Some(b) = self.tokens.as_mut()
+            && actual > estimated
+        {
+            b.level -= f64::from(actual.saturating_sub(estimated));
+        }
+    }
+}
+
+/// Per-model limiter: FIFO turnstile plus buckets.
+#[derive(Debug)]
+struct ModelLimiter {
+    model: String,
+    turnstile: tokio::sync::Mutex<()>,
+    state: Mutex<BucketState>,
+    waiting: AtomicU64,
+}
+
+impl ModelLimiter {
+    fn new(model: &str, limit: RateLimit) -> Self {
+        Self {
+            model: model.to_owned(),
+            turnstile: tokio::sync::Mutex::new(()),
+            state: Mutex::new(BucketState {
+                requests: limit.requests_per_minute.map(Bucket::new),
+                tokens: limit.tokens_per_minute.map(Bucket::new),
+                refilled_at: Instant::now(),
+                paused_until: None,
+            }),
+            waiting: AtomicU64::new(0),
+        }
+    }
+
+    fn with_state<T>(&self, f: impl FnOnce(&mut BucketState) -> T) -> T {
+        f(&mut self.state.lock().unwrap_or_else(PoisonError::into_inner))
+    }
+
+    async fn admit(
+        &self,
+        tokens: u32,
+        cancel: &CancellationToken,
+        metrics: &SharedMetrics,
+    ) -> Result<(), OvoError> {
+        let started = Instant::now();
+        let _queued = QueueSlot::enter(self, metrics);
+        let _turn = tokio::select! {
+            biased;
+            () = cancel.cancelled() => {
+                return Err(OvoError::llm_cancelled("sample cancelled while rate limited"));
+            }
+            turn = self.turnstile.lock() => turn,
+        };
+        loop {
+            let wait = self.with_state(|s| s.try_take(Instant::now(), f64::from(tokens)));
+            if wait.is_zero() {
+                break;
+            }
+            tokio::select! {
+                biased;
+                () = cancel.cancelled() => {
+                    return Err(OvoError::llm_cancelled("sample cancelled while rate limited"));
+                }
+                () = tokio::time::sleep(wait) => {}
+            }
+        }
+        record_rate_limit_wait(
+            metrics.as_ref(),
+            &self.model,
+            started.elapsed().as_secs_f64() * 1000.0,
+        );
+        Ok(())
+    }
+
+    fn observe_error(&self, err: &OvoError) {
+        if err.code() != ErrorCode::LlmRateLimit {
+            return;
+        }
+        let now = Instant::now();
+        self.with_state(|s| {
+            if let Some(after) = err.retry_after() {
+                s.pause_for(now, after);
+            } else {
+                s.refill(now);
+                for bucket in [&mut s.requests, &mut s.tokens].into_iter().flatten() {
+                    bucket.level = bucket.level.min(0.0);
+                }
+            }
+        });
+    }
+}
+
+/// Queue-depth bookkeeping that also unwinds on cancellation.
+struct QueueSlot<'a> {
+    limiter: &'a ModelLimiter,
+    metrics: &'a SharedMetrics,
+}
+
+impl<'a> QueueSlot<'a> {
+    fn enter(limiter: &'a ModelLimiter, metrics: &'a SharedMetrics) -> Self {
+        let depth = limiter
+            .waiting
+            .fetch_add(1, Ordering::SeqCst)
+            .saturating_add(1);
+        record_rate_limit_queue(metrics.as_ref(), &limiter.model, depth);
+        Self { limiter, metrics }
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        let depth = self
+            .limiter
+            .waiting
+            .fetch_sub(1, Ordering::SeqCst)
+            .saturating_sub(1);
+        record_rate_limit_queue(self.metrics.as_ref(), &self.limiter.model, depth);
+    }
+}
+
+/// Decorator enforcing per-model requests/tokens-per-minute budgets.
+#[derive(Clone)]
+pub struct RateLimitedSampler<S> {
+    inner: Arc<S>,
+    default_limit: RateLimit,
+    model_limits: HashMap<String, RateLimit>,
+    limiters: Arc<Mutex<HashMap<String, Arc<ModelLimiter>>>>,
+    metrics: SharedMetrics,
+}
+
+impl<S> std::fmt::Debug for RateLimitedSampler<S> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RateLimitedSampler")
+            .field("default_limit", &self.default_limit)
+            .field("model_limits", &self.model_limits)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<S> RateLimitedSampler<S> {
+    /// Wrap `inner`, applying `default_limit` to every model without an override.
+    #[must_use]
+    pub fn new(inner: Arc<S>, default_limit: RateLimit) -> Self {
+        Self {
+            inner,
+            default_limit,
+            model_limits: HashMap::new(),
+            limiters: Arc::new(Mutex::new(HashMap::new())),
+            metrics: Arc::new(NoopMetrics),
+        }
+    }
+
+    /// Budget for one exact model id.
+    #[must_use]
+    pub fn with_model_limit(mut self, model: impl Into<String>, limit: RateLimit) -> Self {
+        self.model_limits.insert(model.into(), limit);
+        self
+    }
+
+    /// Report queue depth and admission wait time.
+    #[must_use]
+    pub fn with_metrics(mut self, metrics: SharedMetrics) -> Self {
+        self.metrics = metrics;
+        self
+    }
+
+    /// Effective budget for `model`.
+    #[must_use]
+    pub fn limit_for(&self, model: &str) -> RateLimit {
+        self.model_limits
+            .get(model)
+            .copied()
+            .unwrap_or(self.default_limit)
+    }
+
+    /// Callers currently waiting for admission on `model`.
+    #[must_use]
+    pub fn queue_depth(&self, model: &str) -> u64 {
+        self.limiters
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .get(model)
+            .map_or(0, |l| l.waiting.load(Ordering::SeqCst))
+    }
+
+    fn limiter(&self, model: &str) -> Option<Arc<ModelLimiter>> {
+        let limit = self.limit_for(model);
+        if limit.is_unlimited() {
+            return None;
+        }
+        let mut limiters = self.limiters.lock().unwrap_or_else(PoisonError::into_inner);
+        Some(Arc::clone(limiters.entry(model.to_owned()).or_insert_with(
+            || Arc::new(ModelLimiter::new(model, limit)),
+        )))
+    }
+}
+
+#[async_trait]
+impl<S: LlmSampler + 'static> LlmSampler for RateLimitedSampler<S> {
+    async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+        let Some(limiter) = self.limiter(&request.model) else {
+            return self.inner.sample(request).await;
+        };
+        let estimated = estimate_request_tokens(&request);
+        limiter
+            .admit(estimated, &request.cancel, &self.metrics)
+            .await?;
+        match self.inner.sample(request).await {
+            Ok(response) => {
+                let now = Instant::now();
+                limiter.with_state(|s| {
+                    if let Some(snapshot) = &response.rate_limit {
+                        s.observe(now, snapshot);
+                    }
+                    s.charge(estimated, &response.usage);
+                });
+                Ok(response)
+            }
+            Err(e) => {
+                limiter.observe_error(&e);
+                Err(e)
+            }
+        }
+    }
+
+    async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+        let Some(limiter) = self.limiter(&request.model) else {
+            return self.inner.sample_stream(request).await;
+        };
+        let estimated = estimate_request_tokens(&request);
+        limiter
+            .admit(estimated, &request.cancel, &self.metrics)
+            .await?;
+        match self.inner.sample_stream(request).await {
+            Ok(inner) => Ok(Box::pin(ObservedStream {
+                inner,
+                limiter,
+                estimated,
+                usage: Usage::zero(),
+                charged: false,
+            })),
+            Err(e) => {
+                limiter.observe_error(&e);
+                Err(e)
+            }
+        }
+    }
+
+    fn capabilities(&self, model: &str) -> ModelCapabilities {
+        self.inner.capabilities(model)
+    }
+}
+
+/// Feeds rate-limit headers and final usage from a stream back to its bucket.
+struct ObservedStream {
+    inner: SampleStream,
+    limiter: Arc<ModelLimiter>,
+    estimated: u32,
+    usage: Usage,
+    charged: bool,
+}
+
+impl Stream for ObservedStream {
+    type Item = SampleEvent;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let polled = Pin::new(&mut self.inner).poll_next(cx);
+        match &polled {
+            Poll::Ready(Some(SampleEvent::RateLimit(snapshot))) => {
+                let now = Instant::now();
+                self.limiter.with_state(|s| s.observe(now, snapshot));
+            }
+            Poll::Ready(Some(SampleEvent::Usage(u))) => self.usage += *u,
+            Poll::Ready(None) if !self.charged => {
+                self.charged = true;
+                let (estimated, usage) = (self.estimated, self.usage);
+                self.limiter.with_state(|s| s.charge(estimated, &usage));
+            }
+            _ => {}
+        }
+        polled
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use ovo_types::{Message, SamplingParams};
+
+    use super::*;
+    use crate::mock::MockSampler;
+    use crate::sample::ToolChoice;
+
+    fn req(model: &str) -> SampleRequest {
+        SampleRequest {
+            model: model.into(),
+            messages: vec![Message::user("hello there")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: Some(16),
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[test]
+    fn parses_headers_and_go_durations() {
+        let headers = [
+            ("x-ratelimit-remaining-requests", "0"),
+            ("x-ratelimit-remaining-tokens", "1200"),
+            ("x-ratelimit-reset-requests", "1m30.5s"),
+            ("x-ratelimit-reset-tokens", "20ms"),
+        ];
+        let snap = parse_rate_limit_headers(|name| {
+            headers.iter().find(|(k, _)| *k == name).map(|(_, v)| *v)
+        })
+        .expect("snapshot");
+        assert_eq!(snap.remaining_requests, Some(0));
+        assert_eq!(snap.remaining_tokens, Some(1200));
+        assert_eq!(snap.reset_requests, Some(Duration::from_millis(90_500)));
+        assert_eq!(snap.reset_tokens, Some(Duration::from_millis(20)));
+        assert_eq!(parse_reset_duration("2"), Some(Duration::from_secs(2)));
+        assert_eq!(parse_reset_duration("soon"), None);
+        assert!(parse_rate_limit_headers(|_| None).is_none());
+    }
+
+    #[tokio::test]
+    async fn exhausted_headers_pause_the_next_caller() {
+        let mock = Arc::new(MockSampler::new());
+        mock.push(SampleResponse {
+            message: Message::assistant("first"),
+            usage: Usage::new(5, 5),
+            stop_reason: Some("stop".into()),
+            served_by: None,
+            rate_limit: Some(RateLimitSnapshot {
+                remaining_requests: Some(0),
+                reset_requests: Some(Duration::from_millis(60)),
+                ..RateLimitSnapshot::default()
+            }),
+        });
+        mock.push_text("second");
+        let limited = RateLimitedSampler::new(mock, RateLimit::unlimited())
+            .with_model_limit("m", RateLimit::unlimited().with_rpm(1000));
+        limited.sample(req("m")).await.expect("first");
+        let started = Instant::now();
+        limited.sample(req("m")).await.expect("second");
+        assert!(started.elapsed() >= Duration::from_millis(50));
+        assert_eq!(limited.queue_depth("m"), 0);
+    }
+
+    #[tokio::test]
+    async fn retry_after_pauses_and_cancel_leaves_queue() {
+        let mock = Arc::new(MockSampler::new());
+        mock.push_error(
+            OvoError::new(ErrorCode::LlmRateLimit, "429").with_retry_after(Duration::from_secs(30)),
+        );
+        let limited = Arc::new(RateLimitedSampler::new(
+            mock,
+            RateLimit::unlimited().with_tpm(100_000),
+        ));
+        let err = limited.sample(req("m")).await.expect_err("429");
+        assert_eq!(err.code(), ErrorCode::LlmRateLimit);
+
+        let request = req("m");
+        let cancel = request.cancel.clone();
+        let waiting = Arc::clone(&limited);
+        let task = tokio::spawn(async move { waiting.sample(request).await });
+        while limited.queue_depth("m") == 0 {
+            tokio::task::yield_now().await;
+        }
+        cancel.cancel();
+        let err = task.await.expect("join").expect_err("cancelled");
+        assert_eq!(err.code(), ErrorCode::LlmCancelled);
+        assert_eq!(limited.queue_depth("m"), 0);
+    }
+}
@@ -0,0 +1,48 @@
+//! Reasoning replay policy for wire builders.
+//!
+//! Parsers keep model reasoning on the assistant message as
+//! [`ContentPart::Reasoning`] so it survives in history. Whether it goes back
+//! to the provider depends on the API: Anthropic requires signed thinking
+//! blocks during tool use and always gets them; OpenAI-compatible servers
+//! (`reasoning_content`) and Ollama (`thinking`) follow a [`ReasoningReplay`]
+//! policy; Gemini and the Responses adapter never echo the text (they replay
+//! their own opaque signatures / encrypted items instead).
+
+use ovo_types::{ContentPart, Message, Role};
+use serde_json::{Value, json};
+
+/// Whether stored reasoning is sent back on assistant messages.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ReasoningReplay {
+    /// Drop reasoning from outgoing requests (default; some servers reject it).
+    #[default]
+    Strip,
+    /// Echo reasoning in the provider's assistant reasoning field.
+    Echo,
+}
+
+/// Append non-empty `text` to `message` as an unsigned reasoning part.
+pub(crate) fn attach_reasoning(message: &mut Message, text: String) {
+    if !text.is_empty() {
+        message.parts.push(ContentPart::Reasoning {
+            text,
+            signature: None,
+        });
+    }
+}
+
+/// Under [`ReasoningReplay::Echo`], set `field` on an assistant wire message to
+/// the reasoning stored on `msg`.
+pub(crate) fn echo_reasoning(
+    wire: &mut Value,
+    msg: &Message,
+    replay: ReasoningReplay,
+    field: &str,
+) {
+    if replay != ReasoningReplay::Echo || msg.role != Role::Assistant {
+        return;
+    }
+    if let (Some(text), Some(obj)) = (msg.reasoning(), wire.as_object_mut()) {
+        obj.insert(field.into(), json!(text));
+    }
+}
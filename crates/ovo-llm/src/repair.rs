@@ -0,0 +1,264 @@
+//! Lenient repair of malformed tool-call argument JSON.
+//!
+//! Small local models often emit `function.arguments` with trailing commas,
+//! single quotes, bare keys, Python literals, markdown fences or a truncated
+//! tail. [`parse_tool_arguments`] tries strict JSON first, then (in
+//! [`ArgumentRepair::Lenient`] mode) one repair pass. Arguments that still do
+//! not parse are kept as a raw string, which `ToolDispatch` rejects with
+//! `ToolInvalidArgs` so the model sees a structured error and can retry.
+
+use serde_json::{Value, json};
+
+/// Repair policy for tool-call arguments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum ArgumentRepair {
+    /// Strict JSON only.
+    Off,
+    /// Fix common defects before giving up (default).
+    #[default]
+    Lenient,
+}
+
+/// Outcome of [`parse_tool_arguments`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum ToolArguments {
+    /// Valid JSON as sent.
+    Parsed(Value),
+    /// Parsed after repair.
+    Repaired(Value),
+    /// Unparseable; carries the raw text.
+    Invalid(String),
+}
+
+impl ToolArguments {
+    /// True when a repair was applied.
+    #[must_use]
+    pub const fn was_repaired(&self) -> bool {
+        matches!(self, Self::Repaired(_))
+    }
+
+    /// Arguments value for [`ovo_types::ToolCall`] (raw string when invalid).
+    #[must_use]
+    pub fn into_value(self) -> Value {
+        match self {
+            Self::Parsed(v) | Self::Repaired(v) => v,
+            Self::Invalid(raw) => Value::String(raw),
+        }
+    }
+}
+
+/// Parse a provider `arguments` string, repairing it when `mode` allows.
+///
+/// Empty / whitespace-only input is `{}`.
+#[must_use]
+pub fn parse_tool_arguments(raw: &str, mode: ArgumentRepair) -> ToolArguments {
+    if raw.trim().is_empty() {
+        return ToolArguments::Parsed(json!({}));
+    }
+    if let Ok(v) = serde_json::from_str(raw) {
+        return ToolArguments::Parsed(v);
+    }
+    if mode == ArgumentRepair::Off {
+        return ToolArguments::Invalid(raw.to_owned());
+    }
+    repair_json(raw).map_or_else(
+        || ToolArguments::Invalid(raw.to_owned()),
+        ToolArguments::Repaired,
+    )
+}
+
+/// Best-effort repair of near-JSON text into a value.
+///
+/// Handles markdown fences and surrounding prose, single-quoted strings,
+/// unquoted keys, `True` / `False` / `None`, trailing commas, raw control
+/// characters inside strings, and unterminated strings / objects / arrays.
+#[must_use]
+pub fn repair_json(raw: &str) -> Option<Value> {
+    let body = strip_fences(raw.trim());
+    let start = body.find(['{', '['])?;
+    let text = body.get(start..)?;
+    let fixed = Repairer::default().run(text);
+    serde_json::from_str(&fixed).ok()
+}
+
+fn strip_fences(s: &str) -> &str {
+    let Some(rest) = s.strip_prefix("```") else {
+        return s;
+    };
+    let rest = rest.find('\n').and_then(|i| rest.get(i..)).unwrap_or(rest);
+    rest.trim_end().strip_suffix("```").unwrap_or(rest).trim()
+}
+
+#[derive(Default)]
+struct Repairer {
+    out: String,
+    /// Expected closers for open containers.
+    stack: Vec<char>,
+}
+
+impl Repairer {
+    fn run(mut self, text: &str) -> String {
+        let mut chars = text.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '"' | '\'' => self.string(c, &mut chars),
+                '{' => self.open(c, '}'),
+                '[' => self.open(c, ']'),
+                '}' | ']' => {
+                    if self.stack.last() == Some(&c) {
+                        self.drop_trailing_comma();
+                        self.stack.pop();
+                        self.out.push(c);
+                    }
+                    if self.stack.is_empty() {
+                        break;
+                    }
+                }
+                c if c.is_alphabetic() || c == '_' => {
+                    let mut word = String::from(c);
+                    while let Some(&n) = chars.peek() {
+                        if n.is_alphanumeric() || n == '_' {
+                            word.push(n);
+                            chars.next();
+                        } else {
+                            break;
+                        }
+                    }
+                    self.word(&word);
+                }
+                c => self.out.push(c),
+            }
+        }
+        self.finish()
+    }
+
+    fn open(&mut self, c: char, closer: char) {
+        self.stack.push(closer);
+        self.out.push(c);
+    }
+
+    fn string(&mut self, quote: char, chars: &mut std::iter::Peekable<std::str::Chars<'_>>) {
+        self.out.push('"');
+        while let Some(c) = chars.next() {
+            match c {
+                '\\' => {
+                    if let Some(n) = chars.next() {
+                        if n == '\'' {
+                            self.out.push('\'');
+                        } else {
+                            self.out.push('\\');
+                            self.out.push(n);
+                        }
+                    }
+                }
+                c if c == quote => {
+                    self.out.push('"');
+                    return;
+                }
+                '"' => self.out.push_str("\\\""),
+                '\n' => self.out.push_str("\\n"),
+                '\r' => self.out.push_str("\\r"),
+                '\t' => self.out.push_str("\\t"),
+                c => self.out.push(c),
+            }
+        }
+        // Truncated inside a string.
+        self.out.push('"');
+    }
+
+    fn word(&mut self, word: &str) {
+        let literal = match word {
+            "true" | "True" => "true",
+            "false" | "False" => "false",
+            "null" | "None" => "null",
+            _ => {
+                self.out.push('"');
+                self.out.push_str(word);
+                self.out.push('"');
+                return;
+            }
+        };
+        self.out.push_str(literal);
+    }
+
+    fn drop_trailing_comma(&mut self) {
+        let trimmed = self.out.trim_end().len();
+        self.out.truncate(trimmed);
+        if self.out.ends_with(',') {
+            self.out.pop();
+        }
+    }
+
+    fn finish(mut self) -> String {
+        self.drop_trailing_comma();
+        if self.out.ends_with(':') {
+            self.out.push_str("null");
+        }
+        while let Some(closer) = self.stack.pop() {
+            self.drop_trailing_comma();
+            self.out.push(closer);
+        }
+        self.out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lenient(raw: &str) -> ToolArguments {
+        parse_tool_arguments(raw, ArgumentRepair::Lenient)
+    }
+
+    #[test]
+    fn valid_json_is_not_marked_repaired() {
+        assert_eq!(
+            lenient(r#"{"expr":"1+2"}"#),
+            ToolArguments::Parsed(json!({"expr": "1+2"}))
+        );
+        assert_eq!(lenient("  "), ToolArguments::Parsed(json!({})));
+    }
+
+    #[test]
+    fn repairs_common_defects() {
+        let cases = [
+            (r#"{"a": 1, "b": [1, 2,],}"#, json!({"a": 1, "b": [1, 2]})),
+            (
+                "{'path': 'it\\'s \"here\"'}",
+                json!({"path": "it's \"here\""}),
+            ),
+            (
+                "{path: 'x', recursive: True, depth: None}",
+                json!({"path": "x", "recursive": true, "depth": null}),
+            ),
+            (
+                r#"{"cmd": "ls", "args": ["-l", "-a"#,
+                json!({"cmd": "ls", "args": ["-l", "-a"]}),
+            ),
+            (r#"{"a": {"b": 1"#, json!({"a": {"b": 1}})),
+            (r#"{"note": "line1"#, json!({"note": "line1"})),
+            ("{\"a\":", json!({"a": null})),
+            ("```json\n{\"a\": 1,}\n```", json!({"a": 1})),
+            (
+                "Sure! Here you go: {\"a\": 1} hope that helps",
+                json!({"a": 1}),
+            ),
+            ("{\"text\": \"two\nlines\"}", json!({"text": "two\nlines"})),
+        ];
+        for (raw, want) in cases {
+            let got = lenient(raw);
+            assert!(got.was_repaired(), "{raw:?} -> {got:?}");
+            assert_eq!(got.into_value(), want, "{raw:?}");
+        }
+    }
+
+    #[test]
+    fn off_mode_and_hopeless_input_stay_raw() {
+        assert_eq!(
+            parse_tool_arguments("{'a': 1}", ArgumentRepair::Off),
+            ToolArguments::Invalid("{'a': 1}".into())
+        );
+        let got = lenient("not json at all");
+        assert_eq!(got.into_value(), json!("not json at all"));
+    }
+}
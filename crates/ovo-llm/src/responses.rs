@@ -0,0 +1,760 @@
+//! Responses API (`/v1/responses`) wire format (build + parse + stream).
+//!
+//! Pure helpers are always available; the live [`ResponsesSampler`] is behind
+//! the `openai` feature.
+//!
+//! Requests are stateless (`store: false`) and ask for
+//! `reasoning.encrypted_content`. Returned reasoning items are kept verbatim on
+//! the assistant message as [`ContentPart::Opaque`] parts tagged
+//! [`RESPONSES_PROVIDER`], and replayed ahead of that message's text and
+//! function calls on the next request.
+
+use ovo_tools::ToolDefinition;
+use ovo_types::{ContentPart, ErrorCode, Message, OvoError, Role, ToolCall, ToolCallId, Usage};
+use serde_json::{Value, json};
+
+use crate::sample::{SampleRequest, SampleResponse, SamplingDialect, ToolChoice, sampling_fields};
+use crate::stream::SampleEvent;
+
+/// [`ContentPart::Opaque`] provider tag for Responses API output items.
+pub const RESPONSES_PROVIDER: &str = "openai_responses";
+
+/// Configuration for a Responses API endpoint.
+#[derive(Debug, Clone)]
+pub struct ResponsesConfig {
+    /// Base URL without trailing slash (e.g. `https://api.openai.com`).
+    pub base_url: String,
+    /// API key sent as `Authorization: Bearer …` when non-empty.
+    pub api_key: String,
+    /// Path under base URL (default `/v1/responses`).
+    pub responses_path: String,
+}
+
+impl ResponsesConfig {
+    /// Responses API at `{base_url}/v1/responses`.
+    #[must_use]
+    pub fn new(base_url: impl Into<String>, api_key: impl Into<String>) -> Self {
+        let mut base_url = base_url.into();
+        while base_url.ends_with('/') {
+            base_url.pop();
+        }
+        Self {
+            base_url,
+            api_key: api_key.into(),
+            responses_path: "/v1/responses".into(),
+        }
+    }
+
+    /// Full request URL.
+    #[must_use]
+    pub fn responses_url(&self) -> String {
+        format!("{}{}", self.base_url, self.responses_path)
+    }
+}
+
+/// Build the Responses API JSON body from a kernel [`SampleRequest`].
+#[must_use]
+pub fn build_responses_body(req: &SampleRequest) -> Value {
+    let mut input: Vec<Value> = Vec::new();
+    for msg in &req.messages {
+        push_input_items(msg, &mut input);
+    }
+    let mut map = serde_json::Map::new();
+    map.insert("model".into(), json!(req.model));
+    map.insert("input".into(), Value::Array(input));
+    map.insert("store".into(), json!(false));
+    map.insert("include".into(), json!(["reasoning.encrypted_content"]));
+    if !req.tools.is_empty() {
+        map.insert(
+            "tools".into(),
+            Value::Array(req.tools.iter().map(tool_to_responses).collect()),
+        );
+        map.insert(
+            "tool_choice".into(),
+            tool_choice_to_responses(&req.tool_choice),
+        );
+    }
+    if let Some(max) = req.max_output_tokens {
+        map.insert("max_output_tokens".into(), json!(max));
+    }
+    // Responses has no seed / stop / penalty parameters.
+    map.extend(
+        sampling_fields(&req.sampling, SamplingDialect::Responses).map(|(k, v)| (k.to_owned(), v)),
+    );
+    if let Some(effort) = req.sampling.reasoning_effort {
+        map.insert("reasoning".into(), json!({ "effort": effort.as_str() }));
+    }
+    if let Some(fmt) = &req.response_format {
+        map.insert("text".into(), json!({ "format": text_format(fmt) }));
+    }
+    Value::Object(map)
+}
+
+/// Build the streaming Responses body (`stream: true`).
+#[must_use]
+pub fn build_responses_stream_body(req: &SampleRequest) -> Value {
+    let mut body = build_responses_body(req);
+    if let Some(obj) = body.as_object_mut() {
+        obj.insert("stream".into(), json!(true));
+    }
+    body
+}
+
+/// Raw JSON Schema, a Chat Completions envelope, or `json_object` → `text.format`.
+fn text_format(fmt: &Value) -> Value {
+    match fmt.get("type").and_then(Value::as_str) {
+        Some("json_object") => json!({ "type": "json_object" }),
+        Some("json_schema") => {
+            let inner = fmt.get("json_schema").unwrap_or(fmt);
+            let mut out = serde_json::Map::new();
+            out.insert("type".into(), json!("json_schema"));
+            out.insert(
+                "name".into(),
+                inner
+                    .get("name")
+                    .cloned()
+                    .unwrap_or_else(|| json!("ovo_output")),
+            );
+            if let Some(schema) = inner.get("schema") {
+                out.insert("schema".into(), schema.clone());
+            }
+            if let Some(strict) = inner.get("strict") {
+                out.insert("strict".into(), strict.clone());
+            }
+            Value::Object(out)
+        }
+        _ => json!({
+            "type": "json_schema",
+            "name": "ovo_output",
+            "strict": true,
+            "schema": fmt,
+        }),
+    }
+}
+
+fn push_input_items(msg: &Message, input: &mut Vec<Value>) {
+    match msg.role {
+        Role::Tool => {
+            if let Some(id) = &msg.tool_call_id {
+                input.push(json!({
+                    "type": "function_call_output",
+                    "call_id": id.as_str(),
+                    "output": msg.text(),
+                }));
+            }
+        }
+        Role::Assistant => {
+            // Reasoning must precede the output it produced.
+            for part in &msg.parts {
+                if let ContentPart::Opaque { provider, data } = part
+                    && provider == RESPONSES_PROVIDER
+                    && let Ok(item) = serde_json::from_str::<Value>(data)
+                {
+                    input.push(item);
+                }
+            }
+            let text = msg.text();
+            if !text.is_empty() {
+                input.push(json!({ "role": "assistant", "content": text }));
+            }
+            for tc in &msg.tool_calls {
+                let args = match &tc.arguments {
+                    Value::String(s) => s.clone(),
+                    other => other.to_string(),
+                };
+                input.push(json!({
+                    "type": "function_call",
+                    "call_id": tc.id.as_str(),
+                    "name": tc.name,
+                    "arguments": args,
+                }));
+            }
+        }
+        role => input.push(json!({ "role": role.as_str(), "content": msg.text() })),
+    }
+}
+
+fn tool_to_responses(tool: &ToolDefinition) -> Value {
+    json!({
+        "type": "function",
+        "name": tool.name,
+        "description": tool.description,
+        "parameters": tool.parameters,
+    })
+}
+
+fn tool_choice_to_responses(choice: &ToolChoice) -> Value {
+    match choice {
+        ToolChoice::Auto => json!("auto"),
+        ToolChoice::None => json!("none"),
+        ToolChoice::Required => json!("required"),
+        ToolChoice::Named(name) => json!({ "type": "function", "name": name }),
+    }
+}
+
+/// Parse a Responses API `response` object into a kernel [`SampleResponse`].
+///
+/// Reasoning items become [`ContentPart::Opaque`] parts; when any are present
+/// the assistant text is stored as a [`ContentPart::Text`] part as well.
+///
+/// # Errors
+///
+/// Returns [`OvoError`] for an in-band `error`, a missing `output` array, or
+/// malformed function calls.
+pub fn parse_responses_response(body: &Value) -> Result<SampleResponse, OvoError> {
+    if let Some(err) = body.get("error").filter(|e| !e.is_null()) {
+        return Err(responses_error(err));
+    }
+    let output = body
+        .get("output")
+        .and_then(Value::as_array)
+        .ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "responses payload missing output",
+            )
+        })?;
+    let mut text = String::new();
+    let mut calls = Vec::new();
+    let mut opaque = Vec::new();
+    for item in output {
+        match item.get("type").and_then(Value::as_str) {
+            Some("reasoning") => opaque.push(ContentPart::Opaque {
+                provider: RESPONSES_PROVIDER.to_owned(),
+                data: item.to_string(),
+            }),
+            Some("message") => {
+                let parts = item
+                    .get("content")
+                    .and_then(Value::as_array)
+                    .map_or(&[][..], Vec::as_slice);
+                for part in parts {
+                    let piece = match part.get("type").and_then(Value::as_str) {
+                        Some("output_text") => part.get("text"),
+                        Some("refusal") => part.get("refusal"),
+                        _ => None,
+                    };
+                    if let Some(piece) = piece.and_then(Value::as_str) {
+                        text.push_str(piece);
+                    }
+                }
+            }
+            Some("function_call") => calls.push(parse_function_call(item)?),
+            _ => {}
+        }
+    }
+    let message = if opaque.is_empty() {
+        if calls.is_empty() {
+            Message::assistant(text)
+        } else {
+            let mut m = Message::assistant_tools(calls);
+            if !text.is_empty() {
+                m.content = Some(text);
+            }
+            m
+        }
+    } else {
+        let mut m = Message::assistant_tools(calls);
+        m.parts = opaque;
+        if !text.is_empty() {
+            m.parts.push(ContentPart::Text { text });
+        }
+        m
+    };
+    let stop_reason = match body.get("status").and_then(Value::as_str) {
+        Some("incomplete") => body
+            .pointer("/incomplete_details/reason")
+            .and_then(Value::as_str)
+            .or(Some("incomplete")),
+        other => other,
+    };
+    Ok(SampleResponse {
+        message,
+        usage: parse_usage(body.get("usage")),
+        stop_reason: stop_reason.map(str::to_owned),
+        served_by: None,
+        rate_limit: None,
+    })
+}
+
+fn parse_function_call(item: &Value) -> Result<ToolCall, OvoError> {
+    let id = item.get("call_id").and_then(Value::as_str).ok_or_else(|| {
+        OvoError::new(
+            ErrorCode::LlmInvalidResponse,
+            "function_call missing call_id",
+        )
+    })?;
+    let name = item.get("name").and_then(Value::as_str).ok_or_else(|| {
+        OvoError::new(ErrorCode::LlmInvalidResponse, "function_call missing name")
+    })?;
+    let arguments = match item.get("arguments").cloned().unwrap_or(json!("{}")) {
+        Value::String(s) => serde_json::from_str(&s).unwrap_or(Value::String(s)),
+        other => other,
+    };
+    Ok(ToolCall {
+        id: ToolCallId::new(id)?,
+        name: name.to_owned(),
+        arguments,
+    })
+}
+
+fn parse_usage(raw: Option<&Value>) -> Usage {
+    let Some(u) = raw else {
+        return Usage::zero();
+    };
+    let field = |ptr: &str| {
+        u.pointer(ptr)
+            .and_then(Value::as_u64)
+            .and_then(|n| u32::try_from(n).ok())
+            .unwrap_or(0)
+    };
+    let mut usage = Usage::new(field("/input_tokens"), field("/output_tokens"));
+    let total = field("/total_tokens");
+    if total > 0 {
+        usage.total_tokens = total;
+    }
+    let cached = field("/input_tokens_details/cached_tokens");
+    usage.cache_read_tokens = cached;
+    usage.prompt_details.cached_tokens = cached;
+    let reasoning = field("/output_tokens_details/reasoning_tokens");
+    usage.reasoning_tokens = reasoning;
+    usage.completion_details.reasoning_tokens = reasoning;
+    usage
+}
+
+fn responses_error(err: &Value) -> OvoError {
+    let msg = err
+        .get("message")
+        .and_then(Value::as_str)
+        .map_or_else(|| err.to_string(), str::to_owned);
+    let code = match err.get("code").and_then(Value::as_str) {
+        Some("rate_limit_exceeded") => ErrorCode::LlmRateLimit,
+        _ => ErrorCode::LlmProvider,
+    };
+    OvoError::new(code, format!("responses error: {msg}"))
+}
+
+/// Accumulates Responses API stream events into [`SampleEvent`]s.
+///
+/// Text and reasoning-summary deltas are surfaced as they arrive; the final
+/// message, usage and reasoning items come from `response.completed` (or
+/// `response.incomplete`).
+#[derive(Debug, Default)]
+pub struct ResponsesStream {
+    /// `output_index` of each function call, in arrival order.
+    call_indices: Vec<u64>,
+    response: Option<SampleResponse>,
+}
+
+impl ResponsesStream {
+    /// Empty accumulator.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// True once a terminal `response.completed` / `response.incomplete` was seen.
+    #[must_use]
+    pub const fn is_done(&self) -> bool {
+        self.response.is_some()
+    }
+
+    /// Decode one SSE `data:` payload.
+    ///
+    /// # Errors
+    ///
+    /// Invalid JSON yields [`ErrorCode::LlmInvalidResponse`]; `error` and
+    /// `response.failed` events yield [`ErrorCode::LlmProvider`] (or
+    /// [`ErrorCode::LlmRateLimit`]).
+    pub fn push_data(&mut self, data: &str) -> Result<Vec<SampleEvent>, OvoError> {
+        let event: Value = serde_json::from_str(data).map_err(|e| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                format!("invalid responses stream event: {e}"),
+            )
+        })?;
+        self.push_event(&event)
+    }
+
+    /// Apply one parsed event.
+    ///
+    /// # Errors
+    ///
+    /// Same as [`Self::push_data`].
+    pub fn push_event(&mut self, event: &Value) -> Result<Vec<SampleEvent>, OvoError> {
+        let delta = || {
+            event
+                .get("delta")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned()
+        };
+        let out = match event.get("type").and_then(Value::as_str) {
+            Some("response.output_text.delta") => vec![SampleEvent::TextDelta { text: delta() }],
+            Some("response.reasoning_summary_text.delta") => {
+                vec![SampleEvent::ReasoningDelta { text: delta() }]
+            }
+            Some("response.output_item.added") => {
+                if event.pointer("/item/type").and_then(Value::as_str) == Some("function_call") {
+                    let idx = event.get("output_index").and_then(Value::as_u64);
+                    self.call_indices.push(idx.unwrap_or(u64::MAX));
+                }
+                Vec::new()
+            }
+            Some("response.function_call_arguments.delta") => {
+                let output_index = event.get("output_index").and_then(Value::as_u64);
+                let index = self
+                    .call_indices
+                    .iter()
+                    .position(|i| Some(*i) == output_index)
+                    .unwrap_or(self.call_indices.len().saturating_sub(1));
+                vec![SampleEvent::ToolCallDelta {
+                    index: u32::try_from(index).unwrap_or(u32::MAX),
+                    arguments_delta: delta(),
+                }]
+            }
+            Some("response.completed" | "response.incomplete") => {
+                let response = event.get("response").ok_or_else(|| {
+                    OvoError::new(
+                        ErrorCode::LlmInvalidResponse,
+                        "responses terminal event missing response",
+                    )
+                })?;
+                self.response = Some(parse_responses_response(response)?);
+                Vec::new()
+            }
+            Some("response.failed") => {
+                let err = event
+                    .pointer("/response/error")
+                    .cloned()
+                    .unwrap_or(Value::Null);
+                return Err(responses_error(&err));
+            }
+            Some("error") => return Err(responses_error(event)),
+            _ => Vec::new(),
+        };
+        Ok(out)
+    }
+
+    /// Terminal events: `ToolCalls` (when any), `Usage`, `Completed`.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::LlmInvalidResponse`] when the stream ended before a
+    /// terminal response event.
+    pub fn finish(self) -> Result<Vec<SampleEvent>, OvoError> {
+        let response = self.response.ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "responses stream ended before response.completed",
+            )
+        })?;
+        let mut out = Vec::with_capacity(3);
+        if !response.message.tool_calls.is_empty() {
+            out.push(SampleEvent::ToolCalls {
+                message: response.message.clone(),
+            });
+        }
+        out.push(SampleEvent::Usage(response.usage));
+        out.push(SampleEvent::Completed {
+            message: response.message,
+            stop_reason: response.stop_reason,
+            served_by: None,
+        });
+        Ok(out)
+    }
+}
+
+#[cfg(feature = "openai")]
+mod client {
+    use async_trait::async_trait;
+    use ovo_types::{ErrorCode, OvoError};
+    use tracing::{Instrument, info_span};
+
+    use super::{
+        ResponsesConfig, ResponsesStream, build_responses_body, build_responses_stream_body,
+        parse_responses_response,
+    };
+    use crate::http::{
+        ChunkDecoder, body_stream, precheck, rate_limit_from_headers, read_text, send_checked,
+    };
+    use crate::sample::{SampleRequest, SampleResponse};
+    use crate::sampler::LlmSampler;
+    use crate::sse::SseDecoder;
+    use crate::stream::{SampleEvent, SampleStream};
+
+    /// HTTP client for the Responses API (`/v1/responses`).
+    #[derive(Debug, Clone)]
+    pub struct ResponsesSampler {
+        config: ResponsesConfig,
+        client: reqwest::Client,
+    }
+
+    impl ResponsesSampler {
+        /// Create a sampler with a default `reqwest` client.
+        ///
+        /// # Errors
+        ///
+        /// Returns an error when the HTTP client cannot be built.
+        pub fn new(config: ResponsesConfig) -> Result<Self, OvoError> {
+            let client = reqwest::Client::builder().build().map_err(|e| {
+                OvoError::new(ErrorCode::LlmProvider, format!("http client build: {e}"))
+            })?;
+            Ok(Self { config, client })
+        }
+
+        /// Create with an existing client.
+        #[must_use]
+        pub fn with_client(config: ResponsesConfig, client: reqwest::Client) -> Self {
+            Self { config, client }
+        }
+
+        fn post(&self, body: &serde_json::Value) -> reqwest::RequestBuilder {
+            let mut req = self.client.post(self.config.responses_url()).json(body);
+            if !self.config.api_key.is_empty() {
+                req = req.bearer_auth(&self.config.api_key);
+            }
+            req
+        }
+    }
+
+    #[async_trait]
+    impl LlmSampler for ResponsesSampler {
+        async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+            precheck(&request)?;
+            let body = build_responses_body(&request);
+            let span = info_span!(
+                "ovo.sample.http",
+                ovo.model = %request.model,
+                ovo.provider = "openai_responses",
+            );
+            async move {
+                let response =
+                    send_checked("openai-responses", self.post(&body), &request.cancel).await?;
+                let rate_limit = rate_limit_from_headers(&response);
+                let text = read_text(response, &request.cancel).await?;
+                let value: serde_json::Value = serde_json::from_str(&text).map_err(|e| {
+                    OvoError::new(
+                        ErrorCode::LlmInvalidResponse,
+                        format!("invalid JSON body: {e}"),
+                    )
+                })?;
+                let mut response = parse_responses_response(&value)?;
+                response.rate_limit = rate_limit;
+                Ok(response)
+            }
+            .instrument(span)
+            .await
+        }
+
+        async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, OvoError> {
+            precheck(&request)?;
+            let body = build_responses_stream_body(&request);
+            let span = info_span!(
+                "ovo.sample.http",
+                ovo.model = %request.model,
+                ovo.provider = "openai_responses",
+                ovo.stream = true,
+            );
+            let response = send_checked("openai-responses", self.post(&body), &request.cancel)
+                .instrument(span)
+                .await?;
+            Ok(body_stream(
+                response,
+                request.cancel,
+                SseResponsesDecoder::default(),
+            ))
+        }
+    }
+
+    /// SSE framing + [`ResponsesStream`] mapping; the terminal response event ends the stream.
+    #[derive(Debug, Default)]
+    struct SseResponsesDecoder {
+        sse: SseDecoder,
+        acc: ResponsesStream,
+    }
+
+    impl ChunkDecoder for SseResponsesDecoder {
+        fn push(&mut self, chunk: &[u8]) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            for ev in self.sse.push(chunk) {
+                if self.acc.is_done() {
+                    break;
+                }
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            Ok(out)
+        }
+
+        fn is_done(&self) -> bool {
+            self.acc.is_done()
+        }
+
+        fn finish(&mut self) -> Result<Vec<SampleEvent>, OvoError> {
+            let mut out = Vec::new();
+            if !self.acc.is_done()
+                && let Some(ev) = self.sse.finish()
+            {
+                out.extend(self.acc.push_data(&ev.data)?);
+            }
+            out.extend(std::mem::take(&mut self.acc).finish()?);
+            Ok(out)
+        }
+    }
+}
+
+#[cfg(feature = "openai")]
+pub use client::ResponsesSampler;
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use tokio_util::sync::CancellationToken;
+
+    use ovo_types::SamplingParams;
+
+    use super::*;
+
+    fn req(messages: Vec<Message>) -> SampleRequest {
+        SampleRequest {
+            model: "o-test".into(),
+            messages,
+            tools: vec![ToolDefinition {
+                name: "lookup".into(),
+                description: "look things up".into(),
+                parameters: json!({"type": "object"}),
+            }],
+            tool_choice: ToolChoice::Named("lookup".into()),
+            response_format: None,
+            max_output_tokens: Some(64),
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    fn reasoning_response() -> Value {
+        json!({
+            "status": "completed",
+            "output": [
+                {"type": "reasoning", "id": "rs_1", "summary": [], "encrypted_content": "gAAA"},
+                {"type": "function_call", "id": "fc_1", "call_id": "call_1", "name": "lookup", "arguments": "{\"q\":\"x\"}"}
+            ],
+            "usage": {
+                "input_tokens": 12,
+                "input_tokens_details": {"cached_tokens": 4},
+                "output_tokens": 30,
+                "output_tokens_details": {"reasoning_tokens": 20},
+                "total_tokens": 42
+            }
+        })
+    }
+
+    #[test]
+    fn parse_keeps_reasoning_items_and_usage_details() {
+        let resp = parse_responses_response(&reasoning_response()).expect("parse");
+        let call = resp.message.tool_calls.first().expect("call");
+        assert_eq!(call.id.as_str(), "call_1");
+        assert_eq!(call.arguments, json!({"q": "x"}));
+        assert!(matches!(
+            resp.message.parts.first(),
+            Some(ContentPart::Opaque { provider, data })
+                if provider == RESPONSES_PROVIDER && data.contains("gAAA")
+        ));
+        assert_eq!(resp.usage.completion_details.reasoning_tokens, 20);
+        assert_eq!(resp.usage.prompt_details.cached_tokens, 4);
+        assert_eq!(resp.usage.total_tokens, 42);
+        assert_eq!(resp.stop_reason.as_deref(), Some("completed"));
+    }
+
+    #[test]
+    fn reasoning_items_are_replayed_before_function_calls() {
+        let assistant = parse_responses_response(&reasoning_response())
+            .expect("parse")
+            .message;
+        let id = ToolCallId::new("call_1").expect("id");
+        let body = build_responses_body(&req(vec![
+            Message::system("sys"),
+            Message::user("go"),
+            assistant,
+            Message::tool_result(id, "lookup", "42"),
+        ]));
+        let input = body.get("input").and_then(Value::as_array).expect("input");
+        let kinds: Vec<&str> = input
+            .iter()
+            .map(|i| {
+                i.get("type")
+                    .or_else(|| i.get("role"))
+                    .and_then(Value::as_str)
+                    .unwrap_or_default()
+            })
+            .collect();
+        assert_eq!(
+            kinds,
+            [
+                "system",
+                "user",
+                "reasoning",
+                "function_call",
+                "function_call_output"
+            ]
+        );
+        assert_eq!(
+            input.get(2).and_then(|i| i.get("encrypted_content")),
+            Some(&json!("gAAA"))
+        );
+        assert_eq!(
+            input.get(3).and_then(|i| i.get("arguments")),
+            Some(&json!("{\"q\":\"x\"}"))
+        );
+        assert_eq!(body.get("store"), Some(&json!(false)));
+        assert_eq!(
+            body.get("tool_choice"),
+            Some(&json!({"type": "function", "name": "lookup"}))
+        );
+        assert_eq!(body.pointer("/tools/0/name"), Some(&json!("lookup")));
+    }
+
+    #[test]
+    fn stream_maps_deltas_and_terminal_response() {
+        let mut acc = ResponsesStream::new();
+        let mut out = Vec::new();
+        for ev in [
+            json!({"type": "response.reasoning_summary_text.delta", "delta": "think"}),
+            json!({"type": "response.output_text.delta", "delta": "Hi"}),
+            json!({"type": "response.output_item.added", "output_index": 2, "item": {"type": "function_call"}}),
+            json!({"type": "response.function_call_arguments.delta", "output_index": 2, "delta": "{}"}),
+        ] {
+            out.extend(acc.push_event(&ev).expect("event"));
+        }
+        assert!(!acc.is_done());
+        acc.push_event(&json!({"type": "response.completed", "response": reasoning_response()}))
+            .expect("completed");
+        assert!(acc.is_done());
+        assert!(matches!(
+            out.as_slice(),
+            [
+                SampleEvent::ReasoningDelta { .. },
+                SampleEvent::TextDelta { .. },
+                SampleEvent::ToolCallDelta { index: 0, .. }
+            ]
+        ));
+        let tail = acc.finish().expect("finish");
+        assert!(matches!(tail.first(), Some(SampleEvent::ToolCalls { .. })));
+        assert!(matches!(tail.last(), Some(SampleEvent::Completed { .. })));
+    }
+
+    #[test]
+    fn stream_without_terminal_event_fails() {
+        let mut acc = ResponsesStream::new();
+        acc.push_event(&json!({"type": "response.output_text.delta", "delta": "x"}))
+            .expect("delta");
+        let err = acc.finish().expect_err("truncated");
+        assert_eq!(err.code(), ErrorCode::LlmInvalidResponse);
+        let err = ResponsesStream::new()
+            .push_event(&json!({"type": "error", "code": "rate_limit_exceeded", "message": "slow"}))
+            .expect_err("error event");
+        assert_eq!(err.code(), ErrorCode::LlmRateLimit);
+    }
+}
@@ -15,7 +15,7 @@
 use tokio_util::sync::CancellationToken;
 
 use crate::retry::{RetryContext, RetryDecision, RetryPolicy, decide_retry, is_empty_response};
-use crate::sample::{SampleRequest, SampleResponse};
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
 use crate::sampler::LlmSampler;
 use crate::stream::{SampleEvent, SampleStream};
 
@@ -149,9 +149,13 @@ async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, Ov
             }
         }
     }
+
+    fn capabilities(&self, model: &str) -> ModelCapabilities {
+        self.inner.capabilities(model)
+    }
 }
 
-fn retry_ctx(attempt: u32, rate_limit_retries: u32, err: &OvoError) -> RetryContext {
+pub(crate) fn retry_ctx(attempt: u32, rate_limit_retries: u32, err: &OvoError) -> RetryContext {
     RetryContext {
         attempt,
         rate_limit_retries,
@@ -239,7 +243,7 @@ fn idle_timeout_stream(
 #[cfg(test)]
 #[allow(clippy::expect_used, reason = "unit tests")]
 mod tests {
-    use ovo_types::Message;
+    use ovo_types::{Message, SamplingParams};
 
     use super::*;
     use crate::mock::MockSampler;
@@ -254,9 +258,10 @@ fn req(text: &str) -> SampleRequest {
             tool_choice: ToolChoice::Auto,
             response_format: None,
             max_output_tokens: None,
-            temperature: None,
+            sampling: SamplingParams::default(),
             cancel: CancellationToken::new(),
             deadline: None,
+            cacheable: false,
         }
     }
 
@@ -0,0 +1,272 @@
+//! [`RoutingSampler`]: dispatch on [`SampleRequest::model`] to different backends.
+//!
+//! Lets one host mix providers: per-spawn model overrides pick the backend as
+//! well as the model string. Resolution order: alias → first matching route
+//! (insertion order) → default.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use ovo_types::{ErrorCode, OvoError};
+
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
+use crate::sampler::LlmSampler;
+use crate::stream::SampleStream;
+
+/// How a route matches model ids.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum ModelPattern {
+    /// Glob with `*` (any run) and `?` (one char); the model is forwarded unchanged.
+    Glob(String),
+    /// Literal prefix, stripped before forwarding (`ollama/` + `llama3` → `llama3`).
+    Prefix(String),
+}
+
+impl ModelPattern {
+    /// Forwarded model id when `model` matches.
+    #[must_use]
+    pub fn apply(&self, model: &str) -> Option<String> {
+        match self {
+            Self::Glob(pattern) => glob_match(pattern, model).then(|| model.to_owned()),
+            Self::Prefix(prefix) => model
+                .strip_prefix(prefix.as_str())
+                .filter(|rest| !rest.is_empty())
+                .map(str::to_owned),
+        }
+    }
+}
+
+/// Iterative glob matcher (`*`, `?`); no character classes.
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let p: Vec<char> = pattern.chars().collect();
+    let t: Vec<char> = text.chars().collect();
+    let (mut pi, mut ti) = (0usize, 0usize);
+    let mut star: Option<(usize, usize)> = None;
+    while ti < t.len() {
+        match (p.get(pi), t.get(ti)) {
+            (Some('*'), _) => {
+                star = Some((pi, ti));
+                pi = pi.saturating_add(1);
+            }
+            (Some('?'), _) => {
+                pi = pi.saturating_add(1);
+                ti = ti.saturating_add(1);
+            }
+            (Some(pc), Some(tc)) if pc == tc => {
+                pi = pi.saturating_add(1);
+                ti = ti.saturating_add(1);
+            }
+            _ => match star {
+                Some((sp, st)) => {
+                    pi = sp.saturating_add(1);
+                    ti = st.saturating_add(1);
+                    star = Some((sp, ti));
+                }
+                None => return false,
+            },
+        }
+    }
+    p.get(pi..)
+        .is_some_and(|rest| rest.iter().all(|c| *c == '*'))
+}
+
+struct Route {
+    pattern: ModelPattern,
+    sampler: Arc<dyn LlmSampler>,
+}
+
+/// Sampler that picks a backend per request from the model id
+/// (e.g. `ollama/*` → local, `gpt-*` → Chat Completions).
+#[derive(Default)]
+pub struct RoutingSampler {
+    routes: Vec<Route>,
+    aliases: HashMap<String, String>,
+    default: Option<Arc<dyn LlmSampler>>,
+}
+
+impl fmt::Debug for RoutingSampler {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RoutingSampler")
+            .field(
+                "routes",
+                &self.routes.iter().map(|r| &r.pattern).collect::<Vec<_>>(),
+            )
+            .field("aliases", &self.aliases)
+            .field("default", &self.default.is_some())
+            .finish()
+    }
+}
+
+impl RoutingSampler {
+    /// Router with no routes (every request fails until routes or a default are added).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Route models matching glob `pattern` (e.g. `gpt-*`) to `sampler`, model unchanged.
+    #[must_use]
+    pub fn with_route(mut self, pattern: impl Into<String>, sampler: Arc<dyn LlmSampler>) -> Self {
+        self.routes.push(Route {
+            pattern: ModelPattern::Glob(pattern.into()),
+            sampler,
+        });
+        self
+    }
+
+    /// Route models starting with `prefix` (e.g. `ollama/`) to `sampler`, prefix stripped.
+    #[must_use]
+    pub fn with_prefix_route(
+        mut self,
+        prefix: impl Into<String>,
+        sampler: Arc<dyn LlmSampler>,
+    ) -> Self {
+        self.routes.push(Route {
+            pattern: ModelPattern::Prefix(prefix.into()),
+            sampler,
+        });
+        self
+    }
+
+    /// Rewrite model `alias` to `target` before route matching.
+    #[must_use]
+    pub fn with_alias(mut self, alias: impl Into<String>, target: impl Into<String>) -> Self {
+        self.aliases.insert(alias.into(), target.into());
+        self
+    }
+
+    /// Backend for models no route matches (model forwarded unchanged).
+    #[must_use]
+    pub fn with_default(mut self, sampler: Arc<dyn LlmSampler>) -> Self {
+        self.default = Some(sampler);
+        self
+    }
+
+    /// Resolve `model` to a backend and the model id it will receive.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::LlmProvider`] when no route matches and there is no default.
+    pub fn resolve(&self, model: &str) -> Result<(Arc<dyn LlmSampler>, String), OvoError> {
+        let model = self.aliases.get(model).map_or(model, String::as_str);
+        for route in &self.routes {
+            if let Some(forwarded) = route.pattern.apply(model) {
+                return Ok((Arc::clone(&route.sampler), forwarded));
+            }
+        }
+        self.default
+            .as_ref()
+            .map(|s| (Arc::clone(s), model.to_owned()))
+            .ok_or_else(|| {
+                OvoError::new(
+                    ErrorCode::LlmProvider,
+                    format!("no sampler route for model '{model}'"),
+                )
+            })
+    }
+}
+
+#[async_trait]
+impl LlmSampler for RoutingSampler {
+    async fn sample(&self, mut request: SampleRequest) -> Result<SampleResponse, OvoError> {
+        let (sampler, model) = self.resolve(&request.model)?;
+        request.model = model;
+        sampler.sample(request).await
+    }
+
+    async fn sample_stream(&self, mut request: SampleRequest) -> Result<SampleStream, OvoError> {
+        let (sampler, model) = self.resolve(&request.model)?;
+        request.model = model;
+        sampler.sample_stream(request).await
+    }
+
+    fn capabilities(&self, model: &str) -> ModelCapabilities {
+        self.resolve(model).map_or_else(
+            |_| ModelCapabilities::new(),
+            |(sampler, model)| sampler.capabilities(&model),
+        )
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use ovo_types::{Message, SamplingParams, Usage};
+    use tokio_util::sync::CancellationToken;
+
+    use super::*;
+    use crate::sample::ToolChoice;
+
+    /// Replies with `{tag}:{model}` so tests can see backend and forwarded model.
+    struct Echo(&'static str);
+
+    #[async_trait]
+    impl LlmSampler for Echo {
+        async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+            Ok(SampleResponse {
+                message: Message::assistant(format!("{}:{}", self.0, request.model)),
+                usage: Usage::new(1, 1),
+                stop_reason: Some("stop".into()),
+                served_by: None,
+                rate_limit: None,
+            })
+        }
+    }
+
+    fn req(model: &str) -> SampleRequest {
+        SampleRequest {
+            model: model.into(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    async fn reply(router: &RoutingSampler, model: &str) -> String {
+        router
+            .sample(req(model))
+            .await
+            .expect("sample")
+            .message
+            .text()
+    }
+
+    #[test]
+    fn glob_matches_star_and_question_mark() {
+        assert!(glob_match("gpt-*", "gpt-4o"));
+        assert!(glob_match("*-mini", "o4-mini"));
+        assert!(glob_match("claude-?-*", "claude-4-opus"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("gpt-*", "o3"));
+        assert!(!glob_match("a?c", "ac"));
+    }
+
+    #[tokio::test]
+    async fn routes_aliases_and_default() {
+        let router = RoutingSampler::new()
+            .with_prefix_route("ollama/", Arc::new(Echo("local")))
+            .with_route("gpt-*", Arc::new(Echo("openai")))
+            .with_alias("planner", "gpt-5")
+            .with_alias("explorer", "ollama/qwen3");
+        assert_eq!(reply(&router, "ollama/llama3").await, "local:llama3");
+        assert_eq!(reply(&router, "gpt-4o").await, "openai:gpt-4o");
+        assert_eq!(reply(&router, "planner").await, "openai:gpt-5");
+        assert_eq!(reply(&router, "explorer").await, "local:qwen3");
+
+        let err = router.sample(req("claude-x")).await.expect_err("no route");
+        assert_eq!(err.code(), ErrorCode::LlmProvider);
+        assert!(err.message().contains("claude-x"), "{err}");
+
+        let router = router.with_default(Arc::new(Echo("fallback")));
+        assert_eq!(reply(&router, "claude-x").await, "fallback:claude-x");
+    }
+}
@@ -1,27 +1,14 @@
 //! Sample request/response types.
 
+use std::time::Duration;
+
 use ovo_tools::ToolDefinition;
-use ovo_types::{Deadline, Message, Usage};
+pub use ovo_types::ToolChoice;
+use ovo_types::{Deadline, Message, SamplingParams, Usage};
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
+use serde_json::{Value, json};
 use tokio_util::sync::CancellationToken;
 
-/// Whether the model must/may/must-not call tools.
-#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
-#[serde(rename_all = "snake_case")]
-#[non_exhaustive]
-pub enum ToolChoice {
-    /// Provider default.
-    #[default]
-    Auto,
-    /// Disallow tools.
-    None,
-    /// Require a tool call.
-    Required,
-    /// Force a specific tool name.
-    Named(String),
-}
-
 /// One sampling request.
 #[derive(Debug, Clone)]
 pub struct SampleRequest {
@@ -37,16 +24,19 @@ pub struct SampleRequest {
     pub response_format: Option<Value>,
     /// Max output tokens.
     pub max_output_tokens: Option<u32>,
-    /// Temperature.
-    pub temperature: Option<f32>,
+    /// Temperature, top-p, seed, stop sequences, penalties and reasoning effort.
+    pub sampling: SamplingParams,
     /// Cancel token.
     pub cancel: CancellationToken,
     /// Optional deadline.
     pub deadline: Option<Deadline>,
+    /// Opt in to response caching ([`crate::CachingSampler`]); leave `false`
+    /// for nondeterministic sampling.
+    pub cacheable: bool,
 }
 
 /// Sampling result.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct SampleResponse {
     /// Assistant message (text and/or tool calls).
     pub message: Message,
@@ -54,4 +44,160 @@ pub struct SampleResponse {
     pub usage: Usage,
     /// Provider stop reason (opaque).
     pub stop_reason: Option<String>,
+    /// Model that actually served the response when a decorator rerouted it
+    /// (e.g. [`crate::FallbackSampler`]); `None` means the requested model.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub served_by: Option<String>,
+    /// Provider rate-limit headers observed on this response, when sent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rate_limit: Option<RateLimitSnapshot>,
+}
+
+/// What a sampler supports for one model.
+///
+/// Reported by [`crate::LlmSampler::capabilities`]; the turn runtime uses it to
+/// pick how structured output is requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ModelCapabilities {
+    /// Honors a JSON Schema `response_format`.
+    pub json_schema: bool,
+}
+
+impl Default for ModelCapabilities {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ModelCapabilities {
+    /// Full support (the default for providers that do not say otherwise).
+    #[must_use]
+    pub const fn new() -> Self {
+        Self { json_schema: true }
+    }
+
+    /// Set [`Self::json_schema`].
+    #[must_use]
+    pub const fn with_json_schema(mut self, supported: bool) -> Self {
+        self.json_schema = supported;
+        self
+    }
+}
+
+/// Remaining provider budget from `x-ratelimit-*` style response headers.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RateLimitSnapshot {
+    /// Requests left in the current window.
+    #[serde(default)]
+    pub remaining_requests: Option<u64>,
+    /// Tokens left in the current window.
+    #[serde(default)]
+    pub remaining_tokens: Option<u64>,
+    /// Time until the request window resets.
+    #[serde(default)]
+    pub reset_requests: Option<Duration>,
+    /// Time until the token window resets.
+    #[serde(default)]
+    pub reset_tokens: Option<Duration>,
+}
+
+/// Wire dialect for [`sampling_fields`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum SamplingDialect {
+    /// Chat Completions top-level fields.
+    OpenAi,
+    /// Ollama `options` map.
+    #[cfg_attr(
+        not(feature = "ollama"),
+        allow(dead_code, reason = "only the ollama adapter sends options")
+    )]
+    Ollama,
+    /// Gemini `generationConfig`.
+    Gemini,
+    /// Anthropic Messages top-level fields.
+    Anthropic,
+    /// Responses API top-level fields.
+    Responses,
+}
+
+impl SamplingDialect {
+    /// Column of this dialect in the [`sampling_fields`] key table.
+    const fn column(self) -> usize {
+        match self {
+            Self::OpenAi => 0,
+            Self::Ollama => 1,
+            Self::Gemini => 2,
+            Self::Anthropic => 3,
+            Self::Responses => 4,
+        }
+    }
+}
+
+/// The set [`SamplingParams`] fields as `(wire key, value)` pairs for
+/// `dialect`; fields the dialect has no key for are dropped.
+pub(crate) fn sampling_fields(
+    params: &SamplingParams,
+    dialect: SamplingDialect,
+) -> impl Iterator<Item = (&'static str, Value)> {
+    // Keys per field: [OpenAI, Ollama, Gemini, Anthropic, Responses]. `None`
+    // means the API has no such parameter and the value is not sent; Anthropic
+    // and Responses carry reasoning effort in their own nested objects.
+    let table: [([Option<&'static str>; 5], Option<Value>); 7] = [
+        (
+            [Some("temperature"); 5],
+            params.temperature.map(|v| json!(v)),
+        ),
+        (
+            [
+                Some("top_p"),
+                Some("top_p"),
+                Some("topP"),
+                Some("top_p"),
+                Some("top_p"),
+            ],
+            params.top_p.map(|v| json!(v)),
+        ),
+        (
+            [Some("seed"), Some("seed"), Some("seed"), None, None],
+            params.seed.map(|v| json!(v)),
+        ),
+        (
+            [
+                Some("stop"),
+                Some("stop"),
+                Some("stopSequences"),
+                Some("stop_sequences"),
+                None,
+            ],
+            (!params.stop.is_empty()).then(|| json!(params.stop)),
+        ),
+        (
+            [
+                Some("presence_penalty"),
+                Some("presence_penalty"),
+                Some("presencePenalty"),
+                None,
+                None,
+            ],
+            params.presence_penalty.map(|v| json!(v)),
+        ),
+        (
+            [
+                Some("frequency_penalty"),
+                Some("frequency_penalty"),
+                Some("frequencyPenalty"),
+                None,
+                None,
+            ],
+            params.frequency_penalty.map(|v| json!(v)),
+        ),
+        (
+            [Some("reasoning_effort"), None, None, None, None],
+            params.reasoning_effort.map(|e| json!(e.as_str())),
+        ),
+    ];
+    table.into_iter().filter_map(move |(keys, value)| {
+        let key = keys.get(dialect.column()).copied().flatten()?;
+        Some((key, value?))
+    })
 }
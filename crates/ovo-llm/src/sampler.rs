@@ -4,7 +4,7 @@
 use futures::stream;
 use ovo_types::OvoError;
 
-use crate::sample::{SampleRequest, SampleResponse};
+use crate::sample::{ModelCapabilities, SampleRequest, SampleResponse};
 use crate::stream::{SampleEvent, SampleStream};
 
 /// Abstraction over model providers.
@@ -19,6 +19,11 @@ async fn sample_stream(&self, request: SampleRequest) -> Result<SampleStream, Ov
         let response = self.sample(request).await?;
         Ok(response_to_stream(response))
     }
+
+    /// What `model` supports through this sampler. Default: everything.
+    fn capabilities(&self, _model: &str) -> ModelCapabilities {
+        ModelCapabilities::new()
+    }
 }
 
 /// Convert a complete response into a short stream.
@@ -37,12 +42,14 @@ pub fn response_to_stream(response: SampleResponse) -> SampleStream {
     if response.usage.total_tokens > 0
         || response.usage.input_tokens > 0
         || response.usage.output_tokens > 0
+        || response.usage.response_cache_hits > 0
     {
         events.push(SampleEvent::Usage(response.usage));
     }
     events.push(SampleEvent::Completed {
         message: response.message,
         stop_reason: response.stop_reason,
+        served_by: response.served_by,
     });
     Box::pin(stream::iter(events))
 }
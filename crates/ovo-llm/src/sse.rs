@@ -0,0 +1,127 @@
+//! Incremental `text/event-stream` framing for streaming providers.
+//!
+//! Pure byte → event decoding; transports feed raw chunks in whatever sizes the
+//! socket delivers (frames and UTF-8 sequences may be torn across chunks).
+
+/// One dispatched server-sent event.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SseEvent {
+    /// `event:` field when present.
+    pub event: Option<String>,
+    /// Joined `data:` lines (newline separated).
+    pub data: String,
+}
+
+/// Stateful SSE decoder (one per response body).
+#[derive(Debug, Default)]
+pub struct SseDecoder {
+    buf: Vec<u8>,
+    event: Option<String>,
+    data: Vec<String>,
+}
+
+impl SseDecoder {
+    /// Empty decoder.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Feed raw bytes; returns every event completed by this chunk.
+    pub fn push(&mut self, chunk: &[u8]) -> Vec<SseEvent> {
+        self.buf.extend_from_slice(chunk);
+        let mut out = Vec::new();
+        while let Some(pos) = self.buf.iter().position(|b| *b == b'\n') {
+            let mut line: Vec<u8> = self.buf.drain(..=pos).collect();
+            line.pop();
+            if line.last() == Some(&b'\r') {
+                line.pop();
+            }
+            if let Some(ev) = self.line(&String::from_utf8_lossy(&line)) {
+                out.push(ev);
+            }
+        }
+        out
+    }
+
+    /// Flush a trailing event when the body ends without a blank line.
+    pub fn finish(&mut self) -> Option<SseEvent> {
+        if !self.buf.is_empty() {
+            let rest = std::mem::take(&mut self.buf);
+            if let Some(ev) = self.line(String::from_utf8_lossy(&rest).trim_end_matches('\r')) {
+                return Some(ev);
+            }
+        }
+        self.dispatch()
+    }
+
+    fn line(&mut self, line: &str) -> Option<SseEvent> {
+        if line.is_empty() {
+            return self.dispatch();
+        }
+        if line.starts_with(':') {
+            return None;
+        }
+        let (field, value) = line.split_once(':').unwrap_or((line, ""));
+        let value = value.strip_prefix(' ').unwrap_or(value);
+        match field {
+            "data" => self.data.push(value.to_owned()),
+            "event" => self.event = Some(value.to_owned()),
+            _ => {}
+        }
+        None
+    }
+
+    fn dispatch(&mut self) -> Option<SseEvent> {
+        if self.data.is_empty() {
+            self.event = None;
+            return None;
+        }
+        Some(SseEvent {
+            event: self.event.take(),
+            data: std::mem::take(&mut self.data).join("\n"),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn torn_frames_reassemble() {
+        let mut dec = SseDecoder::new();
+        assert!(dec.push(b"data: {\"a\":").is_empty());
+        assert!(dec.push(b"1}\r\n").is_empty());
+        let out = dec.push(b"\r\nevent: ping\ndata: x\n\n: comment\n");
+        assert_eq!(
+            out,
+            vec![
+                SseEvent {
+                    event: None,
+                    data: "{\"a\":1}".into(),
+                },
+                SseEvent {
+                    event: Some("ping".into()),
+                    data: "x".into(),
+                },
+            ]
+        );
+        assert_eq!(dec.finish(), None);
+    }
+
+    #[test]
+    fn finish_flushes_unterminated_event() {
+        let mut dec = SseDecoder::new();
+        let (head, tail) = "data: h\u{e9}".as_bytes().split_at(8);
+        assert!(dec.push(head).is_empty());
+        assert!(dec.push(tail).is_empty());
+        assert_eq!(
+            dec.finish(),
+            Some(SseEvent {
+                event: None,
+                data: "h\u{e9}".into(),
+            })
+        );
+    }
+}
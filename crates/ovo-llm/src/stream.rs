@@ -2,11 +2,15 @@
 
 use std::pin::Pin;
 
-use futures::Stream;
-use ovo_types::{Message, Usage};
+use futures::{Stream, StreamExt};
+use ovo_types::{ErrorCode, Message, OvoError, Usage};
+use serde::{Deserialize, Serialize};
+
+use crate::sample::{RateLimitSnapshot, SampleResponse};
 
 /// One event in a streaming sample.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
 #[non_exhaustive]
 pub enum SampleEvent {
     /// Incremental assistant text.
@@ -38,6 +42,8 @@ pub enum SampleEvent {
         /// Cache-creation tokens if known at start.
         cache_creation: Option<u32>,
     },
+    /// Provider rate-limit headers observed when the response started.
+    RateLimit(RateLimitSnapshot),
     /// Decorator is about to retry a failed sample.
     Retrying {
         /// 1-based attempt about to run.
@@ -53,6 +59,10 @@ pub enum SampleEvent {
         message: Message,
         /// Provider stop reason.
         stop_reason: Option<String>,
+        /// Model that actually served the stream, when a decorator picked it
+        /// (see [`SampleResponse::served_by`]).
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        served_by: Option<String>,
     },
     /// Stream failed after partial progress (terminal).
     Failed {
@@ -63,3 +73,60 @@ pub enum SampleEvent {
 
 /// Opaque pinned sample event stream.
 pub type SampleStream = Pin<Box<dyn Stream<Item = SampleEvent> + Send>>;
+
+/// Name `model` as the server on the stream's [`SampleEvent::Completed`],
+/// unless an inner decorator already did.
+pub(crate) fn with_served_by(stream: SampleStream, model: String) -> SampleStream {
+    Box::pin(stream.map(move |mut ev| {
+        if let SampleEvent::Completed { served_by, .. } = &mut ev {
+            served_by.get_or_insert_with(|| model.clone());
+        }
+        ev
+    }))
+}
+
+/// Fold a finished event list back into one response (replay / cache fill).
+pub(crate) fn response_from_events(events: Vec<SampleEvent>) -> Result<SampleResponse, OvoError> {
+    let mut usage = Usage::zero();
+    let mut text = String::new();
+    let mut tool_message: Option<Message> = None;
+    for ev in events {
+        match ev {
+            SampleEvent::TextDelta { text: t } => text.push_str(&t),
+            SampleEvent::ToolCalls { message } => tool_message = Some(message),
+            SampleEvent::Usage(u) => usage += u,
+            SampleEvent::Completed {
+                message,
+                stop_reason,
+                served_by,
+            } => {
+                return Ok(SampleResponse {
+                    message,
+                    usage,
+                    stop_reason,
+                    served_by,
+                    rate_limit: None,
+                });
+            }
+            SampleEvent::Failed { message } => {
+                return Err(OvoError::new(ErrorCode::LlmInvalidResponse, message));
+            }
+            _ => {}
+        }
+    }
+    let message = tool_message
+        .or_else(|| (!text.is_empty()).then(|| Message::assistant(text)))
+        .ok_or_else(|| {
+            OvoError::new(
+                ErrorCode::LlmInvalidResponse,
+                "stream ended without Completed",
+            )
+        })?;
+    Ok(SampleResponse {
+        message,
+        usage,
+        stop_reason: None,
+        served_by: None,
+        rate_limit: None,
+    })
+}
@@ -0,0 +1,233 @@
+//! Drive real [`AnthropicSampler`] against a local HTTP fake.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links provider deps"
+)]
+#![cfg(feature = "anthropic")]
+
+#[cfg(test)]
+mod http_fake {
+    #![allow(
+        clippy::expect_used,
+        clippy::unwrap_used,
+        clippy::panic,
+        reason = "integration test harness"
+    )]
+
+    use futures::StreamExt;
+    use ovo_llm::{
+        AnthropicConfig, AnthropicSampler, LlmSampler, RetryPolicy, RetryingSampler, SampleEvent,
+        SampleRequest, ToolChoice,
+    };
+    use ovo_types::{ErrorCode, Message, SamplingParams};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio_util::sync::CancellationToken;
+
+    /// Accept one connection, capture the request head+body, and write `head` then `pieces`.
+    async fn serve_once(listener: &TcpListener, head: String, pieces: Vec<String>) -> String {
+        let (mut socket, _) = listener.accept().await.expect("accept");
+        let mut buf = vec![0u8; 16384];
+        let n = socket.read(&mut buf).await.expect("read request");
+        let request = String::from_utf8_lossy(buf.get(..n).unwrap_or_default()).into_owned();
+        socket.write_all(head.as_bytes()).await.expect("write head");
+        for piece in pieces {
+            socket.write_all(piece.as_bytes()).await.expect("write");
+            socket.flush().await.expect("flush");
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        request
+    }
+
+    fn request() -> SampleRequest {
+        SampleRequest {
+            model: "claude-test".into(),
+            messages: vec![Message::system("sys"), Message::user("hi")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: Some(128),
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn anthropic_sample_sends_headers_and_parses_usage() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let body = serde_json::json!({
+            "id": "msg_1",
+            "type": "message",
+            "role": "assistant",
+            "content": [{"type": "text", "text": "wire-ok"}],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 4, "output_tokens": 2, "cache_read_input_tokens": 6}
+        })
+        .to_string();
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        let server = tokio::spawn(async move { serve_once(&listener, head, vec![body]).await });
+        let sampler =
+            AnthropicSampler::new(AnthropicConfig::new(format!("http://{addr}"), "sk-test"))
+                .expect("client");
+        let response = sampler.sample(request()).await.expect("sample");
+        let raw = server.await.expect("join").to_ascii_lowercase();
+        assert!(raw.starts_with("post /v1/messages"), "{raw}");
+        assert!(raw.contains("x-api-key: sk-test"), "{raw}");
+        assert!(raw.contains("anthropic-version: 2023-06-01"), "{raw}");
+        assert!(raw.contains("\"system\":\"sys\""), "{raw}");
+        assert_eq!(response.message.text(), "wire-ok");
+        assert_eq!(response.usage.input_tokens, 10);
+        assert_eq!(response.usage.cache_read_tokens, 6);
+        assert_eq!(response.stop_reason.as_deref(), Some("end_turn"));
+    }
+
+    #[tokio::test]
+    async fn anthropic_sample_stream_emits_incremental_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let frames = [
+            (
+                "message_start",
+                serde_json::json!({"type":"message_start","message":{"usage":{"input_tokens":3,"output_tokens":1}}}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type":"content_block_start","index":0,"content_block":{"type":"thinking","thinking":""}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type":"content_block_delta","index":0,"delta":{"type":"thinking_delta","thinking":"hmm"}}),
+            ),
+            (
+                "content_block_start",
+                serde_json::json!({"type":"content_block_start","index":1,"content_block":{"type":"text","text":""}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"Hel"}}),
+            ),
+            (
+                "content_block_delta",
+                serde_json::json!({"type":"content_block_delta","index":1,"delta":{"type":"text_delta","text":"lo"}}),
+            ),
+            (
+                "message_delta",
+                serde_json::json!({"type":"message_delta","delta":{"stop_reason":"end_turn"},"usage":{"output_tokens":5}}),
+            ),
+            ("message_stop", serde_json::json!({"type":"message_stop"})),
+        ];
+        let mut sse = String::new();
+        for (name, data) in &frames {
+            sse.push_str("event: ");
+            sse.push_str(name);
+            sse.push_str("\ndata: ");
+            sse.push_str(&data.to_string());
+            sse.push_str("\n\n");
+        }
+        let pieces: Vec<String> = sse
+            .as_bytes()
+            .chunks(29)
+            .map(|c| String::from_utf8_lossy(c).into_owned())
+            .collect();
+        let head =
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n"
+                .to_owned();
+        let server = tokio::spawn(async move { serve_once(&listener, head, pieces).await });
+
+        let sampler = AnthropicSampler::new(AnthropicConfig::new(format!("http://{addr}"), ""))
+            .expect("client");
+        let events: Vec<SampleEvent> = sampler
+            .sample_stream(request())
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        let raw = server.await.expect("join");
+        assert!(raw.contains("\"stream\":true"), "{raw}");
+
+        let text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                SampleEvent::TextDelta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "Hello");
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, SampleEvent::ReasoningDelta { text } if text == "hmm"))
+        );
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SampleEvent::Usage(u) if u.input_tokens == 3 && u.output_tokens == 5
+        )));
+        let Some(SampleEvent::Completed {
+            message,
+            stop_reason,
+            ..
+        }) = events.last()
+        else {
+            panic!("expected Completed last: {events:?}");
+        };
+        assert_eq!(message.text(), "Hello");
+        assert_eq!(stop_reason.as_deref(), Some("end_turn"));
+    }
+
+    #[tokio::test]
+    async fn anthropic_overloaded_status_is_retried_by_decorator() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let ok = serde_json::json!({
+            "content": [{"type": "text", "text": "recovered"}],
+            "stop_reason": "end_turn",
+            "usage": {"input_tokens": 1, "output_tokens": 1}
+        })
+        .to_string();
+        let server = tokio::spawn(async move {
+            let overloaded = r#"{"type":"error","error":{"type":"overloaded_error"}}"#;
+            serve_once(
+                &listener,
+                format!(
+                    "HTTP/1.1 529 Overloaded\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    overloaded.len()
+                ),
+                vec![overloaded.to_owned()],
+            )
+            .await;
+            serve_once(
+                &listener,
+                format!(
+                    "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+                    ok.len()
+                ),
+                vec![ok],
+            )
+            .await;
+        });
+
+        let inner = AnthropicSampler::new(AnthropicConfig::new(format!("http://{addr}"), ""))
+            .expect("client");
+        let direct = inner.sample(request()).await.expect_err("529");
+        assert_eq!(direct.code(), ErrorCode::LlmProvider);
+        assert_eq!(direct.http_status(), Some(529));
+
+        let retrying =
+            RetryingSampler::new(std::sync::Arc::new(inner)).with_policy(RetryPolicy::for_tests());
+        let response = retrying.sample(request()).await.expect("retried");
+        server.await.expect("join");
+        assert_eq!(response.message.text(), "recovered");
+    }
+
+    #[test]
+    fn anthropic_structured_output_uses_final_answer_tool() {
+        let sampler = AnthropicSampler::new(AnthropicConfig::default()).expect("sampler");
+        assert!(!sampler.capabilities("claude-sonnet-4").json_schema);
+    }
+}
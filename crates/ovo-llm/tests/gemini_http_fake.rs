@@ -0,0 +1,152 @@
+//! Drive real [`GeminiSampler`] against a local HTTP fake.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links provider deps"
+)]
+#![cfg(feature = "gemini")]
+
+#[cfg(test)]
+mod http_fake {
+    #![allow(
+        clippy::expect_used,
+        clippy::unwrap_used,
+        clippy::panic,
+        reason = "integration test harness"
+    )]
+
+    use futures::StreamExt;
+    use ovo_llm::{
+        GeminiConfig, GeminiSampler, LlmSampler, SampleEvent, SampleRequest, ToolChoice,
+    };
+    use ovo_types::{Message, SamplingParams};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio_util::sync::CancellationToken;
+
+    /// Accept one connection, capture the request head+body, and write `head` then `pieces`.
+    async fn serve_once(listener: &TcpListener, head: String, pieces: Vec<String>) -> String {
+        let (mut socket, _) = listener.accept().await.expect("accept");
+        let mut buf = vec![0u8; 16384];
+        let n = socket.read(&mut buf).await.expect("read request");
+        let request = String::from_utf8_lossy(buf.get(..n).unwrap_or_default()).into_owned();
+        socket.write_all(head.as_bytes()).await.expect("write head");
+        for piece in pieces {
+            socket.write_all(piece.as_bytes()).await.expect("write");
+            socket.flush().await.expect("flush");
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        request
+    }
+
+    fn request() -> SampleRequest {
+        SampleRequest {
+            model: "gemini-test".into(),
+            messages: vec![Message::system("sys"), Message::user("hi")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: Some(128),
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn gemini_sample_sends_key_and_parses_usage() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let body = serde_json::json!({
+            "candidates": [{
+                "content": {"role": "model", "parts": [{"text": "wire-ok"}]},
+                "finishReason": "STOP"
+            }],
+            "usageMetadata": {"promptTokenCount": 4, "candidatesTokenCount": 2}
+        })
+        .to_string();
+        let head = format!(
+            "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n",
+            body.len()
+        );
+        let server = tokio::spawn(async move { serve_once(&listener, head, vec![body]).await });
+        let sampler = GeminiSampler::new(GeminiConfig::new(format!("http://{addr}"), "g-key"))
+            .expect("client");
+        let response = sampler.sample(request()).await.expect("sample");
+        let raw = server.await.expect("join");
+        let lower = raw.to_ascii_lowercase();
+        assert!(
+            raw.starts_with("POST /v1beta/models/gemini-test:generateContent"),
+            "{raw}"
+        );
+        assert!(lower.contains("x-goog-api-key: g-key"), "{raw}");
+        assert!(raw.contains("\"systemInstruction\""), "{raw}");
+        assert_eq!(response.message.text(), "wire-ok");
+        assert_eq!(response.usage.input_tokens, 4);
+        assert_eq!(response.usage.output_tokens, 2);
+        assert_eq!(response.stop_reason.as_deref(), Some("STOP"));
+    }
+
+    #[tokio::test]
+    async fn gemini_sample_stream_emits_incremental_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let chunks = [
+            serde_json::json!({"candidates":[{"content":{"role":"model","parts":[{"text":"Hel"}]}}]}),
+            serde_json::json!({"candidates":[{"content":{"role":"model","parts":[{"text":"lo"}]}}]}),
+            serde_json::json!({
+                "candidates":[{"content":{"role":"model","parts":[{"functionCall":{"name":"lookup","args":{"q":"x"}}}]},"finishReason":"STOP"}],
+                "usageMetadata":{"promptTokenCount":3,"candidatesTokenCount":5}
+            }),
+        ];
+        let mut sse = String::new();
+        for data in &chunks {
+            sse.push_str("data: ");
+            sse.push_str(&data.to_string());
+            sse.push_str("\r\n\r\n");
+        }
+        let pieces: Vec<String> = sse
+            .as_bytes()
+            .chunks(23)
+            .map(|c| String::from_utf8_lossy(c).into_owned())
+            .collect();
+        let head =
+            "HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n"
+                .to_owned();
+        let server = tokio::spawn(async move { serve_once(&listener, head, pieces).await });
+
+        let sampler =
+            GeminiSampler::new(GeminiConfig::new(format!("http://{addr}"), "")).expect("client");
+        let events: Vec<SampleEvent> = sampler
+            .sample_stream(request())
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        let raw = server.await.expect("join");
+        assert!(
+            raw.starts_with("POST /v1beta/models/gemini-test:streamGenerateContent?alt=sse"),
+            "{raw}"
+        );
+
+        let text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                SampleEvent::TextDelta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "Hello");
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SampleEvent::Usage(u) if u.input_tokens == 3 && u.output_tokens == 5
+        )));
+        let Some(SampleEvent::Completed { message, .. }) = events.last() else {
+            panic!("expected Completed last: {events:?}");
+        };
+        assert_eq!(message.text(), "Hello");
+        let call = message.tool_calls.first().expect("tool call");
+        assert_eq!(call.name, "lookup");
+        assert!(call.id.as_str().starts_with("gemini_call_0_"));
+    }
+}
@@ -0,0 +1,136 @@
+//! Drive real [`OllamaSampler::sample_stream`] against a local NDJSON HTTP fake.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links provider deps"
+)]
+#![cfg(feature = "ollama")]
+
+#[cfg(test)]
+mod http_fake {
+    #![allow(
+        clippy::expect_used,
+        clippy::unwrap_used,
+        clippy::panic,
+        reason = "integration test harness"
+    )]
+
+    use futures::StreamExt;
+    use ovo_llm::{
+        LlmSampler, OllamaConfig, OllamaSampler, SampleEvent, SampleRequest, ToolChoice,
+    };
+    use ovo_types::{Message, SamplingParams};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::TcpListener;
+    use tokio_util::sync::CancellationToken;
+
+    async fn serve_ndjson(listener: TcpListener, pieces: Vec<Vec<u8>>) -> String {
+        let (mut socket, _) = listener.accept().await.expect("accept");
+        let mut buf = vec![0u8; 8192];
+        let n = socket.read(&mut buf).await.expect("read request");
+        let request = String::from_utf8_lossy(buf.get(..n).unwrap_or_default()).into_owned();
+        socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: application/x-ndjson\r\nConnection: close\r\n\r\n",
+            )
+            .await
+            .expect("write head");
+        for piece in pieces {
+            socket.write_all(&piece).await.expect("write");
+            socket.flush().await.expect("flush");
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        request
+    }
+
+    fn request() -> SampleRequest {
+        SampleRequest {
+            model: "llama-test".into(),
+            messages: vec![Message::user("hi")],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn ollama_sample_stream_emits_incremental_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let lines = [
+            serde_json::json!({"message":{"role":"assistant","content":"","thinking":"plan"},"done":false}),
+            serde_json::json!({"message":{"role":"assistant","content":"wire-"},"done":false}),
+            serde_json::json!({"message":{"role":"assistant","content":"ok"},"done":false}),
+            serde_json::json!({"message":{"role":"assistant","content":""},"done":true,"done_reason":"stop","prompt_eval_count":6,"eval_count":2}),
+        ];
+        let mut body = Vec::new();
+        for line in &lines {
+            body.extend_from_slice(line.to_string().as_bytes());
+            body.push(b'\n');
+        }
+        let pieces: Vec<Vec<u8>> = body.chunks(11).map(<[u8]>::to_vec).collect();
+        let server = tokio::spawn(serve_ndjson(listener, pieces));
+
+        let sampler =
+            OllamaSampler::new(OllamaConfig::new(format!("http://{addr}"))).expect("client");
+        let stream = sampler.sample_stream(request()).await.expect("stream");
+        let events: Vec<SampleEvent> = stream.collect().await;
+        let raw_request = server.await.expect("join");
+        assert!(raw_request.contains("\"stream\":true"), "{raw_request}");
+
+        let deltas: Vec<&str> = events
+            .iter()
+            .filter_map(|e| match e {
+                SampleEvent::TextDelta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(deltas, vec!["wire-", "ok"]);
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, SampleEvent::ReasoningDelta { text } if text == "plan"))
+        );
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SampleEvent::Usage(u) if u.input_tokens == 6 && u.output_tokens == 2
+        )));
+        let Some(SampleEvent::Completed { message, .. }) = events.last() else {
+            panic!("expected Completed last: {events:?}");
+        };
+        assert_eq!(message.text(), "wire-ok");
+    }
+
+    #[tokio::test]
+    async fn ollama_sample_stream_fails_on_mid_stream_error() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let pieces = vec![
+            b"{\"message\":{\"content\":\"par\"},\"done\":false}\n".to_vec(),
+            b"{\"error\":\"out of memory\"}\n".to_vec(),
+        ];
+        let server = tokio::spawn(serve_ndjson(listener, pieces));
+        let sampler =
+            OllamaSampler::new(OllamaConfig::new(format!("http://{addr}"))).expect("client");
+        let events: Vec<SampleEvent> = sampler
+            .sample_stream(request())
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        server.await.expect("join");
+        let Some(SampleEvent::Failed { message }) = events.last() else {
+            panic!("expected Failed last: {events:?}");
+        };
+        assert!(message.contains("out of memory"), "{message}");
+        assert!(
+            !events
+                .iter()
+                .any(|e| matches!(e, SampleEvent::Completed { .. }))
+        );
+    }
+}
@@ -1,9 +1,9 @@
-//! Drive real [`OpenAiCompatSampler::sample`] against a local HTTP fake.
-#![cfg(feature = "openai")]
+//! Drive real [`OpenAiCompatSampler`] / [`ResponsesSampler`] against a local HTTP fake.
 #![allow(
     unused_crate_dependencies,
     reason = "integration binary links provider deps"
 )]
+#![cfg(feature = "openai")]
 
 #[cfg(test)]
 mod http_fake {
@@ -11,11 +11,16 @@ mod http_fake {
         clippy::expect_used,
         clippy::unwrap_used,
         clippy::print_stdout,
+        clippy::panic,
         reason = "integration test harness"
     )]
 
-    use ovo_llm::{LlmSampler, OpenAiCompatConfig, OpenAiCompatSampler, SampleRequest, ToolChoice};
-    use ovo_types::Message;
+    use futures::StreamExt;
+    use ovo_llm::{
+        LlmSampler, OpenAiCompatConfig, OpenAiCompatSampler, RESPONSES_PROVIDER, ResponsesConfig,
+        ResponsesSampler, SampleEvent, SampleRequest, ToolChoice,
+    };
+    use ovo_types::{ContentPart, ErrorCode, Message, SamplingParams};
     use tokio::io::{AsyncReadExt, AsyncWriteExt};
     use tokio::net::TcpListener;
     use tokio_util::sync::CancellationToken;
@@ -35,6 +40,146 @@ async fn serve_one_json_response(listener: TcpListener, body: String) {
             .expect("write response");
     }
 
+    /// Serve an SSE body in the given raw pieces (frames deliberately torn) and
+    /// return the captured request body.
+    async fn serve_one_sse_response(listener: TcpListener, pieces: Vec<String>) -> String {
+        let (mut socket, _) = listener.accept().await.expect("accept");
+        let mut buf = vec![0u8; 8192];
+        let n = socket.read(&mut buf).await.expect("read request");
+        let request = String::from_utf8_lossy(buf.get(..n).unwrap_or_default()).into_owned();
+        socket
+            .write_all(
+                b"HTTP/1.1 200 OK\r\nContent-Type: text/event-stream\r\nConnection: close\r\n\r\n",
+            )
+            .await
+            .expect("write head");
+        for piece in pieces {
+            socket.write_all(piece.as_bytes()).await.expect("write");
+            socket.flush().await.expect("flush");
+            tokio::time::sleep(std::time::Duration::from_millis(5)).await;
+        }
+        request
+    }
+
+    fn request(model: &str, text: &str) -> SampleRequest {
+        SampleRequest {
+            model: model.into(),
+            messages: vec![Message::user(text)],
+            tools: vec![],
+            tool_choice: ToolChoice::Auto,
+            response_format: None,
+            max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            cancel: CancellationToken::new(),
+            deadline: None,
+            cacheable: false,
+        }
+    }
+
+    #[tokio::test]
+    async fn openai_compat_sample_stream_emits_incremental_events() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let frames = [
+            serde_json::json!({"choices":[{"index":0,"delta":{"role":"assistant","content":"Hel"}}]}),
+            serde_json::json!({"choices":[{"index":0,"delta":{"content":"lo"}}]}),
+            serde_json::json!({"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"id":"call_s","type":"function","function":{"name":"calc","arguments":"{\"expr\""}}]}}]}),
+            serde_json::json!({"choices":[{"index":0,"delta":{"tool_calls":[{"index":0,"function":{"arguments":":\"2+3\"}"}}]},"finish_reason":"tool_calls"}]}),
+            serde_json::json!({"choices":[],"usage":{"prompt_tokens":9,"completion_tokens":4}}),
+        ];
+        let mut sse = String::new();
+        for frame in &frames {
+            sse.push_str("data: ");
+            sse.push_str(&frame.to_string());
+            sse.push_str("\n\n");
+        }
+        sse.push_str("data: [DONE]\n\n");
+        // Tear frames at arbitrary byte offsets.
+        let pieces: Vec<String> = sse
+            .as_bytes()
+            .chunks(37)
+            .map(|c| String::from_utf8_lossy(c).into_owned())
+            .collect();
+        let server = tokio::spawn(serve_one_sse_response(listener, pieces));
+
+        let sampler =
+            OpenAiCompatSampler::new(OpenAiCompatConfig::new(format!("http://{addr}"), "k"))
+                .expect("client");
+        let stream = sampler
+            .sample_stream(request("gpt-test", "stream please"))
+            .await
+            .expect("stream");
+        let events: Vec<SampleEvent> = stream.collect().await;
+        let raw_request = server.await.expect("join");
+        assert!(raw_request.contains("\"stream\":true"), "{raw_request}");
+        assert!(raw_request.contains("include_usage"), "{raw_request}");
+
+        assert!(matches!(
+            events.first(),
+            Some(SampleEvent::ResponseStarted { .. })
+        ));
+        let text: String = events
+            .iter()
+            .filter_map(|e| match e {
+                SampleEvent::TextDelta { text } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(text, "Hello");
+        let arg_deltas = events
+            .iter()
+            .filter(|e| matches!(e, SampleEvent::ToolCallDelta { index: 0, .. }))
+            .count();
+        assert_eq!(arg_deltas, 2);
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SampleEvent::Usage(u) if u.input_tokens == 9 && u.output_tokens == 4
+        )));
+        let Some(SampleEvent::Completed {
+            message,
+            stop_reason,
+            ..
+        }) = events.last()
+        else {
+            panic!("expected Completed last: {events:?}");
+        };
+        assert_eq!(stop_reason.as_deref(), Some("tool_calls"));
+        assert_eq!(message.content.as_deref(), Some("Hello"));
+        let tc = message.tool_calls.first().expect("tc");
+        assert_eq!(tc.id.as_str(), "call_s");
+        assert_eq!(
+            tc.arguments.get("expr").and_then(|v| v.as_str()),
+            Some("2+3")
+        );
+    }
+
+    #[tokio::test]
+    async fn openai_compat_sample_stream_maps_http_status_before_streaming() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let server = tokio::spawn(async move {
+            let (mut socket, _) = listener.accept().await.expect("accept");
+            let mut buf = vec![0u8; 8192];
+            let _n = socket.read(&mut buf).await.expect("read request");
+            let body = "rate limited";
+            let response = format!(
+                "HTTP/1.1 429 Too Many Requests\r\nRetry-After: 2\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+                body.len()
+            );
+            socket.write_all(response.as_bytes()).await.expect("write");
+        });
+        let sampler =
+            OpenAiCompatSampler::new(OpenAiCompatConfig::new(format!("http://{addr}"), ""))
+                .expect("client");
+        let Err(err) = sampler.sample_stream(request("m", "hi")).await else {
+            panic!("expected HTTP error");
+        };
+        server.await.expect("join");
+        assert_eq!(err.code(), ErrorCode::LlmRateLimit);
+        assert_eq!(err.http_status(), Some(429));
+        assert_eq!(err.retry_after(), Some(std::time::Duration::from_secs(2)));
+    }
+
     #[tokio::test]
     async fn openai_compat_sample_parses_tool_calls_from_fake_http() {
         let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
@@ -73,9 +218,10 @@ async fn openai_compat_sample_parses_tool_calls_from_fake_http() {
                 tool_choice: ToolChoice::Auto,
                 response_format: None,
                 max_output_tokens: None,
-                temperature: None,
+                sampling: SamplingParams::default(),
                 cancel: CancellationToken::new(),
                 deadline: None,
+                cacheable: false,
             })
             .await
             .expect("sample");
@@ -119,9 +265,10 @@ async fn openai_compat_sample_parses_assistant_text_from_fake_http() {
                 tool_choice: ToolChoice::Auto,
                 response_format: None,
                 max_output_tokens: None,
-                temperature: None,
+                sampling: SamplingParams::default(),
                 cancel: CancellationToken::new(),
                 deadline: None,
+                cacheable: false,
             })
             .await
             .expect("sample");
@@ -129,4 +276,85 @@ async fn openai_compat_sample_parses_assistant_text_from_fake_http() {
         assert_eq!(response.message.text(), "wire-ok-42");
         assert_eq!(response.usage.output_tokens, 4);
     }
+
+    #[tokio::test]
+    async fn responses_sample_stream_round_trips_reasoning_items() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let completed = serde_json::json!({
+            "type": "response.completed",
+            "response": {
+                "status": "completed",
+                "output": [
+                    {"type": "reasoning", "id": "rs_9", "summary": [], "encrypted_content": "enc-blob"},
+                    {"type": "message", "role": "assistant", "content": [{"type": "output_text", "text": "Hello"}]}
+                ],
+                "usage": {"input_tokens": 5, "output_tokens": 9, "output_tokens_details": {"reasoning_tokens": 7}}
+            }
+        });
+        let frames = [
+            serde_json::json!({"type": "response.created", "response": {"status": "in_progress"}}),
+            serde_json::json!({"type": "response.reasoning_summary_text.delta", "delta": "plan"}),
+            serde_json::json!({"type": "response.output_text.delta", "delta": "Hel"}),
+            serde_json::json!({"type": "response.output_text.delta", "delta": "lo"}),
+            completed,
+        ];
+        let mut sse = String::new();
+        for frame in &frames {
+            let name = frame
+                .get("type")
+                .and_then(|t| t.as_str())
+                .unwrap_or_default();
+            sse.push_str("event: ");
+            sse.push_str(name);
+            sse.push_str("\ndata: ");
+            sse.push_str(&frame.to_string());
+            sse.push_str("\n\n");
+        }
+        let pieces: Vec<String> = sse
+            .as_bytes()
+            .chunks(41)
+            .map(|c| String::from_utf8_lossy(c).into_owned())
+            .collect();
+        let server = tokio::spawn(serve_one_sse_response(listener, pieces));
+
+        let sampler = ResponsesSampler::new(ResponsesConfig::new(format!("http://{addr}"), "sk"))
+            .expect("client");
+        let events: Vec<SampleEvent> = sampler
+            .sample_stream(request("o-test", "hi"))
+            .await
+            .expect("stream")
+            .collect()
+            .await;
+        let raw = server.await.expect("join");
+        assert!(raw.starts_with("POST /v1/responses"), "{raw}");
+        assert!(raw.contains("reasoning.encrypted_content"), "{raw}");
+
+        assert!(
+            events
+                .iter()
+                .any(|e| matches!(e, SampleEvent::ReasoningDelta { text } if text == "plan"))
+        );
+        assert!(events.iter().any(|e| matches!(
+            e,
+            SampleEvent::Usage(u) if u.completion_details.reasoning_tokens == 7
+        )));
+        let Some(SampleEvent::Completed { message, .. }) = events.last() else {
+            panic!("expected Completed last: {events:?}");
+        };
+        assert_eq!(message.text(), "Hello");
+        assert!(message.parts.iter().any(|p| matches!(
+            p,
+            ContentPart::Opaque { provider, data }
+                if provider == RESPONSES_PROVIDER && data.contains("enc-blob")
+        )));
+
+        let mut next = request("o-test", "again");
+        next.messages.insert(0, message.clone());
+        let body = ovo_llm::build_responses_body(&next);
+        assert_eq!(
+            body.pointer("/input/0/encrypted_content"),
+            Some(&serde_json::json!("enc-blob"))
+        );
+    }
 }
@@ -0,0 +1,215 @@
+//! Procedural macros for Ovo.
+//!
+//! [`macro@tool`] turns a plain (async) fn into a typed `ovo_tools::Tool`.
+
+use proc_macro::TokenStream;
+use proc_macro_crate::{FoundCrate, crate_name};
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use quote::{format_ident, quote};
+use syn::{
+    Error, Expr, ExprLit, FnArg, GenericArgument, Ident, ItemFn, Lit, LitStr, Meta, Path,
+    PathArguments, ReturnType, Type, parse_macro_input, parse_quote,
+};
+
+/// Turn a fn into a unit struct implementing `ovo_tools::Tool`.
+///
+/// The fn (async or not) takes `args` or `ctx, args` (`ToolCallContext`, then a
+/// `Deserialize + JsonSchema` type) and returns `Result<Output, E>` with
+/// `E: Into<ToolError>`. `add_numbers` becomes `AddNumbersTool`, named
+/// `"add_numbers"` and described by the fn's doc comment; the fn itself is
+/// kept.
+///
+/// Options: `name = "..."`, `description = "..."`, `read_only` (metadata
+/// [`ToolMetadata::read_only`]), `crate = "path"` (defaults to `ovo_tools`,
+/// or `ovo::tools` through the facade).
+///
+/// [`ToolMetadata::read_only`]: https://docs.rs/ovo-tools/latest/ovo_tools/struct.ToolMetadata.html#method.read_only
+#[proc_macro_attribute]
+pub fn tool(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut opts = Options::default();
+    let parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("name") {
+            opts.name = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("description") {
+            opts.description = Some(meta.value()?.parse()?);
+        } else if meta.path.is_ident("read_only") {
+            opts.read_only = true;
+        } else if meta.path.is_ident("crate") {
+            opts.krate = Some(meta.value()?.parse::<LitStr>()?.parse()?);
+        } else {
+            return Err(meta.error("expected `name`, `description`, `read_only` or `crate`"));
+        }
+        Ok(())
+    });
+    parse_macro_input!(attr with parser);
+    let func = parse_macro_input!(item as ItemFn);
+    expand(opts, func)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Options {
+    name: Option<LitStr>,
+    description: Option<LitStr>,
+    read_only: bool,
+    krate: Option<Path>,
+}
+
+fn expand(opts: Options, func: ItemFn) -> syn::Result<TokenStream2> {
+    let sig = &func.sig;
+    let mut params = Vec::new();
+    for input in &sig.inputs {
+        match input {
+            FnArg::Typed(pat) => params.push(&pat.ty),
+            FnArg::Receiver(r) => return Err(Error::new_spanned(r, "#[tool] fn cannot take self")),
+        }
+    }
+    let (with_ctx, args_ty) = match params.as_slice() {
+        [args] => (false, args),
+        [_, args] => (true, args),
+        _ => {
+            return Err(Error::new_spanned(
+                &sig.inputs,
+                "#[tool] fn takes `args` or `ctx, args`",
+            ));
+        }
+    };
+    let output_ty = ok_type(&sig.output)?;
+
+    let krate = opts.krate.unwrap_or_else(tools_crate);
+    let fn_name = &sig.ident;
+    let name = opts
+        .name
+        .unwrap_or_else(|| LitStr::new(&fn_name.to_string(), fn_name.span()));
+    let description = match opts.description {
+        Some(d) => d,
+        None => LitStr::new(
+            &doc_text(&func).ok_or_else(|| {
+                Error::new_spanned(fn_name, "#[tool] needs a doc comment or `description`")
+            })?,
+            fn_name.span(),
+        ),
+    };
+    let struct_name = format_ident!("{}Tool", camel_case(fn_name));
+    let struct_doc = format!("Tool `{}` backed by [`{fn_name}`].", name.value());
+    let vis = &func.vis;
+    let metadata = opts.read_only.then(|| {
+        quote! {
+            fn metadata(&self) -> #krate::ToolMetadata {
+                #krate::ToolMetadata::read_only()
+            }
+        }
+    });
+    let (ctx, mut call) = if with_ctx {
+        (quote!(ctx), quote!(#fn_name(ctx, args)))
+    } else {
+        (quote!(_ctx), quote!(#fn_name(args)))
+    };
+    if sig.asyncness.is_some() {
+        call = quote!(#call.await);
+    }
+
+    Ok(quote! {
+        #func
+
+        #[doc = #struct_doc]
+        #[derive(Debug, Default, Clone, Copy)]
+        #vis struct #struct_name;
+
+        impl #krate::Tool for #struct_name {
+            type Args = #args_ty;
+            type Output = #output_ty;
+
+            fn name(&self) -> &str {
+                #name
+            }
+
+            fn description(&self) -> &str {
+                #description
+            }
+
+            #metadata
+
+            async fn call(
+                &self,
+                #ctx: #krate::ToolCallContext,
+                args: Self::Args,
+            ) -> ::core::result::Result<Self::Output, #krate::ToolError> {
+                #call.map_err(::core::convert::Into::into)
+            }
+        }
+    })
+}
+
+/// `T` from a `Result<T, E>` return type.
+fn ok_type(output: &ReturnType) -> syn::Result<&Type> {
+    let err = || Error::new_spanned(output, "#[tool] fn must return `Result<Output, E>`");
+    let ReturnType::Type(_, ty) = output else {
+        return Err(err());
+    };
+    let Type::Path(path) = &**ty else {
+        return Err(err());
+    };
+    let segment = path.path.segments.last().ok_or_else(err)?;
+    if segment.ident != "Result" {
+        return Err(err());
+    }
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return Err(err());
+    };
+    match args.args.first() {
+        Some(GenericArgument::Type(ok)) => Ok(ok),
+        _ => Err(err()),
+    }
+}
+
+/// Doc comment lines joined, or `None` when there are none.
+fn doc_text(func: &ItemFn) -> Option<String> {
+    let lines: Vec<String> = func
+        .attrs
+        .iter()
+        .filter_map(|attr| match &attr.meta {
+            Meta::NameValue(nv) if nv.path.is_ident("doc") => match &nv.value {
+                Expr::Lit(ExprLit {
+                    lit: Lit::Str(s), ..
+                }) => Some(s.value().trim().to_owned()),
+                _ => None,
+            },
+            _ => None,
+        })
+        .collect();
+    let text = lines.join("\n").trim().to_owned();
+    (!text.is_empty()).then_some(text)
+}
+
+fn camel_case(ident: &Ident) -> String {
+    ident
+        .to_string()
+        .split('_')
+        .map(|word| {
+            let mut chars = word.chars();
+            chars.next().map_or_else(String::new, |first| {
+                first.to_uppercase().chain(chars).collect()
+            })
+        })
+        .collect()
+}
+
+/// Path to `ovo_tools` as the calling crate sees it.
+fn tools_crate() -> Path {
+    let found = |name: &str| crate_name(name).ok();
+    match (found("ovo-tools"), found("ovo")) {
+        (Some(FoundCrate::Itself), _) => parse_quote!(crate),
+        (Some(FoundCrate::Name(name)), _) => {
+            let ident = Ident::new(&name, Span::call_site());
+            parse_quote!(::#ident)
+        }
+        (None, Some(FoundCrate::Name(name))) => {
+            let ident = Ident::new(&name, Span::call_site());
+            parse_quote!(::#ident::tools)
+        }
+        (None, Some(FoundCrate::Itself)) => parse_quote!(crate::tools),
+        (None, None) => parse_quote!(::ovo_tools),
+    }
+}
@@ -0,0 +1,208 @@
+//! Minimal stdio MCP server for offline tests.
+//!
+//! Tools: `echo` (text + structured), `fail` (`isError`), `slow` (progress
+//! notifications; `hang: true` waits for `notifications/cancelled`),
+//! `cancelled_requests` (ids seen in `notifications/cancelled`) and `add_tool`
+//! (registers `extra` and emits `notifications/tools/list_changed`).
+//! `tools/list` pages two tools at a time.
+#![allow(
+    unused_crate_dependencies,
+    reason = "bin target links the library's deps"
+)]
+
+use std::collections::HashSet;
+use std::io::{BufRead, Write};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::thread;
+use std::time::Duration;
+
+use serde_json::{Value, json};
+
+const PAGE: usize = 2;
+
+#[derive(Clone, Default)]
+struct Server {
+    out: Arc<Mutex<()>>,
+    cancelled: Arc<Mutex<HashSet<String>>>,
+    extra: Arc<AtomicBool>,
+}
+
+impl Server {
+    fn write(&self, message: &Value) {
+        let _guard = self.out.lock().unwrap_or_else(PoisonError::into_inner);
+        let mut stdout = std::io::stdout().lock();
+        let _ = writeln!(stdout, "{message}");
+        let _ = stdout.flush();
+    }
+
+    fn reply(&self, id: &Value, result: Value) {
+        self.write(&json!({"jsonrpc": "2.0", "id": id, "result": result}));
+    }
+
+    fn error(&self, id: &Value, code: i64, message: &str) {
+        self.write(
+            &json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}}),
+        );
+    }
+
+    fn is_cancelled(&self, id: &Value) -> bool {
+        self.cancelled
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .contains(&id.to_string())
+    }
+
+    fn tools(&self) -> Vec<Value> {
+        let object = |props: Value| json!({"type": "object", "properties": props});
+        let mut tools = vec![
+            json!({"name": "add_tool", "description": "Register `extra`", "inputSchema": object(json!({}))}),
+            json!({"name": "cancelled_requests", "description": "Cancelled ids",
+                   "inputSchema": object(json!({})), "annotations": {"readOnlyHint": true}}),
+            json!({"name": "echo", "description": "Echo text",
+                   "inputSchema": {"type": "object", "properties": {"text": {"type": "string"}}, "required": ["text"]},
+                   "annotations": {"readOnlyHint": true, "openWorldHint": false}}),
+            json!({"name": "fail", "description": "Always fails", "inputSchema": object(json!({}))}),
+            json!({"name": "slow", "description": "Reports progress",
+                   "inputSchema": object(json!({"steps": {"type": "integer"}, "hang": {"type": "boolean"}}))}),
+        ];
+        if self.extra.load(Ordering::SeqCst) {
+            tools.push(json!({"name": "extra", "description": "Added later", "inputSchema": object(json!({}))}));
+        }
+        tools
+    }
+
+    fn list(&self, id: &Value, params: &Value) {
+        let start = params
+            .get("cursor")
+            .and_then(Value::as_str)
+            .and_then(|c| c.parse::<usize>().ok())
+            .unwrap_or(0);
+        let tools = self.tools();
+        let page: Vec<Value> = tools.iter().skip(start).take(PAGE).cloned().collect();
+        let next = start + PAGE;
+        let cursor = (next < tools.len()).then(|| next.to_string());
+        self.reply(id, json!({"tools": page, "nextCursor": cursor}));
+    }
+
+    fn call(&self, id: Value, params: &Value) {
+        let args = params.get("arguments").cloned().unwrap_or(Value::Null);
+        let token = params.pointer("/_meta/progressToken").cloned();
+        match params.get("name").and_then(Value::as_str) {
+            Some("echo") => {
+                let text = args.get("text").and_then(Value::as_str).unwrap_or_default();
+                self.reply(
+                    &id,
+                    json!({
+                        "content": [{"type": "text", "text": text}],
+                        "structuredContent": {"text": text}
+                    }),
+                );
+            }
+            Some("fail") => self.reply(
+                &id,
+                json!({
+                    "content": [{"type": "text", "text": "boom"}],
+                    "isError": true
+                }),
+            ),
+            Some("cancelled_requests") => {
+                let mut ids: Vec<String> = self
+                    .cancelled
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .iter()
+                    .cloned()
+                    .collect();
+                ids.sort();
+                self.reply(
+                    &id,
+                    json!({"content": [{"type": "text", "text": ids.join(",")}]}),
+                );
+            }
+            Some("add_tool") => {
+                self.extra.store(true, Ordering::SeqCst);
+                self.reply(&id, json!({"content": [{"type": "text", "text": "added"}]}));
+                self.write(
+                    &json!({"jsonrpc": "2.0", "method": "notifications/tools/list_changed"}),
+                );
+            }
+            Some("slow") => {
+                let server = self.clone();
+                thread::spawn(move || server.slow(&id, &args, token));
+            }
+            _ => self.error(&id, -32602, "unknown tool"),
+        }
+    }
+
+    #[allow(
+        clippy::disallowed_methods,
+        reason = "sync fake server paces progress on its own thread"
+    )]
+    fn slow(&self, id: &Value, args: &Value, token: Option<Value>) {
+        let steps = args.get("steps").and_then(Value::as_u64).unwrap_or(2);
+        for step in 1..=steps {
+            if let Some(token) = &token {
+                self.write(&json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/progress",
+                    "params": {"progressToken": token, "progress": step, "total": steps,
+                               "message": format!("step {step}")}
+                }));
+            }
+            thread::sleep(Duration::from_millis(10));
+        }
+        if args.get("hang").and_then(Value::as_bool) == Some(true) {
+            while !self.is_cancelled(id) {
+                thread::sleep(Duration::from_millis(5));
+            }
+            return;
+        }
+        self.reply(
+            id,
+            json!({"content": [{"type": "text", "text": format!("{steps} steps")}]}),
+        );
+    }
+
+    fn handle(&self, message: Value) {
+        let method = message
+            .get("method")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let params = message.get("params").cloned().unwrap_or(Value::Null);
+        let Some(id) = message.get("id").cloned() else {
+            if method == "notifications/cancelled"
+                && let Some(request) = params.get("requestId")
+            {
+                self.cancelled
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .insert(request.to_string());
+            }
+            return;
+        };
+        match method {
+            "initialize" => self.reply(&id, json!({
+                "protocolVersion": params.get("protocolVersion").cloned().unwrap_or(Value::Null),
+                "capabilities": {"tools": {"listChanged": true}},
+                "serverInfo": {"name": "ovo-mcp-fake", "version": "0"}
+            })),
+            "ping" => self.reply(&id, json!({})),
+            "tools/list" => self.list(&id, &params),
+            "tools/call" => self.call(id, &params),
+            _ => self.error(&id, -32601, "method not found"),
+        }
+    }
+}
+
+fn main() {
+    let server = Server::default();
+    for line in std::io::stdin().lock().lines() {
+        let Ok(line) = line else {
+            break;
+        };
+        if let Ok(message) = serde_json::from_str(&line) {
+            server.handle(message);
+        }
+    }
+}
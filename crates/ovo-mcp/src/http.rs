@@ -0,0 +1,241 @@
+//! Streamable HTTP transport: JSON-RPC `POST`s answered with JSON or an SSE
+//! stream, plus an optional `GET` stream for server-initiated messages.
+//!
+//! Each request is posted from its own task so slow SSE replies never block
+//! the connection; HTTP failures come back as synthetic JSON-RPC errors for
+//! that request id. The session id from `initialize` is echoed on every call.
+
+use std::sync::{Arc, Mutex, PoisonError};
+
+use async_trait::async_trait;
+use ovo_llm::SseDecoder;
+use ovo_tools::ToolError;
+use ovo_tools::error::codes;
+use serde_json::Value;
+use tokio::sync::mpsc;
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+use crate::protocol::{self, PROTOCOL_VERSION};
+use crate::rpc::Transport;
+
+const SESSION_HEADER: &str = "mcp-session-id";
+/// JSON-RPC server error used for transport failures.
+const TRANSPORT_ERROR: i64 = -32000;
+
+/// Connection settings for a streamable HTTP MCP server.
+#[derive(Debug, Clone)]
+pub struct HttpServer {
+    url: String,
+    headers: Vec<(String, String)>,
+}
+
+impl HttpServer {
+    /// MCP endpoint URL (e.g. `https://host/mcp`).
+    #[must_use]
+    pub fn new(url: impl Into<String>) -> Self {
+        Self {
+            url: url.into(),
+            headers: Vec::new(),
+        }
+    }
+
+    /// Extra header sent on every request (auth, tenancy, …).
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// `Authorization: Bearer <token>`.
+    #[must_use]
+    pub fn with_bearer_token(self, token: impl AsRef<str>) -> Self {
+        let value = format!("Bearer {}", token.as_ref());
+        self.with_header("authorization", value)
+    }
+}
+
+#[derive(Clone)]
+struct Conn {
+    client: reqwest::Client,
+    server: HttpServer,
+    session: Arc<Mutex<Option<String>>>,
+    inbound: mpsc::UnboundedSender<Value>,
+}
+
+pub(crate) struct HttpTransport {
+    conn: Conn,
+    stop: CancellationToken,
+    _stop_on_drop: DropGuard,
+}
+
+impl HttpTransport {
+    pub(crate) fn new(
+        server: &HttpServer,
+        inbound: mpsc::UnboundedSender<Value>,
+    ) -> Result<Self, ToolError> {
+        let client = reqwest::Client::builder()
+            .build()
+            .map_err(|e| codes::execution(format!("mcp http client: {e}")))?;
+        let stop = CancellationToken::new();
+        Ok(Self {
+            conn: Conn {
+                client,
+                server: server.clone(),
+                session: Arc::new(Mutex::new(None)),
+                inbound,
+            },
+            _stop_on_drop: stop.clone().drop_guard(),
+            stop,
+        })
+    }
+}
+
+#[async_trait]
+impl Transport for HttpTransport {
+    async fn send(&self, message: Value) -> Result<(), ToolError> {
+        let is_request = message.get("method").is_some() && message.get("id").is_some();
+        if !is_request {
+            let resp = self.conn.post(&message).await?;
+            return check_status(&resp);
+        }
+        let conn = self.conn.clone();
+        let stop = self.stop.clone();
+        tokio::spawn(async move {
+            let id = message.get("id").cloned().unwrap_or(Value::Null);
+            let delivered = async {
+                let resp = conn.post(&message).await?;
+                check_status(&resp)?;
+                conn.pump(resp).await
+            };
+            tokio::select! {
+                () = stop.cancelled() => {}
+                res = delivered => {
+                    if let Err(e) = res {
+                        let reply = protocol::error_response(id, TRANSPORT_ERROR, e.message());
+                        let _ = conn.inbound.send(reply);
+                    }
+                }
+            }
+        });
+        Ok(())
+    }
+
+    fn initialized(&self) {
+        let conn = self.conn.clone();
+        let stop = self.stop.clone();
+        tokio::spawn(async move {
+            tokio::select! {
+                () = stop.cancelled() => {}
+                res = conn.listen() => {
+                    if let Err(e) = res {
+                        tracing::debug!(error = %e, "mcp http: no server event stream");
+                    }
+                }
+            }
+        });
+    }
+}
+
+impl Conn {
+    fn request(&self, method: reqwest::Method) -> reqwest::RequestBuilder {
+        let mut req = self
+            .client
+            .request(method, &self.server.url)
+            .header("accept", "application/json, text/event-stream")
+            .header("mcp-protocol-version", PROTOCOL_VERSION);
+        for (name, value) in &self.server.headers {
+            req = req.header(name, value);
+        }
+        let session = self
+            .session
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone();
+        match session {
+            Some(session) => req.header(SESSION_HEADER, session),
+            None => req,
+        }
+    }
+
+    async fn post(&self, message: &Value) -> Result<reqwest::Response, ToolError> {
+        let resp = self
+            .request(reqwest::Method::POST)
+            .header("content-type", "application/json")
+            .body(message.to_string())
+            .send()
+            .await
+            .map_err(|e| codes::network(format!("mcp http: {e}")))?;
+        if let Some(session) = resp
+            .headers()
+            .get(SESSION_HEADER)
+            .and_then(|v| v.to_str().ok())
+        {
+            *self.session.lock().unwrap_or_else(PoisonError::into_inner) = Some(session.to_owned());
+        }
+        Ok(resp)
+    }
+
+    /// Long-lived `GET` stream; servers without one answer 405.
+    async fn listen(&self) -> Result<(), ToolError> {
+        let resp = self
+            .request(reqwest::Method::GET)
+            .header("accept", "text/event-stream")
+            .send()
+            .await
+            .map_err(|e| codes::network(format!("mcp http: {e}")))?;
+        check_status(&resp)?;
+        self.pump(resp).await
+    }
+
+    /// Forward every JSON-RPC message in a JSON or SSE response body.
+    async fn pump(&self, mut resp: reqwest::Response) -> Result<(), ToolError> {
+        let is_sse = resp
+            .headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok())
+            .is_some_and(|ct| ct.starts_with("text/event-stream"));
+        let read_err = |e: reqwest::Error| codes::network(format!("mcp http body: {e}"));
+        if !is_sse {
+            let body = resp.bytes().await.map_err(read_err)?;
+            if !body.is_empty() {
+                self.forward(&body);
+            }
+            return Ok(());
+        }
+        let mut decoder = SseDecoder::new();
+        while let Some(chunk) = resp.chunk().await.map_err(read_err)? {
+            for event in decoder.push(&chunk) {
+                self.forward(event.data.as_bytes());
+            }
+        }
+        if let Some(event) = decoder.finish() {
+            self.forward(event.data.as_bytes());
+        }
+        Ok(())
+    }
+
+    fn forward(&self, body: &[u8]) {
+        match serde_json::from_slice(body) {
+            Ok(Value::Array(batch)) => {
+                for message in batch {
+                    let _ = self.inbound.send(message);
+                }
+            }
+            Ok(message) => {
+                let _ = self.inbound.send(message);
+            }
+            Err(e) => tracing::warn!(error = %e, "mcp http: non-JSON message ignored"),
+        }
+    }
+}
+
+fn check_status(resp: &reqwest::Response) -> Result<(), ToolError> {
+    let status = resp.status();
+    if status.is_success() {
+        Ok(())
+    } else {
+        Err(codes::service_unavailable(format!(
+            "mcp http status {status}"
+        )))
+    }
+}
@@ -0,0 +1,26 @@
+//! Model Context Protocol (MCP) integration for Ovo.
+//!
+//! [`McpToolSource`] launches (stdio) or connects to (streamable HTTP, feature
+//! `http`) an MCP server and exposes its tools as [`ovo_tools::SharedTool`]s
+//! for merging into a [`ovo_tools::ToolRegistry`]. [`McpServer`] goes the other
+//! way and serves a registry to MCP clients over stdio.
+
+#![forbid(unsafe_code)]
+
+#[cfg(feature = "http")]
+pub mod http;
+pub mod protocol;
+mod rpc;
+pub mod server;
+pub mod source;
+pub mod stdio;
+
+#[cfg(feature = "http")]
+pub use http::HttpServer;
+pub use protocol::{
+    PROGRESS_SUBKIND, PROTOCOL_VERSION, annotations_from_metadata, metadata_from_annotations,
+    progress_from_mcp, tool_result_from_mcp, tool_result_to_mcp,
+};
+pub use server::McpServer;
+pub use source::{McpConfig, McpToolSource};
+pub use stdio::StdioServer;
@@ -0,0 +1,293 @@
+//! MCP JSON-RPC framing and tool-shape mapping.
+//!
+//! Pure `Value` helpers shared by transports and the tool source: message
+//! construction and classification, `tools/call` results → [`ToolResult`],
+//! `notifications/progress` → [`ToolProgress`], and tool annotations →
+//! [`ToolMetadata`].
+
+use ovo_tools::error::codes;
+use ovo_tools::{
+    CapabilityFlag, Destructiveness, ToolError, ToolMetadata, ToolProgress, ToolResult,
+};
+use serde_json::{Value, json};
+
+/// MCP protocol revision sent in `initialize`.
+pub const PROTOCOL_VERSION: &str = "2025-06-18";
+
+/// [`ToolProgress::Custom`] subkind for MCP progress notifications.
+pub const PROGRESS_SUBKIND: &str = "mcp_progress";
+
+/// JSON-RPC "method not found".
+pub(crate) const METHOD_NOT_FOUND: i64 = -32601;
+/// JSON-RPC "invalid params".
+pub(crate) const INVALID_PARAMS: i64 = -32602;
+
+/// One decoded inbound JSON-RPC message.
+#[derive(Debug)]
+pub(crate) enum Incoming {
+    /// Reply to one of our requests (`Err` carries the JSON-RPC error object).
+    Response {
+        id: u64,
+        result: Result<Value, Value>,
+    },
+    /// Request from the peer.
+    Request {
+        id: Value,
+        method: String,
+        params: Value,
+    },
+    /// Notification from the peer.
+    Notification { method: String, params: Value },
+}
+
+impl Incoming {
+    /// Classify a JSON-RPC message; `None` for malformed input.
+    pub(crate) fn parse(mut msg: Value) -> Option<Self> {
+        let obj = msg.as_object_mut()?;
+        let params = obj.remove("params").unwrap_or(Value::Null);
+        match (obj.remove("method"), obj.remove("id")) {
+            (Some(Value::String(method)), Some(id)) => Some(Self::Request { id, method, params }),
+            (Some(Value::String(method)), None) => Some(Self::Notification { method, params }),
+            (None, Some(id)) => {
+                let id = id.as_u64()?;
+                let result = match (obj.remove("result"), obj.remove("error")) {
+                    (_, Some(error)) => Err(error),
+                    (Some(result), None) => Ok(result),
+                    (None, None) => Ok(Value::Null),
+                };
+                Some(Self::Response { id, result })
+            }
+            _ => None,
+        }
+    }
+}
+
+/// JSON-RPC request.
+pub(crate) fn request(id: u64, method: &str, params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params})
+}
+
+/// JSON-RPC notification.
+pub(crate) fn notification(method: &str, params: Value) -> Value {
+    json!({"jsonrpc": "2.0", "method": method, "params": params})
+}
+
+/// JSON-RPC success response.
+pub(crate) fn response(id: Value, result: Value) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "result": result})
+}
+
+/// JSON-RPC error response.
+pub(crate) fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+}
+
+/// Tool error for a JSON-RPC error object returned by `method`.
+pub(crate) fn rpc_error(method: &str, error: &Value) -> ToolError {
+    let code = error.get("code").and_then(Value::as_i64).unwrap_or(0);
+    let message = error
+        .get("message")
+        .and_then(Value::as_str)
+        .unwrap_or("unknown error");
+    let msg = format!("mcp {method}: {message} ({code})");
+    if code == INVALID_PARAMS {
+        codes::invalid_args(msg)
+    } else {
+        codes::execution(msg)
+    }
+}
+
+/// Map an MCP `CallToolResult` onto a [`ToolResult`].
+///
+/// Text blocks are joined; other blocks are summarized by type.
+/// `structuredContent` becomes [`ToolResult::structured`].
+#[must_use]
+pub fn tool_result_from_mcp(result: &Value) -> ToolResult {
+    let blocks = result
+        .get("content")
+        .and_then(Value::as_array)
+        .map(Vec::as_slice)
+        .unwrap_or_default();
+    let content = blocks
+        .iter()
+        .map(|block| match block.get("type").and_then(Value::as_str) {
+            Some("text") => block
+                .get("text")
+                .and_then(Value::as_str)
+                .unwrap_or_default()
+                .to_owned(),
+            Some(kind) => {
+                let mime = block.get("mimeType").and_then(Value::as_str);
+                mime.map_or_else(|| format!("[{kind}]"), |m| format!("[{kind}: {m}]"))
+            }
+            None => block.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n");
+    ToolResult {
+        content,
+        structured: result
+            .get("structuredContent")
+            .filter(|v| !v.is_null())
+            .cloned(),
+        is_error: result
+            .get("isError")
+            .and_then(Value::as_bool)
+            .unwrap_or(false),
+    }
+}
+
+/// Map a tool outcome onto an MCP `CallToolResult`.
+///
+/// Tool failures become `isError` results (the model sees them), not JSON-RPC
+/// errors.
+#[must_use]
+pub fn tool_result_to_mcp(result: &Result<ToolResult, ToolError>) -> Value {
+    match result {
+        Ok(r) => {
+            let mut out = json!({
+                "content": [{"type": "text", "text": r.content}],
+                "isError": r.is_error,
+            });
+            if let (Some(structured), Some(obj)) = (&r.structured, out.as_object_mut()) {
+                obj.insert("structuredContent".to_owned(), structured.clone());
+            }
+            out
+        }
+        Err(e) => json!({
+            "content": [{"type": "text", "text": e.to_string()}],
+            "isError": true,
+        }),
+    }
+}
+
+/// Map `notifications/progress` params onto a [`ToolProgress::Custom`]
+/// (`progress`, `total`, `message`).
+#[must_use]
+pub fn progress_from_mcp(params: &Value) -> ToolProgress {
+    let mut payload = serde_json::Map::new();
+    for key in ["progress", "total", "message"] {
+        if let Some(v) = params.get(key) {
+            payload.insert(key.to_owned(), v.clone());
+        }
+    }
+    ToolProgress::Custom {
+        subkind: PROGRESS_SUBKIND.to_owned(),
+        payload: Value::Object(payload),
+    }
+}
+
+/// Progress token as a map key (tokens may be strings or numbers).
+pub(crate) fn token_key(token: &Value) -> String {
+    token
+        .as_str()
+        .map_or_else(|| token.to_string(), str::to_owned)
+}
+
+/// [`ToolMetadata`] from MCP tool `annotations` (spec defaults when absent).
+///
+/// `readOnlyHint` → read-only; otherwise a write tool, irreversible unless
+/// `destructiveHint` is `false`. `openWorldHint` (default `true`) adds
+/// [`CapabilityFlag::Network`].
+#[must_use]
+pub fn metadata_from_annotations(annotations: Option<&Value>) -> ToolMetadata {
+    let hint = |key: &str| {
+        annotations
+            .and_then(|a| a.get(key))
+            .and_then(Value::as_bool)
+    };
+    let mut meta = if hint("readOnlyHint") == Some(true) {
+        ToolMetadata::read_only()
+    } else {
+        let mut meta = ToolMetadata::default();
+        meta.capabilities.push(CapabilityFlag::Write);
+        meta.destructiveness = if hint("destructiveHint") == Some(false) {
+            Destructiveness::Reversible
+        } else {
+            Destructiveness::Irreversible
+        };
+        meta
+    };
+    if hint("openWorldHint") != Some(false) {
+        meta.capabilities.push(CapabilityFlag::Network);
+    }
+    meta
+}
+
+/// MCP tool `annotations` for a [`ToolMetadata`] (inverse of
+/// [`metadata_from_annotations`]).
+#[must_use]
+pub fn annotations_from_metadata(meta: &ToolMetadata) -> Value {
+    json!({
+        "readOnlyHint": meta.allowed_in_read_only(),
+        "destructiveHint": meta.destructiveness == Destructiveness::Irreversible,
+        "openWorldHint": meta.capabilities.contains(&CapabilityFlag::Network),
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_messages() {
+        let resp = Incoming::parse(json!({"jsonrpc": "2.0", "id": 3, "result": {"ok": true}}));
+        assert!(matches!(
+            resp,
+            Some(Incoming::Response {
+                id: 3,
+                result: Ok(_)
+            })
+        ));
+        let err = Incoming::parse(json!({"jsonrpc": "2.0", "id": 4, "error": {"code": -1}}));
+        assert!(matches!(
+            err,
+            Some(Incoming::Response {
+                id: 4,
+                result: Err(_)
+            })
+        ));
+        let req = Incoming::parse(json!({"jsonrpc": "2.0", "id": "a", "method": "ping"}));
+        assert!(matches!(req, Some(Incoming::Request { ref method, .. }) if method == "ping"));
+        let note = Incoming::parse(notification("notifications/tools/list_changed", json!({})));
+        assert!(matches!(note, Some(Incoming::Notification { .. })));
+        assert!(Incoming::parse(json!([1, 2])).is_none());
+    }
+
+    #[test]
+    fn maps_results_and_annotations() {
+        let out = tool_result_from_mcp(&json!({
+            "content": [
+                {"type": "text", "text": "a"},
+                {"type": "image", "data": "…", "mimeType": "image/png"}
+            ],
+            "structuredContent": {"n": 1},
+            "isError": true
+        }));
+        assert_eq!(out.content, "a\n[image: image/png]");
+        assert_eq!(out.structured, Some(json!({"n": 1})));
+        assert!(out.is_error);
+
+        let ro =
+            metadata_from_annotations(Some(&json!({"readOnlyHint": true, "openWorldHint": false})));
+        assert!(ro.allowed_in_read_only());
+        let write = metadata_from_annotations(None);
+        assert_eq!(write.destructiveness, Destructiveness::Irreversible);
+        assert!(write.capabilities.contains(&CapabilityFlag::Network));
+        let round = metadata_from_annotations(Some(&annotations_from_metadata(&ro)));
+        assert!(round.allowed_in_read_only());
+        assert!(!round.capabilities.contains(&CapabilityFlag::Network));
+
+        let back = tool_result_to_mcp(&Ok(out));
+        assert_eq!(back.pointer("/structuredContent/n"), Some(&json!(1)));
+        let failed = tool_result_to_mcp(&Err(codes::cancelled()));
+        assert_eq!(failed.get("isError"), Some(&json!(true)));
+
+        let err = rpc_error(
+            "tools/call",
+            &json!({"code": INVALID_PARAMS, "message": "bad"}),
+        );
+        assert_eq!(err.code(), ovo_types::ErrorCode::ToolInvalidArgs);
+    }
+}
@@ -0,0 +1,215 @@
+//! JSON-RPC session over a transport: request ids, pending replies,
+//! progress routing and `tools/list_changed` signalling.
+//!
+//! Transports push every inbound message into one channel; a router task
+//! (holding only a `Weak`) matches responses to pending requests, answers
+//! peer `ping`s and fans out notifications. Dropping the last [`Rpc`] drops
+//! the transport, which closes the connection.
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ovo_tools::error::codes;
+use ovo_tools::{ToolError, ToolProgress};
+use serde_json::{Value, json};
+use tokio::sync::{mpsc, oneshot, watch};
+use tokio_util::sync::CancellationToken;
+
+use crate::protocol::{self, Incoming, METHOD_NOT_FOUND};
+
+/// Outbound half of a connection.
+#[async_trait]
+pub(crate) trait Transport: Send + Sync {
+    /// Send one JSON-RPC message.
+    async fn send(&self, message: Value) -> Result<(), ToolError>;
+
+    /// Called once the `initialize` handshake completes.
+    fn initialized(&self) {}
+}
+
+type Reply = Result<Value, Value>;
+
+pub(crate) struct Rpc {
+    transport: Box<dyn Transport>,
+    next_id: AtomicU64,
+    pending: Mutex<HashMap<u64, oneshot::Sender<Reply>>>,
+    progress: Mutex<HashMap<String, mpsc::UnboundedSender<ToolProgress>>>,
+    list_changed: watch::Sender<u64>,
+    closed: AtomicBool,
+}
+
+impl Rpc {
+    /// Wrap `transport` and start routing `inbound`.
+    pub(crate) fn start(
+        transport: Box<dyn Transport>,
+        inbound: mpsc::UnboundedReceiver<Value>,
+    ) -> Arc<Self> {
+        let rpc = Arc::new(Self {
+            transport,
+            next_id: AtomicU64::new(1),
+            pending: Mutex::new(HashMap::new()),
+            progress: Mutex::new(HashMap::new()),
+            list_changed: watch::Sender::new(0),
+            closed: AtomicBool::new(false),
+        });
+        tokio::spawn(route(Arc::downgrade(&rpc), inbound));
+        rpc
+    }
+
+    /// Send a request and await its result.
+    ///
+    /// On `cancel` (or `limit` elapsing) the request is abandoned and the peer
+    /// gets `notifications/cancelled`.
+    pub(crate) async fn request(
+        &self,
+        method: &str,
+        params: Value,
+        cancel: &CancellationToken,
+        limit: Option<Duration>,
+    ) -> Result<Value, ToolError> {
+        let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+        let (tx, rx) = oneshot::channel();
+        self.pending().insert(id, tx);
+        if self.closed.load(Ordering::Acquire) {
+            self.pending().remove(&id);
+            return Err(closed());
+        }
+        if let Err(e) = self
+            .transport
+            .send(protocol::request(id, method, params))
+            .await
+        {
+            self.pending().remove(&id);
+            return Err(e);
+        }
+        let reply = async {
+            match limit {
+                Some(limit) => tokio::time::timeout(limit, rx).await.ok(),
+                None => Some(rx.await),
+            }
+        };
+        tokio::select! {
+            () = cancel.cancelled() => {
+                self.abandon(id, "cancelled").await;
+                Err(codes::cancelled())
+            }
+            reply = reply => match reply {
+                None => {
+                    self.abandon(id, "timed out").await;
+                    Err(codes::timeout(format!("mcp {method} timed out")))
+                }
+                Some(Err(_)) => Err(closed()),
+                Some(Ok(Ok(result))) => Ok(result),
+                Some(Ok(Err(error))) => Err(protocol::rpc_error(method, &error)),
+            },
+        }
+    }
+
+    /// Send a notification.
+    pub(crate) async fn notify(&self, method: &str, params: Value) -> Result<(), ToolError> {
+        self.transport
+            .send(protocol::notification(method, params))
+            .await
+    }
+
+    /// Signal the transport that the handshake is done.
+    pub(crate) fn initialized(&self) {
+        self.transport.initialized();
+    }
+
+    /// Fresh progress token plus the receiver its notifications go to.
+    pub(crate) fn watch_progress(&self) -> (String, mpsc::UnboundedReceiver<ToolProgress>) {
+        let token = format!("ovo-{}", self.next_id.fetch_add(1, Ordering::Relaxed));
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .insert(token.clone(), tx);
+        (token, rx)
+    }
+
+    /// Stop routing progress for `token`.
+    pub(crate) fn unwatch_progress(&self, token: &str) {
+        self.progress
+            .lock()
+            .unwrap_or_else(PoisonError::into_inner)
+            .remove(token);
+    }
+
+    /// Bumped on every `notifications/tools/list_changed`.
+    pub(crate) fn list_changed(&self) -> watch::Receiver<u64> {
+        self.list_changed.subscribe()
+    }
+
+    fn pending(&self) -> std::sync::MutexGuard<'_, HashMap<u64, oneshot::Sender<Reply>>> {
+        self.pending.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+
+    async fn abandon(&self, id: u64, reason: &str) {
+        self.pending().remove(&id);
+        let params = json!({"requestId": id, "reason": reason});
+        if let Err(e) = self.notify("notifications/cancelled", params).await {
+            tracing::debug!(error = %e, "mcp cancel notification not sent");
+        }
+    }
+
+    async fn handle(&self, message: Value) {
+        match Incoming::parse(message) {
+            Some(Incoming::Response { id, result }) => {
+                let waiter = self.pending().remove(&id);
+                if let Some(tx) = waiter {
+                    let _ = tx.send(result);
+                }
+            }
+            Some(Incoming::Request { id, method, .. }) => {
+                let reply = if method == "ping" {
+                    protocol::response(id, json!({}))
+                } else {
+                    protocol::error_response(id, METHOD_NOT_FOUND, "method not supported")
+                };
+                if let Err(e) = self.transport.send(reply).await {
+                    tracing::debug!(error = %e, %method, "mcp reply not sent");
+                }
+            }
+            Some(Incoming::Notification { method, params }) => match method.as_str() {
+                "notifications/progress" => {
+                    let key = params.get("progressToken").map(protocol::token_key);
+                    let progress = self.progress.lock().unwrap_or_else(PoisonError::into_inner);
+                    if let Some(tx) = key.and_then(|k| progress.get(&k)) {
+                        let _ = tx.send(protocol::progress_from_mcp(&params));
+                    }
+                }
+                "notifications/tools/list_changed" => {
+                    self.list_changed.send_modify(|n| *n = n.wrapping_add(1));
+                }
+                _ => tracing::trace!(%method, "mcp notification ignored"),
+            },
+            None => tracing::warn!("mcp: malformed JSON-RPC message ignored"),
+        }
+    }
+
+    fn close(&self) {
+        self.closed.store(true, Ordering::Release);
+        // Dropping the senders fails every waiter.
+        self.pending().clear();
+    }
+}
+
+fn closed() -> ToolError {
+    codes::service_unavailable("mcp server closed the connection")
+}
+
+async fn route(rpc: Weak<Rpc>, mut inbound: mpsc::UnboundedReceiver<Value>) {
+    while let Some(message) = inbound.recv().await {
+        let Some(rpc) = rpc.upgrade() else {
+            return;
+        };
+        rpc.handle(message).await;
+    }
+    if let Some(rpc) = rpc.upgrade() {
+        rpc.close();
+    }
+}
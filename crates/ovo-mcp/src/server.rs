@@ -0,0 +1,358 @@
+//! [`McpServer`]: serve a [`ToolRegistry`] to MCP clients.
+//!
+//! Calls go through [`ToolDispatch`], so capability filtering, argument
+//! validation, approval and per-tool timeouts behave exactly as in a turn.
+//! Progress frames surface as `notifications/progress` (when the client sent a
+//! `progressToken`) and `notifications/cancelled` cancels the call's
+//! [`ToolCallContext::cancel`].
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use ovo_protocol::{TurnEvent, TurnEventKind};
+use ovo_tools::error::codes;
+use ovo_tools::{
+    ApprovalGate, ApprovalPolicy, CapabilityMode, DispatchRequest, EventBus, ToolCallContext,
+    ToolDispatch, ToolError, ToolRegistry,
+};
+use ovo_types::{RunId, ToolCall, ToolCallId};
+use serde_json::{Value, json};
+use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
+
+use crate::protocol::{self, INVALID_PARAMS, Incoming, METHOD_NOT_FOUND, PROTOCOL_VERSION};
+
+/// MCP server over a [`ToolRegistry`].
+///
+/// Defaults: [`CapabilityMode::Full`], [`ovo_tools::AutoApprove`] and
+/// [`ApprovalPolicy::Destructive`]. Only tools admitted by the capability mode
+/// are listed or callable.
+#[derive(Clone)]
+pub struct McpServer {
+    registry: ToolRegistry,
+    dispatch: ToolDispatch,
+    context: ToolCallContext,
+    name: String,
+    version: String,
+}
+
+impl std::fmt::Debug for McpServer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpServer")
+            .field("registry", &self.registry)
+            .field("dispatch", &self.dispatch)
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl McpServer {
+    /// Serve `registry` with default dispatch settings.
+    #[must_use]
+    pub fn new(registry: ToolRegistry) -> Self {
+        Self {
+            registry,
+            dispatch: ToolDispatch::default(),
+            context: ToolCallContext::default(),
+            name: "ovo".to_owned(),
+            version: env!("CARGO_PKG_VERSION").to_owned(),
+        }
+    }
+
+    /// Builder: capability mode (filters `tools/list` and `tools/call`).
+    #[must_use]
+    pub fn with_capability(mut self, mode: CapabilityMode) -> Self {
+        self.dispatch = self.dispatch.with_capability(mode);
+        self
+    }
+
+    /// Builder: approval gate consulted before calls.
+    #[must_use]
+    pub fn with_approval(mut self, gate: Arc<dyn ApprovalGate>) -> Self {
+        self.dispatch = self.dispatch.with_approval(gate);
+        self
+    }
+
+    /// Builder: when the approval gate is consulted.
+    #[must_use]
+    pub fn with_approval_policy(mut self, policy: ApprovalPolicy) -> Self {
+        self.dispatch = self.dispatch.with_approval_policy(policy);
+        self
+    }
+
+    /// Builder: base context for every call (cwd, extras, …). Cancelling its
+    /// token cancels all in-flight calls.
+    #[must_use]
+    pub fn with_context(mut self, ctx: ToolCallContext) -> Self {
+        self.context = ctx;
+        self
+    }
+
+    /// Builder: `serverInfo` reported from `initialize`.
+    #[must_use]
+    pub fn with_server_info(mut self, name: impl Into<String>, version: impl Into<String>) -> Self {
+        self.name = name.into();
+        self.version = version.into();
+        self
+    }
+
+    /// Serve on this process's stdin / stdout until stdin closes.
+    ///
+    /// # Errors
+    ///
+    /// I/O failure on stdin or stdout.
+    pub async fn serve_stdio(&self) -> Result<(), ToolError> {
+        self.serve(tokio::io::stdin(), tokio::io::stdout()).await
+    }
+
+    /// Serve newline-delimited JSON-RPC until `reader` reaches EOF; in-flight
+    /// calls are then cancelled and, like any cancelled request, get no reply.
+    ///
+    /// # Errors
+    ///
+    /// I/O failure on either stream.
+    pub async fn serve<R, W>(&self, reader: R, mut writer: W) -> Result<(), ToolError>
+    where
+        R: AsyncRead + Unpin,
+        W: AsyncWrite + Unpin,
+    {
+        let (out, mut outbox) = mpsc::unbounded_channel::<Value>();
+        let read = self.read_loop(reader, out);
+        let write = async move {
+            while let Some(message) = outbox.recv().await {
+                let mut line = message.to_string();
+                line.push('\n');
+                writer.write_all(line.as_bytes()).await.map_err(io_error)?;
+                writer.flush().await.map_err(io_error)?;
+            }
+            Ok(())
+        };
+        tokio::try_join!(read, write).map(|_| ())
+    }
+
+    async fn read_loop<R>(
+        &self,
+        reader: R,
+        out: mpsc::UnboundedSender<Value>,
+    ) -> Result<(), ToolError>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let in_flight: InFlight = Arc::default();
+        let mut lines = BufReader::new(reader).lines();
+        let result = loop {
+            let line = match lines.next_line().await {
+                Ok(Some(line)) => line,
+                Ok(None) => break Ok(()),
+                Err(e) => break Err(io_error(e)),
+            };
+            if line.trim().is_empty() {
+                continue;
+            }
+            match serde_json::from_str(&line).ok().and_then(Incoming::parse) {
+                Some(Incoming::Request { id, method, params }) => {
+                    self.handle_request(id, &method, params, &out, &in_flight);
+                }
+                Some(Incoming::Notification { method, params }) => {
+                    handle_notification(&method, &params, &in_flight);
+                }
+                Some(Incoming::Response { .. }) => {}
+                None => tracing::warn!("mcp server: malformed message ignored"),
+            }
+        };
+        let pending = std::mem::take(&mut *lock(&in_flight));
+        for cancel in pending.into_values() {
+            cancel.cancel();
+        }
+        result
+    }
+
+    fn handle_request(
+        &self,
+        id: Value,
+        method: &str,
+        params: Value,
+        out: &mpsc::UnboundedSender<Value>,
+        in_flight: &InFlight,
+    ) {
+        let reply = match method {
+            "initialize" => protocol::response(
+                id,
+                json!({
+                    "protocolVersion": PROTOCOL_VERSION,
+                    "capabilities": {"tools": {"listChanged": false}},
+                    "serverInfo": {"name": self.name, "version": self.version}
+                }),
+            ),
+            "ping" => protocol::response(id, json!({})),
+            "tools/list" => protocol::response(id, json!({"tools": self.listing()})),
+            "tools/call" => {
+                let key = id.to_string();
+                let cancel = self.context.cancel.child_token();
+                lock(in_flight).insert(key.clone(), cancel.clone());
+                let server = self.clone();
+                let out = out.clone();
+                let in_flight = Arc::clone(in_flight);
+                tokio::spawn(async move {
+                    let reply = server.call(id, &params, &cancel, &out).await;
+                    lock(&in_flight).remove(&key);
+                    // Cancelled requests get no response.
+                    if let Some(reply) = reply.filter(|_| !cancel.is_cancelled()) {
+                        let _ = out.send(reply);
+                    }
+                });
+                return;
+            }
+            _ => protocol::error_response(id, METHOD_NOT_FOUND, "method not found"),
+        };
+        let _ = out.send(reply);
+    }
+
+    fn listing(&self) -> Vec<Value> {
+        self.registry
+            .definitions(self.dispatch.capability_mode)
+            .into_iter()
+            .map(|def| {
+                let annotations = self
+                    .registry
+                    .get(&def.name)
+                    .map(|t| protocol::annotations_from_metadata(&t.metadata()));
+                json!({
+                    "name": def.name,
+                    "description": def.description,
+                    "inputSchema": def.parameters,
+                    "annotations": annotations,
+                })
+            })
+            .collect()
+    }
+
+    async fn call(
+        &self,
+        id: Value,
+        params: &Value,
+        cancel: &CancellationToken,
+        out: &mpsc::UnboundedSender<Value>,
+    ) -> Option<Value> {
+        let name = params
+            .get("name")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+        let admitted = self.registry.get(name).is_some_and(|t| {
+            self.registry
+                .allows(t.as_ref(), self.dispatch.capability_mode)
+        });
+        if !admitted {
+            let message = format!("unknown tool '{name}'");
+            return Some(protocol::error_response(id, INVALID_PARAMS, &message));
+        }
+        let arguments = params
+            .get("arguments")
+            .filter(|a| !a.is_null())
+            .cloned()
+            .unwrap_or_else(|| json!({}));
+        let request = DispatchRequest {
+            call: ToolCall {
+                id: ToolCallId::generate(),
+                name: name.to_owned(),
+                arguments,
+            },
+        };
+        let (events, mut updates) = mpsc::unbounded_channel();
+        let ctx = self
+            .context
+            .clone()
+            .with_cancel(cancel.clone())
+            .with_events(EventBus::new(events, RunId::generate()));
+        let mut progress = Progress {
+            token: params.pointer("/_meta/progressToken").cloned(),
+            sent: 0,
+            out,
+        };
+
+        let dispatch = self
+            .dispatch
+            .execute_batch(&self.registry, ctx, vec![request]);
+        tokio::pin!(dispatch);
+        let outcomes = loop {
+            tokio::select! {
+                biased;
+                Some(event) = updates.recv() => progress.forward(event),
+                outcomes = &mut dispatch => break outcomes,
+            }
+        };
+        while let Ok(event) = updates.try_recv() {
+            progress.forward(event);
+        }
+        let result = outcomes.into_iter().next().map_or_else(
+            || Err(codes::execution("tool produced no outcome")),
+            |o| o.result,
+        );
+        Some(protocol::response(
+            id,
+            protocol::tool_result_to_mcp(&result),
+        ))
+    }
+}
+
+/// Cancel tokens of running `tools/call` requests, keyed by JSON request id.
+type InFlight = Arc<Mutex<HashMap<String, CancellationToken>>>;
+
+fn lock(in_flight: &InFlight) -> std::sync::MutexGuard<'_, HashMap<String, CancellationToken>> {
+    in_flight.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+fn handle_notification(method: &str, params: &Value, in_flight: &InFlight) {
+    if method != "notifications/cancelled" {
+        return;
+    }
+    let Some(request) = params.get("requestId") else {
+        return;
+    };
+    let cancel = lock(in_flight).remove(&request.to_string());
+    if let Some(cancel) = cancel {
+        cancel.cancel();
+    }
+}
+
+/// Turns dispatch progress events into `notifications/progress`: the tool's
+/// own `progress` / `total` when it reports them, else a running frame count.
+struct Progress<'a> {
+    token: Option<Value>,
+    sent: u64,
+    out: &'a mpsc::UnboundedSender<Value>,
+}
+
+impl Progress<'_> {
+    fn forward(&mut self, event: TurnEvent) {
+        let (
+            Some(token),
+            TurnEventKind::ToolExecutionUpdate {
+                message,
+                progress,
+                total,
+                ..
+            },
+        ) = (&self.token, event.kind)
+        else {
+            return;
+        };
+        self.sent = self.sent.saturating_add(1);
+        let mut params = json!({
+            "progressToken": token,
+            "progress": progress.map_or_else(|| json!(self.sent), |p| json!(p)),
+            "message": message,
+        });
+        if let (Some(total), Some(obj)) = (total, params.as_object_mut()) {
+            obj.insert("total".to_owned(), json!(total));
+        }
+        let _ = self
+            .out
+            .send(protocol::notification("notifications/progress", params));
+    }
+}
+
+fn io_error(e: std::io::Error) -> ToolError {
+    codes::service_unavailable(format!("mcp server io: {e}"))
+}
@@ -0,0 +1,323 @@
+//! [`McpToolSource`]: tools of one MCP server as a [`ToolSource`].
+
+use std::sync::{Arc, PoisonError, RwLock, Weak};
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream;
+use ovo_tools::{
+    DynTool, SharedTool, ToolCallContext, ToolError, ToolMetadata, ToolResult, ToolSource,
+    ToolStream, ToolStreamItem, drain_terminal,
+};
+use serde_json::{Value, json};
+use tokio::sync::{mpsc, watch};
+use tokio_util::sync::CancellationToken;
+
+#[cfg(feature = "http")]
+use crate::http::{HttpServer, HttpTransport};
+use crate::protocol::{self, PROTOCOL_VERSION};
+use crate::rpc::{Rpc, Transport};
+use crate::stdio::{StdioServer, StdioTransport};
+
+/// Default timeout for `initialize` and `tools/list`.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+#[derive(Debug, Clone)]
+enum Endpoint {
+    Stdio(StdioServer),
+    #[cfg(feature = "http")]
+    Http(HttpServer),
+}
+
+/// How to reach an MCP server and how to present its tools.
+#[derive(Debug, Clone)]
+pub struct McpConfig {
+    endpoint: Endpoint,
+    tool_prefix: Option<String>,
+    request_timeout: Duration,
+}
+
+impl McpConfig {
+    /// Launch a server over stdio.
+    #[must_use]
+    pub fn stdio(server: StdioServer) -> Self {
+        Self::with_endpoint(Endpoint::Stdio(server))
+    }
+
+    /// Connect to a streamable HTTP server.
+    #[cfg(feature = "http")]
+    #[must_use]
+    pub fn http(server: HttpServer) -> Self {
+        Self::with_endpoint(Endpoint::Http(server))
+    }
+
+    fn with_endpoint(endpoint: Endpoint) -> Self {
+        Self {
+            endpoint,
+            tool_prefix: None,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+
+    /// Expose tools as `<prefix>__<name>` (avoids collisions across servers).
+    #[must_use]
+    pub fn with_tool_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.tool_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Timeout for `initialize` / `tools/list` (tool calls use the dispatch
+    /// timeout instead).
+    #[must_use]
+    pub const fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+}
+
+/// Tools of one MCP server.
+///
+/// Runs `initialize` and `tools/list` on connect; each tool's `call` is a
+/// `tools/call` with progress notifications streamed as
+/// [`ovo_tools::ToolProgress`] and cancellation sent as
+/// `notifications/cancelled`. `notifications/tools/list_changed` triggers a
+/// re-list; watch [`McpToolSource::subscribe`] to rebuild registries.
+#[derive(Clone)]
+pub struct McpToolSource {
+    name: String,
+    shared: Arc<Shared>,
+}
+
+struct Shared {
+    rpc: Arc<Rpc>,
+    prefix: Option<String>,
+    timeout: Duration,
+    tools: RwLock<Vec<SharedTool>>,
+    generation: watch::Sender<u64>,
+}
+
+impl std::fmt::Debug for McpToolSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("McpToolSource")
+            .field("name", &self.name)
+            .field("tools", &self.shared.snapshot().len())
+            .finish_non_exhaustive()
+    }
+}
+
+impl McpToolSource {
+    /// Connect, handshake and list tools. `name` is the source id for logs
+    /// (e.g. `mcp:github`).
+    ///
+    /// # Errors
+    ///
+    /// Spawn / network failures, or a failed `initialize` / `tools/list`.
+    pub async fn connect(name: impl Into<String>, config: McpConfig) -> Result<Self, ToolError> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        let transport: Box<dyn Transport> = match &config.endpoint {
+            Endpoint::Stdio(server) => Box::new(StdioTransport::spawn(server, tx)?),
+            #[cfg(feature = "http")]
+            Endpoint::Http(server) => Box::new(HttpTransport::new(server, tx)?),
+        };
+        let rpc = Rpc::start(transport, rx);
+        let params = json!({
+            "protocolVersion": PROTOCOL_VERSION,
+            "capabilities": {},
+            "clientInfo": {"name": "ovo", "version": env!("CARGO_PKG_VERSION")}
+        });
+        let init = rpc
+            .request(
+                "initialize",
+                params,
+                &CancellationToken::new(),
+                Some(config.request_timeout),
+            )
+            .await?;
+        let info = init.get("serverInfo").cloned().unwrap_or_default();
+        tracing::debug!(server = %info, "mcp initialized");
+        rpc.notify("notifications/initialized", json!({})).await?;
+        rpc.initialized();
+
+        let changes = rpc.list_changed();
+        let shared = Arc::new(Shared {
+            rpc,
+            prefix: config.tool_prefix,
+            timeout: config.request_timeout,
+            tools: RwLock::new(Vec::new()),
+            generation: watch::Sender::new(0),
+        });
+        shared.refresh().await?;
+        tokio::spawn(follow_list_changes(Arc::downgrade(&shared), changes));
+        Ok(Self {
+            name: name.into(),
+            shared,
+        })
+    }
+
+    /// Re-run `tools/list` now.
+    ///
+    /// # Errors
+    ///
+    /// Transport failure or a JSON-RPC error from the server.
+    pub async fn refresh(&self) -> Result<(), ToolError> {
+        self.shared.refresh().await
+    }
+
+    /// Tool-list generation; bumped after every successful refresh.
+    #[must_use]
+    pub fn subscribe(&self) -> watch::Receiver<u64> {
+        self.shared.generation.subscribe()
+    }
+}
+
+impl ToolSource for McpToolSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn tools(&self) -> Vec<SharedTool> {
+        self.shared.snapshot()
+    }
+}
+
+impl Shared {
+    fn snapshot(&self) -> Vec<SharedTool> {
+        self.tools
+            .read()
+            .unwrap_or_else(PoisonError::into_inner)
+            .clone()
+    }
+
+    async fn refresh(&self) -> Result<(), ToolError> {
+        let mut tools: Vec<SharedTool> = Vec::new();
+        let mut cursor: Option<Value> = None;
+        loop {
+            let params = cursor.map_or_else(|| json!({}), |c| json!({"cursor": c}));
+            let page = self
+                .rpc
+                .request(
+                    "tools/list",
+                    params,
+                    &CancellationToken::new(),
+                    Some(self.timeout),
+                )
+                .await?;
+            let listed = page.get("tools").and_then(Value::as_array);
+            for tool in listed.into_iter().flatten() {
+                if let Some(tool) = McpTool::from_listing(&self.rpc, self.prefix.as_deref(), tool) {
+                    tools.push(Arc::new(tool));
+                }
+            }
+            cursor = page.get("nextCursor").filter(|c| !c.is_null()).cloned();
+            if cursor.is_none() {
+                break;
+            }
+        }
+        *self.tools.write().unwrap_or_else(PoisonError::into_inner) = tools;
+        self.generation.send_modify(|n| *n = n.wrapping_add(1));
+        Ok(())
+    }
+}
+
+async fn follow_list_changes(shared: Weak<Shared>, mut changes: watch::Receiver<u64>) {
+    while changes.changed().await.is_ok() {
+        let Some(shared) = shared.upgrade() else {
+            return;
+        };
+        if let Err(e) = shared.refresh().await {
+            tracing::warn!(error = %e, "mcp tools/list refresh failed");
+        }
+    }
+}
+
+/// One remote tool; `call` is `tools/call` on the owning connection.
+struct McpTool {
+    rpc: Arc<Rpc>,
+    name: String,
+    remote: String,
+    description: String,
+    parameters: Value,
+    metadata: ToolMetadata,
+}
+
+impl McpTool {
+    fn from_listing(rpc: &Arc<Rpc>, prefix: Option<&str>, tool: &Value) -> Option<Self> {
+        let remote = tool.get("name").and_then(Value::as_str)?.to_owned();
+        let text = |key: &str| tool.get(key).and_then(Value::as_str);
+        Some(Self {
+            rpc: Arc::clone(rpc),
+            name: prefix.map_or_else(|| remote.clone(), |p| format!("{p}__{remote}")),
+            description: text("description")
+                .or_else(|| text("title"))
+                .unwrap_or_default()
+                .to_owned(),
+            parameters: tool
+                .get("inputSchema")
+                .cloned()
+                .unwrap_or_else(|| json!({"type": "object"})),
+            metadata: protocol::metadata_from_annotations(tool.get("annotations")),
+            remote,
+        })
+    }
+}
+
+#[async_trait]
+impl DynTool for McpTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.description
+    }
+
+    fn parameters(&self) -> Value {
+        self.parameters.clone()
+    }
+
+    fn metadata(&self) -> ToolMetadata {
+        self.metadata.clone()
+    }
+
+    async fn call(&self, ctx: ToolCallContext, arguments: Value) -> Result<ToolResult, ToolError> {
+        drain_terminal(self.execute(ctx, arguments).await).await
+    }
+
+    async fn execute(&self, ctx: ToolCallContext, arguments: Value) -> ToolStream {
+        let rpc = Arc::clone(&self.rpc);
+        let (token, mut progress) = rpc.watch_progress();
+        let arguments = if arguments.is_null() {
+            json!({})
+        } else {
+            arguments
+        };
+        let params = json!({
+            "name": self.remote,
+            "arguments": arguments,
+            "_meta": {"progressToken": token}
+        });
+        let (tx, rx) = mpsc::unbounded_channel();
+        tokio::spawn(async move {
+            let call = rpc.request("tools/call", params, &ctx.cancel, None);
+            tokio::pin!(call);
+            let result = loop {
+                tokio::select! {
+                    biased;
+                    Some(p) = progress.recv() => {
+                        let _ = tx.send(ToolStreamItem::Progress(p));
+                    }
+                    result = &mut call => break result,
+                }
+            };
+            rpc.unwatch_progress(&token);
+            while let Ok(p) = progress.try_recv() {
+                let _ = tx.send(ToolStreamItem::Progress(p));
+            }
+            let result = result.map(|v| protocol::tool_result_from_mcp(&v));
+            let _ = tx.send(ToolStreamItem::Terminal(result));
+        });
+        Box::pin(stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        }))
+    }
+}
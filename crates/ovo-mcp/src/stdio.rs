@@ -0,0 +1,142 @@
+//! stdio transport: a child process speaking newline-delimited JSON-RPC.
+
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use async_trait::async_trait;
+use ovo_tools::ToolError;
+use ovo_tools::error::codes;
+use serde_json::Value;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::{Child, ChildStdin, Command};
+use tokio::sync::{Mutex, mpsc};
+
+use crate::rpc::Transport;
+
+/// Launch settings for a stdio MCP server.
+#[derive(Debug, Clone)]
+pub struct StdioServer {
+    program: String,
+    args: Vec<String>,
+    env: Vec<(String, String)>,
+    cwd: Option<PathBuf>,
+}
+
+impl StdioServer {
+    /// Run `program` (resolved via `PATH`).
+    #[must_use]
+    pub fn new(program: impl Into<String>) -> Self {
+        Self {
+            program: program.into(),
+            args: Vec::new(),
+            env: Vec::new(),
+            cwd: None,
+        }
+    }
+
+    /// Append command-line arguments.
+    #[must_use]
+    pub fn with_args<I, S>(mut self, args: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.args.extend(args.into_iter().map(Into::into));
+        self
+    }
+
+    /// Set an environment variable for the child.
+    #[must_use]
+    pub fn with_env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Working directory for the child.
+    #[must_use]
+    pub fn with_cwd(mut self, cwd: impl Into<PathBuf>) -> Self {
+        self.cwd = Some(cwd.into());
+        self
+    }
+}
+
+/// Running child; killed when dropped.
+pub(crate) struct StdioTransport {
+    stdin: Mutex<ChildStdin>,
+    _child: Child,
+}
+
+impl StdioTransport {
+    /// Spawn the server; stdout lines go to `inbound`, stderr to `tracing`.
+    pub(crate) fn spawn(
+        server: &StdioServer,
+        inbound: mpsc::UnboundedSender<Value>,
+    ) -> Result<Self, ToolError> {
+        let mut cmd = Command::new(&server.program);
+        cmd.args(&server.args)
+            .envs(server.env.iter().map(|(k, v)| (k, v)))
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .kill_on_drop(true);
+        if let Some(cwd) = &server.cwd {
+            cmd.current_dir(cwd);
+        }
+        let mut child = cmd.spawn().map_err(|e| {
+            codes::service_unavailable(format!("spawn mcp server '{}': {e}", server.program))
+        })?;
+        let (Some(stdin), Some(stdout), Some(stderr)) =
+            (child.stdin.take(), child.stdout.take(), child.stderr.take())
+        else {
+            return Err(codes::execution("mcp server stdio not captured"));
+        };
+        let program = server.program.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stdout).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                if line.trim().is_empty() {
+                    continue;
+                }
+                match serde_json::from_str(&line) {
+                    Ok(message) => {
+                        if inbound.send(message).is_err() {
+                            return;
+                        }
+                    }
+                    Err(e) => {
+                        tracing::warn!(server = %program, error = %e, "mcp: non-JSON stdout line");
+                    }
+                }
+            }
+        });
+        let program = server.program.clone();
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stderr).lines();
+            while let Ok(Some(line)) = lines.next_line().await {
+                tracing::debug!(server = %program, "{line}");
+            }
+        });
+        Ok(Self {
+            stdin: Mutex::new(stdin),
+            _child: child,
+        })
+    }
+}
+
+#[async_trait]
+impl Transport for StdioTransport {
+    #[allow(
+        clippy::significant_drop_tightening,
+        reason = "one line is written and flushed under the stdin lock"
+    )]
+    async fn send(&self, message: Value) -> Result<(), ToolError> {
+        let mut line = message.to_string();
+        line.push('\n');
+        let mut stdin = self.stdin.lock().await;
+        let written = match stdin.write_all(line.as_bytes()).await {
+            Ok(()) => stdin.flush().await,
+            Err(e) => Err(e),
+        };
+        written.map_err(|e| codes::service_unavailable(format!("mcp server stdin: {e}")))
+    }
+}
@@ -0,0 +1,192 @@
+//! Drive [`McpToolSource`] over streamable HTTP against an in-process fake.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links crate deps"
+)]
+
+#[cfg(test)]
+mod http_fake {
+    #![allow(
+        clippy::expect_used,
+        clippy::indexing_slicing,
+        reason = "integration test harness"
+    )]
+
+    use std::sync::{Arc, Mutex, PoisonError};
+
+    use ovo_mcp::{HttpServer, McpConfig, McpToolSource};
+    use ovo_tools::{ToolCallContext, ToolSource, drain_with_progress, merge_tool_sources};
+    use serde_json::{Value, json};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+
+    const SESSION: &str = "fake-session";
+
+    /// Session header seen on each post after `initialize`.
+    type Seen = Arc<Mutex<Vec<Option<String>>>>;
+
+    async fn serve(listener: TcpListener, seen: Seen) {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(handle(stream, Arc::clone(&seen)));
+        }
+    }
+
+    async fn handle(stream: TcpStream, seen: Seen) {
+        let mut reader = BufReader::new(stream);
+        let mut head = String::new();
+        let mut session = None;
+        let mut length = 0;
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap_or(0) == 0 {
+                return;
+            }
+            if line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                let value = value.trim().to_owned();
+                match name.to_ascii_lowercase().as_str() {
+                    "content-length" => length = value.parse().unwrap_or(0),
+                    "mcp-session-id" => session = Some(value),
+                    _ => {}
+                }
+            }
+            head.push_str(&line);
+        }
+        if head.starts_with("GET") {
+            respond(
+                reader.get_mut(),
+                "405 Method Not Allowed",
+                "text/plain",
+                None,
+                "",
+            )
+            .await;
+            return;
+        }
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.expect("body");
+        let message: Value = serde_json::from_slice(&body).expect("json");
+        let method = message["method"].as_str().unwrap_or_default();
+        let id = message.get("id").cloned();
+        if method != "initialize" {
+            seen.lock()
+                .unwrap_or_else(PoisonError::into_inner)
+                .push(session);
+        }
+        let stream = reader.get_mut();
+        let Some(id) = id else {
+            respond(stream, "202 Accepted", "text/plain", None, "").await;
+            return;
+        };
+        let reply = |result: Value| json!({"jsonrpc": "2.0", "id": id, "result": result});
+        match method {
+            "initialize" => {
+                let body = reply(json!({
+                    "protocolVersion": message["params"]["protocolVersion"],
+                    "capabilities": {"tools": {}},
+                    "serverInfo": {"name": "http-fake", "version": "0"}
+                }));
+                respond(
+                    stream,
+                    "200 OK",
+                    "application/json",
+                    Some(SESSION),
+                    &body.to_string(),
+                )
+                .await;
+            }
+            "tools/list" => {
+                let body = reply(json!({"tools": [{
+                    "name": "shout",
+                    "inputSchema": {"type": "object"},
+                    "annotations": {"readOnlyHint": true}
+                }]}));
+                respond(
+                    stream,
+                    "200 OK",
+                    "application/json",
+                    None,
+                    &body.to_string(),
+                )
+                .await;
+            }
+            "tools/call" => {
+                let token = &message["params"]["_meta"]["progressToken"];
+                let text = message["params"]["arguments"]["text"]
+                    .as_str()
+                    .unwrap_or_default()
+                    .to_uppercase();
+                let progress = json!({
+                    "jsonrpc": "2.0",
+                    "method": "notifications/progress",
+                    "params": {"progressToken": token, "progress": 1}
+                });
+                let result = reply(json!({"content": [{"type": "text", "text": text}]}));
+                let body = format!(
+                    "event: message\ndata: {progress}\n\nevent: message\ndata: {result}\n\n"
+                );
+                respond(stream, "200 OK", "text/event-stream", None, &body).await;
+            }
+            _ => {
+                let body = json!({"jsonrpc": "2.0", "id": id, "error": {"code": -32601, "message": "nope"}});
+                respond(
+                    stream,
+                    "200 OK",
+                    "application/json",
+                    None,
+                    &body.to_string(),
+                )
+                .await;
+            }
+        }
+    }
+
+    async fn respond(
+        stream: &mut TcpStream,
+        status: &str,
+        content_type: &str,
+        session: Option<&str>,
+        body: &str,
+    ) {
+        let extra = session.map_or_else(String::new, |s| format!("Mcp-Session-Id: {s}\r\n"));
+        let out = format!(
+            "HTTP/1.1 {status}\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\nconnection: close\r\n{extra}\r\n{body}",
+            body.len()
+        );
+        let _ = stream.write_all(out.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+
+    #[tokio::test]
+    async fn lists_and_calls_over_streamable_http() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        let seen = Seen::default();
+        tokio::spawn(serve(listener, Arc::clone(&seen)));
+
+        let server = HttpServer::new(format!("http://{addr}/mcp")).with_bearer_token("t");
+        let source = McpToolSource::connect("mcp:http", McpConfig::http(server))
+            .await
+            .expect("connect");
+        let source: &dyn ToolSource = &source;
+        let reg = merge_tool_sources([source]);
+        let shout = reg.get("shout").expect("shout");
+        assert!(shout.metadata().allowed_in_read_only());
+
+        let stream = shout
+            .execute(ToolCallContext::default(), json!({"text": "hi"}))
+            .await;
+        let (progress, result) = drain_with_progress(stream).await;
+        assert_eq!(result.expect("shout").content, "HI");
+        assert_eq!(progress.len(), 1);
+
+        let seen = seen.lock().unwrap_or_else(PoisonError::into_inner).clone();
+        assert!(!seen.is_empty());
+        assert!(
+            seen.iter().all(|s| s.as_deref() == Some(SESSION)),
+            "{seen:?}"
+        );
+    }
+}
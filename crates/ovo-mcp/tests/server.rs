@@ -0,0 +1,262 @@
+//! Drive [`McpServer`] with raw JSON-RPC lines over an in-memory pipe.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links crate deps"
+)]
+
+#[cfg(test)]
+mod server {
+    #![allow(clippy::expect_used, reason = "integration test harness")]
+
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::Duration;
+
+    use async_trait::async_trait;
+    use ovo_mcp::McpServer;
+    use ovo_tools::error::codes;
+    use ovo_tools::{
+        AlwaysDeny, CapabilityMode, DynTool, ToolCallContext, ToolError, ToolMetadata,
+        ToolProgress, ToolRegistry, ToolResult, ToolStream, with_progress,
+    };
+    use serde_json::{Value, json};
+    use tokio::io::{
+        AsyncBufReadExt, AsyncWriteExt, BufReader, DuplexStream, Lines, ReadHalf, WriteHalf,
+    };
+
+    struct Tick;
+
+    #[async_trait]
+    impl DynTool for Tick {
+        fn name(&self) -> &'static str {
+            "tick"
+        }
+
+        fn description(&self) -> &'static str {
+            "Reports two steps"
+        }
+
+        fn parameters(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        fn metadata(&self) -> ToolMetadata {
+            ToolMetadata::read_only()
+        }
+
+        async fn call(&self, _ctx: ToolCallContext, _args: Value) -> Result<ToolResult, ToolError> {
+            Ok(ToolResult::text("done"))
+        }
+
+        async fn execute(&self, _ctx: ToolCallContext, _args: Value) -> ToolStream {
+            with_progress(
+                [
+                    ToolProgress::text("one"),
+                    ToolProgress::Custom {
+                        subkind: "step".into(),
+                        payload: json!({"progress": 2.5, "total": 4}),
+                    },
+                ],
+                || async { Ok(ToolResult::text("done")) },
+            )
+        }
+    }
+
+    /// Blocks until cancelled, then records it.
+    #[derive(Default)]
+    struct Wait {
+        cancelled: Arc<AtomicBool>,
+    }
+
+    #[async_trait]
+    impl DynTool for Wait {
+        fn name(&self) -> &'static str {
+            "wait"
+        }
+
+        fn description(&self) -> &'static str {
+            "Waits for cancellation"
+        }
+
+        fn parameters(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        fn metadata(&self) -> ToolMetadata {
+            ToolMetadata::read_only()
+        }
+
+        async fn call(&self, ctx: ToolCallContext, _args: Value) -> Result<ToolResult, ToolError> {
+            ctx.cancel.cancelled().await;
+            self.cancelled.store(true, Ordering::SeqCst);
+            Err(codes::cancelled())
+        }
+    }
+
+    struct Erase;
+
+    #[async_trait]
+    impl DynTool for Erase {
+        fn name(&self) -> &'static str {
+            "erase"
+        }
+
+        fn description(&self) -> &'static str {
+            "Destructive"
+        }
+
+        fn parameters(&self) -> Value {
+            json!({"type": "object"})
+        }
+
+        fn metadata(&self) -> ToolMetadata {
+            ToolMetadata::exclusive_write()
+        }
+
+        async fn call(&self, _ctx: ToolCallContext, _args: Value) -> Result<ToolResult, ToolError> {
+            Ok(ToolResult::text("erased"))
+        }
+    }
+
+    struct Client {
+        writer: WriteHalf<DuplexStream>,
+        lines: Lines<BufReader<ReadHalf<DuplexStream>>>,
+    }
+
+    impl Client {
+        fn start(server: McpServer) -> Self {
+            let (client, remote) = tokio::io::duplex(64 * 1024);
+            tokio::spawn(async move {
+                let (reader, writer) = tokio::io::split(remote);
+                server.serve(reader, writer).await
+            });
+            let (reader, writer) = tokio::io::split(client);
+            Self {
+                writer,
+                lines: BufReader::new(reader).lines(),
+            }
+        }
+
+        async fn send(&mut self, message: Value) {
+            let line = format!("{message}\n");
+            self.writer.write_all(line.as_bytes()).await.expect("write");
+        }
+
+        async fn recv(&mut self) -> Value {
+            let line = tokio::time::timeout(Duration::from_secs(5), self.lines.next_line())
+                .await
+                .expect("reply in time")
+                .expect("read")
+                .expect("open");
+            serde_json::from_str(&line).expect("json")
+        }
+
+        async fn request(&mut self, id: u64, method: &str, params: Value) -> Value {
+            self.send(json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params}))
+                .await;
+            self.recv().await
+        }
+    }
+
+    fn registry(wait: Wait) -> ToolRegistry {
+        ToolRegistry::from_tools(vec![Arc::new(Tick), Arc::new(wait), Arc::new(Erase)])
+    }
+
+    #[tokio::test]
+    async fn lists_and_filters_by_capability() {
+        let server = McpServer::new(registry(Wait::default()))
+            .with_capability(CapabilityMode::ReadOnly)
+            .with_server_info("fixture", "1.0");
+        let mut client = Client::start(server);
+
+        let init = client.request(1, "initialize", json!({})).await;
+        assert_eq!(
+            init.pointer("/result/serverInfo/name"),
+            Some(&json!("fixture"))
+        );
+
+        let list = client.request(2, "tools/list", json!({})).await;
+        let names: Vec<&str> = list
+            .pointer("/result/tools")
+            .and_then(Value::as_array)
+            .expect("tools")
+            .iter()
+            .filter_map(|t| t.get("name").and_then(Value::as_str))
+            .collect();
+        assert_eq!(names, ["tick", "wait"]);
+        assert_eq!(
+            list.pointer("/result/tools/0/annotations/readOnlyHint"),
+            Some(&json!(true))
+        );
+
+        let hidden = client
+            .request(3, "tools/call", json!({"name": "erase", "arguments": {}}))
+            .await;
+        assert_eq!(hidden.pointer("/error/code"), Some(&json!(-32602)));
+        let unknown = client.request(4, "resources/list", json!({})).await;
+        assert_eq!(unknown.pointer("/error/code"), Some(&json!(-32601)));
+    }
+
+    #[tokio::test]
+    async fn forwards_progress_and_routes_approval() {
+        let server = McpServer::new(registry(Wait::default())).with_approval(Arc::new(AlwaysDeny));
+        let mut client = Client::start(server);
+
+        client
+            .send(json!({
+                "jsonrpc": "2.0", "id": 1, "method": "tools/call",
+                "params": {"name": "tick", "arguments": {}, "_meta": {"progressToken": "p1"}}
+            }))
+            .await;
+        let first = client.recv().await;
+        assert_eq!(first.get("method"), Some(&json!("notifications/progress")));
+        assert_eq!(first.pointer("/params/progressToken"), Some(&json!("p1")));
+        assert_eq!(first.pointer("/params/message"), Some(&json!("one")));
+        assert_eq!(first.pointer("/params/progress"), Some(&json!(1)));
+        let second = client.recv().await;
+        assert_eq!(second.pointer("/params/progress"), Some(&json!(2.5)));
+        assert_eq!(second.pointer("/params/total"), Some(&json!(4.0)));
+        let done = client.recv().await;
+        assert_eq!(done.get("id"), Some(&json!(1)));
+        assert_eq!(done.pointer("/result/content/0/text"), Some(&json!("done")));
+        assert_eq!(done.pointer("/result/isError"), Some(&json!(false)));
+
+        let denied = client
+            .request(2, "tools/call", json!({"name": "erase"}))
+            .await;
+        assert_eq!(denied.pointer("/result/isError"), Some(&json!(true)));
+        let text = denied
+            .pointer("/result/content/0/text")
+            .and_then(Value::as_str)
+            .expect("text");
+        assert!(text.contains("approval denied"), "{text}");
+    }
+
+    #[tokio::test]
+    async fn client_cancel_reaches_tool_context() {
+        let wait = Wait::default();
+        let cancelled = Arc::clone(&wait.cancelled);
+        let mut client = Client::start(McpServer::new(registry(wait)));
+
+        client
+            .send(json!({"jsonrpc": "2.0", "id": "w", "method": "tools/call", "params": {"name": "wait"}}))
+            .await;
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        client
+            .send(json!({
+                "jsonrpc": "2.0", "method": "notifications/cancelled",
+                "params": {"requestId": "w", "reason": "user"}
+            }))
+            .await;
+        // The cancelled call gets no reply; the next request is answered first.
+        let pong = client.request(7, "ping", json!({})).await;
+        assert_eq!(pong, json!({"jsonrpc": "2.0", "id": 7, "result": {}}));
+        for _ in 0..100 {
+            if cancelled.load(Ordering::SeqCst) {
+                return;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        unreachable!("tool context was not cancelled");
+    }
+}
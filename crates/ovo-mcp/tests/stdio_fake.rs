@@ -0,0 +1,139 @@
+//! Drive [`McpToolSource`] against the `ovo-mcp-fake` stdio server.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links crate deps"
+)]
+
+#[cfg(test)]
+mod stdio_fake {
+    #![allow(clippy::expect_used, reason = "integration test harness")]
+
+    use std::time::Duration;
+
+    use ovo_mcp::{McpConfig, McpToolSource, PROGRESS_SUBKIND, StdioServer};
+    use ovo_tools::{
+        CapabilityFlag, ToolCallContext, ToolProgress, ToolRegistry, ToolSource,
+        drain_with_progress, merge_tool_sources,
+    };
+    use ovo_types::ErrorCode;
+    use serde_json::json;
+    use tokio_util::sync::CancellationToken;
+
+    fn registry(source: &McpToolSource) -> ToolRegistry {
+        let source: &dyn ToolSource = source;
+        merge_tool_sources([source])
+    }
+
+    async fn connect() -> McpToolSource {
+        let server = StdioServer::new(env!("CARGO_BIN_EXE_ovo-mcp-fake"));
+        McpToolSource::connect("mcp:fake", McpConfig::stdio(server))
+            .await
+            .expect("connect")
+    }
+
+    #[tokio::test]
+    async fn lists_pages_and_calls_tools() {
+        let source = connect().await;
+        let reg = registry(&source);
+        assert_eq!(
+            reg.names(),
+            ["add_tool", "cancelled_requests", "echo", "fail", "slow"]
+        );
+
+        let echo = reg.get("echo").expect("echo");
+        let meta = echo.metadata();
+        assert!(meta.allowed_in_read_only());
+        assert!(!meta.capabilities.contains(&CapabilityFlag::Network));
+        let out = echo
+            .call(ToolCallContext::default(), json!({"text": "hi"}))
+            .await
+            .expect("echo");
+        assert_eq!(out.content, "hi");
+        assert_eq!(out.structured, Some(json!({"text": "hi"})));
+
+        let fail = reg.get("fail").expect("fail");
+        assert!(
+            fail.metadata()
+                .capabilities
+                .contains(&CapabilityFlag::Network)
+        );
+        let out = fail
+            .call(ToolCallContext::default(), json!({}))
+            .await
+            .expect("fail result");
+        assert!(out.is_error);
+
+        let source = McpToolSource::connect(
+            "mcp:prefixed",
+            McpConfig::stdio(StdioServer::new(env!("CARGO_BIN_EXE_ovo-mcp-fake")))
+                .with_tool_prefix("fake"),
+        )
+        .await
+        .expect("connect");
+        let names: Vec<String> = source.tools().iter().map(|t| t.name().to_owned()).collect();
+        assert!(names.contains(&"fake__echo".to_owned()), "{names:?}");
+    }
+
+    #[tokio::test]
+    async fn streams_progress_and_sends_cancellation() {
+        let source = connect().await;
+        let reg = registry(&source);
+        let slow = reg.get("slow").expect("slow");
+
+        let stream = slow
+            .execute(ToolCallContext::default(), json!({"steps": 3}))
+            .await;
+        let (progress, result) = drain_with_progress(stream).await;
+        assert_eq!(result.expect("slow").content, "3 steps");
+        assert_eq!(progress.len(), 3);
+        let Some(ToolProgress::Custom { subkind, payload }) = progress.last() else {
+            unreachable!("custom progress");
+        };
+        assert_eq!(subkind, PROGRESS_SUBKIND);
+        assert_eq!(
+            payload,
+            &json!({"progress": 3, "total": 3, "message": "step 3"})
+        );
+
+        let cancel = CancellationToken::new();
+        let ctx = ToolCallContext::default().with_cancel(cancel.clone());
+        let canceller = tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            cancel.cancel();
+        });
+        let err = slow
+            .call(ctx, json!({"steps": 1, "hang": true}))
+            .await
+            .expect_err("cancelled");
+        assert_eq!(err.code(), ErrorCode::ToolCancelled);
+        canceller.await.expect("join");
+
+        let seen = reg
+            .get("cancelled_requests")
+            .expect("tool")
+            .call(ToolCallContext::default(), json!({}))
+            .await
+            .expect("ids");
+        assert!(
+            !seen.content.is_empty(),
+            "server saw notifications/cancelled"
+        );
+    }
+
+    #[tokio::test]
+    async fn refreshes_on_list_changed() {
+        let source = connect().await;
+        let mut generation = source.subscribe();
+        generation.borrow_and_update();
+        let add = registry(&source).get("add_tool").expect("add_tool");
+        add.call(ToolCallContext::default(), json!({}))
+            .await
+            .expect("added");
+        tokio::time::timeout(Duration::from_secs(5), generation.changed())
+            .await
+            .expect("refresh in time")
+            .expect("source alive");
+        let reg = registry(&source);
+        assert!(reg.get("extra").is_some());
+    }
+}
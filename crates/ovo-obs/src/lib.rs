@@ -15,12 +15,14 @@
 pub mod redact;
 
 pub use metrics::{
-    METRIC_COMPACTIONS_TOTAL, METRIC_SAMPLE_DURATION_MS, METRIC_SPAWNS_TOTAL, METRIC_TOKENS_TOTAL,
+    METRIC_COMPACTIONS_TOTAL, METRIC_COST_TOTAL, METRIC_RATELIMIT_QUEUE_DEPTH,
+    METRIC_RATELIMIT_WAIT_MS, METRIC_SAMPLE_DURATION_MS, METRIC_SPAWNS_TOTAL, METRIC_TOKENS_TOTAL,
     METRIC_TOOL_CALLS_TOTAL, METRIC_TOOL_DURATION_MS, METRIC_TURN_DURATION_MS, METRIC_TURN_STEPS,
     METRIC_TURNS_TOTAL, METRIC_WORKFLOW_AGENTS_TOTAL, METRIC_WORKFLOW_RUNS_TOTAL, MetricsSink,
     NoopMetrics, SharedMetrics, emit_catalogue_smoke, metric_catalogue_snapshot, record_compaction,
-    record_sample, record_spawn, record_tool_call, record_turn, record_workflow_agents,
-    record_workflow_run, required_metric_names,
+    record_cost, record_rate_limit_queue, record_rate_limit_wait, record_sample, record_spawn,
+    record_tool_call, record_turn, record_workflow_agents, record_workflow_run,
+    required_metric_names,
 };
 pub use ovo_protocol::observability::{
     SPAN_COMPACT, SPAN_SAMPLE, SPAN_SESSION, SPAN_SPAWN, SPAN_TOOL, SPAN_TOOL_BATCH, SPAN_TURN,
@@ -16,6 +16,8 @@
 pub const METRIC_SAMPLE_DURATION_MS: &str = "ovo_sample_duration_ms";
 /// Counter: tokens by direction (`input`, `output`).
 pub const METRIC_TOKENS_TOTAL: &str = "ovo_tokens_total";
+/// Counter: priced spend in micro-USD (USD × 10⁶), by model.
+pub const METRIC_COST_TOTAL: &str = "ovo_cost_total";
 /// Counter: nested agent spawns by status.
 pub const METRIC_SPAWNS_TOTAL: &str = "ovo_spawns_total";
 /// Counter: workflow runs by outcome.
@@ -24,6 +26,10 @@
 pub const METRIC_WORKFLOW_AGENTS_TOTAL: &str = "ovo_workflow_agents_total";
 /// Counter: compaction passes by strategy/result.
 pub const METRIC_COMPACTIONS_TOTAL: &str = "ovo_compactions_total";
+/// Gauge: callers waiting on a rate-limited model, by model.
+pub const METRIC_RATELIMIT_QUEUE_DEPTH: &str = "ovo_ratelimit_queue_depth";
+/// Histogram: rate-limit admission wait in milliseconds, by model.
+pub const METRIC_RATELIMIT_WAIT_MS: &str = "ovo_ratelimit_wait_ms";
 
 /// Required metric name catalogue (contract tests / CI snapshots).
 ///
@@ -39,10 +45,13 @@ pub fn required_metric_names() -> &'static [&'static str] {
         METRIC_TOOL_DURATION_MS,
         METRIC_SAMPLE_DURATION_MS,
         METRIC_TOKENS_TOTAL,
+        METRIC_COST_TOTAL,
         METRIC_SPAWNS_TOTAL,
         METRIC_WORKFLOW_RUNS_TOTAL,
         METRIC_WORKFLOW_AGENTS_TOTAL,
         METRIC_COMPACTIONS_TOTAL,
+        METRIC_RATELIMIT_QUEUE_DEPTH,
+        METRIC_RATELIMIT_WAIT_MS,
     ]
 }
 
@@ -58,9 +67,12 @@ pub fn emit_catalogue_smoke(metrics: &dyn MetricsSink) {
     record_spawn(metrics, "ok");
     record_tool_call(metrics, "smoke", "ok", 1.0);
     record_sample(metrics, 1.0, 2, 3);
+    record_cost(metrics, "smoke", 0.000_001);
     record_workflow_run(metrics, "completed");
     record_workflow_agents(metrics, 1);
     record_compaction(metrics, "max_messages", "ok");
+    record_rate_limit_queue(metrics, "smoke", 0);
+    record_rate_limit_wait(metrics, "smoke", 1.0);
 }
 
 /// Host-provided metrics backend.
@@ -128,6 +140,19 @@ pub fn record_sample(
     }
 }
 
+/// Record USD spend for `model` (rounded to whole micro-USD; zero is skipped).
+pub fn record_cost(metrics: &dyn MetricsSink, model: &str, usd: f64) {
+    #[allow(
+        clippy::cast_possible_truncation,
+        clippy::cast_sign_loss,
+        reason = "clamped to a non-negative micro-USD count"
+    )]
+    let micros = (usd * 1_000_000.0).round().max(0.0) as u64;
+    if micros > 0 {
+        metrics.counter(METRIC_COST_TOTAL, micros, &[("model", model)]);
+    }
+}
+
 /// Record workflow terminal outcome.
 pub fn record_workflow_run(metrics: &dyn MetricsSink, outcome: &str) {
     metrics.counter(METRIC_WORKFLOW_RUNS_TOTAL, 1, &[("outcome", outcome)]);
@@ -149,6 +174,20 @@ pub fn record_compaction(metrics: &dyn MetricsSink, strategy: &str, status: &str
     );
 }
 
+/// Set the rate-limit queue depth for `model`.
+pub fn record_rate_limit_queue(metrics: &dyn MetricsSink, model: &str, depth: u64) {
+    metrics.gauge(
+        METRIC_RATELIMIT_QUEUE_DEPTH,
+        depth as f64,
+        &[("model", model)],
+    );
+}
+
+/// Record how long a caller waited for rate-limit admission.
+pub fn record_rate_limit_wait(metrics: &dyn MetricsSink, model: &str, wait_ms: f64) {
+    metrics.histogram(METRIC_RATELIMIT_WAIT_MS, wait_ms, &[("model", model)]);
+}
+
 #[cfg(test)]
 mod tests {
     use std::sync::Mutex;
@@ -164,10 +203,13 @@ mod tests {
 ovo_tool_duration_ms
 ovo_sample_duration_ms
 ovo_tokens_total
+ovo_cost_total
 ovo_spawns_total
 ovo_workflow_runs_total
 ovo_workflow_agents_total
-ovo_compactions_total";
+ovo_compactions_total
+ovo_ratelimit_queue_depth
+ovo_ratelimit_wait_ms";
 
     #[test]
     fn metric_catalogue_is_stable_and_prefixed() {
@@ -210,6 +252,7 @@ fn emit_catalogue_smoke_covers_all_names() {
             METRIC_SPAWNS_TOTAL,
             METRIC_TOOL_CALLS_TOTAL,
             METRIC_TOKENS_TOTAL,
+            METRIC_COST_TOTAL,
             METRIC_WORKFLOW_RUNS_TOTAL,
             METRIC_WORKFLOW_AGENTS_TOTAL,
             METRIC_COMPACTIONS_TOTAL,
@@ -0,0 +1,13 @@
+//! OpenAPI 3 integration for Ovo.
+//!
+//! [`OpenApiToolSource`] turns every operation of an OpenAPI 3 document (JSON)
+//! into an HTTP-backed [`ovo_tools::SharedTool`] for merging into a
+//! [`ovo_tools::ToolRegistry`]. Credentials come from [`OpenApiConfig`], never
+//! from model arguments.
+
+#![forbid(unsafe_code)]
+
+pub mod source;
+mod spec;
+
+pub use source::{DEFAULT_MAX_RESPONSE_BYTES, OpenApiConfig, OpenApiToolSource};
@@ -0,0 +1,566 @@
+//! [`OpenApiToolSource`]: one HTTP tool per OpenAPI operation.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use ovo_tools::error::codes;
+use ovo_tools::{
+    CapabilityFlag, Destructiveness, DynTool, SharedTool, ToolCallContext, ToolError, ToolMetadata,
+    ToolResult, ToolSource,
+};
+use reqwest::{Method, Url};
+use serde_json::{Value, json};
+
+use crate::spec::{self, Location, Operation};
+
+/// Default cap on response bytes handed back to the model.
+pub const DEFAULT_MAX_RESPONSE_BYTES: usize = 64 * 1024;
+
+/// Default per-request timeout.
+const DEFAULT_REQUEST_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Host-side settings: where to send requests and which credentials to inject.
+///
+/// Credentials never appear in tool schemas; they are applied per operation
+/// from the first of its `security` requirements they fully satisfy.
+#[derive(Clone)]
+pub struct OpenApiConfig {
+    base_url: Option<String>,
+    headers: Vec<(String, String)>,
+    credentials: HashMap<String, String>,
+    tool_prefix: Option<String>,
+    max_response_bytes: usize,
+    request_timeout: Duration,
+}
+
+impl std::fmt::Debug for OpenApiConfig {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut schemes: Vec<_> = self.credentials.keys().collect();
+        schemes.sort_unstable();
+        f.debug_struct("OpenApiConfig")
+            .field("base_url", &self.base_url)
+            .field("credentials", &schemes)
+            .field("tool_prefix", &self.tool_prefix)
+            .field("max_response_bytes", &self.max_response_bytes)
+            .field("request_timeout", &self.request_timeout)
+            .finish_non_exhaustive()
+    }
+}
+
+impl Default for OpenApiConfig {
+    fn default() -> Self {
+        Self {
+            base_url: None,
+            headers: Vec::new(),
+            credentials: HashMap::new(),
+            tool_prefix: None,
+            max_response_bytes: DEFAULT_MAX_RESPONSE_BYTES,
+            request_timeout: DEFAULT_REQUEST_TIMEOUT,
+        }
+    }
+}
+
+impl OpenApiConfig {
+    /// Server URL; overrides the document's first `servers` entry.
+    #[must_use]
+    pub fn with_base_url(mut self, url: impl Into<String>) -> Self {
+        self.base_url = Some(url.into());
+        self
+    }
+
+    /// Header sent on every request (tenancy, tracing, …).
+    #[must_use]
+    pub fn with_header(mut self, name: impl Into<String>, value: impl Into<String>) -> Self {
+        self.headers.push((name.into(), value.into()));
+        self
+    }
+
+    /// Secret for the `components.securitySchemes` entry named `scheme`.
+    ///
+    /// HTTP `bearer`, `oauth2` and `openIdConnect` take a token; HTTP `basic`
+    /// takes `user:password`; `apiKey` is sent in its declared header, query
+    /// parameter or cookie.
+    #[must_use]
+    pub fn with_credential(mut self, scheme: impl Into<String>, secret: impl Into<String>) -> Self {
+        self.credentials.insert(scheme.into(), secret.into());
+        self
+    }
+
+    /// Expose tools as `<prefix>__<operation>`.
+    #[must_use]
+    pub fn with_tool_prefix(mut self, prefix: impl Into<String>) -> Self {
+        self.tool_prefix = Some(prefix.into());
+        self
+    }
+
+    /// Truncate response bodies after `bytes`.
+    #[must_use]
+    pub const fn with_max_response_bytes(mut self, bytes: usize) -> Self {
+        self.max_response_bytes = bytes;
+        self
+    }
+
+    /// Per-request timeout.
+    #[must_use]
+    pub const fn with_request_timeout(mut self, timeout: Duration) -> Self {
+        self.request_timeout = timeout;
+        self
+    }
+}
+
+/// Tools generated from an OpenAPI 3 document (JSON).
+///
+/// Each operation becomes a tool named after its `operationId` (or
+/// `<method>_<path>`). Path, query, header and cookie parameters plus the JSON
+/// request body (`body`) are merged into one argument schema. Responses come
+/// back as `HTTP <status>` text with `{status, body, truncated}` in
+/// [`ToolResult::structured`]; non-2xx statuses set `is_error`.
+///
+/// `GET` / `HEAD` / `OPTIONS` are read-only; other methods are irreversible
+/// writes. All tools carry [`CapabilityFlag::Network`].
+#[derive(Clone)]
+pub struct OpenApiToolSource {
+    name: String,
+    tools: Vec<SharedTool>,
+}
+
+impl std::fmt::Debug for OpenApiToolSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("OpenApiToolSource")
+            .field("name", &self.name)
+            .field("tools", &self.tools.len())
+            .finish()
+    }
+}
+
+impl OpenApiToolSource {
+    /// Build tools from a parsed document.
+    ///
+    /// # Errors
+    ///
+    /// `ToolInvalidArgs` for a non-OpenAPI-3 document or a missing / invalid
+    /// base URL; `ToolExecution` when the HTTP client cannot be built.
+    pub fn from_spec(
+        name: impl Into<String>,
+        spec: &Value,
+        config: OpenApiConfig,
+    ) -> Result<Self, ToolError> {
+        let ops = spec::operations(spec)?;
+        let base = base_url(spec, config.base_url.as_deref())?;
+        let client = reqwest::Client::builder()
+            .timeout(config.request_timeout)
+            .build()
+            .map_err(|e| codes::execution(format!("openapi http client: {e}")))?;
+        let http = Arc::new(Http {
+            client,
+            base,
+            headers: config.headers,
+            max_response_bytes: config.max_response_bytes,
+        });
+        let schemes = spec
+            .pointer("/components/securitySchemes")
+            .map(|s| spec::resolve(spec, s))
+            .unwrap_or_default();
+        let tools = ops
+            .into_iter()
+            .map(|op| {
+                // Requirements are alternatives: use the first one whose
+                // schemes all have credentials, and inject only those.
+                let auth = op
+                    .security
+                    .iter()
+                    .find_map(|requirement| {
+                        requirement
+                            .iter()
+                            .map(|scheme| {
+                                let secret = config.credentials.get(scheme)?;
+                                Auth::from_scheme(schemes.get(scheme)?, secret)
+                            })
+                            .collect::<Option<Vec<_>>>()
+                    })
+                    .unwrap_or_default();
+                let name = config
+                    .tool_prefix
+                    .as_deref()
+                    .map_or_else(|| op.name.clone(), |p| format!("{p}__{}", op.name));
+                let tool: SharedTool = Arc::new(OpenApiTool {
+                    name,
+                    metadata: metadata_for(&op.method),
+                    op,
+                    auth,
+                    http: Arc::clone(&http),
+                });
+                tool
+            })
+            .collect();
+        Ok(Self {
+            name: name.into(),
+            tools,
+        })
+    }
+
+    /// Build tools from document text (JSON).
+    ///
+    /// # Errors
+    ///
+    /// `ToolInvalidArgs` when `text` is not JSON, plus [`Self::from_spec`]
+    /// errors.
+    pub fn from_json(
+        name: impl Into<String>,
+        text: &str,
+        config: OpenApiConfig,
+    ) -> Result<Self, ToolError> {
+        let spec: Value = serde_json::from_str(text)
+            .map_err(|e| codes::invalid_args(format!("OpenAPI document is not JSON: {e}")))?;
+        Self::from_spec(name, &spec, config)
+    }
+}
+
+impl ToolSource for OpenApiToolSource {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn tools(&self) -> Vec<SharedTool> {
+        self.tools.clone()
+    }
+}
+
+/// `GET` / `HEAD` / `OPTIONS` → read-only; everything else → irreversible write.
+fn metadata_for(method: &str) -> ToolMetadata {
+    let mut meta = if matches!(method, "GET" | "HEAD" | "OPTIONS") {
+        ToolMetadata::read_only()
+    } else {
+        let mut meta = ToolMetadata::exclusive_write();
+        meta.destructiveness = Destructiveness::Irreversible;
+        meta
+    };
+    meta.capabilities.push(CapabilityFlag::Network);
+    meta
+}
+
+/// Configured base URL, else the document's first server (variables take
+/// their defaults).
+fn base_url(spec: &Value, configured: Option<&str>) -> Result<Url, ToolError> {
+    let raw = if let Some(url) = configured {
+        url.to_owned()
+    } else {
+        let server = spec.pointer("/servers/0").ok_or_else(|| {
+            codes::invalid_args("OpenAPI document has no servers; set a base URL")
+        })?;
+        let mut url = server
+            .get("url")
+            .and_then(Value::as_str)
+            .unwrap_or_default()
+            .to_owned();
+        let vars = server.get("variables").and_then(Value::as_object);
+        for (var, def) in vars.into_iter().flatten() {
+            let value = def
+                .get("default")
+                .and_then(Value::as_str)
+                .unwrap_or_default();
+            url = url.replace(&format!("{{{var}}}"), value);
+        }
+        url
+    };
+    Url::parse(&raw)
+        .ok()
+        .filter(|u| !u.cannot_be_a_base())
+        .ok_or_else(|| codes::invalid_args(format!("invalid OpenAPI base URL '{raw}'")))
+}
+
+/// Credential placement for one security scheme.
+#[derive(Clone)]
+enum Auth {
+    Header(String, String),
+    Query(String, String),
+    Cookie(String, String),
+}
+
+impl Auth {
+    fn from_scheme(scheme: &Value, secret: &str) -> Option<Self> {
+        let field = |key: &str| scheme.get(key).and_then(Value::as_str).unwrap_or_default();
+        match field("type") {
+            "http" if field("scheme").eq_ignore_ascii_case("basic") => {
+                use base64::Engine as _;
+                let encoded = base64::engine::general_purpose::STANDARD.encode(secret);
+                Some(Self::Header(
+                    "authorization".to_owned(),
+                    format!("Basic {encoded}"),
+                ))
+            }
+            "http" | "oauth2" | "openIdConnect" => Some(Self::Header(
+                "authorization".to_owned(),
+                format!("Bearer {secret}"),
+            )),
+            "apiKey" => {
+                let name = field("name").to_owned();
+                let secret = secret.to_owned();
+                match field("in") {
+                    "header" => Some(Self::Header(name, secret)),
+                    "query" => Some(Self::Query(name, secret)),
+                    "cookie" => Some(Self::Cookie(name, secret)),
+                    _ => None,
+                }
+            }
+            _ => None,
+        }
+    }
+}
+
+struct Http {
+    client: reqwest::Client,
+    base: Url,
+    headers: Vec<(String, String)>,
+    max_response_bytes: usize,
+}
+
+/// One operation; `call` performs the HTTP request.
+struct OpenApiTool {
+    name: String,
+    metadata: ToolMetadata,
+    op: Operation,
+    auth: Vec<Auth>,
+    http: Arc<Http>,
+}
+
+impl OpenApiTool {
+    fn request(&self, args: &Value) -> Result<reqwest::RequestBuilder, ToolError> {
+        let mut url = self.http.base.clone();
+        {
+            let mut segments = url
+                .path_segments_mut()
+                .map_err(|()| codes::invalid_args("OpenAPI base URL cannot take a path"))?;
+            segments.pop_if_empty();
+            for segment in self.op.path.split('/').filter(|s| !s.is_empty()) {
+                segments.push(&self.expand(segment, args)?);
+            }
+        }
+        let mut headers: Vec<(String, String)> = self.http.headers.clone();
+        let mut cookies: Vec<String> = Vec::new();
+        {
+            let mut query = url.query_pairs_mut();
+            for param in &self.op.params {
+                let Some(value) = args.get(&param.name).filter(|v| !v.is_null()) else {
+                    continue;
+                };
+                match param.location {
+                    Location::Path => {}
+                    Location::Query => {
+                        let values = value
+                            .as_array()
+                            .map_or_else(|| vec![value], |a| a.iter().collect());
+                        for v in values {
+                            query.append_pair(&param.name, &scalar(v));
+                        }
+                    }
+                    Location::Header => headers.push((param.name.clone(), scalar(value))),
+                    Location::Cookie => cookies.push(format!("{}={}", param.name, scalar(value))),
+                }
+            }
+            for auth in &self.auth {
+                match auth {
+                    Auth::Header(name, value) => headers.push((name.clone(), value.clone())),
+                    Auth::Query(name, value) => {
+                        query.append_pair(name, value);
+                    }
+                    Auth::Cookie(name, value) => cookies.push(format!("{name}={value}")),
+                }
+            }
+        }
+        if url.query() == Some("") {
+            url.set_query(None);
+        }
+
+        let method = Method::from_bytes(self.op.method.as_bytes())
+            .map_err(|e| codes::invalid_args(format!("HTTP method: {e}")))?;
+        let mut req = self.http.client.request(method, url);
+        for (name, value) in headers {
+            req = req.header(name, value);
+        }
+        if !cookies.is_empty() {
+            req = req.header("cookie", cookies.join("; "));
+        }
+        if let Some(body) = self.op.body.as_ref().and_then(|key| args.get(key)) {
+            req = req.json(body);
+        }
+        Ok(req)
+    }
+
+    /// Substitute `{param}` placeholders in one path segment.
+    fn expand(&self, segment: &str, args: &Value) -> Result<String, ToolError> {
+        let mut out = String::new();
+        let mut rest = segment;
+        while let Some(start) = rest.find('{') {
+            let Some(len) = rest.get(start..).and_then(|r| r.find('}')) else {
+                break;
+            };
+            let name = rest.get(start + 1..start + len).unwrap_or_default();
+            let value = args.get(name).filter(|v| !v.is_null()).ok_or_else(|| {
+                codes::invalid_args(format!(
+                    "tool '{}' missing path parameter '{name}'",
+                    self.name
+                ))
+            })?;
+            out.push_str(rest.get(..start).unwrap_or_default());
+            out.push_str(&scalar(value));
+            rest = rest.get(start + len + 1..).unwrap_or_default();
+        }
+        out.push_str(rest);
+        Ok(out)
+    }
+
+    async fn send(&self, req: reqwest::RequestBuilder) -> Result<ToolResult, ToolError> {
+        let mut resp = req.send().await.map_err(http_error)?;
+        let status = resp.status().as_u16();
+        let limit = self.http.max_response_bytes;
+        let mut body: Vec<u8> = Vec::new();
+        let mut truncated = false;
+        while let Some(chunk) = resp.chunk().await.map_err(http_error)? {
+            let room = limit.saturating_sub(body.len());
+            if chunk.len() > room {
+                body.extend_from_slice(chunk.get(..room).unwrap_or_default());
+                truncated = true;
+                break;
+            }
+            body.extend_from_slice(&chunk);
+        }
+        Ok(response_result(status, &body, truncated))
+    }
+}
+
+#[async_trait]
+impl DynTool for OpenApiTool {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn description(&self) -> &str {
+        &self.op.description
+    }
+
+    fn parameters(&self) -> Value {
+        self.op.schema.clone()
+    }
+
+    fn metadata(&self) -> ToolMetadata {
+        self.metadata.clone()
+    }
+
+    async fn call(&self, ctx: ToolCallContext, arguments: Value) -> Result<ToolResult, ToolError> {
+        let req = self.request(&arguments)?;
+        tokio::select! {
+            () = ctx.cancel.cancelled() => Err(codes::cancelled()),
+            result = self.send(req) => result,
+        }
+    }
+}
+
+/// Query / header / path text for an argument value.
+fn scalar(value: &Value) -> String {
+    value
+        .as_str()
+        .map_or_else(|| value.to_string(), str::to_owned)
+}
+
+fn http_error(e: reqwest::Error) -> ToolError {
+    if e.is_timeout() {
+        codes::timeout(format!("openapi request timed out: {e}"))
+    } else {
+        codes::network(format!("openapi request: {e}"))
+    }
+}
+
+/// `HTTP <status>` + body text, with `{status, body, truncated}` structured.
+///
+/// JSON bodies are parsed unless truncated; anything else stays a string.
+fn response_result(status: u16, body: &[u8], truncated: bool) -> ToolResult {
+    // Cut at a char boundary when the byte cap split a code point.
+    let text = match std::str::from_utf8(body) {
+        Ok(text) => text.to_owned(),
+        Err(e) if truncated && e.error_len().is_none() => {
+            String::from_utf8_lossy(body.get(..e.valid_up_to()).unwrap_or_default()).into_owned()
+        }
+        Err(_) => String::from_utf8_lossy(body).into_owned(),
+    };
+    let parsed = if truncated || text.trim().is_empty() {
+        None
+    } else {
+        serde_json::from_str::<Value>(&text).ok()
+    };
+    let content = if truncated {
+        format!(
+            "HTTP {status}\n{text}\n[truncated after {} bytes]",
+            body.len()
+        )
+    } else {
+        format!("HTTP {status}\n{text}")
+    };
+    ToolResult {
+        content,
+        structured: Some(json!({
+            "status": status,
+            "body": parsed.unwrap_or(Value::String(text)),
+            "truncated": truncated,
+        })),
+        is_error: !(200..300).contains(&status),
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn metadata_follows_http_method() {
+        let get = metadata_for("GET");
+        assert!(get.allowed_in_read_only());
+        assert!(get.capabilities.contains(&CapabilityFlag::Network));
+        for method in ["POST", "PUT", "DELETE"] {
+            let meta = metadata_for(method);
+            assert!(!meta.allowed_in_read_only(), "{method}");
+            assert_eq!(meta.destructiveness, Destructiveness::Irreversible);
+        }
+    }
+
+    #[test]
+    fn truncated_bodies_stay_text() {
+        let full = response_result(200, br#"{"ok":true}"#, false);
+        assert_eq!(
+            full.structured,
+            Some(json!({"status": 200, "body": {"ok": true}, "truncated": false}))
+        );
+        assert!(!full.is_error);
+
+        let cut = response_result(
+            404,
+            "{\"name\":\"caf\u{e9}"
+                .as_bytes()
+                .split_last()
+                .expect("bytes")
+                .1,
+            true,
+        );
+        assert!(cut.is_error);
+        assert_eq!(
+            cut.structured.as_ref().and_then(|s| s.get("body")),
+            Some(&json!("{\"name\":\"caf"))
+        );
+        assert!(
+            cut.content.ends_with("[truncated after 13 bytes]"),
+            "{}",
+            cut.content
+        );
+
+        let base = base_url(
+            &json!({"servers": [{"url": "https://{host}/v1", "variables": {"host": {"default": "api.test"}}}]}),
+            None,
+        )
+        .expect("base");
+        assert_eq!(base.as_str(), "https://api.test/v1");
+        assert!(base_url(&json!({}), None).is_err());
+    }
+}
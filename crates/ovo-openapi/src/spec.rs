@@ -0,0 +1,430 @@
+//! OpenAPI 3 document → operation descriptors.
+//!
+//! Local `$ref`s (`#/components/...`) are inlined so each tool schema stands on
+//! its own; cycles collapse to an unconstrained `{}`.
+
+use std::collections::HashSet;
+
+use ovo_tools::ToolError;
+use ovo_tools::error::codes;
+use serde_json::{Map, Value, json};
+
+/// HTTP methods that may appear under a path item.
+const METHODS: [&str; 8] = [
+    "get", "put", "post", "delete", "options", "head", "patch", "trace",
+];
+
+/// Nested `$ref` hops before giving up on a chain.
+const MAX_REF_DEPTH: usize = 32;
+
+/// Provider limit on tool names.
+const MAX_NAME_LEN: usize = 64;
+
+/// Where a parameter goes on the wire.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Location {
+    Path,
+    Query,
+    Header,
+    Cookie,
+}
+
+/// One operation parameter (argument key == wire name).
+#[derive(Debug, Clone)]
+pub(crate) struct Param {
+    pub(crate) name: String,
+    pub(crate) location: Location,
+}
+
+/// One OpenAPI operation, ready to become a tool.
+#[derive(Debug, Clone)]
+pub(crate) struct Operation {
+    pub(crate) name: String,
+    pub(crate) description: String,
+    /// Upper-case HTTP method.
+    pub(crate) method: String,
+    /// Path template (`/pets/{id}`).
+    pub(crate) path: String,
+    pub(crate) params: Vec<Param>,
+    /// Argument key holding the JSON request body.
+    pub(crate) body: Option<String>,
+    /// Merged JSON Schema for the tool arguments.
+    pub(crate) schema: Value,
+    /// Security requirements the operation accepts: any one alternative, each
+    /// needing all of its scheme names.
+    pub(crate) security: Vec<Vec<String>>,
+}
+
+/// Every operation in `spec`, in path then method order.
+///
+/// # Errors
+///
+/// `ToolInvalidArgs` when `spec` is not an OpenAPI 3 document.
+pub(crate) fn operations(spec: &Value) -> Result<Vec<Operation>, ToolError> {
+    let version = spec
+        .get("openapi")
+        .and_then(Value::as_str)
+        .unwrap_or_default();
+    if !version.starts_with("3.") {
+        return Err(codes::invalid_args("not an OpenAPI 3 document"));
+    }
+    let paths = spec
+        .get("paths")
+        .and_then(Value::as_object)
+        .ok_or_else(|| codes::invalid_args("OpenAPI document has no paths"))?;
+    let mut ops = Vec::new();
+    let mut names = HashSet::new();
+    // Sorted so tool order does not depend on map ordering.
+    let mut sorted: Vec<_> = paths.iter().collect();
+    sorted.sort_by(|a, b| a.0.cmp(b.0));
+    for (path, item) in sorted {
+        let item = resolve(spec, item);
+        for method in METHODS {
+            let Some(op) = item.get(method) else {
+                continue;
+            };
+            let Some(mut op) = operation(spec, path, method, &item, op) else {
+                continue;
+            };
+            op.name = unique(&mut names, op.name);
+            ops.push(op);
+        }
+    }
+    Ok(ops)
+}
+
+fn operation(
+    spec: &Value,
+    path: &str,
+    method: &str,
+    item: &Value,
+    op: &Value,
+) -> Option<Operation> {
+    let mut properties = Map::new();
+    let mut required = Vec::new();
+    let mut params = Vec::new();
+    for param in merged_params(spec, item, op) {
+        let Some((p, schema, is_required)) = parameter(spec, &param) else {
+            continue;
+        };
+        if is_required {
+            required.push(Value::String(p.name.clone()));
+        }
+        properties.insert(p.name.clone(), schema);
+        params.push(p);
+    }
+
+    let body = if let Some(request_body) = op.get("requestBody").map(|b| resolve(spec, b)) {
+        let Some(schema) = json_body_schema(spec, &request_body) else {
+            tracing::debug!(%path, %method, "openapi: operation without a JSON body skipped");
+            return None;
+        };
+        let key = if properties.contains_key("body") {
+            "requestBody"
+        } else {
+            "body"
+        };
+        if request_body.get("required").and_then(Value::as_bool) == Some(true) {
+            required.push(Value::String(key.to_owned()));
+        }
+        properties.insert(key.to_owned(), schema);
+        Some(key.to_owned())
+    } else {
+        None
+    };
+
+    let mut schema = json!({"type": "object", "properties": properties});
+    if !required.is_empty()
+        && let Some(obj) = schema.as_object_mut()
+    {
+        obj.insert("required".to_owned(), Value::Array(required));
+    }
+
+    let method = method.to_ascii_uppercase();
+    let text = |key: &str| {
+        op.get(key)
+            .and_then(Value::as_str)
+            .filter(|s| !s.is_empty())
+    };
+    let description = text("summary")
+        .or_else(|| text("description"))
+        .map_or_else(|| format!("{method} {path}"), str::to_owned);
+    let name = text("operationId").map_or_else(
+        || sanitize(&format!("{}_{path}", method.to_ascii_lowercase())),
+        sanitize,
+    );
+    Some(Operation {
+        name,
+        description,
+        method,
+        path: path.to_owned(),
+        params,
+        body,
+        schema,
+        security: security(spec, op),
+    })
+}
+
+/// Path-item parameters overridden by operation parameters with the same
+/// `name` + `in`.
+fn merged_params(spec: &Value, item: &Value, op: &Value) -> Vec<Value> {
+    let list = |v: &Value| -> Vec<Value> {
+        v.get("parameters")
+            .and_then(Value::as_array)
+            .map(|ps| ps.iter().map(|p| resolve(spec, p)).collect())
+            .unwrap_or_default()
+    };
+    let key = |p: &Value| (p.get("name").cloned(), p.get("in").cloned());
+    let own = list(op);
+    let mut merged: Vec<Value> = list(item)
+        .into_iter()
+        .filter(|p| !own.iter().any(|o| key(o) == key(p)))
+        .collect();
+    merged.extend(own);
+    merged
+}
+
+fn parameter(spec: &Value, param: &Value) -> Option<(Param, Value, bool)> {
+    let name = param.get("name").and_then(Value::as_str)?;
+    let location = match param.get("in").and_then(Value::as_str)? {
+        "path" => Location::Path,
+        "query" => Location::Query,
+        "cookie" => Location::Cookie,
+        // Set by the client and auth config, never by the model.
+        "header"
+            if ["accept", "content-type", "authorization"]
+                .contains(&name.to_ascii_lowercase().as_str()) =>
+        {
+            return None;
+        }
+        "header" => Location::Header,
+        _ => return None,
+    };
+    let raw = param.get("schema").or_else(|| {
+        param
+            .get("content")
+            .and_then(Value::as_object)
+            .and_then(|c| c.values().next())
+            .and_then(|m| m.get("schema"))
+    });
+    let mut schema = raw.map_or_else(|| json!({}), |s| resolve(spec, s));
+    if let (Some(obj), Some(desc)) = (schema.as_object_mut(), param.get("description"))
+        && !obj.contains_key("description")
+    {
+        obj.insert("description".to_owned(), desc.clone());
+    }
+    let required =
+        location == Location::Path || param.get("required").and_then(Value::as_bool) == Some(true);
+    let param = Param {
+        name: name.to_owned(),
+        location,
+    };
+    Some((param, schema, required))
+}
+
+/// Schema of the first JSON media type (`application/json` or `*+json`).
+fn json_body_schema(spec: &Value, body: &Value) -> Option<Value> {
+    let content = body.get("content").and_then(Value::as_object)?;
+    let media = content.get("application/json").or_else(|| {
+        content
+            .iter()
+            .find(|(ty, _)| ty.ends_with("+json") || ty.ends_with("/json"))
+            .map(|(_, m)| m)
+    })?;
+    Some(
+        media
+            .get("schema")
+            .map_or_else(|| json!({}), |s| resolve(spec, s)),
+    )
+}
+
+/// Requirement alternatives from the operation's `security` (falls back to
+/// the document default; an explicit `[]` means none, and a `{}` entry allows
+/// anonymous calls).
+fn security(spec: &Value, op: &Value) -> Vec<Vec<String>> {
+    op.get("security")
+        .or_else(|| spec.get("security"))
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_object)
+        .map(|requirement| requirement.keys().cloned().collect())
+        .collect()
+}
+
+/// Inline local `$ref`s in `value`.
+pub(crate) fn resolve(spec: &Value, value: &Value) -> Value {
+    resolve_in(spec, value, &mut Vec::new())
+}
+
+fn resolve_in(spec: &Value, value: &Value, stack: &mut Vec<String>) -> Value {
+    match value {
+        Value::Object(map) => {
+            if let Some(Value::String(reference)) = map.get("$ref") {
+                if stack.contains(reference) || stack.len() >= MAX_REF_DEPTH {
+                    return json!({});
+                }
+                let Some(target) = reference.strip_prefix('#').and_then(|p| spec.pointer(p)) else {
+                    tracing::debug!(%reference, "openapi: unresolved $ref");
+                    return json!({});
+                };
+                stack.push(reference.clone());
+                let resolved = resolve_in(spec, target, stack);
+                stack.pop();
+                return resolved;
+            }
+            Value::Object(
+                map.iter()
+                    .map(|(k, v)| (k.clone(), resolve_in(spec, v, stack)))
+                    .collect(),
+            )
+        }
+        Value::Array(items) => {
+            Value::Array(items.iter().map(|v| resolve_in(spec, v, stack)).collect())
+        }
+        other => other.clone(),
+    }
+}
+
+/// Tool-name safe: `[A-Za-z0-9_-]`, no repeated `_`, at most 64 chars.
+fn sanitize(raw: &str) -> String {
+    let mut out = String::with_capacity(raw.len());
+    for c in raw.chars() {
+        let c = if c.is_ascii_alphanumeric() || c == '-' {
+            c
+        } else {
+            '_'
+        };
+        if !(c == '_' && (out.is_empty() || out.ends_with('_'))) {
+            out.push(c);
+        }
+    }
+    let trimmed: String = out
+        .trim_end_matches('_')
+        .chars()
+        .take(MAX_NAME_LEN)
+        .collect();
+    if trimmed.is_empty() {
+        "operation".to_owned()
+    } else {
+        trimmed
+    }
+}
+
+fn unique(seen: &mut HashSet<String>, name: String) -> String {
+    let mut candidate = name.clone();
+    let mut n = 2_u32;
+    while !seen.insert(candidate.clone()) {
+        candidate = format!("{name}_{n}");
+        n = n.saturating_add(1);
+    }
+    candidate
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use super::*;
+
+    fn petstore() -> Value {
+        json!({
+            "openapi": "3.0.3",
+            "security": [{"key": []}],
+            "paths": {
+                "/pets/{petId}": {
+                    "parameters": [
+                        {"name": "petId", "in": "path", "schema": {"type": "integer"}, "description": "Pet id"}
+                    ],
+                    "get": {
+                        "operationId": "getPet",
+                        "summary": "Fetch a pet",
+                        "parameters": [
+                            {"name": "fields", "in": "query", "schema": {"type": "array", "items": {"type": "string"}}},
+                            {"name": "Accept", "in": "header", "schema": {"type": "string"}}
+                        ]
+                    },
+                    "delete": {"security": []}
+                },
+                "/pets": {
+                    "post": {
+                        "operationId": "create pet!",
+                        "security": [{"key": [], "tenant": []}, {}],
+                        "parameters": [{"$ref": "#/components/parameters/Trace"}],
+                        "requestBody": {
+                            "required": true,
+                            "content": {"application/json": {"schema": {"$ref": "#/components/schemas/Pet"}}}
+                        }
+                    },
+                    "put": {
+                        "requestBody": {"content": {"application/xml": {"schema": {"type": "string"}}}}
+                    }
+                }
+            },
+            "components": {
+                "parameters": {
+                    "Trace": {"name": "X-Trace", "in": "header", "required": true, "schema": {"type": "string"}}
+                },
+                "schemas": {
+                    "Pet": {
+                        "type": "object",
+                        "properties": {
+                            "name": {"type": "string"},
+                            "parent": {"$ref": "#/components/schemas/Pet"}
+                        }
+                    }
+                }
+            }
+        })
+    }
+
+    fn find<'a>(ops: &'a [Operation], name: &str) -> &'a Operation {
+        ops.iter().find(|o| o.name == name).expect("operation")
+    }
+
+    #[test]
+    fn merges_parameters_and_body_into_one_schema() {
+        let ops = operations(&petstore()).expect("ops");
+        let names: Vec<&str> = ops.iter().map(|o| o.name.as_str()).collect();
+        assert_eq!(names, ["create_pet", "getPet", "delete_pets_petId"]);
+
+        let get = find(&ops, "getPet");
+        assert_eq!(get.method, "GET");
+        assert_eq!(get.description, "Fetch a pet");
+        assert_eq!(get.params.len(), 2, "Accept header is client-owned");
+        assert_eq!(
+            get.schema.pointer("/properties/petId/description"),
+            Some(&json!("Pet id"))
+        );
+        assert_eq!(get.schema.get("required"), Some(&json!(["petId"])));
+        assert_eq!(get.security, [["key"]]);
+
+        let post = find(&ops, "create_pet");
+        assert_eq!(post.security, [vec!["key", "tenant"], vec![]]);
+        assert_eq!(post.body.as_deref(), Some("body"));
+        assert_eq!(
+            post.schema.get("required"),
+            Some(&json!(["X-Trace", "body"]))
+        );
+        assert_eq!(
+            post.schema.pointer("/properties/body/properties/name/type"),
+            Some(&json!("string"))
+        );
+        assert_eq!(
+            post.schema.pointer("/properties/body/properties/parent"),
+            Some(&json!({})),
+            "recursive $ref collapses"
+        );
+
+        assert!(find(&ops, "delete_pets_petId").security.is_empty());
+    }
+
+    #[test]
+    fn rejects_non_openapi_documents() {
+        let err = operations(&json!({"swagger": "2.0"})).expect_err("swagger 2");
+        assert_eq!(err.code(), ovo_types::ErrorCode::ToolInvalidArgs);
+        assert_eq!(sanitize("__a//b__"), "a_b");
+        let mut seen = HashSet::new();
+        assert_eq!(unique(&mut seen, "x".to_owned()), "x");
+        assert_eq!(unique(&mut seen, "x".to_owned()), "x_2");
+    }
+}
@@ -0,0 +1,236 @@
+//! Drive [`OpenApiToolSource`] against an in-process HTTP fake.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links crate deps"
+)]
+
+#[cfg(test)]
+mod petstore {
+    #![allow(
+        clippy::expect_used,
+        clippy::indexing_slicing,
+        reason = "integration test harness"
+    )]
+
+    use std::collections::HashMap;
+    use std::net::SocketAddr;
+
+    use ovo_openapi::{OpenApiConfig, OpenApiToolSource};
+    use ovo_tools::{
+        CapabilityFlag, Destructiveness, ToolCallContext, ToolRegistry, ToolSource,
+        merge_tool_sources,
+    };
+    use serde_json::{Value, json};
+    use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+    use tokio::net::{TcpListener, TcpStream};
+
+    /// Echoes the request as JSON; `/missing` answers 404, `/big` a long text.
+    async fn serve(listener: TcpListener) {
+        while let Ok((stream, _)) = listener.accept().await {
+            tokio::spawn(handle(stream));
+        }
+    }
+
+    async fn handle(stream: TcpStream) {
+        let mut reader = BufReader::new(stream);
+        let mut request_line = String::new();
+        if reader.read_line(&mut request_line).await.unwrap_or(0) == 0 {
+            return;
+        }
+        let mut headers = HashMap::new();
+        loop {
+            let mut line = String::new();
+            if reader.read_line(&mut line).await.unwrap_or(0) == 0 || line == "\r\n" {
+                break;
+            }
+            if let Some((name, value)) = line.split_once(':') {
+                headers.insert(name.to_ascii_lowercase(), value.trim().to_owned());
+            }
+        }
+        let length = headers
+            .get("content-length")
+            .and_then(|l| l.parse().ok())
+            .unwrap_or(0);
+        let mut body = vec![0; length];
+        reader.read_exact(&mut body).await.expect("body");
+
+        let mut parts = request_line.split_whitespace();
+        let method = parts.next().unwrap_or_default();
+        let target = parts.next().unwrap_or_default();
+        let header = |name: &str| headers.get(name).cloned();
+        let (status, content_type, out) = if target.contains("/missing") {
+            (
+                "404 Not Found",
+                "application/json",
+                r#"{"error":"missing"}"#.to_owned(),
+            )
+        } else if target.contains("/big") {
+            ("200 OK", "text/plain", "x".repeat(200))
+        } else {
+            let echo = json!({
+                "method": method,
+                "target": target,
+                "authorization": header("authorization"),
+                "tenant": header("x-tenant"),
+                "trace": header("x-trace"),
+                "body": serde_json::from_slice::<Value>(&body).ok(),
+            });
+            ("200 OK", "application/json", echo.to_string())
+        };
+        let response = format!(
+            "HTTP/1.1 {status}\r\ncontent-type: {content_type}\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{out}",
+            out.len()
+        );
+        let stream = reader.get_mut();
+        let _ = stream.write_all(response.as_bytes()).await;
+        let _ = stream.shutdown().await;
+    }
+
+    fn spec(addr: SocketAddr) -> Value {
+        json!({
+            "openapi": "3.1.0",
+            "servers": [{"url": format!("http://{addr}/v1")}],
+            "security": [{"key": []}],
+            "paths": {
+                "/pets/{petId}": {
+                    "get": {
+                        "operationId": "getPet",
+                        "security": [{"bearer": []}, {"key": []}],
+                        "parameters": [
+                            {"name": "petId", "in": "path", "schema": {"type": "string"}},
+                            {"name": "fields", "in": "query", "schema": {"type": "array", "items": {"type": "string"}}}
+                        ]
+                    }
+                },
+                "/pets": {
+                    "post": {
+                        "operationId": "createPet",
+                        "parameters": [{"name": "X-Trace", "in": "header", "schema": {"type": "string"}}],
+                        "requestBody": {
+                            "content": {"application/json": {"schema": {"type": "object"}}}
+                        }
+                    }
+                },
+                "/big": {"get": {"operationId": "big", "security": []}},
+                "/missing": {"get": {"operationId": "missing", "security": []}}
+            },
+            "components": {
+                "securitySchemes": {
+                    "bearer": {"type": "http", "scheme": "bearer"},
+                    "key": {"type": "apiKey", "in": "query", "name": "api_key"}
+                }
+            }
+        })
+    }
+
+    async fn registry(config: OpenApiConfig) -> ToolRegistry {
+        let listener = TcpListener::bind("127.0.0.1:0").await.expect("bind");
+        let addr = listener.local_addr().expect("addr");
+        tokio::spawn(serve(listener));
+        let source =
+            OpenApiToolSource::from_spec("openapi:pets", &spec(addr), config).expect("source");
+        let source: &dyn ToolSource = &source;
+        merge_tool_sources([source])
+    }
+
+    #[tokio::test]
+    async fn injects_auth_and_encodes_arguments() {
+        let config = OpenApiConfig::default()
+            .with_header("x-tenant", "acme")
+            .with_credential("bearer", "tok")
+            .with_credential("key", "k1");
+        let reg = registry(config).await;
+
+        let get = reg.get("getPet").expect("getPet");
+        assert!(get.metadata().allowed_in_read_only());
+        assert!(
+            get.metadata()
+                .capabilities
+                .contains(&CapabilityFlag::Network)
+        );
+        let result = get
+            .call(
+                ToolCallContext::default(),
+                json!({"petId": "a b/c", "fields": ["id", "x y"]}),
+            )
+            .await
+            .expect("getPet");
+        assert!(!result.is_error);
+        assert!(
+            result.content.starts_with("HTTP 200\n"),
+            "{}",
+            result.content
+        );
+        let echo = &result.structured.expect("structured")["body"];
+        assert_eq!(echo["method"], "GET");
+        assert_eq!(echo["target"], "/v1/pets/a%20b%2Fc?fields=id&fields=x+y");
+        assert_eq!(echo["authorization"], "Bearer tok");
+        assert_eq!(echo["tenant"], "acme");
+
+        let post = reg.get("createPet").expect("createPet");
+        assert!(!post.metadata().allowed_in_read_only());
+        assert_eq!(
+            post.metadata().destructiveness,
+            Destructiveness::Irreversible
+        );
+        let result = post
+            .call(
+                ToolCallContext::default(),
+                json!({"X-Trace": "t-1", "body": {"name": "rex"}}),
+            )
+            .await
+            .expect("createPet");
+        let echo = &result.structured.expect("structured")["body"];
+        assert_eq!(echo["method"], "POST");
+        assert_eq!(echo["target"], "/v1/pets?api_key=k1");
+        assert_eq!(echo["authorization"], Value::Null);
+        assert_eq!(echo["trace"], "t-1");
+        assert_eq!(echo["body"], json!({"name": "rex"}));
+    }
+
+    #[tokio::test]
+    async fn falls_back_to_the_next_satisfiable_requirement() {
+        let reg = registry(OpenApiConfig::default().with_credential("key", "k1")).await;
+
+        let result = reg
+            .get("getPet")
+            .expect("getPet")
+            .call(ToolCallContext::default(), json!({"petId": "7"}))
+            .await
+            .expect("getPet");
+        let echo = &result.structured.expect("structured")["body"];
+        assert_eq!(echo["target"], "/v1/pets/7?api_key=k1");
+        assert_eq!(echo["authorization"], Value::Null);
+    }
+
+    #[tokio::test]
+    async fn truncates_bodies_and_flags_http_errors() {
+        let reg = registry(OpenApiConfig::default().with_max_response_bytes(32)).await;
+
+        let big = reg
+            .get("big")
+            .expect("big")
+            .call(ToolCallContext::default(), json!({}))
+            .await
+            .expect("big");
+        let structured = big.structured.expect("structured");
+        assert_eq!(structured["truncated"], true);
+        assert_eq!(structured["body"], "x".repeat(32));
+        assert!(
+            big.content.ends_with("[truncated after 32 bytes]"),
+            "{}",
+            big.content
+        );
+
+        let missing = reg
+            .get("missing")
+            .expect("missing")
+            .call(ToolCallContext::default(), json!({}))
+            .await
+            .expect("missing");
+        assert!(missing.is_error);
+        let structured = missing.structured.expect("structured");
+        assert_eq!(structured["status"], 404);
+        assert_eq!(structured["body"], json!({"error": "missing"}));
+    }
+}
@@ -2,7 +2,7 @@
 //!
 //! Every effect that matters to a host should appear here (event completeness).
 
-use ovo_types::{AgentId, RunId};
+use ovo_types::{AgentId, RunId, ToolChoice};
 use serde::{Deserialize, Serialize};
 
 /// One ordered event from a turn or nested spawn tree.
@@ -71,6 +71,12 @@ pub enum TurnEventKind {
         name: String,
         /// Human-readable progress fragment.
         message: String,
+        /// Tool-reported progress value, when the frame carries one.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        progress: Option<f64>,
+        /// Tool-reported total for `progress`, when known.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        total: Option<f64>,
     },
     /// Tool execution finished.
     ToolExecutionEnd {
@@ -114,6 +120,15 @@ pub enum TurnEventKind {
     InterjectionApplied,
     /// Stationarity nudge injected.
     StationarityNudge,
+    /// A non-`auto` tool choice was sent for this step.
+    ToolChoiceApplied {
+        /// One-based step index.
+        step: u32,
+        /// Choice sent with the sample request.
+        choice: ToolChoice,
+        /// Policy rule that selected it (e.g. `require_tool_steps`).
+        reason: String,
+    },
     /// Turn completed normally (including cancelled-with-outcome paths).
     TurnFinished {
         /// Steps executed.
@@ -8,6 +8,7 @@
 pub mod content;
 pub mod event;
 pub mod observability;
+pub mod tokenizer;
 pub mod tokens;
 pub mod tool_id;
 
@@ -17,6 +18,9 @@
     SPAN_COMPACT, SPAN_SAMPLE, SPAN_SESSION, SPAN_SPAWN, SPAN_TOOL, SPAN_TOOL_BATCH, SPAN_TURN,
     SPAN_WORKFLOW, SPAN_WORKFLOW_HOST, field, required_span_names, span_catalogue_snapshot,
 };
+pub use tokenizer::{
+    BpeCounter, HeuristicCounter, SharedTokenCounter, TOOL_FRAME_TOKENS, TokenCounter,
+};
 pub use tokens::{
     IMAGE_TOKEN_COST, MESSAGE_FRAME_TOKENS, PreflightOverflow, check_context_overflow,
     estimate_image_tokens, estimate_text_tokens,
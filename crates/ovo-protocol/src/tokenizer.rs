@@ -0,0 +1,393 @@
+//! Pluggable token counting for preflight, compaction and admission control.
+//!
+//! [`HeuristicCounter`] is the `bytes / 4` fallback from [`crate::tokens`].
+//! [`BpeCounter`] loads a tiktoken-format rank file (`cl100k_base.tiktoken`,
+//! `o200k_base.tiktoken`) from local disk and counts real BPE tokens. Both
+//! price images at [`IMAGE_TOKEN_COST`] and tool definitions at
+//! [`TOOL_FRAME_TOKENS`] plus their text.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::path::Path;
+use std::sync::Arc;
+
+use base64::Engine as _;
+use base64::engine::general_purpose::STANDARD;
+use ovo_types::{ContentPart, ErrorCode, Message, OvoError};
+
+use crate::tokens::{IMAGE_TOKEN_COST, MESSAGE_FRAME_TOKENS, estimate_text_tokens};
+
+/// Framing overhead per tool definition (name/description/schema wrapper).
+pub const TOOL_FRAME_TOKENS: u32 = 8;
+
+/// Counts tokens the way a model family's tokenizer would.
+pub trait TokenCounter: Send + Sync + fmt::Debug {
+    /// Stable id for logs (`heuristic`, `cl100k_base`, …).
+    fn name(&self) -> &str;
+
+    /// Tokens in a UTF-8 string.
+    fn count_text(&self, text: &str) -> u32;
+
+    /// Tokens for one image attachment.
+    fn count_image(&self) -> u32 {
+        IMAGE_TOKEN_COST
+    }
+
+    /// Framing tokens added per message (role + separators).
+    fn message_overhead(&self) -> u32 {
+        MESSAGE_FRAME_TOKENS
+    }
+
+    /// Tokens for one message: framing, text/image/reasoning parts, opaque
+    /// provider payloads and tool-call JSON.
+    fn count_message(&self, message: &Message) -> u32 {
+        let mut n = self.message_overhead();
+        if !message
+            .parts
+            .iter()
+            .any(|p| matches!(p, ContentPart::Text { .. }))
+        {
+            n = n.saturating_add(self.count_text(&message.text()));
+        }
+        for part in &message.parts {
+            n = n.saturating_add(match part {
+                ContentPart::Text { text } | ContentPart::Reasoning { text, .. } => {
+                    self.count_text(text)
+                }
+                ContentPart::Opaque { data, .. } => self.count_text(data),
+                ContentPart::Image { .. } => self.count_image(),
+                _ => 0,
+            });
+        }
+        for call in &message.tool_calls {
+            n = n.saturating_add(self.count_text(&call.name));
+            n = n.saturating_add(self.count_text(&call.arguments.to_string()));
+        }
+        n
+    }
+
+    /// Tokens for a whole conversation.
+    fn count_messages(&self, messages: &[Message]) -> u32 {
+        messages
+            .iter()
+            .fold(0u32, |acc, m| acc.saturating_add(self.count_message(m)))
+    }
+
+    /// Tokens for one tool definition (`parameters_json` is the serialized schema).
+    fn count_tool_definition(&self, name: &str, description: &str, parameters_json: &str) -> u32 {
+        TOOL_FRAME_TOKENS
+            .saturating_add(self.count_text(name))
+            .saturating_add(self.count_text(description))
+            .saturating_add(self.count_text(parameters_json))
+    }
+}
+
+/// Shared counter handle.
+pub type SharedTokenCounter = Arc<dyn TokenCounter>;
+
+/// `ceil(bytes / 4)` fallback (no vocabulary needed).
+#[derive(Debug, Default, Clone, Copy)]
+pub struct HeuristicCounter;
+
+impl TokenCounter for HeuristicCounter {
+    fn name(&self) -> &'static str {
+        "heuristic"
+    }
+
+    fn count_text(&self, text: &str) -> u32 {
+        estimate_text_tokens(text)
+    }
+}
+
+/// Byte-level BPE counter over a tiktoken rank file.
+///
+/// Pieces are split with the `cl100k_base` pre-tokenizer rules (contractions,
+/// letter runs, 1–3 digit groups, punctuation runs, whitespace); `o200k_base`
+/// vocabularies load the same way with slightly coarser splitting. Special
+/// tokens are not recognised.
+pub struct BpeCounter {
+    name: String,
+    ranks: HashMap<Vec<u8>, u32>,
+}
+
+impl fmt::Debug for BpeCounter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BpeCounter")
+            .field("name", &self.name)
+            .field("vocab_size", &self.ranks.len())
+            .finish()
+    }
+}
+
+impl BpeCounter {
+    /// Parse tiktoken text (`<base64 token> <rank>` per line).
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::TypesValidation`] on a malformed line or an empty vocabulary.
+    pub fn from_tiktoken(name: impl Into<String>, contents: &str) -> Result<Self, OvoError> {
+        let name = name.into();
+        let mut ranks = HashMap::new();
+        for (n, line) in contents.lines().enumerate() {
+            let line = line.trim();
+            if line.is_empty() {
+                continue;
+            }
+            let invalid = || {
+                OvoError::new(
+                    ErrorCode::TypesValidation,
+                    format!("{name}: invalid tiktoken line {}", n.saturating_add(1)),
+                )
+            };
+            let (token, rank) = line.split_once(' ').ok_or_else(invalid)?;
+            let token = STANDARD.decode(token).map_err(|_| invalid())?;
+            let rank: u32 = rank.trim().parse().map_err(|_| invalid())?;
+            ranks.insert(token, rank);
+        }
+        if ranks.is_empty() {
+            return Err(OvoError::new(
+                ErrorCode::TypesValidation,
+                format!("{name}: empty tiktoken vocabulary"),
+            ));
+        }
+        Ok(Self { name, ranks })
+    }
+
+    /// Load a tiktoken file from disk; the name is the file stem.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::TypesValidation`] when the file cannot be read or parsed.
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, OvoError> {
+        let path = path.as_ref();
+        let contents = std::fs::read_to_string(path).map_err(|e| {
+            OvoError::new(
+                ErrorCode::TypesValidation,
+                format!("read tiktoken vocab {}: {e}", path.display()),
+            )
+        })?;
+        let name = path
+            .file_stem()
+            .and_then(|s| s.to_str())
+            .unwrap_or("bpe")
+            .to_owned();
+        Self::from_tiktoken(name, &contents)
+    }
+
+    /// Number of ranked tokens.
+    #[must_use]
+    pub fn vocab_size(&self) -> usize {
+        self.ranks.len()
+    }
+
+    /// Token ranks for `text` (bytes missing from the vocabulary map to `u32::MAX`).
+    #[must_use]
+    pub fn encode(&self, text: &str) -> Vec<u32> {
+        let mut out = Vec::new();
+        for piece in split_pieces(text) {
+            let bytes = piece.as_bytes();
+            if let Some(rank) = self.ranks.get(bytes) {
+                out.push(*rank);
+                continue;
+            }
+            let bounds = self.merge(bytes);
+            out.extend(bounds.windows(2).map(|w| match w {
+                [a, b] => self.rank(bytes, *a, *b).unwrap_or(u32::MAX),
+                _ => u32::MAX,
+            }));
+        }
+        out
+    }
+
+    fn rank(&self, bytes: &[u8], start: usize, end: usize) -> Option<u32> {
+        bytes
+            .get(start..end)
+            .and_then(|slice| self.ranks.get(slice))
+            .copied()
+    }
+
+    /// Greedy lowest-rank pair merging; returns token boundaries.
+    fn merge(&self, bytes: &[u8]) -> Vec<usize> {
+        let mut bounds: Vec<usize> = (0..=bytes.len()).collect();
+        loop {
+            let best = bounds
+                .windows(3)
+                .enumerate()
+                .filter_map(|(i, w)| match w {
+                    [a, _, c] => self.rank(bytes, *a, *c).map(|r| (r, i)),
+                    _ => None,
+                })
+                .min();
+            let Some((_, i)) = best else {
+                break;
+            };
+            bounds.remove(i.saturating_add(1));
+        }
+        bounds
+    }
+}
+
+impl TokenCounter for BpeCounter {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn count_text(&self, text: &str) -> u32 {
+        let mut n: u32 = 0;
+        for piece in split_pieces(text) {
+            let count = if self.ranks.contains_key(piece.as_bytes()) {
+                1
+            } else {
+                self.merge(piece.as_bytes()).len().saturating_sub(1)
+            };
+            n = n.saturating_add(u32::try_from(count).unwrap_or(u32::MAX));
+        }
+        n
+    }
+}
+
+const fn is_newline(c: char) -> bool {
+    matches!(c, '\r' | '\n')
+}
+
+/// Split `text` into pre-tokenizer pieces following the `cl100k_base` pattern.
+fn split_pieces(text: &str) -> Vec<&str> {
+    let chars: Vec<(usize, char)> = text.char_indices().collect();
+    let byte_at = |i: usize| chars.get(i).map_or(text.len(), |(b, _)| *b);
+    let char_at = |i: usize| chars.get(i).map(|(_, c)| *c);
+    let run = |mut i: usize, pred: &dyn Fn(char) -> bool| {
+        while char_at(i).is_some_and(pred) {
+            i = i.saturating_add(1);
+        }
+        i
+    };
+    let mut pieces = Vec::new();
+    let mut i = 0usize;
+    while let Some(c) = char_at(i) {
+        let next = char_at(i.saturating_add(1));
+        let end = if let Some(len) = contraction_len(c, next, char_at(i.saturating_add(2))) {
+            i.saturating_add(len)
+        } else if c.is_alphabetic() {
+            run(i, &char::is_alphabetic)
+        } else if !c.is_numeric() && !is_newline(c) && next.is_some_and(char::is_alphabetic) {
+            run(i.saturating_add(1), &char::is_alphabetic)
+        } else if c.is_numeric() {
+            let digits = run(i, &char::is_numeric);
+            digits.min(i.saturating_add(3))
+        } else if !c.is_whitespace() || (c == ' ' && next.is_some_and(is_symbol)) {
+            let start = if c == ' ' { i.saturating_add(1) } else { i };
+            let symbols = run(start, &is_symbol);
+            run(symbols, &is_newline)
+        } else {
+            let ws_end = run(i, &char::is_whitespace);
+            let last_newline = (i..ws_end)
+                .rev()
+                .find(|j| char_at(*j).is_some_and(is_newline));
+            match last_newline {
+                Some(j) => j.saturating_add(1),
+                None if ws_end >= chars.len() || ws_end.saturating_sub(i) == 1 => ws_end,
+                None => ws_end.saturating_sub(1),
+            }
+        };
+        let end = end.max(i.saturating_add(1));
+        if let Some(piece) = text.get(byte_at(i)..byte_at(end)) {
+            pieces.push(piece);
+        }
+        i = end;
+    }
+    pieces
+}
+
+fn is_symbol(c: char) -> bool {
+    !(c.is_whitespace() || c.is_alphabetic() || c.is_numeric())
+}
+
+/// Length in chars of an English contraction (`'s`, `'re`, …) starting at `c`.
+fn contraction_len(c: char, next: Option<char>, after: Option<char>) -> Option<usize> {
+    if c != '\'' {
+        return None;
+    }
+    let next = next?.to_ascii_lowercase();
+    let after = after.map(|a| a.to_ascii_lowercase());
+    match (next, after) {
+        ('r', Some('e')) | ('v', Some('e')) | ('l', Some('l')) => Some(3),
+        ('s' | 't' | 'm' | 'd', _) => Some(2),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use super::*;
+
+    /// 256 single bytes plus a few merges, in tiktoken file format.
+    fn tiny_vocab() -> String {
+        let mut lines: Vec<String> = (0u8..=255)
+            .map(|b| {
+                let mut line = STANDARD.encode([b]);
+                line.push(' ');
+                line.push_str(&b.to_string());
+                line
+            })
+            .collect();
+        for (rank, token) in ["he", "ll", "hell", "hello", " w", " wo"]
+            .iter()
+            .enumerate()
+        {
+            let mut line = STANDARD.encode(token);
+            line.push(' ');
+            line.push_str(&(256 + rank).to_string());
+            lines.push(line);
+        }
+        lines.join("\n")
+    }
+
+    #[test]
+    fn pre_tokenizer_follows_cl100k_rules() {
+        assert_eq!(
+            split_pieces("Hello world, it's 12345!\n\n  x"),
+            vec![
+                "Hello", " world", ",", " it", "'s", " ", "123", "45", "!\n\n", " ", " x"
+            ]
+        );
+    }
+
+    #[test]
+    fn bpe_merges_by_rank_and_counts() {
+        let bpe = BpeCounter::from_tiktoken("tiny", &tiny_vocab()).expect("vocab");
+        assert_eq!(bpe.vocab_size(), 262);
+        assert_eq!(bpe.encode("hello"), vec![259]);
+        assert_eq!(bpe.count_text("hello world"), 1 + 4);
+        assert_eq!(bpe.count_text("hellx"), 2);
+        assert!(BpeCounter::from_tiktoken("bad", "!!! x").is_err());
+    }
+
+    #[test]
+    fn counters_price_messages_and_tools() {
+        let heuristic = HeuristicCounter;
+        let msgs = vec![Message::user("abcdefgh")];
+        assert_eq!(heuristic.count_messages(&msgs), MESSAGE_FRAME_TOKENS + 2);
+        let mut thought = Message::assistant("abcd");
+        thought.parts.push(ContentPart::Reasoning {
+            text: "abcdefgh".into(),
+            signature: None,
+        });
+        assert_eq!(
+            heuristic.count_message(&thought),
+            MESSAGE_FRAME_TOKENS + 1 + 2
+        );
+        thought.parts.push(ContentPart::Opaque {
+            provider: "openai_responses".into(),
+            data: "abcdefghabcd".into(),
+        });
+        assert_eq!(
+            heuristic.count_message(&thought),
+            MESSAGE_FRAME_TOKENS + 1 + 2 + 3
+        );
+        assert_eq!(
+            heuristic.count_tool_definition("abcd", "", "{}"),
+            TOOL_FRAME_TOKENS + 1 + 1
+        );
+    }
+}
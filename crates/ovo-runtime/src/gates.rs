@@ -19,9 +19,34 @@ pub enum GateDecision {
     Fail {
         /// Human-readable reason (surfaced as [`ovo_types::ErrorCode::RuntimeGate`]).
         reason: String,
+        /// Which requirement failed.
+        kind: GateFailKind,
     },
 }
 
+impl GateDecision {
+    /// [`GateDecision::Fail`] from a custom gate ([`GateFailKind::Other`]).
+    #[must_use]
+    pub fn fail(reason: impl Into<String>) -> Self {
+        Self::Fail {
+            reason: reason.into(),
+            kind: GateFailKind::Other,
+        }
+    }
+}
+
+/// Why a gate failed closed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[non_exhaustive]
+pub enum GateFailKind {
+    /// The agent's required completion tool was never called; the turn may
+    /// still force it (`ToolChoicePolicy::force_completion_tool`).
+    CompletionTool,
+    /// Any other gate.
+    #[default]
+    Other,
+}
+
 /// Extensible stop-gate. Gates run in order; first non-[`GateDecision::Complete`] wins.
 pub trait StopGate: Send + Sync {
     /// Evaluate after a final assistant message is appended.
@@ -133,6 +158,7 @@ fn completion_gate(
                 "required tool '{}' not called after {} reminder(s)",
                 req.tool, req.max_retries
             ),
+            kind: GateFailKind::CompletionTool,
         });
     }
     Some(GateDecision::Continue {
@@ -246,7 +272,8 @@ fn max_retries_then_fail_without_tool() {
         assert!(
             matches!(
                 evaluate_stop_gates(&agent, &state, 2),
-                GateDecision::Fail { ref reason } if reason.contains("submit")
+                GateDecision::Fail { ref reason, kind: GateFailKind::CompletionTool }
+                    if reason.contains("submit")
             ),
             "{:?}",
             evaluate_stop_gates(&agent, &state, 2)
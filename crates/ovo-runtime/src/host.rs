@@ -21,7 +21,7 @@
 use ovo_state::ChatStateHandle;
 use ovo_tools::registry::CapabilityMode;
 use ovo_tools::{EventBus, SharedTool};
-use ovo_types::{AgentId, ErrorCode, Message, OvoError, Usage};
+use ovo_types::{AgentId, ErrorCode, Message, OvoError, PriceTable, SamplingParams, Usage};
 use ovo_workflow::{WorkflowRunStatus, WorkflowRunStore};
 use serde_json::Value;
 use tokio::sync::{OwnedSemaphorePermit, Semaphore};
@@ -66,6 +66,8 @@ pub struct SpawnOpts {
     pub resume_from: Option<String>,
     /// Max output tokens hint for the child sample.
     pub max_output_tokens: Option<u64>,
+    /// Sampling overrides layered over the child definition's `sampling`.
+    pub sampling: SamplingParams,
     /// Nesting depth of this spawn (`0` = first level under the host).
     pub depth: u32,
     /// Parent turn event bus (spawn lifecycle events use this stream).
@@ -89,6 +91,7 @@ pub fn new(prompt: impl Into<String>) -> Self {
             fork_messages: None,
             resume_from: None,
             max_output_tokens: None,
+            sampling: SamplingParams::default(),
             depth: 0,
             events: None,
         }
@@ -150,6 +153,13 @@ pub const fn with_max_output_tokens(mut self, n: u64) -> Self {
         self
     }
 
+    /// Set sampling overrides for the child.
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: SamplingParams) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
     /// Request parent conversation fork (requires [`Self::with_fork_messages`]).
     #[must_use]
     pub const fn with_fork_context(mut self, fork: bool) -> Self {
@@ -195,6 +205,8 @@ pub struct AgentRunResult {
     pub cancelled: bool,
     /// Usage.
     pub usage: Usage,
+    /// USD spend under the host price table (0 when unpriced).
+    pub cost: f64,
     /// Wall duration ms.
     pub duration_ms: u64,
     /// Steps.
@@ -234,6 +246,8 @@ pub struct InProcessHost {
     /// Isolation backend for child environments (default in-process).
     isolation: Arc<dyn IsolationBackend>,
     metrics: SharedMetrics,
+    /// Per-model prices applied to child turns.
+    prices: Option<Arc<PriceTable>>,
     /// Parent session handle: seeds `fork_context` when `fork_messages` is unset.
     parent_handle: Option<ChatStateHandle>,
     /// Workflow run store for `resume_from` lookups.
@@ -274,6 +288,7 @@ pub fn new(sampler: Arc<dyn LlmSampler>, tools: Vec<SharedTool>) -> Self {
             prompt_assembler: Arc::new(IdentityAssembler),
             isolation: Arc::new(InProcessIsolation),
             metrics: Arc::new(NoopMetrics),
+            prices: None,
             parent_handle: None,
             run_store: None,
         }
@@ -341,6 +356,13 @@ pub fn with_metrics(mut self, metrics: SharedMetrics) -> Self {
         self
     }
 
+    /// Price child turns so [`AgentRunResult::cost`] reports spend.
+    #[must_use]
+    pub fn with_prices(mut self, prices: Arc<PriceTable>) -> Self {
+        self.prices = Some(prices);
+        self
+    }
+
     /// Parent conversation handle used when `fork_context` is set without messages.
     #[must_use]
     pub fn with_parent_handle(mut self, handle: ChatStateHandle) -> Self {
@@ -462,6 +484,7 @@ fn try_resume(&self, opts: &SpawnOpts) -> Result<Option<AgentRunResult>, OvoErro
                     output,
                     cancelled: false,
                     usage: Usage::zero(),
+                    cost: 0.0,
                     duration_ms: 0,
                     steps: 0,
                 }))
@@ -685,8 +708,10 @@ async fn spawn_one(&self, opts: SpawnOpts) -> Result<AgentRunResult, OvoError> {
                 cancel: opts.cancel.clone(),
                 agent_id: Some(agent_id.clone()),
                 metrics: Arc::clone(&self.metrics),
+                prices: self.prices.clone(),
                 spawn_depth: Some(opts.depth),
                 max_output_tokens,
+                sampling: opts.sampling.clone(),
                 cwd: isolation_env.cwd.clone(),
                 events: opts.events.clone(),
                 ..TurnOptions::default()
@@ -754,6 +779,7 @@ async fn spawn_one(&self, opts: SpawnOpts) -> Result<AgentRunResult, OvoError> {
                 output,
                 cancelled: outcome.cancelled,
                 usage: outcome.usage,
+                cost: outcome.cost,
                 duration_ms: u64::try_from(started.elapsed().as_millis()).unwrap_or(u64::MAX),
                 steps: outcome.steps,
             })
@@ -23,7 +23,9 @@
 pub mod workflow_host;
 
 pub use events::EventSink;
-pub use gates::{CompletionToolGate, GateChain, GateDecision, StopGate, evaluate_stop_gates};
+pub use gates::{
+    CompletionToolGate, GateChain, GateDecision, GateFailKind, StopGate, evaluate_stop_gates,
+};
 pub use host::{
     AgentRunResult, DEFAULT_MAX_CONCURRENT_CHILDREN, DEFAULT_MAX_SPAWN_DEPTH, InProcessHost,
     SessionHost, SpawnOpts,
@@ -31,18 +33,21 @@
 pub use isolation::{InProcessIsolation, IsolationBackend, IsolationEnv, isolation_error};
 pub use lifecycle::{LifecycleFanout, NoopLifecycle, TurnAbortReason, TurnLifecycleContributor};
 pub use metrics::{
-    METRIC_COMPACTIONS_TOTAL, METRIC_SAMPLE_DURATION_MS, METRIC_SPAWNS_TOTAL, METRIC_TOKENS_TOTAL,
+    METRIC_COMPACTIONS_TOTAL, METRIC_COST_TOTAL, METRIC_RATELIMIT_QUEUE_DEPTH,
+    METRIC_RATELIMIT_WAIT_MS, METRIC_SAMPLE_DURATION_MS, METRIC_SPAWNS_TOTAL, METRIC_TOKENS_TOTAL,
     METRIC_TOOL_CALLS_TOTAL, METRIC_TOOL_DURATION_MS, METRIC_TURN_DURATION_MS, METRIC_TURN_STEPS,
     METRIC_TURNS_TOTAL, METRIC_WORKFLOW_AGENTS_TOTAL, METRIC_WORKFLOW_RUNS_TOTAL, MetricsSink,
-    NoopMetrics, SharedMetrics, record_compaction, record_sample, record_spawn, record_tool_call,
-    record_turn, record_workflow_agents, record_workflow_run, required_metric_names,
+    NoopMetrics, SharedMetrics, record_compaction, record_cost, record_rate_limit_queue,
+    record_rate_limit_wait, record_sample, record_spawn, record_tool_call, record_turn,
+    record_workflow_agents, record_workflow_run, required_metric_names,
 };
 pub use ovo_compaction::{CompactionOutcome, CompactionStrategy, MaxMessages, TokenThreshold};
 pub use ovo_protocol::{TurnEvent, TurnEventKind};
 pub use ovo_tools::EventBus;
 pub use schema::{
-    STRUCTURED_OUTPUT_MAX_RETRIES, compile_schema, schema_retry_reminder,
-    validate_structured_output,
+    FINAL_ANSWER_TOOL, STRUCTURED_OUTPUT_MAX_RETRIES, compile_schema, final_answer_reminder,
+    final_answer_tool, schema_retry_reminder, validate_structured_output,
+    validate_structured_value,
 };
 pub use session::Session;
 pub use side_effects::WorkflowSideEffects;
@@ -4,9 +4,11 @@
 //! is the `ovo-obs` crate.
 
 pub use ovo_obs::{
-    METRIC_COMPACTIONS_TOTAL, METRIC_SAMPLE_DURATION_MS, METRIC_SPAWNS_TOTAL, METRIC_TOKENS_TOTAL,
+    METRIC_COMPACTIONS_TOTAL, METRIC_COST_TOTAL, METRIC_RATELIMIT_QUEUE_DEPTH,
+    METRIC_RATELIMIT_WAIT_MS, METRIC_SAMPLE_DURATION_MS, METRIC_SPAWNS_TOTAL, METRIC_TOKENS_TOTAL,
     METRIC_TOOL_CALLS_TOTAL, METRIC_TOOL_DURATION_MS, METRIC_TURN_DURATION_MS, METRIC_TURN_STEPS,
     METRIC_TURNS_TOTAL, METRIC_WORKFLOW_AGENTS_TOTAL, METRIC_WORKFLOW_RUNS_TOTAL, MetricsSink,
-    NoopMetrics, SharedMetrics, record_compaction, record_sample, record_spawn, record_tool_call,
-    record_turn, record_workflow_agents, record_workflow_run, required_metric_names,
+    NoopMetrics, SharedMetrics, record_compaction, record_cost, record_rate_limit_queue,
+    record_rate_limit_wait, record_sample, record_spawn, record_tool_call, record_turn,
+    record_workflow_agents, record_workflow_run, required_metric_names,
 };
@@ -1,12 +1,28 @@
 //! JSON Schema validation for structured agent outputs.
 
 use jsonschema::Validator;
+use ovo_tools::ToolDefinition;
 use ovo_types::{ErrorCode, OvoError};
 use serde_json::Value;
 
 /// Max corrective re-samples when structured output fails validation.
 pub const STRUCTURED_OUTPUT_MAX_RETRIES: u32 = 3;
 
+/// Synthetic tool offered in [`ovo_agent::StructuredOutputMode::ToolCall`] mode.
+pub const FINAL_ANSWER_TOOL: &str = "final_answer";
+
+/// `final_answer` definition whose parameters are the output schema.
+#[must_use]
+pub fn final_answer_tool(schema: &Value) -> ToolDefinition {
+    ToolDefinition {
+        name: FINAL_ANSWER_TOOL.into(),
+        description: "Return the final answer. The arguments are the answer; \
+                      call this once, when done."
+            .into(),
+        parameters: schema.clone(),
+    }
+}
+
 /// Compile a JSON Schema once per turn (or once per agent definition).
 ///
 /// # Errors
@@ -29,12 +45,21 @@ pub fn compile_schema(schema: &Value) -> Result<Validator, OvoError> {
 pub fn validate_structured_output(validator: &Validator, raw: &str) -> Result<Value, String> {
     let value: Value = serde_json::from_str(raw.trim())
         .map_err(|e| format!("model output was not valid JSON: {e}"))?;
-    validator
-        .validate(&value)
-        .map_err(|e| format!("output does not match the required schema: {e}"))?;
+    validate_structured_value(validator, &value)?;
     Ok(value)
 }
 
+/// Validate an already-parsed value (e.g. `final_answer` arguments).
+///
+/// # Errors
+///
+/// Returns a human-readable validation error suitable for model feedback.
+pub fn validate_structured_value(validator: &Validator, value: &Value) -> Result<(), String> {
+    validator
+        .validate(value)
+        .map_err(|e| format!("output does not match the required schema: {e}"))
+}
+
 /// Build a corrective user reminder after a schema failure.
 #[must_use]
 pub fn schema_retry_reminder(error: &str) -> String {
@@ -44,6 +69,12 @@ pub fn schema_retry_reminder(error: &str) -> String {
     )
 }
 
+/// Reminder after a plain-text reply in tool-call structured-output mode.
+#[must_use]
+pub fn final_answer_reminder() -> String {
+    format!("Call the `{FINAL_ANSWER_TOOL}` tool with your answer as its arguments.")
+}
+
 #[cfg(test)]
 mod tests {
     use serde_json::json;
@@ -172,6 +172,9 @@ pub async fn run_turn_on_handle_checkpointed(
         handle.replace(local.messages().to_vec()).await;
         if let Ok(o) = &outcome {
             handle.record_main_usage(o.usage).await;
+            for (model, cost) in &o.cost_by_model {
+                handle.record_cost(model.as_str(), *cost).await;
+            }
             if o.cancelled {
                 handle.mark_incomplete().await;
             }
@@ -307,6 +310,52 @@ async fn multi_turn_on_handle() {
         handle.shutdown().await;
     }
 
+    #[tokio::test]
+    async fn handle_ledger_books_cost_under_serving_model() {
+        use ovo_llm::FallbackSampler;
+        use ovo_types::{ErrorCode, ModelPrice, OvoError, PriceTable};
+
+        let primary = Arc::new(MockSampler::new());
+        primary.push_error(OvoError::new(ErrorCode::LlmRateLimit, "429"));
+        let backup = Arc::new(MockSampler::new());
+        backup.push_text("served");
+        let sampler = FallbackSampler::new()
+            .with_target(primary, "mock")
+            .with_target(backup, "backup");
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .build()
+            .expect("agent");
+        let options = TurnOptions {
+            prices: Some(Arc::new(
+                PriceTable::new()
+                    .with_model("mock", ModelPrice::new(2.0, 4.0))
+                    .with_model("backup", ModelPrice::new(20.0, 40.0)),
+            )),
+            ..TurnOptions::default()
+        };
+        let handle = ChatStateHandle::spawn(vec![]);
+        Session::new()
+            .run_turn_on_handle(
+                &agent,
+                &sampler,
+                &handle,
+                TurnInput::Text("hi".into()),
+                options,
+            )
+            .await
+            .expect("turn");
+        let snap = handle.snapshot().await.expect("snapshot");
+        let models: Vec<&str> = snap
+            .usage
+            .per_model_cost
+            .keys()
+            .map(String::as_str)
+            .collect();
+        assert_eq!(models, ["backup"]);
+        handle.shutdown().await;
+    }
+
     #[tokio::test]
     async fn handle_checkpoint_to_memory() {
         use ovo_state::MemoryPersistence;
@@ -1,8 +1,8 @@
 //! Conversation state abstractions.
 
 use ovo_compaction::max_messages::compact_max_messages;
-use ovo_protocol::{MESSAGE_FRAME_TOKENS, estimate_image_tokens, estimate_text_tokens};
-use ovo_types::{ContentPart, Message};
+use ovo_protocol::{HeuristicCounter, TokenCounter};
+use ovo_types::Message;
 
 /// Mutable conversation backing a turn or session.
 pub trait ConversationState: Send {
@@ -18,34 +18,16 @@ pub trait ConversationState: Send {
     fn token_estimate(&self) -> u64 {
         u64::from(estimate_messages_tokens(self.messages()))
     }
+    /// Token count under `counter` (turns pass `TurnOptions::token_counter`).
+    fn token_estimate_with(&self, counter: &dyn TokenCounter) -> u64 {
+        u64::from(counter.count_messages(self.messages()))
+    }
 }
 
-/// Shared estimator used by [`ConversationState::token_estimate`] and turn preflight.
+/// Shared `bytes / 4` estimator used by [`ConversationState::token_estimate`].
 #[must_use]
 pub fn estimate_messages_tokens(messages: &[Message]) -> u32 {
-    messages
-        .iter()
-        .fold(0u32, |acc, m| acc.saturating_add(estimate_one_message(m)))
-}
-
-fn estimate_one_message(m: &Message) -> u32 {
-    let mut n = MESSAGE_FRAME_TOKENS;
-    if m.parts.is_empty() {
-        n = n.saturating_add(estimate_text_tokens(&m.text()));
-    } else {
-        for part in &m.parts {
-            n = n.saturating_add(match part {
-                ContentPart::Text { text } => estimate_text_tokens(text),
-                ContentPart::Image { .. } => estimate_image_tokens(),
-                _ => 0,
-            });
-        }
-    }
-    for call in &m.tool_calls {
-        n = n.saturating_add(estimate_text_tokens(&call.name));
-        n = n.saturating_add(estimate_text_tokens(&call.arguments.to_string()));
-    }
-    n
+    HeuristicCounter.count_messages(messages)
 }
 
 /// In-memory conversation state.
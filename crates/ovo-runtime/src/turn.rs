@@ -5,29 +5,36 @@
 use std::time::Instant;
 
 use futures::StreamExt;
-use ovo_agent::Agent;
+use ovo_agent::{Agent, StructuredOutputMode, ToolChoicePolicy};
 use ovo_compaction::{CompactionStrategy, MaxMessages};
 use ovo_llm::{LlmSampler, SampleEvent, SampleRequest, SampleResponse, ToolChoice};
-use ovo_obs::{NoopMetrics, SharedMetrics, record_compaction, record_sample};
-use ovo_protocol::{PreflightOverflow, TurnEvent, TurnEventKind, check_context_overflow};
+use ovo_obs::{NoopMetrics, SharedMetrics, record_compaction, record_cost, record_sample};
+use ovo_protocol::{
+    HeuristicCounter, PreflightOverflow, SharedTokenCounter, TokenCounter, TurnEvent,
+    TurnEventKind, check_context_overflow,
+};
 use ovo_tools::registry::CapabilityMode;
 use ovo_tools::{
     ApprovalGate, ApprovalPolicy, AutoApprove, DispatchRequest, EventBus, ToolCallContext,
-    ToolDispatch,
+    ToolDefinition, ToolDispatch,
 };
 // EventBus used by TurnOptions
-use ovo_types::{AgentId, Deadline, ErrorCode, Message, OvoError, RunId, SessionId, Usage};
+use ovo_types::{
+    AgentId, Deadline, ErrorCode, Message, OvoError, PriceTable, RunId, SamplingParams, SessionId,
+    ToolCall, Usage,
+};
 use serde_json::Value;
 use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 use tracing::{Instrument, info_span};
 
 use crate::events::EventSink;
-use crate::gates::{GateChain, GateDecision};
+use crate::gates::{GateChain, GateDecision, GateFailKind};
 use crate::lifecycle::{LifecycleFanout, TurnAbortReason, TurnLifecycleContributor};
 use crate::schema::{
-    STRUCTURED_OUTPUT_MAX_RETRIES, compile_schema, schema_retry_reminder,
-    validate_structured_output,
+    FINAL_ANSWER_TOOL, STRUCTURED_OUTPUT_MAX_RETRIES, compile_schema, final_answer_reminder,
+    final_answer_tool, schema_retry_reminder, validate_structured_output,
+    validate_structured_value,
 };
 use crate::state::{ConversationState, estimate_messages_tokens};
 use crate::stationarity::{StationarityAction, StationarityTracker, nudge_message};
@@ -83,14 +90,24 @@ pub struct TurnOptions {
     pub spawn_depth: Option<u32>,
     /// Optional max output tokens for the sampler.
     pub max_output_tokens: Option<u32>,
+    /// Sampling overrides layered over the agent definition's `sampling`.
+    pub sampling: SamplingParams,
+    /// Tool-choice policy replacing the agent definition's.
+    pub tool_choice: Option<ToolChoicePolicy>,
     /// Prefer [`LlmSampler::sample_stream`] and aggregate into a full response.
     pub use_stream: bool,
+    /// Mark sample requests cacheable (served by a `CachingSampler` when wired).
+    pub cache_responses: bool,
+    /// Per-model prices; priced samples feed [`TurnOutcome::cost`] and `ovo_cost_total`.
+    pub prices: Option<Arc<PriceTable>>,
     /// Lifecycle contributors.
     pub contributors: Arc<dyn TurnLifecycleContributor>,
     /// Optional mid-turn user interjections drained before each sample.
     pub interject_rx: Option<Arc<tokio::sync::Mutex<mpsc::UnboundedReceiver<Message>>>>,
     /// Context window size for preflight overflow (tokens). `None` disables check.
     pub context_window_tokens: Option<u32>,
+    /// Tokenizer for preflight and compaction triggers (default: `bytes / 4`).
+    pub token_counter: SharedTokenCounter,
     /// Soft threshold ratio of context window (default 0.9).
     pub context_overflow_ratio: f32,
     /// When true, overflow after compaction is a hard error; else continue.
@@ -112,8 +129,13 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
             .field("approval_policy", &self.approval_policy)
             .field("spawn_depth", &self.spawn_depth)
             .field("max_output_tokens", &self.max_output_tokens)
+            .field("sampling", &self.sampling)
+            .field("tool_choice", &self.tool_choice)
             .field("use_stream", &self.use_stream)
+            .field("cache_responses", &self.cache_responses)
+            .field("has_prices", &self.prices.is_some())
             .field("context_window_tokens", &self.context_window_tokens)
+            .field("token_counter", &self.token_counter.name())
             .field("context_overflow_ratio", &self.context_overflow_ratio)
             .field("fail_on_context_overflow", &self.fail_on_context_overflow)
             .field("has_event_tx", &self.event_tx.is_some())
@@ -140,10 +162,15 @@ fn default() -> Self {
             metrics: Arc::new(NoopMetrics),
             spawn_depth: None,
             max_output_tokens: None,
+            sampling: SamplingParams::default(),
+            tool_choice: None,
             use_stream: false,
+            cache_responses: false,
+            prices: None,
             contributors: Arc::new(LifecycleFanout::new()),
             interject_rx: None,
             context_window_tokens: None,
+            token_counter: Arc::new(HeuristicCounter),
             context_overflow_ratio: 0.9,
             fail_on_context_overflow: true,
             event_tx: None,
@@ -240,6 +267,20 @@ pub const fn with_deadline(mut self, deadline: Deadline) -> Self {
         self
     }
 
+    /// Override sampling parameters (set fields win over the agent's).
+    #[must_use]
+    pub fn with_sampling(mut self, sampling: SamplingParams) -> Self {
+        self.sampling = sampling;
+        self
+    }
+
+    /// Replace the agent's tool-choice policy for this turn.
+    #[must_use]
+    pub const fn with_tool_choice(mut self, policy: ToolChoicePolicy) -> Self {
+        self.tool_choice = Some(policy);
+        self
+    }
+
     /// Prefer streaming sample aggregation for this turn.
     #[must_use]
     pub const fn with_stream(mut self, use_stream: bool) -> Self {
@@ -290,12 +331,34 @@ pub struct TurnOutcome {
     pub output_json: Option<Value>,
     /// Accumulated usage.
     pub usage: Usage,
+    /// USD spend under [`TurnOptions::prices`] (0 when unpriced).
+    pub cost: f64,
+    /// [`Self::cost`] split by the model that served each sample
+    /// ([`SampleResponse::served_by`], else the agent's model).
+    pub cost_by_model: Vec<(String, f64)>,
     /// Steps consumed.
     pub steps: usize,
     /// Whether cancelled.
     pub cancelled: bool,
 }
 
+/// Priced spend so far, per serving model.
+#[derive(Debug, Clone, Default)]
+struct Spend {
+    total: f64,
+    by_model: Vec<(String, f64)>,
+}
+
+impl Spend {
+    fn add(&mut self, model: &str, cost: f64) {
+        self.total += cost;
+        match self.by_model.iter_mut().find(|(m, _)| m == model) {
+            Some((_, c)) => *c += cost,
+            None => self.by_model.push((model.to_owned(), cost)),
+        }
+    }
+}
+
 /// Stateless turn engine.
 #[derive(Debug, Default, Clone, Copy)]
 pub struct TurnRuntime;
@@ -365,6 +428,7 @@ async fn run_inner(
         state.append(input.into_message());
 
         let mut usage = Usage::zero();
+        let mut spend = Spend::default();
         let mut steps = 0usize;
         let mut completion_retries_used = 0u32;
         let mut schema_retries_used = 0u32;
@@ -372,6 +436,25 @@ async fn run_inner(
             Some(schema) => Some(compile_schema(schema)?),
             None => None,
         };
+        let final_answer = agent
+            .definition()
+            .output_schema
+            .as_ref()
+            .filter(|_| {
+                let caps = sampler.capabilities(agent.model());
+                agent
+                    .definition()
+                    .structured_output
+                    .resolve(caps.json_schema)
+                    == StructuredOutputMode::ToolCall
+            })
+            .map(final_answer_tool);
+        let mut choice = StepChoice::new(
+            options
+                .tool_choice
+                .unwrap_or(agent.definition().tool_choice),
+            final_answer.is_some(),
+        );
         let stop_gates = options
             .stop_gates
             .clone()
@@ -406,7 +489,7 @@ async fn run_inner(
                     steps: u32::try_from(steps).unwrap_or(u32::MAX),
                     cancelled: true,
                 });
-                return Ok(empty_cancelled(run_id, usage, steps));
+                return Ok(empty_cancelled(run_id, usage, spend, steps));
             }
             if deadline_expired(&options) {
                 let err = OvoError::new(ErrorCode::RuntimeDeadline, "turn deadline expired");
@@ -442,14 +525,18 @@ async fn run_inner(
             maybe_compact(
                 state,
                 options.compaction.as_deref(),
+                options.token_counter.as_ref(),
                 options.metrics.as_ref(),
                 false,
                 &events,
             )?;
 
+            let mut tools = agent.tools().definitions(options.capability_mode);
+            tools.extend(final_answer.iter().cloned());
             if let Err(err) = preflight_with_optional_force_compact(
                 state,
                 &options,
+                count_tool_tokens(options.token_counter.as_ref(), &tools),
                 options.metrics.as_ref(),
                 &events,
             ) {
@@ -460,17 +547,30 @@ async fn run_inner(
                 return Err(err);
             }
 
-            let tools = agent.tools().definitions(options.capability_mode);
+            let (tool_choice, choice_reason) = choice.next(step_u32, agent, &tools);
+            if let Some(reason) = choice_reason {
+                events.emit(TurnEventKind::ToolChoiceApplied {
+                    step: step_u32,
+                    choice: tool_choice.clone(),
+                    reason: reason.into(),
+                });
+            }
+
             let request = SampleRequest {
                 model: agent.model().to_owned(),
                 messages: state.messages().to_vec(),
                 tools,
-                tool_choice: ToolChoice::Auto,
-                response_format: agent.definition().output_schema.clone(),
+                tool_choice,
+                response_format: agent
+                    .definition()
+                    .output_schema
+                    .clone()
+                    .filter(|_| final_answer.is_none()),
                 max_output_tokens: options.max_output_tokens,
-                temperature: None,
+                sampling: agent.definition().sampling.merged(&options.sampling),
                 cancel: options.cancel.clone(),
                 deadline: options.deadline,
+                cacheable: options.cache_responses,
             };
 
             let sample_span = info_span!("ovo.sample", ovo.step = step_u32);
@@ -481,7 +581,7 @@ async fn run_inner(
             {
                 Ok(r) => r,
                 Err(e) => {
-                    return finish_sample_error(e, &options, run_id, usage, steps, &events);
+                    return finish_sample_error(e, &options, run_id, usage, spend, steps, &events);
                 }
             };
             let sample_ms = sample_started.elapsed().as_secs_f64() * 1000.0;
@@ -492,9 +592,22 @@ async fn run_inner(
                 u64::from(response.usage.output_tokens),
             );
             usage += response.usage;
+            if let Some(prices) = &options.prices {
+                let model = response.served_by.as_deref().unwrap_or(agent.model());
+                if let Some(c) = prices.cost(model, &response.usage) {
+                    record_cost(options.metrics.as_ref(), model, c);
+                    spend.add(model, c);
+                }
+            }
 
             let message = response.message;
-            if message.tool_calls.is_empty() {
+            let answer_call = final_answer.as_ref().and_then(|_| {
+                message
+                    .tool_calls
+                    .iter()
+                    .find(|c| c.name == FINAL_ANSWER_TOOL)
+            });
+            if message.tool_calls.is_empty() || answer_call.is_some() {
                 stationarity.reset();
                 let mut final_ctx = FinalCtx {
                     agent,
@@ -504,11 +617,17 @@ async fn run_inner(
                     stop_gates: stop_gates.as_ref(),
                     completion_retries_used: &mut completion_retries_used,
                     schema_retries_used: &mut schema_retries_used,
+                    choice: &mut choice,
                     run_id: run_id.clone(),
                     usage,
+                    spend: &spend,
                     steps,
                 };
-                match handle_final_assistant(&mut final_ctx) {
+                let step = match answer_call {
+                    Some(call) => handle_final_answer(&mut final_ctx, call),
+                    None => handle_final_assistant(&mut final_ctx),
+                };
+                match step {
                     Ok(FinalStep::Done(outcome)) => {
                         options.contributors.on_turn_done(&run_id, outcome.steps);
                         events.emit(TurnEventKind::TurnFinished {
@@ -560,22 +679,116 @@ async fn run_inner(
     }
 }
 
+/// Progress of [`ToolChoicePolicy::force_completion_tool`] within one turn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ForcedCompletion {
+    /// Not triggered yet.
+    Unused,
+    /// Next sample forces the completion tool.
+    Pending,
+    /// Already spent; a further gate failure ends the turn.
+    Used,
+}
+
+/// Per-turn tool-choice state: the policy plus one-shot forcing.
+struct StepChoice {
+    policy: ToolChoicePolicy,
+    forced_completion: ForcedCompletion,
+    /// `final_answer` is offered (tool-call structured output).
+    final_answer: bool,
+    /// Force `final_answer` on the next sample.
+    final_answer_due: bool,
+}
+
+impl StepChoice {
+    const fn new(policy: ToolChoicePolicy, final_answer: bool) -> Self {
+        Self {
+            policy,
+            forced_completion: ForcedCompletion::Unused,
+            final_answer,
+            final_answer_due: false,
+        }
+    }
+
+    /// Tool choice for `step` and the rule behind it (`None` = plain `Auto`).
+    fn next(
+        &mut self,
+        step: u32,
+        agent: &Agent,
+        tools: &[ToolDefinition],
+    ) -> (ToolChoice, Option<&'static str>) {
+        if tools.is_empty() {
+            return (ToolChoice::Auto, None);
+        }
+        if self.forced_completion == ForcedCompletion::Pending {
+            self.forced_completion = ForcedCompletion::Used;
+            if let Some(req) = &agent.definition().completion
+                && tools.iter().any(|t| t.name == req.tool)
+            {
+                return (
+                    ToolChoice::Named(req.tool.clone()),
+                    Some("force_completion_tool"),
+                );
+            }
+        }
+        let by_policy = self.policy.choice_for_step(step);
+        // `final_answer` is the only way out in tool-call mode: force it when
+        // it is the only tool, after a plain-text reply, or when the policy
+        // would disallow tools.
+        if self.final_answer
+            && (self.final_answer_due
+                || tools.len() == 1
+                || matches!(by_policy, Some((ToolChoice::None, _))))
+        {
+            self.final_answer_due = false;
+            return (
+                ToolChoice::Named(FINAL_ANSWER_TOOL.into()),
+                Some("structured_output"),
+            );
+        }
+        by_policy.map_or((ToolChoice::Auto, None), |(choice, reason)| {
+            (choice, Some(reason))
+        })
+    }
+}
+
+/// Tokens the tool definitions add to every request.
+fn count_tool_tokens(counter: &dyn TokenCounter, tools: &[ToolDefinition]) -> u32 {
+    tools.iter().fold(0u32, |acc, t| {
+        let schema = serde_json::to_string(&t.parameters).unwrap_or_default();
+        acc.saturating_add(counter.count_tool_definition(&t.name, &t.description, &schema))
+    })
+}
+
 /// Preflight overflow: if over limit, force one compaction pass then re-check.
 fn preflight_with_optional_force_compact(
     state: &mut dyn ConversationState,
     options: &TurnOptions,
+    tool_tokens: u32,
     metrics: &dyn ovo_obs::MetricsSink,
     events: &EventSink,
 ) -> Result<(), OvoError> {
     let Some(window) = options.context_window_tokens else {
         return Ok(());
     };
-    let estimated = estimate_messages_tokens(state.messages());
+    let counter = options.token_counter.as_ref();
+    let estimated = counter
+        .count_messages(state.messages())
+        .saturating_add(tool_tokens);
     match check_context_overflow(estimated, window, options.context_overflow_ratio) {
         PreflightOverflow::Ok { .. } => Ok(()),
         PreflightOverflow::Overflow { .. } => {
-            maybe_compact(state, options.compaction.as_deref(), metrics, true, events)?;
-            let estimated2 = estimate_messages_tokens(state.messages());
+            maybe_compact(
+                state,
+                options.compaction.as_deref(),
+                counter,
+                metrics,
+                true,
+                events,
+            )?;
+            let estimated2 = counter
+                .count_messages(state.messages())
+                .saturating_add(tool_tokens);
             match check_context_overflow(estimated2, window, options.context_overflow_ratio) {
                 PreflightOverflow::Ok { .. } => Ok(()),
                 PreflightOverflow::Overflow {
@@ -600,10 +813,11 @@ fn finish_sample_error(
     options: &TurnOptions,
     run_id: RunId,
     usage: Usage,
+    spend: Spend,
     steps: usize,
     events: &EventSink,
 ) -> Result<TurnOutcome, OvoError> {
-    let mapped = map_sample_error(e, options, run_id.clone(), usage, steps);
+    let mapped = map_sample_error(e, options, run_id.clone(), usage, spend, steps);
     match &mapped {
         Ok(outcome) if outcome.cancelled => {
             options
@@ -666,38 +880,99 @@ struct FinalCtx<'a> {
     stop_gates: &'a GateChain,
     completion_retries_used: &'a mut u32,
     schema_retries_used: &'a mut u32,
+    choice: &'a mut StepChoice,
     run_id: RunId,
     usage: Usage,
+    spend: &'a Spend,
     steps: usize,
 }
 
 fn handle_final_assistant(ctx: &mut FinalCtx<'_>) -> Result<FinalStep, OvoError> {
     ctx.state.append(ctx.message.clone());
+    let text = ctx.message.text();
+
+    if ctx.choice.final_answer {
+        ctx.choice.final_answer_due = true;
+        return retry_structured_output(
+            ctx,
+            &format!("replied without calling `{FINAL_ANSWER_TOOL}`"),
+            Some(final_answer_reminder()),
+        );
+    }
 
     if let Some(validator) = ctx.schema_validator {
-        match validate_structured_output(validator, &ctx.message.text()) {
-            Ok(value) => return apply_stop_gates(ctx, Some(value)),
+        return match validate_structured_output(validator, &text) {
+            Ok(value) => apply_stop_gates(ctx, text, Some(value)),
             Err(err) => {
-                if *ctx.schema_retries_used >= STRUCTURED_OUTPUT_MAX_RETRIES {
-                    return Err(OvoError::new(
-                        ErrorCode::RuntimeStructuredOutput,
-                        format!(
-                            "structured output invalid after {STRUCTURED_OUTPUT_MAX_RETRIES} retries: {err}"
-                        ),
-                    ));
-                }
-                *ctx.schema_retries_used = ctx.schema_retries_used.saturating_add(1);
-                ctx.state.append(Message::user(schema_retry_reminder(&err)));
-                return Ok(FinalStep::Continue);
+                let reminder = schema_retry_reminder(&err);
+                retry_structured_output(ctx, &err, Some(reminder))
             }
+        };
+    }
+
+    apply_stop_gates(ctx, text, None)
+}
+
+/// Tool-call structured output: `call` is the model's `final_answer`.
+///
+/// Every call in the message gets a tool result (the others are not run) so
+/// the transcript stays valid for the next sample or turn.
+fn handle_final_answer(ctx: &mut FinalCtx<'_>, call: &ToolCall) -> Result<FinalStep, OvoError> {
+    let verdict = ctx
+        .schema_validator
+        .map_or(Ok(()), |v| validate_structured_value(v, &call.arguments));
+    ctx.state.append(ctx.message.clone());
+    for tc in &ctx.message.tool_calls {
+        let content = if tc.id != call.id {
+            format!("not run: `{FINAL_ANSWER_TOOL}` was called in the same step")
+        } else if let Err(err) = &verdict {
+            format!("error: {err}")
+        } else {
+            "accepted".to_owned()
+        };
+        ctx.state.append(Message::tool_result(
+            tc.id.clone(),
+            tc.name.clone(),
+            content,
+        ));
+    }
+    match verdict {
+        Ok(()) => apply_stop_gates(
+            ctx,
+            call.arguments.to_string(),
+            Some(call.arguments.clone()),
+        ),
+        Err(err) => {
+            ctx.choice.final_answer_due = true;
+            retry_structured_output(ctx, &err, None)
         }
     }
+}
 
-    apply_stop_gates(ctx, None)
+/// Count a structured-output retry, failing once the budget is spent.
+fn retry_structured_output(
+    ctx: &mut FinalCtx<'_>,
+    err: &str,
+    reminder: Option<String>,
+) -> Result<FinalStep, OvoError> {
+    if *ctx.schema_retries_used >= STRUCTURED_OUTPUT_MAX_RETRIES {
+        return Err(OvoError::new(
+            ErrorCode::RuntimeStructuredOutput,
+            format!(
+                "structured output invalid after {STRUCTURED_OUTPUT_MAX_RETRIES} retries: {err}"
+            ),
+        ));
+    }
+    *ctx.schema_retries_used = ctx.schema_retries_used.saturating_add(1);
+    if let Some(reminder) = reminder {
+        ctx.state.append(Message::user(reminder));
+    }
+    Ok(FinalStep::Continue)
 }
 
 fn apply_stop_gates(
     ctx: &mut FinalCtx<'_>,
+    output_text: String,
     output_json: Option<Value>,
 ) -> Result<FinalStep, OvoError> {
     match ctx
@@ -706,15 +981,11 @@ fn apply_stop_gates(
     {
         GateDecision::Complete => Ok(FinalStep::Done(TurnOutcome {
             run_id: ctx.run_id.clone(),
-            output_text: ctx.message.text(),
-            output_json: output_json.or_else(|| {
-                ctx.agent
-                    .definition()
-                    .output_schema
-                    .as_ref()
-                    .and_then(|_| serde_json::from_str(&ctx.message.text()).ok())
-            }),
+            output_text,
+            output_json,
             usage: ctx.usage,
+            cost: ctx.spend.total,
+            cost_by_model: ctx.spend.by_model.clone(),
             steps: ctx.steps,
             cancelled: false,
         })),
@@ -723,7 +994,18 @@ fn apply_stop_gates(
             ctx.state.append(Message::user(reminder));
             Ok(FinalStep::Continue)
         }
-        GateDecision::Fail { reason } => Err(OvoError::new(ErrorCode::RuntimeGate, reason)),
+        GateDecision::Fail { reason, kind } => {
+            if kind == GateFailKind::CompletionTool
+                && ctx.choice.policy.force_completion_tool
+                && ctx.choice.forced_completion == ForcedCompletion::Unused
+                && let Some(req) = &ctx.agent.definition().completion
+            {
+                ctx.choice.forced_completion = ForcedCompletion::Pending;
+                ctx.state.append(Message::user(req.reminder.clone()));
+                return Ok(FinalStep::Continue);
+            }
+            Err(OvoError::new(ErrorCode::RuntimeGate, reason))
+        }
     }
 }
 
@@ -772,6 +1054,7 @@ async fn dispatch_tools(
 fn maybe_compact(
     state: &mut dyn ConversationState,
     strategy: Option<&dyn CompactionStrategy>,
+    counter: &dyn TokenCounter,
     metrics: &dyn ovo_obs::MetricsSink,
     force: bool,
     events: &EventSink,
@@ -780,7 +1063,7 @@ fn maybe_compact(
         return Ok(());
     };
     let msgs = state.messages();
-    let tokens = state.token_estimate();
+    let tokens = state.token_estimate_with(counter);
     if !force && !strategy.should_compact(msgs, tokens) {
         return Ok(());
     }
@@ -828,6 +1111,7 @@ async fn collect_sample_stream(
     let mut message: Option<Message> = None;
     let mut usage = Usage::zero();
     let mut stop_reason = None;
+    let mut served_by = None;
     let mut text_buf = String::new();
 
     while let Some(ev) = stream.next().await {
@@ -844,9 +1128,11 @@ async fn collect_sample_stream(
             SampleEvent::Completed {
                 message: m,
                 stop_reason: reason,
+                served_by: model,
             } => {
                 message = Some(m);
                 stop_reason = reason;
+                served_by = model;
             }
             SampleEvent::Failed { message: msg } => {
                 return Err(OvoError::new(ErrorCode::LlmInvalidResponse, msg));
@@ -870,6 +1156,8 @@ async fn collect_sample_stream(
         message,
         usage,
         stop_reason,
+        served_by,
+        rate_limit: None,
     })
 }
 
@@ -878,6 +1166,7 @@ fn map_sample_error(
     options: &TurnOptions,
     run_id: RunId,
     usage: Usage,
+    spend: Spend,
     steps: usize,
 ) -> Result<TurnOutcome, OvoError> {
     if deadline_expired(options) {
@@ -891,6 +1180,8 @@ fn map_sample_error(
             output_text: String::new(),
             output_json: None,
             usage,
+            cost: spend.total,
+            cost_by_model: spend.by_model,
             steps,
             cancelled: true,
         });
@@ -898,12 +1189,14 @@ fn map_sample_error(
     Err(e)
 }
 
-fn empty_cancelled(run_id: RunId, usage: Usage, steps: usize) -> TurnOutcome {
+fn empty_cancelled(run_id: RunId, usage: Usage, spend: Spend, steps: usize) -> TurnOutcome {
     TurnOutcome {
         run_id,
         output_text: String::new(),
         output_json: None,
         usage,
+        cost: spend.total,
+        cost_by_model: spend.by_model,
         steps,
         cancelled: true,
     }
@@ -1033,6 +1326,85 @@ async fn tool_then_final() {
         assert!(out.steps >= 2);
     }
 
+    #[tokio::test]
+    async fn prices_accumulate_cost_per_sample() {
+        use ovo_types::ModelPrice;
+
+        let sampler = Arc::new(MockSampler::new());
+        sampler.push_tools(Message::assistant_tools(vec![ToolCall {
+            id: ToolCallId::new("c1").expect("id"),
+            name: "echo".into(),
+            arguments: json!({"text":"pong"}),
+        }]));
+        sampler.push_text("done");
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .tools(vec![Arc::new(EchoTool)])
+            .build()
+            .expect("agent");
+        let options = TurnOptions {
+            prices: Some(Arc::new(
+                PriceTable::new().with_model("mock", ModelPrice::new(2.0, 4.0)),
+            )),
+            ..TurnOptions::default()
+        };
+        let mut state = VecConversationState::new();
+        let out = TurnRuntime::new()
+            .run(
+                &agent,
+                sampler.as_ref(),
+                &mut state,
+                TurnInput::Text("ping".into()),
+                options,
+            )
+            .await
+            .expect("turn");
+        // Two samples of 1 input + 1 output token at $2 / $4 per million.
+        assert!((out.cost - 12e-6).abs() < 1e-12, "cost {}", out.cost);
+    }
+
+    #[tokio::test]
+    async fn streamed_fallback_is_priced_at_serving_model() {
+        use ovo_llm::FallbackSampler;
+        use ovo_types::ModelPrice;
+
+        let primary = Arc::new(MockSampler::new());
+        primary.push_error(OvoError::new(ErrorCode::LlmRateLimit, "429"));
+        let backup = Arc::new(MockSampler::new());
+        backup.push_text("done");
+        let sampler = FallbackSampler::new()
+            .with_target(primary, "mock")
+            .with_target(backup, "backup");
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .build()
+            .expect("agent");
+        let options = TurnOptions {
+            prices: Some(Arc::new(
+                PriceTable::new()
+                    .with_model("mock", ModelPrice::new(2.0, 4.0))
+                    .with_model("backup", ModelPrice::new(20.0, 40.0)),
+            )),
+            ..TurnOptions::default()
+        }
+        .with_stream(true);
+        let mut state = VecConversationState::new();
+        let out = TurnRuntime::new()
+            .run(
+                &agent,
+                &sampler,
+                &mut state,
+                TurnInput::Text("ping".into()),
+                options,
+            )
+            .await
+            .expect("turn");
+        // 1 input + 1 output token at the backup's $20 / $40 per million.
+        assert!((out.cost - 60e-6).abs() < 1e-12, "cost {}", out.cost);
+        let models: Vec<&str> = out.cost_by_model.iter().map(|(m, _)| m.as_str()).collect();
+        assert_eq!(models, ["backup"]);
+    }
+
     #[tokio::test]
     async fn event_stream_emits_lifecycle_and_tools() {
         use ovo_protocol::TurnEventKind;
@@ -1301,6 +1673,115 @@ async fn structured_output_exhausted() {
         assert_eq!(err.code(), ErrorCode::RuntimeStructuredOutput);
     }
 
+    #[tokio::test]
+    async fn structured_output_via_final_answer_tool() {
+        use ovo_llm::ModelCapabilities;
+
+        let sampler = Arc::new(
+            MockSampler::new().with_capabilities(ModelCapabilities::new().with_json_schema(false)),
+        );
+        sampler.push_text("{\"ok\": true}");
+        let answer = |id: &str, arguments: Value| {
+            Message::assistant_tools(vec![ToolCall {
+                id: ToolCallId::new(id).expect("id"),
+                name: FINAL_ANSWER_TOOL.into(),
+                arguments,
+            }])
+        };
+        sampler.push_tools(answer("f1", json!({"ok": "nope"})));
+        sampler.push_tools(answer("f2", json!({"ok": true})));
+
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .output_schema(json!({
+                "type": "object",
+                "properties": { "ok": { "type": "boolean" } },
+                "required": ["ok"]
+            }))
+            .build()
+            .expect("agent");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut state = VecConversationState::new();
+        let out = TurnRuntime::new()
+            .run(
+                &agent,
+                sampler.as_ref(),
+                &mut state,
+                TurnInput::Text("answer".into()),
+                TurnOptions::default().with_event_tx(tx),
+            )
+            .await
+            .expect("turn");
+        assert_eq!(out.output_json, Some(json!({"ok": true})));
+        assert_eq!(out.steps, 3);
+        let results: Vec<String> = state
+            .messages()
+            .iter()
+            .filter(|m| m.role == ovo_types::Role::Tool)
+            .map(Message::text)
+            .collect();
+        assert_eq!(results.len(), 2);
+        assert!(results.first().is_some_and(|r| r.starts_with("error:")));
+        assert_eq!(results.get(1).map(String::as_str), Some("accepted"));
+        let mut forced = 0;
+        while let Ok(ev) = rx.try_recv() {
+            if let TurnEventKind::ToolChoiceApplied { choice, reason, .. } = ev.kind {
+                assert_eq!(choice, ToolChoice::Named(FINAL_ANSWER_TOOL.into()));
+                assert_eq!(reason, "structured_output");
+                forced += 1;
+            }
+        }
+        assert_eq!(forced, 3);
+    }
+
+    #[tokio::test]
+    async fn replayed_final_answer_turn_hits_the_cassette() {
+        use ovo_llm::{ModelCapabilities, RecordingSampler, ReplaySampler};
+
+        let caps = ModelCapabilities::new().with_json_schema(false);
+        let live = Arc::new(MockSampler::new().with_capabilities(caps));
+        live.push_tools(Message::assistant_tools(vec![ToolCall {
+            id: ToolCallId::new("f1").expect("id"),
+            name: FINAL_ANSWER_TOOL.into(),
+            arguments: json!({"ok": true}),
+        }]));
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .output_schema(json!({
+                "type": "object",
+                "properties": { "ok": { "type": "boolean" } },
+                "required": ["ok"]
+            }))
+            .build()
+            .expect("agent");
+        let run = |sampler: Arc<dyn LlmSampler>| {
+            let agent = agent.clone();
+            async move {
+                let mut state = VecConversationState::new();
+                TurnRuntime::new()
+                    .run(
+                        &agent,
+                        sampler.as_ref(),
+                        &mut state,
+                        TurnInput::Text("answer".into()),
+                        TurnOptions::default(),
+                    )
+                    .await
+            }
+        };
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("turn.cassette.jsonl");
+        let recorder = RecordingSampler::create(live, &path).expect("create");
+        let recorded = run(Arc::new(recorder)).await.expect("record");
+        let replay = ReplaySampler::load(&path)
+            .expect("load")
+            .with_capabilities(caps);
+        let replayed = run(Arc::new(replay)).await.expect("replay");
+        assert_eq!(replayed.output_json, Some(json!({"ok": true})));
+        assert_eq!(replayed.output_json, recorded.output_json);
+    }
+
     #[tokio::test]
     async fn completion_gate_forces_retry() {
         use ovo_agent::CompletionRequirement;
@@ -1370,6 +1851,262 @@ async fn stream_sample_path() {
         assert_eq!(out.output_text, "streamed-out");
     }
 
+    #[tokio::test]
+    async fn sampling_params_merge_agent_and_turn() {
+        use std::sync::{Mutex, PoisonError};
+
+        use ovo_llm::{SampleRequest, SampleResponse};
+
+        #[derive(Default)]
+        struct CaptureSampler {
+            seen: Mutex<Vec<SamplingParams>>,
+        }
+
+        #[async_trait]
+        impl LlmSampler for CaptureSampler {
+            async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+                self.seen
+                    .lock()
+                    .unwrap_or_else(PoisonError::into_inner)
+                    .push(request.sampling);
+                Ok(SampleResponse {
+                    message: Message::assistant("ok"),
+                    usage: Usage::new(1, 1),
+                    stop_reason: None,
+                    served_by: None,
+                    rate_limit: None,
+                })
+            }
+        }
+
+        let sampler = CaptureSampler::default();
+        let agent = AgentBuilder::named("eval")
+            .model("mock")
+            .sampling(SamplingParams::new().with_temperature(0.7).with_seed(7))
+            .build()
+            .expect("agent");
+        let mut state = VecConversationState::new();
+        TurnRuntime::new()
+            .run(
+                &agent,
+                &sampler,
+                &mut state,
+                TurnInput::Text("hi".into()),
+                TurnOptions::default().with_sampling(SamplingParams::new().with_temperature(0.0)),
+            )
+            .await
+            .expect("turn");
+        let seen = sampler
+            .seen
+            .into_inner()
+            .unwrap_or_else(PoisonError::into_inner);
+        assert_eq!(
+            seen,
+            vec![SamplingParams::new().with_temperature(0.0).with_seed(7)]
+        );
+    }
+
+    /// Replays scripted replies and records each request's tool choice.
+    #[derive(Default)]
+    struct ChoiceSampler {
+        replies: std::sync::Mutex<std::collections::VecDeque<Message>>,
+        seen: std::sync::Mutex<Vec<ToolChoice>>,
+    }
+
+    impl ChoiceSampler {
+        fn new(replies: Vec<Message>) -> Self {
+            Self {
+                replies: std::sync::Mutex::new(replies.into()),
+                seen: std::sync::Mutex::default(),
+            }
+        }
+
+        fn seen(self) -> Vec<ToolChoice> {
+            self.seen
+                .into_inner()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+        }
+    }
+
+    #[async_trait]
+    impl LlmSampler for ChoiceSampler {
+        async fn sample(&self, request: SampleRequest) -> Result<SampleResponse, OvoError> {
+            self.seen
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .push(request.tool_choice);
+            let message = self
+                .replies
+                .lock()
+                .unwrap_or_else(std::sync::PoisonError::into_inner)
+                .pop_front()
+                .unwrap_or_else(|| Message::assistant("done"));
+            Ok(SampleResponse {
+                message,
+                usage: Usage::new(1, 1),
+                stop_reason: None,
+                served_by: None,
+                rate_limit: None,
+            })
+        }
+    }
+
+    fn echo_call(id: &str) -> Message {
+        Message::assistant_tools(vec![ToolCall {
+            id: ToolCallId::new(id).expect("id"),
+            name: "echo".into(),
+            arguments: json!({"text": "pong"}),
+        }])
+    }
+
+    #[tokio::test]
+    async fn tool_choice_policy_per_step() {
+        use ovo_agent::ToolChoicePolicy;
+
+        let sampler = ChoiceSampler::new(vec![echo_call("c1"), echo_call("c2")]);
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .tools(vec![Arc::new(EchoTool)])
+            .tool_choice(ToolChoicePolicy::default().require_tool_steps(1))
+            .build()
+            .expect("agent");
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let mut state = VecConversationState::new();
+        TurnRuntime::new()
+            .run(
+                &agent,
+                &sampler,
+                &mut state,
+                TurnInput::Text("ping".into()),
+                TurnOptions::default().with_event_tx(tx).with_tool_choice(
+                    ToolChoicePolicy::default()
+                        .require_tool_steps(1)
+                        .no_tools_after(2),
+                ),
+            )
+            .await
+            .expect("turn");
+        assert_eq!(
+            sampler.seen(),
+            vec![ToolChoice::Required, ToolChoice::Auto, ToolChoice::None]
+        );
+        let mut reasons = Vec::new();
+        while let Ok(ev) = rx.try_recv() {
+            if let TurnEventKind::ToolChoiceApplied { step, reason, .. } = ev.kind {
+                reasons.push((step, reason));
+            }
+        }
+        assert_eq!(
+            reasons,
+            vec![
+                (1, "require_tool_steps".to_owned()),
+                (3, "no_tools_after".to_owned())
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn force_completion_tool_after_reminders() {
+        use ovo_agent::{CompletionRequirement, ToolChoicePolicy};
+
+        let sampler = ChoiceSampler::new(vec![
+            Message::assistant("thinking"),
+            Message::assistant_tools(vec![ToolCall {
+                id: ToolCallId::new("s1").expect("id"),
+                name: "submit".into(),
+                arguments: json!({}),
+            }]),
+        ]);
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .tools(vec![Arc::new(SubmitTool)])
+            .completion(CompletionRequirement {
+                tool: "submit".into(),
+                reminder: "please call submit".into(),
+                max_retries: 0,
+            })
+            .tool_choice(ToolChoicePolicy::default().force_completion_tool(true))
+            .build()
+            .expect("agent");
+        let mut state = VecConversationState::new();
+        let out = TurnRuntime::new()
+            .run(
+                &agent,
+                &sampler,
+                &mut state,
+                TurnInput::Text("finish".into()),
+                TurnOptions::default(),
+            )
+            .await
+            .expect("turn");
+        assert_eq!(out.output_text, "done");
+        assert_eq!(
+            sampler.seen(),
+            vec![
+                ToolChoice::Auto,
+                ToolChoice::Named("submit".into()),
+                ToolChoice::Auto
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn custom_gate_failure_does_not_force_completion_tool() {
+        use ovo_agent::{CompletionRequirement, ToolChoicePolicy};
+
+        use crate::gates::{CompletionToolGate, StopGate};
+
+        struct Reject;
+        impl StopGate for Reject {
+            fn evaluate(
+                &self,
+                _agent: &Agent,
+                _state: &dyn ConversationState,
+                _retries_used: u32,
+            ) -> GateDecision {
+                GateDecision::fail("answer rejected")
+            }
+        }
+
+        let sampler = ChoiceSampler::new(vec![Message::assistant_tools(vec![ToolCall {
+            id: ToolCallId::new("s1").expect("id"),
+            name: "submit".into(),
+            arguments: json!({}),
+        }])]);
+        let requirement = CompletionRequirement {
+            tool: "submit".into(),
+            reminder: "please call submit".into(),
+            max_retries: 0,
+        };
+        let agent = AgentBuilder::named("a")
+            .model("mock")
+            .tools(vec![Arc::new(SubmitTool)])
+            .completion(requirement.clone())
+            .tool_choice(ToolChoicePolicy::default().force_completion_tool(true))
+            .build()
+            .expect("agent");
+        let gates = GateChain::new()
+            .push(CompletionToolGate { requirement })
+            .push(Reject);
+        let mut state = VecConversationState::new();
+        let err = TurnRuntime::new()
+            .run(
+                &agent,
+                &sampler,
+                &mut state,
+                TurnInput::Text("finish".into()),
+                TurnOptions::default().with_stop_gates(Arc::new(gates)),
+            )
+            .await
+            .expect_err("custom gate fails closed");
+        assert_eq!(err.code(), ErrorCode::RuntimeGate);
+        assert_eq!(
+            sampler.seen(),
+            vec![ToolChoice::Auto, ToolChoice::Auto],
+            "no forced completion call"
+        );
+    }
+
     #[tokio::test]
     async fn token_threshold_compaction() {
         use ovo_compaction::TokenThreshold;
@@ -220,6 +220,7 @@ async fn handle_spawn(
                     output: run.output,
                     cancelled: run.cancelled,
                     tokens_used: tokens,
+                    cost: run.cost,
                     duration_ms: run.duration_ms,
                 })
             }
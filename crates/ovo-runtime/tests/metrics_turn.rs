@@ -55,7 +55,7 @@ fn prometheus_text_nonempty() {
 #[test]
 fn catalogue_snapshots_are_pinned() {
     assert!(metric_catalogue_snapshot().contains(METRIC_TURNS_TOTAL));
-    assert_eq!(required_metric_names().len(), 11);
+    assert_eq!(required_metric_names().len(), 14);
     assert!(span_catalogue_snapshot().contains("ovo.turn"));
 }
 
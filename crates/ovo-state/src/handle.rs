@@ -41,6 +41,11 @@ enum Command {
         model: Option<String>,
         reply: oneshot::Sender<()>,
     },
+    RecordCost {
+        model: String,
+        cost: f64,
+        reply: oneshot::Sender<()>,
+    },
     RecordCompaction {
         strategy: String,
         reply: oneshot::Sender<()>,
@@ -273,6 +278,22 @@ async fn record_usage(&self, usage: Usage, subagent: bool, model: Option<String>
         }
     }
 
+    /// Record USD spend attributed to `model`.
+    pub async fn record_cost(&self, model: impl Into<String>, cost: f64) {
+        let (reply, rx) = oneshot::channel();
+        if self
+            .tx
+            .send(Command::RecordCost {
+                model: model.into(),
+                cost,
+                reply,
+            })
+            .is_ok()
+        {
+            let _ = rx.await;
+        }
+    }
+
     /// Record a compaction event at the current message length.
     pub async fn record_compaction_at(&self, strategy: impl Into<String>) {
         let (reply, rx) = oneshot::channel();
@@ -400,6 +421,10 @@ async fn actor_loop(
                 }
                 let _ = reply.send(());
             }
+            Command::RecordCost { model, cost, reply } => {
+                usage.record_cost(model, cost);
+                let _ = reply.send(());
+            }
             Command::RecordCompaction { strategy, reply } => {
                 usage.record_compaction_at(messages.len(), strategy);
                 let _ = reply.send(());
@@ -18,6 +18,12 @@ pub struct UsageLedger {
     /// Per-model breakdown (stable map for serde).
     #[serde(default)]
     pub per_model: BTreeMap<String, Usage>,
+    /// Total spend in USD across main and nested runs (priced samples only).
+    #[serde(default)]
+    pub cost: f64,
+    /// Per-model spend in USD.
+    #[serde(default)]
+    pub per_model_cost: BTreeMap<String, f64>,
     /// Compaction events recorded at message indices.
     #[serde(default)]
     pub compaction_at: Vec<CompactionRecord>,
@@ -69,6 +75,12 @@ pub fn record_model(&mut self, model: impl Into<String>, usage: Usage) {
         *entry += usage;
     }
 
+    /// Record USD spend attributed to a model id (see `ovo_types::PriceTable`).
+    pub fn record_cost(&mut self, model: impl Into<String>, cost: f64) {
+        self.cost += cost;
+        *self.per_model_cost.entry(model.into()).or_insert(0.0) += cost;
+    }
+
     /// Record that compaction ran when the message list had `len` messages.
     pub fn record_compaction_at(&mut self, at_message_index: usize, strategy: impl Into<String>) {
         self.compaction_at.push(CompactionRecord {
@@ -101,7 +113,13 @@ fn folds_and_per_model() {
         l.record_model("gpt", Usage::new(2, 0));
         l.record_prompt(0, Usage::new(1, 1));
         l.record_compaction_at(4, "max_messages");
+        l.record_cost("gpt", 0.25);
+        l.record_cost("gpt", 0.5);
+        l.record_cost("claude", 1.0);
         assert_eq!(l.total().total_tokens, 7);
+        assert!((l.cost - 1.75).abs() < 1e-9, "total cost {}", l.cost);
+        let gpt = l.per_model_cost.get("gpt").copied().unwrap_or_default();
+        assert!((gpt - 0.75).abs() < 1e-9, "gpt cost {gpt}");
         assert_eq!(l.per_model.get("gpt").map(|u| u.input_tokens), Some(2));
         assert_eq!(l.compaction_at.len(), 1);
     }
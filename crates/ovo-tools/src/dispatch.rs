@@ -3,6 +3,7 @@
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 
+use futures::StreamExt;
 use futures::future::join_all;
 use ovo_obs::{NoopMetrics, SharedMetrics, record_tool_call};
 use ovo_protocol::TurnEventKind;
@@ -15,7 +16,7 @@
 use crate::error::{ToolError, codes};
 use crate::metadata::{ConcurrencyMode, Destructiveness, ToolMetadata};
 use crate::registry::{CapabilityMode, ToolRegistry};
-use crate::stream::{ToolProgress, drain_with_progress};
+use crate::stream::{ToolProgress, ToolStreamItem};
 use crate::tool::{DynTool, SharedTool, ToolResult};
 
 /// One tool call to execute.
@@ -155,7 +156,9 @@ pub async fn execute_batch(
                 Prepare::Ready(tool)
                     if tool.metadata().concurrency == ConcurrencyMode::Exclusive =>
                 {
-                    let out = self.run_one(tool.as_ref(), ctx.clone(), req).await;
+                    let out = self
+                        .run_one(registry, tool.as_ref(), ctx.clone(), req)
+                        .await;
                     set_outcome(&mut outcomes, index, out);
                     index = index.saturating_add(1);
                 }
@@ -193,7 +196,8 @@ async fn run_concurrent_window(
             Some(async move {
                 (
                     win_i,
-                    self.run_one(win_tool.as_ref(), win_ctx, &win_req).await,
+                    self.run_one(registry, win_tool.as_ref(), win_ctx, &win_req)
+                        .await,
                 )
             })
         });
@@ -205,6 +209,7 @@ async fn run_concurrent_window(
 
     async fn run_one(
         &self,
+        registry: &ToolRegistry,
         tool: &dyn DynTool,
         ctx: ToolCallContext,
         req: &DispatchRequest,
@@ -216,7 +221,7 @@ async fn run_one(
         );
         let meta = tool.metadata();
         let started = Instant::now();
-        let result = async { self.execute_tool(tool, &meta, ctx, req).await }
+        let result = async { self.execute_tool(registry, tool, &meta, ctx, req).await }
             .instrument(span)
             .await;
         let ms = started.elapsed().as_secs_f64() * 1000.0;
@@ -238,6 +243,7 @@ async fn run_one(
 
     async fn execute_tool(
         &self,
+        registry: &ToolRegistry,
         tool: &dyn DynTool,
         meta: &ToolMetadata,
         ctx: ToolCallContext,
@@ -246,6 +252,15 @@ async fn execute_tool(
         if ctx.is_cancelled() {
             return Err(codes::cancelled());
         }
+        // Providers keep unparseable argument JSON as a raw string.
+        if let serde_json::Value::String(raw) = &req.call.arguments {
+            return Err(codes::invalid_args(format!(
+                "tool '{}' arguments are not valid JSON: {}",
+                tool.name(),
+                excerpt(raw)
+            )));
+        }
+        registry.validate_arguments(tool, &req.call.arguments)?;
         self.check_approval(tool, meta, &req.call.arguments).await?;
         let call_id = req.call.id.as_str().to_owned();
         let call_name = req.call.name.clone();
@@ -258,18 +273,28 @@ async fn execute_tool(
         let mut call_ctx = ctx.clone();
         call_ctx.cancel = call_cancel.clone();
         let fut = async {
-            let stream = tool
+            let mut stream = tool
                 .execute(call_ctx.clone(), req.call.arguments.clone())
                 .await;
-            let (progress, result) = drain_with_progress(stream).await;
-            for p in progress {
-                call_ctx.emit(TurnEventKind::ToolExecutionUpdate {
-                    id: call_id.clone(),
-                    name: call_name.clone(),
-                    message: progress_message(&p),
-                });
+            // Emit progress as it arrives so live observers see long calls.
+            while let Some(item) = stream.next().await {
+                match item {
+                    ToolStreamItem::Progress(p) => {
+                        let fraction = p.fraction();
+                        call_ctx.emit(TurnEventKind::ToolExecutionUpdate {
+                            id: call_id.clone(),
+                            name: call_name.clone(),
+                            message: progress_message(&p),
+                            progress: fraction.map(|(progress, _)| progress),
+                            total: fraction.and_then(|(_, total)| total),
+                        });
+                    }
+                    ToolStreamItem::Terminal(result) => return result,
+                }
             }
-            result
+            Err(codes::stream_protocol(
+                "tool stream ended without a terminal item",
+            ))
         };
         // Cap by min(tool timeout, turn deadline).
         let tool_limit = meta.timeout.filter(|d| !d.is_zero());
@@ -335,6 +360,16 @@ fn progress_message(p: &ToolProgress) -> String {
     }
 }
 
+pub(crate) fn excerpt(s: &str) -> String {
+    const MAX: usize = 200;
+    if s.chars().count() > MAX {
+        let head: String = s.chars().take(MAX).collect();
+        format!("{head}…")
+    } else {
+        s.to_owned()
+    }
+}
+
 fn needs_approval(policy: ApprovalPolicy, meta: &ToolMetadata) -> bool {
     match policy {
         ApprovalPolicy::Never => false,
@@ -659,6 +694,111 @@ async fn exclusive_serial() {
         assert_eq!(max_active.load(Ordering::SeqCst), 1);
     }
 
+    #[tokio::test]
+    async fn string_arguments_are_invalid_args() {
+        let counter = Arc::new(AtomicUsize::new(0));
+        let tool = Arc::new(CountingTool {
+            name: "r".into(),
+            meta: ToolMetadata::read_only(),
+            active: Arc::clone(&counter),
+            max_active: Arc::new(AtomicUsize::new(0)),
+            barrier: None,
+        });
+        let reg = ToolRegistry::from_tools(vec![tool]);
+        let mut req = call("r", "c1");
+        req.call.arguments = json!("{oops");
+        let outs = ToolDispatch::default()
+            .execute_batch(&reg, ToolCallContext::default(), vec![req])
+            .await;
+        let err = outs
+            .first()
+            .expect("one outcome")
+            .result
+            .as_ref()
+            .expect_err("invalid");
+        assert_eq!(err.code(), ErrorCode::ToolInvalidArgs);
+        assert!(err.message().contains("{oops"), "{err}");
+        assert_eq!(counter.load(Ordering::SeqCst), 0, "tool never ran");
+    }
+
+    struct StrictTool {
+        meta: ToolMetadata,
+        runs: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl DynTool for StrictTool {
+        fn name(&self) -> &str {
+            "strict"
+        }
+        fn description(&self) -> &str {
+            "needs n"
+        }
+        fn parameters(&self) -> serde_json::Value {
+            json!({
+                "type": "object",
+                "properties": {"n": {"type": "integer"}},
+                "required": ["n"]
+            })
+        }
+        fn metadata(&self) -> ToolMetadata {
+            self.meta.clone()
+        }
+        async fn call(
+            &self,
+            _ctx: ToolCallContext,
+            _arguments: serde_json::Value,
+        ) -> Result<ToolResult, ToolError> {
+            self.runs.fetch_add(1, Ordering::SeqCst);
+            Ok(ToolResult::text("ok"))
+        }
+    }
+
+    #[tokio::test]
+    async fn schema_violations_are_rejected_before_approval() {
+        let runs = Arc::new(AtomicUsize::new(0));
+        let reg = ToolRegistry::from_tools(vec![Arc::new(StrictTool {
+            meta: ToolMetadata::exclusive_write(),
+            runs: Arc::clone(&runs),
+        })]);
+        let dispatch = ToolDispatch::default().with_approval(Arc::new(AlwaysDeny));
+        let mut req = call("strict", "c1");
+        req.call.arguments = json!({"n": "three"});
+        let outs = dispatch
+            .execute_batch(&reg, ToolCallContext::default(), vec![req])
+            .await;
+        let err = outs
+            .first()
+            .expect("one")
+            .result
+            .as_ref()
+            .expect_err("invalid");
+        assert_eq!(
+            err.code(),
+            ErrorCode::ToolInvalidArgs,
+            "not approval: {err}"
+        );
+        assert!(err.message().contains("/n: "), "{err}");
+        assert_eq!(runs.load(Ordering::SeqCst), 0);
+
+        let free = ToolRegistry::from_tools(vec![Arc::new(StrictTool {
+            meta: ToolMetadata::read_only().with_free_form_arguments(true),
+            runs: Arc::clone(&runs),
+        })]);
+        let outs = ToolDispatch::default()
+            .execute_batch(
+                &free,
+                ToolCallContext::default(),
+                vec![call("strict", "c2")],
+            )
+            .await;
+        assert!(
+            outs.iter().all(|o| o.result.is_ok()),
+            "free-form skips schema"
+        );
+        assert_eq!(runs.load(Ordering::SeqCst), 1);
+    }
+
     #[tokio::test]
     async fn readonly_mode_denies_write() {
         let tool = Arc::new(CountingTool {
@@ -2,6 +2,10 @@
 
 #![forbid(unsafe_code)]
 
+// Only the `tool_macro` integration test uses the attribute.
+#[cfg(test)]
+use ovo_macros as _;
+
 pub mod approval;
 pub mod calc;
 pub mod context;
@@ -9,9 +13,11 @@
 pub mod error;
 pub mod metadata;
 pub mod registry;
+pub mod schema;
 pub mod source;
 pub mod stream;
 pub mod tool;
+pub mod typed;
 
 pub use approval::{AlwaysDeny, ApprovalDecision, ApprovalGate, AutoApprove, denied_error};
 pub use calc::CalcTool;
@@ -22,9 +28,14 @@
     CapabilityFlag, ConcurrencyMode, Destructiveness, InterruptBehavior, ToolMetadata,
 };
 pub use registry::{CapabilityMode, ToolRegistry};
+pub use schema::check_arguments;
 pub use source::{StaticToolSource, ToolSource, merge_arc_sources, merge_tool_sources};
 pub use stream::{
     MAX_DELTA_BYTES, MAX_FRAME_BYTES, ToolProgress, ToolStream, ToolStreamItem, drain_terminal,
     drain_with_progress, partial_progress_frames, terminal_only, with_progress,
 };
 pub use tool::{DynTool, SharedTool, ToolDefinition, ToolResult};
+pub use typed::{Tool, args_schema, output_result, parse_args};
+
+/// Re-exported for `#[derive(JsonSchema)]` on typed tool arguments.
+pub use schemars::{self, JsonSchema};
@@ -78,6 +78,9 @@ pub struct ToolMetadata {
     /// Optional per-tool concurrency cap (alongside [`ConcurrencyMode`]).
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub max_concurrency: Option<usize>,
+    /// Skip JSON Schema validation of arguments (free-form payloads).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub free_form_arguments: bool,
 }
 
 impl Default for ToolMetadata {
@@ -89,6 +92,7 @@ fn default() -> Self {
             timeout: None,
             capabilities: Vec::new(),
             max_concurrency: None,
+            free_form_arguments: false,
         }
     }
 }
@@ -136,6 +140,7 @@ pub fn shell_execute(timeout: Duration) -> Self {
             timeout: Some(timeout),
             capabilities: vec![CapabilityFlag::Execute, CapabilityFlag::Write],
             max_concurrency: Some(1),
+            free_form_arguments: false,
         }
     }
 
@@ -146,6 +151,13 @@ pub const fn with_max_concurrency(mut self, n: Option<usize>) -> Self {
         self
     }
 
+    /// Opt out of argument schema validation.
+    #[must_use]
+    pub const fn with_free_form_arguments(mut self, free_form: bool) -> Self {
+        self.free_form_arguments = free_form;
+        self
+    }
+
     /// True when the tool is admissible under a read-only capability mode.
     #[must_use]
     pub fn allowed_in_read_only(&self) -> bool {
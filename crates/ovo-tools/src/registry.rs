@@ -3,8 +3,11 @@
 use std::collections::HashMap;
 use std::sync::Arc;
 
+use serde_json::Value;
+
 use crate::error::{ToolError, codes};
 use crate::metadata::CapabilityFlag;
+use crate::schema::SchemaCache;
 use crate::tool::{DynTool, SharedTool, ToolDefinition};
 
 /// How nested/session capability mode filters tools.
@@ -46,9 +49,12 @@ pub const fn intersect(self, other: Self) -> Self {
 }
 
 /// Thread-safe tool registry.
+///
+/// Parameter schemas are compiled once per registry on first validation.
 #[derive(Clone, Default)]
 pub struct ToolRegistry {
     tools: Arc<HashMap<String, SharedTool>>,
+    schemas: Arc<SchemaCache>,
 }
 
 impl std::fmt::Debug for ToolRegistry {
@@ -57,7 +63,7 @@ fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         names.sort_unstable();
         f.debug_struct("ToolRegistry")
             .field("tools", &names)
-            .finish()
+            .finish_non_exhaustive()
     }
 }
 
@@ -77,6 +83,7 @@ pub fn from_tools(tools: Vec<SharedTool>) -> Self {
         }
         Self {
             tools: Arc::new(map),
+            schemas: Arc::default(),
         }
     }
 
@@ -95,6 +102,20 @@ pub fn require(&self, name: &str) -> Result<SharedTool, ToolError> {
         self.get(name).ok_or_else(|| codes::not_found(name))
     }
 
+    /// Validate `arguments` against `tool`'s parameter schema (compiled once
+    /// per registry). Tools with free-form arguments always pass.
+    ///
+    /// # Errors
+    ///
+    /// [`ovo_types::ErrorCode::ToolInvalidArgs`] listing the failing JSON pointers.
+    pub fn validate_arguments(
+        &self,
+        tool: &dyn DynTool,
+        arguments: &Value,
+    ) -> Result<(), ToolError> {
+        self.schemas.check(tool, arguments)
+    }
+
     /// Definitions visible under a capability mode.
     #[must_use]
     pub fn definitions(&self, mode: CapabilityMode) -> Vec<ToolDefinition> {
@@ -147,6 +168,7 @@ pub fn merge(&self, other: &Self) -> Self {
         }
         Self {
             tools: Arc::new(map),
+            schemas: Arc::default(),
         }
     }
 
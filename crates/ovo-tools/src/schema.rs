@@ -0,0 +1,122 @@
+//! Tool argument validation against each tool's parameter JSON Schema.
+//!
+//! Schemas are compiled on first use and cached per [`crate::ToolRegistry`]
+//! (shared by its clones). A schema that fails to compile is logged once and
+//! the tool runs unvalidated; tools with
+//! [`crate::ToolMetadata::free_form_arguments`] are never validated.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, PoisonError};
+
+use jsonschema::Validator;
+use serde_json::Value;
+
+use crate::error::{ToolError, codes};
+use crate::tool::DynTool;
+
+/// Max failures listed in one `ToolInvalidArgs` message.
+const MAX_REPORTED: usize = 8;
+
+/// Compiled parameter schemas keyed by tool name (`None`: schema did not compile).
+#[derive(Default)]
+pub(crate) struct SchemaCache {
+    compiled: Mutex<HashMap<String, Option<Arc<Validator>>>>,
+}
+
+impl SchemaCache {
+    /// Validate `arguments` against `tool`'s parameter schema.
+    pub(crate) fn check(&self, tool: &dyn DynTool, arguments: &Value) -> Result<(), ToolError> {
+        if tool.metadata().free_form_arguments {
+            return Ok(());
+        }
+        match self.validator(tool) {
+            Some(validator) => check_arguments(&validator, tool.name(), arguments),
+            None => Ok(()),
+        }
+    }
+
+    fn validator(&self, tool: &dyn DynTool) -> Option<Arc<Validator>> {
+        let mut compiled = self.compiled.lock().unwrap_or_else(PoisonError::into_inner);
+        compiled
+            .entry(tool.name().to_owned())
+            .or_insert_with(|| match Validator::new(&tool.parameters()) {
+                Ok(v) => Some(Arc::new(v)),
+                Err(e) => {
+                    tracing::warn!(
+                        tool = tool.name(),
+                        error = %e,
+                        "tool parameter schema does not compile; arguments are not validated"
+                    );
+                    None
+                }
+            })
+            .clone()
+    }
+}
+
+/// Validate `arguments` for tool `name`, listing each failing JSON pointer.
+///
+/// # Errors
+///
+/// [`ovo_types::ErrorCode::ToolInvalidArgs`] naming every failing location
+/// (up to a cap) and what is wrong there.
+pub fn check_arguments(
+    validator: &Validator,
+    name: &str,
+    arguments: &Value,
+) -> Result<(), ToolError> {
+    let mut failures: Vec<String> = validator
+        .iter_errors(arguments)
+        .map(|e| {
+            let pointer = e.instance_path().as_str();
+            let at = if pointer.is_empty() { "/" } else { pointer };
+            format!("{at}: {}", crate::dispatch::excerpt(&e.to_string()))
+        })
+        .collect();
+    if failures.is_empty() {
+        return Ok(());
+    }
+    let total = failures.len();
+    failures.truncate(MAX_REPORTED);
+    let more = total.saturating_sub(MAX_REPORTED);
+    let suffix = if more > 0 {
+        format!("\n- … and {more} more")
+    } else {
+        String::new()
+    };
+    Err(codes::invalid_args(format!(
+        "tool '{name}' arguments do not match its schema:\n- {}{suffix}",
+        failures.join("\n- ")
+    )))
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use ovo_types::ErrorCode;
+    use serde_json::json;
+
+    use super::*;
+
+    #[test]
+    fn lists_every_failing_pointer() {
+        let validator = Validator::new(&json!({
+            "type": "object",
+            "properties": {
+                "path": {"type": "string"},
+                "lines": {"type": "array", "items": {"type": "integer"}}
+            },
+            "required": ["path", "mode"]
+        }))
+        .expect("schema");
+        check_arguments(&validator, "read", &json!({"path": "a", "mode": 1})).expect("valid");
+        let err = check_arguments(&validator, "read", &json!({"path": 7, "lines": [1, "x"]}))
+            .expect_err("invalid");
+        assert_eq!(err.code(), ErrorCode::ToolInvalidArgs);
+        let msg = err.message();
+        assert!(msg.contains("tool 'read'"), "{msg}");
+        assert!(msg.contains("\n- /path: "), "{msg}");
+        assert!(msg.contains("\n- /lines/1: "), "{msg}");
+        assert!(msg.contains("\n- /: ") && msg.contains("mode"), "{msg}");
+    }
+}
@@ -59,7 +59,7 @@ fn tools(&self) -> Vec<SharedTool> {
 /// **Last source wins** on tool name collision (deterministic, documented).
 #[must_use]
 pub fn merge_tool_sources<'a>(
-    sources: impl IntoIterator<Item = &'a dyn ToolSource>,
+    sources: impl IntoIterator<Item = &'a (dyn ToolSource + 'a)>,
 ) -> ToolRegistry {
     let mut map: HashMap<String, SharedTool> = HashMap::new();
     for source in sources {
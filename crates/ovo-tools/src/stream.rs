@@ -76,6 +76,17 @@ pub fn text(text: impl Into<String>) -> Self {
         Self::Text { text: text.into() }
     }
 
+    /// Numeric `progress` and optional `total` from a [`Self::Custom`] payload
+    /// (the MCP `notifications/progress` shape); `None` for other frames.
+    #[must_use]
+    pub fn fraction(&self) -> Option<(f64, Option<f64>)> {
+        let Self::Custom { payload, .. } = self else {
+            return None;
+        };
+        let progress = payload.get("progress").and_then(Value::as_f64)?;
+        Some((progress, payload.get("total").and_then(Value::as_f64)))
+    }
+
     /// Partial progress helper.
     #[must_use]
     pub fn partial(delta: impl Into<String>, total_bytes: u64, truncated: bool, gap: u64) -> Self {
@@ -0,0 +1,212 @@
+//! Typed tools: Rust argument and output types instead of raw JSON.
+//!
+//! A [`Tool`] names an `Args` type (`Deserialize + JsonSchema`) and an
+//! `Output` type (`Serialize`). The blanket [`DynTool`] impl derives
+//! `parameters()` from `Args`, deserializes arguments with the failing JSON
+//! pointer in the error, and puts the serialized output in
+//! [`ToolResult::structured`]. `#[ovo_macros::tool]` builds one from an
+//! async fn.
+
+use std::future::Future;
+
+use async_trait::async_trait;
+use schemars::generate::SchemaSettings;
+use schemars::{JsonSchema, SchemaGenerator};
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use serde_path_to_error::Segment;
+
+use crate::context::ToolCallContext;
+use crate::error::{ToolError, codes};
+use crate::metadata::ToolMetadata;
+use crate::tool::{DynTool, ToolResult};
+
+/// Tool with typed arguments and output; registers as a [`DynTool`].
+pub trait Tool: Send + Sync {
+    /// Arguments; their JSON Schema is the model-facing `parameters`.
+    type Args: DeserializeOwned + JsonSchema + Send;
+    /// Output; serialized into [`ToolResult::structured`].
+    type Output: Serialize + Send;
+
+    /// Tool name.
+    fn name(&self) -> &str;
+    /// Description.
+    fn description(&self) -> &str;
+    /// Metadata.
+    fn metadata(&self) -> ToolMetadata {
+        ToolMetadata::default()
+    }
+    /// Execute with parsed arguments.
+    fn call(
+        &self,
+        ctx: ToolCallContext,
+        args: Self::Args,
+    ) -> impl Future<Output = Result<Self::Output, ToolError>> + Send;
+}
+
+#[async_trait]
+impl<T: Tool> DynTool for T {
+    fn name(&self) -> &str {
+        Tool::name(self)
+    }
+
+    fn description(&self) -> &str {
+        Tool::description(self)
+    }
+
+    fn parameters(&self) -> Value {
+        args_schema::<T::Args>()
+    }
+
+    fn metadata(&self) -> ToolMetadata {
+        Tool::metadata(self)
+    }
+
+    async fn call(&self, ctx: ToolCallContext, arguments: Value) -> Result<ToolResult, ToolError> {
+        let args = parse_args(Tool::name(self), arguments)?;
+        let output = Tool::call(self, ctx, args).await?;
+        output_result(&output)
+    }
+}
+
+/// JSON Schema for `A` as tool `parameters` (no `$schema` / `title` keys).
+#[must_use]
+pub fn args_schema<A: JsonSchema>() -> Value {
+    let settings = SchemaSettings::draft2020_12().with(|s| s.meta_schema = None);
+    let mut schema = SchemaGenerator::new(settings).into_root_schema_for::<A>();
+    schema.remove("title");
+    schema.to_value()
+}
+
+/// Deserialize tool `name`'s arguments into `A`.
+///
+/// # Errors
+///
+/// [`ovo_types::ErrorCode::ToolInvalidArgs`] naming the JSON pointer that
+/// failed and why.
+pub fn parse_args<A: DeserializeOwned>(name: &str, arguments: Value) -> Result<A, ToolError> {
+    serde_path_to_error::deserialize(arguments).map_err(|e| {
+        let pointer: String = e.path().iter().map(pointer_segment).collect();
+        let at = if pointer.is_empty() { "/" } else { &pointer };
+        codes::invalid_args(format!("tool '{name}' arguments: {at}: {}", e.inner()))
+    })
+}
+
+fn pointer_segment(segment: &Segment) -> String {
+    match segment {
+        Segment::Seq { index } => format!("/{index}"),
+        Segment::Map { key } => format!("/{}", key.replace('~', "~0").replace('/', "~1")),
+        Segment::Enum { variant } => format!("/{variant}"),
+        Segment::Unknown => "/?".to_owned(),
+    }
+}
+
+/// Tool result for `output`: strings are plain text; other values are
+/// compact JSON text plus [`ToolResult::structured`].
+///
+/// # Errors
+///
+/// [`ovo_types::ErrorCode::ToolExecution`] when `output` does not serialize.
+pub fn output_result<O: Serialize>(output: &O) -> Result<ToolResult, ToolError> {
+    let value = serde_json::to_value(output)
+        .map_err(|e| codes::execution(format!("tool output does not serialize: {e}")))?;
+    Ok(match value {
+        Value::String(text) => ToolResult::text(text),
+        value => ToolResult {
+            content: value.to_string(),
+            structured: Some(value),
+            is_error: false,
+        },
+    })
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use ovo_types::ErrorCode;
+    use serde::Deserialize;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Debug, Deserialize, JsonSchema)]
+    struct AddArgs {
+        /// Addends.
+        terms: Vec<i64>,
+        #[serde(default)]
+        label: Option<String>,
+    }
+
+    #[derive(Serialize)]
+    struct Sum {
+        total: i64,
+    }
+
+    struct Add;
+
+    impl Tool for Add {
+        type Args = AddArgs;
+        type Output = Sum;
+
+        fn name(&self) -> &'static str {
+            "add"
+        }
+
+        fn description(&self) -> &'static str {
+            "Add integers"
+        }
+
+        fn metadata(&self) -> ToolMetadata {
+            ToolMetadata::read_only()
+        }
+
+        async fn call(&self, _ctx: ToolCallContext, args: AddArgs) -> Result<Sum, ToolError> {
+            let _ = args.label;
+            Ok(Sum {
+                total: args.terms.iter().sum(),
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn typed_tool_runs_as_dyn_tool() {
+        let tool: &dyn DynTool = &Add;
+        let def = tool.definition();
+        assert_eq!(def.name, "add");
+        let params = &def.parameters;
+        assert_eq!(params.pointer("/type"), Some(&json!("object")));
+        assert_eq!(params.pointer("/required"), Some(&json!(["terms"])));
+        assert_eq!(
+            params.pointer("/properties/terms/description"),
+            Some(&json!("Addends."))
+        );
+        assert!(def.parameters.get("$schema").is_none());
+        assert!(tool.metadata().allowed_in_read_only());
+
+        let out = tool
+            .call(ToolCallContext::default(), json!({"terms": [1, 2, 3]}))
+            .await
+            .expect("ok");
+        assert_eq!(out.content, r#"{"total":6}"#);
+        assert_eq!(out.structured, Some(json!({"total": 6})));
+
+        let err = tool
+            .call(ToolCallContext::default(), json!({"terms": [1, "x"]}))
+            .await
+            .expect_err("bad arg");
+        assert_eq!(err.code(), ErrorCode::ToolInvalidArgs);
+        assert!(err.message().contains("/terms/1: "), "{err}");
+    }
+
+    #[test]
+    fn text_output_is_not_structured() {
+        let out = output_result(&"done").expect("ok");
+        assert_eq!(out, ToolResult::text("done"));
+        let err = parse_args::<AddArgs>("add", json!({})).expect_err("missing");
+        assert!(
+            err.message().contains("arguments: /: missing field"),
+            "{err}"
+        );
+    }
+}
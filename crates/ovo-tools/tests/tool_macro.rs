@@ -0,0 +1,96 @@
+//! `#[tool]` on an async fn yields a registrable typed tool.
+#![allow(
+    unused_crate_dependencies,
+    reason = "integration binary links runtime deps"
+)]
+
+#[cfg(test)]
+mod tool_macro {
+    #![allow(clippy::expect_used, reason = "integration test harness")]
+
+    use std::sync::Arc;
+
+    use ovo_macros::tool;
+    use ovo_tools::{
+        DispatchRequest, DynTool, JsonSchema, ToolCallContext, ToolDispatch, ToolError,
+        ToolRegistry,
+    };
+    use ovo_types::{ToolCall, ToolCallId};
+    use serde::{Deserialize, Serialize};
+    use serde_json::json;
+
+    #[derive(Deserialize, JsonSchema)]
+    #[schemars(crate = "ovo_tools::schemars")]
+    struct RepeatArgs {
+        /// Text to repeat.
+        text: String,
+        /// Copies (at least one).
+        times: u8,
+    }
+
+    #[derive(Serialize)]
+    struct Repeated {
+        text: String,
+    }
+
+    /// Repeat `text` a number of times.
+    #[tool(read_only)]
+    fn repeat(args: RepeatArgs) -> Result<Repeated, ToolError> {
+        Ok(Repeated {
+            text: args.text.repeat(usize::from(args.times.max(1))),
+        })
+    }
+
+    /// Name of the calling agent.
+    #[tool(name = "whoami", crate = "ovo_tools")]
+    async fn caller(
+        ctx: ToolCallContext,
+        _args: serde_json::Map<String, serde_json::Value>,
+    ) -> Result<String, ToolError> {
+        tokio::task::yield_now().await;
+        Ok(ctx
+            .agent_id
+            .map_or_else(|| "nobody".to_owned(), |id| id.to_string()))
+    }
+
+    #[tokio::test]
+    async fn macro_tools_register_and_dispatch() {
+        let def = RepeatTool.definition();
+        assert_eq!(def.name, "repeat");
+        assert_eq!(def.description, "Repeat `text` a number of times.");
+        assert_eq!(
+            def.parameters.get("required"),
+            Some(&json!(["text", "times"]))
+        );
+        assert!(DynTool::metadata(&RepeatTool).allowed_in_read_only());
+        assert_eq!(DynTool::name(&CallerTool), "whoami");
+
+        let reg = ToolRegistry::from_tools(vec![Arc::new(RepeatTool), Arc::new(CallerTool)]);
+        let request = |name: &str, arguments| DispatchRequest {
+            call: ToolCall {
+                id: ToolCallId::new(format!("c-{name}")).expect("id"),
+                name: name.to_owned(),
+                arguments,
+            },
+        };
+        let outs = ToolDispatch::default()
+            .execute_batch(
+                &reg,
+                ToolCallContext::default(),
+                vec![
+                    request("repeat", json!({"text": "ab", "times": 2})),
+                    request("whoami", json!({})),
+                    request("repeat", json!({"text": "ab", "times": 300})),
+                ],
+            )
+            .await;
+        let [ok, who, bad] = outs.as_slice() else {
+            unreachable!("three outcomes");
+        };
+        let ok = ok.result.as_ref().expect("repeat");
+        assert_eq!(ok.structured, Some(json!({"text": "abab"})));
+        assert_eq!(who.result.as_ref().expect("whoami").content, "nobody");
+        let err = bad.result.as_ref().expect_err("out of range");
+        assert!(err.message().contains("/times"), "{err}");
+    }
+}
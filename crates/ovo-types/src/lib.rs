@@ -10,10 +10,14 @@
 pub mod error;
 pub mod id;
 pub mod message;
+pub mod pricing;
+pub mod sampling;
 pub mod usage;
 
 pub use deadline::Deadline;
 pub use error::{ErrorCode, OvoError, Result, RetryClass};
 pub use id::{AgentId, RunId, SessionId, ToolCallId, WorkflowRunId};
 pub use message::{ContentPart, ImageMime, Message, Role, ToolCall};
+pub use pricing::{ModelPrice, PriceTable};
+pub use sampling::{ReasoningEffort, SamplingParams, ToolChoice};
 pub use usage::{CompletionTokensDetails, PromptTokensDetails, Usage};
@@ -83,6 +83,26 @@ pub enum ContentPart {
         /// Data URL or https URL.
         url: String,
     },
+    /// Model reasoning / thinking text. Adapters echo it back only where the
+    /// provider requires it (e.g. signed Anthropic thinking blocks) and strip
+    /// it otherwise; [`Message::text`] ignores it.
+    Reasoning {
+        /// Reasoning text (may be a provider summary).
+        text: String,
+        /// Provider signature that must accompany the text when replayed.
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        signature: Option<String>,
+    },
+    /// Provider-owned reasoning state replayed verbatim to the same provider
+    /// (encrypted Responses API reasoning, Anthropic `redacted_thinking`, Gemini
+    /// thought signatures). Other adapters skip it; [`Message::strip_reasoning`]
+    /// removes it with the reasoning text.
+    Opaque {
+        /// Adapter tag that produced the item (e.g. `openai_responses`).
+        provider: String,
+        /// Serialized JSON item.
+        data: String,
+    },
 }
 
 /// A model-emitted tool invocation.
@@ -206,11 +226,49 @@ pub fn text(&self) -> String {
             .iter()
             .filter_map(|p| match p {
                 ContentPart::Text { text } => Some(text.as_str()),
-                ContentPart::Image { .. } => None,
+                ContentPart::Image { .. }
+                | ContentPart::Reasoning { .. }
+                | ContentPart::Opaque { .. } => None,
             })
             .collect::<Vec<_>>()
             .join("")
     }
+
+    /// Concatenated [`ContentPart::Reasoning`] text, `None` when there is none.
+    #[must_use]
+    pub fn reasoning(&self) -> Option<String> {
+        let texts: Vec<&str> = self
+            .parts
+            .iter()
+            .filter_map(|p| match p {
+                ContentPart::Reasoning { text, .. } => Some(text.as_str()),
+                _ => None,
+            })
+            .collect();
+        (!texts.is_empty()).then(|| texts.join(""))
+    }
+
+    /// True when the message carries [`ContentPart::Reasoning`] or
+    /// [`ContentPart::Opaque`] parts.
+    #[must_use]
+    pub fn has_reasoning(&self) -> bool {
+        self.parts.iter().any(is_reasoning_part)
+    }
+
+    /// Remove every [`ContentPart::Reasoning`] and [`ContentPart::Opaque`] part;
+    /// true when any was removed.
+    pub fn strip_reasoning(&mut self) -> bool {
+        let before = self.parts.len();
+        self.parts.retain(|p| !is_reasoning_part(p));
+        self.parts.len() != before
+    }
+}
+
+const fn is_reasoning_part(part: &ContentPart) -> bool {
+    matches!(
+        part,
+        ContentPart::Reasoning { .. } | ContentPart::Opaque { .. }
+    )
 }
 
 #[cfg(test)]
@@ -237,4 +295,38 @@ fn tool_call_message() {
         assert_eq!(m.tool_calls.len(), 1);
         assert_eq!(m.tool_calls.first().map(|c| &c.id), Some(&id));
     }
+
+    #[test]
+    fn reasoning_parts_are_separate_from_text() {
+        let mut m = Message::assistant("answer");
+        m.parts.push(ContentPart::Reasoning {
+            text: "step 1".into(),
+            signature: Some("sig".into()),
+        });
+        let json = serde_json::to_value(&m).expect("ser");
+        assert_eq!(
+            json.pointer("/parts/0"),
+            Some(&serde_json::json!({"type": "reasoning", "text": "step 1", "signature": "sig"}))
+        );
+        let mut back: Message = serde_json::from_value(json).expect("de");
+        assert_eq!(back.text(), "answer");
+        assert_eq!(back.reasoning().as_deref(), Some("step 1"));
+        assert!(back.strip_reasoning());
+        assert_eq!(back.reasoning(), None);
+        assert!(!back.strip_reasoning());
+    }
+
+    #[test]
+    fn strip_reasoning_drops_opaque_reasoning_state() {
+        let mut m = Message::assistant("answer");
+        m.parts.push(ContentPart::Opaque {
+            provider: "anthropic".into(),
+            data: r#"{"type":"redacted_thinking","data":"x"}"#.into(),
+        });
+        assert!(m.has_reasoning());
+        assert_eq!(m.reasoning(), None);
+        assert!(m.strip_reasoning());
+        assert!(!m.has_reasoning());
+        assert_eq!(m.text(), "answer");
+    }
 }
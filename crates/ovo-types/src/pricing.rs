@@ -0,0 +1,250 @@
+//! Per-model token prices and spend accounting.
+//!
+//! Rates are USD per million tokens. Token buckets follow the provider
+//! mappings in `ovo-llm`: `input_tokens` already includes cache reads and
+//! cache writes, and `output_tokens` already includes reasoning tokens, so each
+//! bucket is priced once.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorCode, OvoError};
+use crate::usage::Usage;
+
+const PER_MILLION: f64 = 1_000_000.0;
+
+/// USD rates per million tokens for one model.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ModelPrice {
+    /// Uncached input tokens.
+    #[serde(default)]
+    pub input: f64,
+    /// Output tokens.
+    #[serde(default)]
+    pub output: f64,
+    /// Cache-read input tokens (default: `input`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cached_input: Option<f64>,
+    /// Cache-creation / write tokens (default: `input`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_write: Option<f64>,
+    /// Reasoning tokens (default: `output`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning: Option<f64>,
+}
+
+impl ModelPrice {
+    /// Input/output rates; cache and reasoning tokens bill at these rates.
+    #[must_use]
+    pub const fn new(input: f64, output: f64) -> Self {
+        Self {
+            input,
+            output,
+            cached_input: None,
+            cache_write: None,
+            reasoning: None,
+        }
+    }
+
+    /// Set the cache-read rate.
+    #[must_use]
+    pub const fn with_cached_input(mut self, rate: f64) -> Self {
+        self.cached_input = Some(rate);
+        self
+    }
+
+    /// Set the cache-write rate.
+    #[must_use]
+    pub const fn with_cache_write(mut self, rate: f64) -> Self {
+        self.cache_write = Some(rate);
+        self
+    }
+
+    /// Set the reasoning-token rate.
+    #[must_use]
+    pub const fn with_reasoning(mut self, rate: f64) -> Self {
+        self.reasoning = Some(rate);
+        self
+    }
+
+    /// USD cost of `usage` (response-cache hits carry no tokens, so they add
+    /// nothing to a summed `Usage`).
+    #[must_use]
+    pub fn cost(&self, usage: &Usage) -> f64 {
+        let uncached = usage
+            .input_tokens
+            .saturating_sub(usage.cache_read_tokens)
+            .saturating_sub(usage.cache_creation_tokens);
+        let plain_output = usage.output_tokens.saturating_sub(usage.reasoning_tokens);
+        let buckets = [
+            (uncached, self.input),
+            (
+                usage.cache_read_tokens,
+                self.cached_input.unwrap_or(self.input),
+            ),
+            (
+                usage.cache_creation_tokens,
+                self.cache_write.unwrap_or(self.input),
+            ),
+            (plain_output, self.output),
+            (
+                usage.reasoning_tokens,
+                self.reasoning.unwrap_or(self.output),
+            ),
+        ];
+        buckets.iter().fold(0.0, |acc, &(tokens, rate)| {
+            f64::from(tokens).mul_add(rate, acc)
+        }) / PER_MILLION
+    }
+}
+
+/// Model id → [`ModelPrice`] table.
+///
+/// Serialized as a flat map, e.g. JSON `{"gpt-4o": {"input": 2.5, "output": 10}}`
+/// or TOML `["gpt-4o"]` tables. Lookup tries the exact id, then the longest
+/// key that prefixes it (so `gpt-4o` also prices `gpt-4o-2024-08-06`).
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PriceTable {
+    models: BTreeMap<String, ModelPrice>,
+}
+
+impl PriceTable {
+    /// Empty table (every model unpriced).
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add or replace the price for `model`.
+    #[must_use]
+    pub fn with_model(mut self, model: impl Into<String>, price: ModelPrice) -> Self {
+        self.models.insert(model.into(), price);
+        self
+    }
+
+    /// Parse a JSON table.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::TypesSerde`] on malformed input.
+    pub fn from_json(text: &str) -> Result<Self, OvoError> {
+        serde_json::from_str(text)
+            .map_err(|e| OvoError::new(ErrorCode::TypesSerde, format!("price table json: {e}")))
+    }
+
+    /// Parse a TOML table.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::TypesSerde`] on malformed input.
+    pub fn from_toml(text: &str) -> Result<Self, OvoError> {
+        toml::from_str(text)
+            .map_err(|e| OvoError::new(ErrorCode::TypesSerde, format!("price table toml: {e}")))
+    }
+
+    /// Load from disk; `.toml` files parse as TOML, everything else as JSON.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::TypesValidation`] when the file cannot be read, otherwise
+    /// as [`Self::from_json`] / [`Self::from_toml`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, OvoError> {
+        let path = path.as_ref();
+        let text = std::fs::read_to_string(path).map_err(|e| {
+            OvoError::new(
+                ErrorCode::TypesValidation,
+                format!("price table {}: {e}", path.display()),
+            )
+        })?;
+        if path.extension().is_some_and(|ext| ext == "toml") {
+            Self::from_toml(&text)
+        } else {
+            Self::from_json(&text)
+        }
+    }
+
+    /// Price for `model` (exact id, then longest matching prefix).
+    #[must_use]
+    pub fn price_for(&self, model: &str) -> Option<&ModelPrice> {
+        self.models.get(model).or_else(|| {
+            self.models
+                .iter()
+                .filter(|(key, _)| model.starts_with(key.as_str()))
+                .max_by_key(|(key, _)| key.len())
+                .map(|(_, price)| price)
+        })
+    }
+
+    /// USD cost of `usage` on `model`; `None` when the model is unpriced.
+    #[must_use]
+    pub fn cost(&self, model: &str, usage: &Usage) -> Option<f64> {
+        self.price_for(model).map(|p| p.cost(usage))
+    }
+
+    /// Number of priced models.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.models.len()
+    }
+
+    /// True when no model is priced.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.models.is_empty()
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use super::*;
+
+    fn close(a: f64, b: f64) -> bool {
+        (a - b).abs() < 1e-9
+    }
+
+    #[test]
+    fn prices_each_bucket_once() {
+        let price = ModelPrice::new(2.0, 8.0)
+            .with_cached_input(0.5)
+            .with_cache_write(2.5)
+            .with_reasoning(10.0);
+        let mut usage = Usage::new(1_000_000, 500_000);
+        usage.cache_read_tokens = 400_000;
+        usage.cache_creation_tokens = 100_000;
+        usage.reasoning_tokens = 200_000;
+        // 0.5M uncached * 2 + 0.4M * 0.5 + 0.1M * 2.5 + 0.3M * 8 + 0.2M * 10
+        assert!(close(price.cost(&usage), 1.0 + 0.2 + 0.25 + 2.4 + 2.0));
+
+        let mut hit = Usage::zero();
+        hit.response_cache_hits = 1;
+        let summed = usage + hit;
+        assert!(
+            close(price.cost(&summed), price.cost(&usage)),
+            "hits add nothing"
+        );
+    }
+
+    #[test]
+    fn loads_json_and_toml_with_prefix_lookup() {
+        let json = PriceTable::from_json(
+            r#"{"gpt-4o": {"input": 2.5, "output": 10}, "gpt-4o-mini": {"input": 0.15, "output": 0.6}}"#,
+        )
+        .expect("json");
+        let toml = PriceTable::from_toml(
+            "[\"gpt-4o\"]\ninput = 2.5\noutput = 10.0\n\n[\"gpt-4o-mini\"]\ninput = 0.15\noutput = 0.6\n",
+        )
+        .expect("toml");
+        assert_eq!(json, toml);
+        assert_eq!(json.len(), 2);
+
+        let usage = Usage::new(1_000_000, 0);
+        let dated = json.cost("gpt-4o-mini-2024-07-18", &usage).expect("prefix");
+        assert!(close(dated, 0.15), "longest prefix wins: {dated}");
+        assert!(json.cost("claude-sonnet", &usage).is_none());
+        assert!(PriceTable::from_json("[1]").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+//! Provider-neutral sampling parameters.
+//!
+//! Carried from agent definitions through turn / spawn overrides into every
+//! sample request. Unset fields leave the provider default in place.
+//! [`ToolChoice`] lives here too so agent-level policies can name it without
+//! depending on `ovo-llm`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::error::{ErrorCode, OvoError};
+
+/// Reasoning effort hint for reasoning-capable models.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ReasoningEffort {
+    /// Minimal reasoning.
+    Minimal,
+    /// Low effort.
+    Low,
+    /// Medium effort.
+    Medium,
+    /// High effort.
+    High,
+}
+
+impl ReasoningEffort {
+    /// Wire name (`minimal`, `low`, `medium`, `high`).
+    #[must_use]
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Self::Minimal => "minimal",
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Parse a wire name (case-insensitive).
+    #[must_use]
+    pub fn parse(raw: &str) -> Option<Self> {
+        match raw.trim().to_ascii_lowercase().as_str() {
+            "minimal" => Some(Self::Minimal),
+            "low" => Some(Self::Low),
+            "medium" => Some(Self::Medium),
+            "high" => Some(Self::High),
+            _ => None,
+        }
+    }
+}
+
+/// Whether the model must/may/must-not call tools.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ToolChoice {
+    /// Provider default.
+    #[default]
+    Auto,
+    /// Disallow tools.
+    None,
+    /// Require a tool call.
+    Required,
+    /// Force a specific tool name.
+    Named(String),
+}
+
+/// Sampling knobs; `None` / empty means "provider default".
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct SamplingParams {
+    /// Sampling temperature (`0.0..=2.0`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub temperature: Option<f32>,
+    /// Nucleus sampling mass (`0.0..=1.0`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub top_p: Option<f32>,
+    /// Seed for best-effort deterministic sampling.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed: Option<u64>,
+    /// Stop sequences.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub stop: Vec<String>,
+    /// Presence penalty (`-2.0..=2.0`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub presence_penalty: Option<f32>,
+    /// Frequency penalty (`-2.0..=2.0`).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub frequency_penalty: Option<f32>,
+    /// Reasoning effort for reasoning models.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reasoning_effort: Option<ReasoningEffort>,
+}
+
+impl SamplingParams {
+    /// All provider defaults.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set temperature.
+    #[must_use]
+    pub const fn with_temperature(mut self, temperature: f32) -> Self {
+        self.temperature = Some(temperature);
+        self
+    }
+
+    /// Set top-p.
+    #[must_use]
+    pub const fn with_top_p(mut self, top_p: f32) -> Self {
+        self.top_p = Some(top_p);
+        self
+    }
+
+    /// Set seed.
+    #[must_use]
+    pub const fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = Some(seed);
+        self
+    }
+
+    /// Set stop sequences.
+    #[must_use]
+    pub fn with_stop<I, S>(mut self, stop: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.stop = stop.into_iter().map(Into::into).collect();
+        self
+    }
+
+    /// Set presence penalty.
+    #[must_use]
+    pub const fn with_presence_penalty(mut self, penalty: f32) -> Self {
+        self.presence_penalty = Some(penalty);
+        self
+    }
+
+    /// Set frequency penalty.
+    #[must_use]
+    pub const fn with_frequency_penalty(mut self, penalty: f32) -> Self {
+        self.frequency_penalty = Some(penalty);
+        self
+    }
+
+    /// Set reasoning effort.
+    #[must_use]
+    pub const fn with_reasoning_effort(mut self, effort: ReasoningEffort) -> Self {
+        self.reasoning_effort = Some(effort);
+        self
+    }
+
+    /// True when nothing is set.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Field-wise overlay: values set in `over` win, unset ones fall back to `self`.
+    #[must_use]
+    pub fn merged(&self, over: &Self) -> Self {
+        Self {
+            temperature: over.temperature.or(self.temperature),
+            top_p: over.top_p.or(self.top_p),
+            seed: over.seed.or(self.seed),
+            stop: if over.stop.is_empty() {
+                self.stop.clone()
+            } else {
+                over.stop.clone()
+            },
+            presence_penalty: over.presence_penalty.or(self.presence_penalty),
+            frequency_penalty: over.frequency_penalty.or(self.frequency_penalty),
+            reasoning_effort: over.reasoning_effort.or(self.reasoning_effort),
+        }
+    }
+
+    /// Check value ranges.
+    ///
+    /// # Errors
+    ///
+    /// [`ErrorCode::TypesValidation`] naming the first out-of-range field.
+    pub fn validate(&self) -> Result<(), OvoError> {
+        let ranges = [
+            ("temperature", self.temperature, 0.0, 2.0),
+            ("top_p", self.top_p, 0.0, 1.0),
+            ("presence_penalty", self.presence_penalty, -2.0, 2.0),
+            ("frequency_penalty", self.frequency_penalty, -2.0, 2.0),
+        ];
+        for (name, value, lo, hi) in ranges {
+            if let Some(v) = value
+                && !(lo..=hi).contains(&v)
+            {
+                return Err(OvoError::new(
+                    ErrorCode::TypesValidation,
+                    format!("{name} {v} outside {lo}..={hi}"),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+#[allow(clippy::expect_used, reason = "unit tests")]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn overlay_prefers_set_fields() {
+        let base = SamplingParams::new()
+            .with_temperature(0.7)
+            .with_stop(["END"])
+            .with_reasoning_effort(ReasoningEffort::Low);
+        let over = SamplingParams::new().with_temperature(0.0).with_seed(42);
+        let merged = base.merged(&over);
+        assert_eq!(merged.temperature, Some(0.0));
+        assert_eq!(merged.seed, Some(42));
+        assert_eq!(merged.stop, vec!["END".to_owned()]);
+        assert_eq!(merged.reasoning_effort, Some(ReasoningEffort::Low));
+        assert_eq!(base.merged(&SamplingParams::new()), base);
+        assert!(SamplingParams::new().is_empty());
+    }
+
+    #[test]
+    fn validates_ranges_and_round_trips() {
+        assert!(
+            SamplingParams::new()
+                .with_temperature(2.5)
+                .validate()
+                .is_err()
+        );
+        assert!(SamplingParams::new().with_top_p(-0.1).validate().is_err());
+        let params = SamplingParams::new()
+            .with_temperature(0.0)
+            .with_seed(42)
+            .with_reasoning_effort(ReasoningEffort::High);
+        params.validate().expect("valid");
+        let json = serde_json::to_value(&params).expect("ser");
+        assert_eq!(
+            json,
+            serde_json::json!({"temperature": 0.0, "seed": 42, "reasoning_effort": "high"})
+        );
+        let back: SamplingParams = serde_json::from_value(json).expect("de");
+        assert_eq!(back, params);
+    }
+}
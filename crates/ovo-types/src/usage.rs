@@ -53,6 +53,16 @@ pub struct Usage {
     /// Provider API wall time for this sample, when known (milliseconds).
     #[serde(default)]
     pub api_duration_ms: u64,
+    /// Samples answered from a response cache instead of the provider
+    /// (reported with zero tokens; nothing was billed again).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub response_cache_hits: u32,
+    /// Tokens spent on losing hedged requests (not part of input/output).
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub wasted_tokens: u32,
+    /// Tool calls whose argument JSON was malformed and had to be repaired.
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub repaired_tool_args: u32,
     /// Prompt details.
     #[serde(default, alias = "prompt_tokens_details")]
     pub prompt_details: PromptTokensDetails,
@@ -73,6 +83,9 @@ pub const fn zero() -> Self {
             cache_creation_tokens: 0,
             reasoning_tokens: 0,
             api_duration_ms: 0,
+            response_cache_hits: 0,
+            wasted_tokens: 0,
+            repaired_tool_args: 0,
             prompt_details: PromptTokensDetails {
                 cached_tokens: 0,
                 audio_tokens: 0,
@@ -95,6 +108,9 @@ pub const fn new(input_tokens: u32, output_tokens: u32) -> Self {
             cache_creation_tokens: 0,
             reasoning_tokens: 0,
             api_duration_ms: 0,
+            response_cache_hits: 0,
+            wasted_tokens: 0,
+            repaired_tool_args: 0,
             prompt_details: PromptTokensDetails {
                 cached_tokens: 0,
                 audio_tokens: 0,
@@ -116,6 +132,14 @@ pub const fn normalized(mut self) -> Self {
     }
 }
 
+#[allow(
+    clippy::trivially_copy_pass_by_ref,
+    reason = "serde skip_serializing_if passes a reference"
+)]
+const fn is_zero(n: &u32) -> bool {
+    *n == 0
+}
+
 impl Add for Usage {
     type Output = Self;
 
@@ -130,6 +154,13 @@ fn add(self, rhs: Self) -> Self::Output {
                 .saturating_add(rhs.cache_creation_tokens),
             reasoning_tokens: self.reasoning_tokens.saturating_add(rhs.reasoning_tokens),
             api_duration_ms: self.api_duration_ms.saturating_add(rhs.api_duration_ms),
+            response_cache_hits: self
+                .response_cache_hits
+                .saturating_add(rhs.response_cache_hits),
+            wasted_tokens: self.wasted_tokens.saturating_add(rhs.wasted_tokens),
+            repaired_tool_args: self
+                .repaired_tool_args
+                .saturating_add(rhs.repaired_tool_args),
             prompt_details: PromptTokensDetails {
                 cached_tokens: self
                     .prompt_details
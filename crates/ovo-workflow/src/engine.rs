@@ -986,6 +986,7 @@ fn spawn_host(
                             output: serde_json::json!({"prompt": opts.prompt}),
                             cancelled: false,
                             tokens_used: 1,
+                            cost: 0.0,
                             duration_ms: 1,
                         }));
                     }
@@ -1127,6 +1128,7 @@ async fn json_encode_and_scratch_surface() {
                             output: serde_json::json!({"echo": opts.prompt}),
                             cancelled: false,
                             tokens_used: 1,
+                            cost: 0.0,
                             duration_ms: 1,
                         }));
                     }
@@ -1273,6 +1275,7 @@ fn spawn_host_with_policy(
                                 output: serde_json::json!({"prompt": opts.prompt}),
                                 cancelled: false,
                                 tokens_used: 1,
+                                cost: 0.0,
                                 duration_ms: 1,
                             }));
                         }
@@ -1579,6 +1582,7 @@ async fn parallel_quota_journals_null_and_keeps_dense_seq() {
                                 output: serde_json::json!({"prompt": opts.prompt}),
                                 cancelled: false,
                                 tokens_used: 1,
+                                cost: 0.0,
                                 duration_ms: 1,
                             }));
                         }
@@ -51,6 +51,9 @@ pub struct AgentResult {
     pub cancelled: bool,
     /// Tokens used (best effort).
     pub tokens_used: u64,
+    /// USD spend when the host prices models (0 otherwise).
+    #[serde(default)]
+    pub cost: f64,
     /// Duration ms.
     pub duration_ms: u64,
 }
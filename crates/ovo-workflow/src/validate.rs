@@ -166,6 +166,7 @@ fn probe_host_loop(mut rx: mpsc::UnboundedReceiver<WorkflowHostRequest>, agent_b
                     }),
                     cancelled: false,
                     tokens_used: 1,
+                    cost: 0.0,
                     duration_ms: 1,
                 }));
             }
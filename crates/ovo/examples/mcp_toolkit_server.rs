@@ -0,0 +1,26 @@
+//! Serve the jailed toolkit (read-only) to MCP clients over stdio.
+//!
+//! ```bash
+//! cargo run -p ovo --example mcp_toolkit_server --features toolkit,mcp -- /path/to/repo
+//! ```
+#![allow(clippy::expect_used, unused_crate_dependencies, reason = "demo binary")]
+
+use ovo::{CapabilityMode, McpServer, ToolCallContext, ToolRegistry, default_toolkit};
+
+#[tokio::main]
+async fn main() {
+    let jail = std::env::args()
+        .nth(1)
+        .map_or_else(|| std::env::current_dir().expect("cwd"), Into::into);
+    let registry = ToolRegistry::from_tools(default_toolkit(&jail));
+    let ctx = ToolCallContext {
+        cwd: Some(jail),
+        ..ToolCallContext::default()
+    };
+    McpServer::new(registry)
+        .with_capability(CapabilityMode::ReadOnly)
+        .with_context(ctx)
+        .serve_stdio()
+        .await
+        .expect("serve");
+}
@@ -6,8 +6,10 @@
 //! | `ovo-protocol` | tool id, content blocks, span catalogue |
 //! | `ovo-obs` | metrics sink, redact, recording / prometheus text |
 //! | `ovo-tools` | `DynTool`, stream, dispatch, approval |
+//! | `ovo-mcp` | MCP client tool sources and server adapter (feature) |
+//! | `ovo-openapi` | OpenAPI 3 operations as HTTP tools (feature) |
 //! | `ovo-toolkit` | cwd-jailed fs/shell tools (feature) |
-//! | `ovo-llm` | sampler + mock / openai / ollama |
+//! | `ovo-llm` | sampler + mock / openai / ollama / anthropic / gemini |
 //! | `ovo-agent` | definition, builder, discovery |
 //! | `ovo-state` | conversation handle, ledger, persistence |
 //! | `ovo-compaction` | compaction strategies |
@@ -33,28 +35,45 @@
     Agent, AgentBuilder, AgentDefinition, AgentRegistry, AgentSource, CompletionRequirement,
     EXPLORE, GENERAL_PURPOSE, IdentityAssembler, Instructions, ORCHESTRATOR_DELEGATION_PROMPT,
     PLAN, PROJECT_AGENTS_DIR, PROJECT_AGENTS_MD, ProjectPromptAssembler, PromptAssembler,
-    ToolPolicy, USER_AGENTS_DIR, agents_md_path, builtin_definitions, builtin_names,
-    by_name_in_dir, by_name_resolved, discover_in_dir, discover_project, discover_user, load_file,
-    parse_definition_markdown, project_agent_dirs, resolve_agents, user_agents_dir,
+    StructuredOutputMode, ToolChoicePolicy, ToolPolicy, USER_AGENTS_DIR, agents_md_path,
+    builtin_definitions, builtin_names, by_name_in_dir, by_name_resolved, discover_in_dir,
+    discover_project, discover_user, load_file, parse_definition_markdown, project_agent_dirs,
+    resolve_agents, user_agents_dir,
 };
 #[cfg(feature = "compaction")]
 pub use ovo_compaction as compaction;
 #[cfg(feature = "runtime")]
 pub use ovo_llm as llm;
-#[cfg(feature = "openai")]
-pub use ovo_llm::OpenAiCompatSampler;
+#[cfg(feature = "anthropic")]
+pub use ovo_llm::AnthropicSampler;
+#[cfg(feature = "gemini")]
+pub use ovo_llm::GeminiSampler;
 #[cfg(feature = "runtime")]
 pub use ovo_llm::{
-    Admission, BreakerConfig, BreakerOutcome, BreakerSampler, BreakerState, CircuitBreaker,
-    DEFAULT_IDLE_TIMEOUT, DEFAULT_MAX_ATTEMPTS, HttpRetryClass, LlmSampler, MAX_RETRY_AFTER,
-    MAX_RETRY_BACKOFF, MockSampler, OpenAiCompatConfig, RATE_LIMIT_RETRY_THRESHOLD, RetryContext,
-    RetryDecision, RetryPolicy, RetryingSampler, SampleEvent, SampleRequest, SampleResponse,
-    SampleStream, ToolChoice, backoff_for_attempt, build_chat_completions_body,
+    Admission, AnthropicConfig, ArgumentRepair, BreakerConfig, BreakerEmbedder, BreakerOutcome,
+    BreakerSampler, BreakerState, CachingSampler, CircuitBreaker, DEFAULT_IDLE_TIMEOUT,
+    DEFAULT_MAX_ATTEMPTS, DiskCache, Embedder, GeminiConfig, HedgeDelay, HedgedSampler,
+    HttpRetryClass, LlmSampler, MAX_RETRY_AFTER, MAX_RETRY_BACKOFF, MemoryCache, MockEmbedder,
+    MockSampler, ModelCapabilities, OpenAiCompatConfig, RATE_LIMIT_RETRY_THRESHOLD, RateLimit,
+    RateLimitSnapshot, RateLimitedSampler, ReasoningReplay, RecordingSampler, ReplaySampler,
+    RequestMask, ResponseCache, ResponsesConfig, RetryContext, RetryDecision, RetryPolicy,
+    RetryingEmbedder, RetryingSampler, RoutingSampler, SampleEvent, SampleRequest, SampleResponse,
+    SampleStream, ToolArguments, ToolChoice, backoff_for_attempt, build_chat_completions_body,
     classify_http_status, decide_retry, error_code_for_http, is_empty_response,
-    parse_chat_completions_response, response_to_stream,
+    parse_chat_completions_response, parse_tool_arguments, repair_json, response_to_stream,
 };
 #[cfg(feature = "ollama")]
-pub use ovo_llm::{OllamaConfig, OllamaSampler};
+pub use ovo_llm::{OllamaConfig, OllamaEmbedder, OllamaSampler};
+#[cfg(feature = "openai")]
+pub use ovo_llm::{OpenAiCompatEmbedder, OpenAiCompatSampler, ResponsesSampler};
+#[cfg(feature = "runtime")]
+pub use ovo_macros::tool;
+#[cfg(feature = "mcp")]
+pub use ovo_mcp as mcp;
+#[cfg(feature = "mcp-http")]
+pub use ovo_mcp::HttpServer;
+#[cfg(feature = "mcp")]
+pub use ovo_mcp::{McpConfig, McpServer, McpToolSource, StdioServer};
 #[cfg(feature = "obs")]
 pub use ovo_obs as obs;
 #[cfg(feature = "obs")]
@@ -63,11 +82,16 @@
     metric_catalogue_snapshot, redact_key_value, redact_map, required_metric_names,
     required_span_names,
 };
+#[cfg(feature = "openapi")]
+pub use ovo_openapi as openapi;
+#[cfg(feature = "openapi")]
+pub use ovo_openapi::{OpenApiConfig, OpenApiToolSource};
 pub use ovo_protocol as protocol;
 pub use ovo_protocol::{
-    ContentBlock, IMAGE_TOKEN_COST, ImageBlock, MESSAGE_FRAME_TOKENS, PreflightOverflow,
-    SPAN_COMPACT, SPAN_SAMPLE, SPAN_SESSION, SPAN_SPAWN, SPAN_TOOL, SPAN_TOOL_BATCH, SPAN_TURN,
-    SPAN_WORKFLOW, SPAN_WORKFLOW_HOST, ToolId, TurnEvent, TurnEventKind, check_context_overflow,
+    BpeCounter, ContentBlock, HeuristicCounter, IMAGE_TOKEN_COST, ImageBlock, MESSAGE_FRAME_TOKENS,
+    PreflightOverflow, SPAN_COMPACT, SPAN_SAMPLE, SPAN_SESSION, SPAN_SPAWN, SPAN_TOOL,
+    SPAN_TOOL_BATCH, SPAN_TURN, SPAN_WORKFLOW, SPAN_WORKFLOW_HOST, SharedTokenCounter,
+    TOOL_FRAME_TOKENS, TokenCounter, ToolId, TurnEvent, TurnEventKind, check_context_overflow,
     estimate_image_tokens, estimate_text_tokens, span_catalogue_snapshot,
 };
 #[cfg(feature = "runtime")]
@@ -76,9 +100,9 @@
 pub use ovo_runtime::{
     AgentRunResult, CompactionOutcome, CompactionStrategy, CompletionToolGate, ConversationState,
     DEFAULT_MAX_CONCURRENT_CHILDREN, DEFAULT_MAX_SPAWN_DEPTH, EventBus, EventSink, GateChain,
-    GateDecision, HARD_STOP_THRESHOLD, InProcessHost, InProcessIsolation, IsolationBackend,
-    IsolationEnv, LifecycleFanout, MaxMessages, MetricsSink, NUDGE_THRESHOLD, NoopLifecycle,
-    NoopMetrics, Session, SessionHost, SharedMetrics, SpawnAgentTool, SpawnOpts,
+    GateDecision, GateFailKind, HARD_STOP_THRESHOLD, InProcessHost, InProcessIsolation,
+    IsolationBackend, IsolationEnv, LifecycleFanout, MaxMessages, MetricsSink, NUDGE_THRESHOLD,
+    NoopLifecycle, NoopMetrics, Session, SessionHost, SharedMetrics, SpawnAgentTool, SpawnOpts,
     StationarityAction, StationarityTracker, StopGate, TokenThreshold, TurnAbortReason, TurnInput,
     TurnLifecycleContributor, TurnOptions, TurnOutcome, TurnRuntime, VecConversationState,
     estimate_conversation_tokens, evaluate_stop_gates, fingerprint_batch, isolation_error,
@@ -119,17 +143,17 @@
 pub use ovo_tools::{
     AlwaysDeny, ApprovalDecision, ApprovalGate, ApprovalPolicy, AutoApprove, CalcTool,
     CapabilityFlag, CapabilityMode, ConcurrencyMode, Destructiveness, DispatchOutcome,
-    DispatchRequest, DynTool, EXTRA_SPAWN_DEPTH, InterruptBehavior, MAX_DELTA_BYTES,
-    MAX_FRAME_BYTES, SharedTool, StaticToolSource, ToolCallContext, ToolDefinition, ToolDispatch,
-    ToolError, ToolMetadata, ToolProgress, ToolRegistry, ToolResult, ToolSource, ToolStream,
-    ToolStreamItem, drain_terminal, drain_with_progress, merge_arc_sources, merge_tool_sources,
-    partial_progress_frames, terminal_only, with_progress,
+    DispatchRequest, DynTool, EXTRA_SPAWN_DEPTH, InterruptBehavior, JsonSchema, MAX_DELTA_BYTES,
+    MAX_FRAME_BYTES, SharedTool, StaticToolSource, Tool, ToolCallContext, ToolDefinition,
+    ToolDispatch, ToolError, ToolMetadata, ToolProgress, ToolRegistry, ToolResult, ToolSource,
+    ToolStream, ToolStreamItem, drain_terminal, drain_with_progress, merge_arc_sources,
+    merge_tool_sources, partial_progress_frames, terminal_only, with_progress,
 };
 pub use ovo_types as types;
 pub use ovo_types::{
     AgentId, CompletionTokensDetails, ContentPart, Deadline, ErrorCode, ImageMime, Message,
-    OvoError, PromptTokensDetails, Result, RetryClass, Role, RunId, SessionId, ToolCall,
-    ToolCallId, Usage, WorkflowRunId,
+    ModelPrice, OvoError, PriceTable, PromptTokensDetails, ReasoningEffort, Result, RetryClass,
+    Role, RunId, SamplingParams, SessionId, ToolCall, ToolCallId, Usage, WorkflowRunId,
 };
 #[cfg(feature = "workflow")]
 pub use ovo_workflow as workflow;